@@ -0,0 +1,309 @@
+// CI workflows tailored to a project (`new --ci github`, `scale`)
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+use walkdir::WalkDir;
+
+use crate::project::handler_for_project;
+use crate::template_manager::required_rust_version;
+
+/// CI providers `new --ci` accepts; `none` writes no workflow
+pub const CI_PROVIDERS: &[&str] = &["none", "github"];
+
+/// Where the GitHub Actions workflow is written
+pub const GITHUB_WORKFLOW: &str = ".github/workflows/ci.yml";
+
+/// Burn backends that get a job of their own when the project declares them as features
+const BURN_BACKENDS: &[&str] = &["ndarray", "wgpu", "candle", "tch", "cuda"];
+
+/// How the tools of non-cargo build commands are installed, as (command prefix, install step)
+const TOOL_INSTALLS: &[(&[&str], &str)] = &[
+    (&["trunk"], "cargo install trunk --locked"),
+    (&["cargo", "lambda"], "pip install cargo-lambda"),
+];
+
+/// The oldest toolchain that builds each edition, for projects that declare no rust-version
+const EDITION_RUST_VERSIONS: &[(&str, &str)] = &[("2024", "1.85")];
+
+/// Directories never searched for manifests
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", ".git"];
+
+/// What the workflow for a project has to cover
+#[derive(Debug, Clone, PartialEq)]
+pub struct CiPlan {
+    /// The template the project was generated from, for the workflow header
+    pub template: Option<String>,
+    /// The newest edition any crate of the project uses
+    pub edition: String,
+    /// Minimum Rust version, checked in a job of its own
+    pub rust_version: Option<String>,
+    /// Build command of the project's handler when it needs more than cargo, with the step
+    /// that installs its tool
+    pub build: Option<(Vec<String>, &'static str)>,
+    /// Extra rustup targets, such as `wasm32-unknown-unknown` for trunk builds
+    pub targets: Vec<String>,
+    /// Burn backend features to test one job each
+    pub backends: Vec<String>,
+}
+
+impl CiPlan {
+    /// Work out the plan for the project in `project_dir` generated from `template`
+    ///
+    /// The build step comes from the project's handler (the one `ferrisup info` shows),
+    /// the edition and minimum Rust version from its manifests and template.
+    pub fn for_project(project_dir: &Path, template: Option<&str>, variables: &Value) -> Result<Self> {
+        let manifests = read_manifests(project_dir)?;
+        let edition = manifests
+            .iter()
+            .filter_map(|doc| manifest_value(doc, "edition"))
+            .max()
+            .unwrap_or_else(|| "2021".to_string());
+        let rust_version = manifests
+            .iter()
+            .find_map(|doc| manifest_value(doc, "rust-version"))
+            .or_else(|| template.and_then(required_rust_version))
+            .or_else(|| {
+                EDITION_RUST_VERSIONS
+                    .iter()
+                    .find(|(e, _)| *e == edition)
+                    .map(|(_, version)| version.to_string())
+            });
+
+        let build = handler_for_project(template, variables, project_dir).and_then(|handler| {
+            let command = handler.build_command();
+            TOOL_INSTALLS
+                .iter()
+                .find(|(prefix, _)| command.len() >= prefix.len() && command.iter().zip(prefix.iter()).all(|(a, b)| a == b))
+                .map(|(_, install)| (command, *install))
+        });
+        let targets = match &build {
+            Some((command, _)) if command[0] == "trunk" => vec!["wasm32-unknown-unknown".to_string()],
+            _ => Vec::new(),
+        };
+
+        // Burn projects pick their backend with features, so each one gets tested
+        let uses_burn = manifests.iter().any(|doc| {
+            doc.get("dependencies").and_then(|d| d.get("burn")).is_some()
+                || doc
+                    .get("workspace")
+                    .and_then(|w| w.get("dependencies"))
+                    .and_then(|d| d.get("burn"))
+                    .is_some()
+        });
+        let backends = if uses_burn {
+            BURN_BACKENDS
+                .iter()
+                .filter(|backend| manifests.iter().any(|doc| doc.get("features").and_then(|f| f.get(backend)).is_some()))
+                .map(|backend| backend.to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            template: template.map(String::from),
+            edition,
+            rust_version,
+            build,
+            targets,
+            backends,
+        })
+    }
+
+    /// The GitHub Actions workflow for the plan
+    pub fn github_workflow(&self) -> String {
+        let mut header = String::from("# Generated by FerrisUp");
+        if let Some(template) = &self.template {
+            header.push_str(&format!(" for the {} template", template));
+        }
+        header.push_str(&format!(" (Rust edition {}", self.edition));
+        if let Some(version) = &self.rust_version {
+            header.push_str(&format!(", rust-version {}", version));
+        }
+        header.push(')');
+
+        let mut toolchain = String::from("      - uses: dtolnay/rust-toolchain@stable\n        with:\n          components: rustfmt, clippy\n");
+        if !self.targets.is_empty() {
+            toolchain.push_str(&format!("          targets: {}\n", self.targets.join(", ")));
+        }
+
+        let mut workflow = format!(
+            r#"{header}
+name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+{toolchain}      - uses: Swatinem/rust-cache@v2
+      - run: cargo fmt --all --check
+      - run: cargo clippy --workspace --all-targets -- -D warnings
+      - run: cargo test --workspace
+"#
+        );
+        if let Some((command, install)) = &self.build {
+            workflow.push_str(&format!("      - run: {}\n      - run: {}\n", install, command.join(" ")));
+        }
+
+        if let Some(version) = &self.rust_version {
+            workflow.push_str(&format!(
+                r#"
+  msrv:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@master
+        with:
+          toolchain: "{version}"
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo check --workspace
+"#
+            ));
+        }
+
+        if !self.backends.is_empty() {
+            workflow.push_str(&format!(
+                r#"
+  backends:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: false
+      matrix:
+        backend: [{}]
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo test --workspace --no-default-features --features ${{{{ matrix.backend }}}}
+"#,
+                self.backends.join(", ")
+            ));
+        }
+        workflow
+    }
+}
+
+/// Write the GitHub Actions workflow for the project in `project_dir`, returning its path
+pub fn write_github_workflow(project_dir: &Path, template: Option<&str>, variables: &Value) -> Result<PathBuf> {
+    let path = project_dir.join(GITHUB_WORKFLOW);
+    if path.exists() {
+        return Err(anyhow!("{} already exists", path.display()));
+    }
+    let workflow = CiPlan::for_project(project_dir, template, variables)?.github_workflow();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, workflow)?;
+    Ok(path)
+}
+
+/// Every `Cargo.toml` below `project_dir`, parsed
+fn read_manifests(project_dir: &Path) -> Result<Vec<DocumentMut>> {
+    WalkDir::new(project_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "Cargo.toml")
+        .map(|e| {
+            let content = fs::read_to_string(e.path())?;
+            content
+                .parse::<DocumentMut>()
+                .map_err(|err| anyhow!("Failed to parse {}: {}", e.path().display(), err))
+        })
+        .collect()
+}
+
+/// A `[package]` or `[workspace.package]` string such as `edition`, skipping inherited ones
+fn manifest_value(doc: &DocumentMut, key: &str) -> Option<String> {
+    doc.get("package")
+        .and_then(|package| package.get(key))
+        .and_then(Item::as_str)
+        .or_else(|| {
+            doc.get("workspace")
+                .and_then(|workspace| workspace.get("package"))
+                .and_then(|package| package.get(key))
+                .and_then(Item::as_str)
+        })
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn parse(workflow: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(workflow).expect("workflow is valid YAML")
+    }
+
+    #[test]
+    fn test_plain_crate_workflow() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let project = temp_dir.path();
+        fs::write(
+            project.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        )?;
+
+        let path = write_github_workflow(project, Some("minimal"), &json!({}))?;
+        let workflow = fs::read_to_string(&path)?;
+        assert!(workflow.starts_with("# Generated by FerrisUp for the minimal template (Rust edition 2024, rust-version 1.85)"));
+        let yaml = parse(&workflow);
+        let steps = yaml["jobs"]["check"]["steps"].as_sequence().unwrap();
+        assert_eq!(steps.last().unwrap()["run"], "cargo test --workspace");
+        assert_eq!(yaml["jobs"]["msrv"]["steps"][1]["with"]["toolchain"], "1.85");
+        assert!(yaml["jobs"].get("backends").is_none());
+
+        assert!(write_github_workflow(project, Some("minimal"), &json!({})).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_builds_and_backends() -> Result<()> {
+        let leptos = CiPlan {
+            template: Some("client/leptos/counter".to_string()),
+            edition: "2021".to_string(),
+            rust_version: Some("1.88".to_string()),
+            build: Some((vec!["trunk".to_string(), "build".to_string()], "cargo install trunk --locked")),
+            targets: vec!["wasm32-unknown-unknown".to_string()],
+            backends: Vec::new(),
+        };
+        let yaml = parse(&leptos.github_workflow());
+        let steps = yaml["jobs"]["check"]["steps"].as_sequence().unwrap();
+        assert_eq!(steps[1]["with"]["targets"], "wasm32-unknown-unknown");
+        assert_eq!(steps.last().unwrap()["run"], "trunk build");
+
+        let temp_dir = tempdir()?;
+        let project = temp_dir.path();
+        fs::write(
+            project.join("Cargo.toml"),
+            "[package]\nname = \"model\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nburn = \"0.16\"\n\n[features]\ndefault = [\"ndarray\"]\nndarray = []\nwgpu = []\n",
+        )?;
+        let plan = CiPlan::for_project(project, None, &json!({}))?;
+        assert_eq!(plan.backends, vec!["ndarray", "wgpu"]);
+        assert_eq!(plan.rust_version, None);
+        let yaml = parse(&plan.github_workflow());
+        assert_eq!(yaml["jobs"]["backends"]["strategy"]["matrix"]["backend"][1], "wgpu");
+        assert_eq!(
+            yaml["jobs"]["backends"]["steps"][3]["run"],
+            "cargo test --workspace --no-default-features --features ${{ matrix.backend }}"
+        );
+        Ok(())
+    }
+}
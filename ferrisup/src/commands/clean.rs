@@ -0,0 +1,288 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+use walkdir::WalkDir;
+
+/// Artifact categories that are only removed when requested with `--include`:
+/// Trunk and wasm-pack output, trained models and Polars analysis results
+const OPTIONAL_CATEGORIES: &[&str] = &["dist", "models", "outputs"];
+
+/// Directories never descended into when looking for loose artifact files
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", ".git"];
+
+/// A file or directory that `clean` removes, with the space it takes up
+#[derive(Debug)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Execute the clean command for the project at `path`
+///
+/// Removes `target/` of the project and every workspace member (what `cargo clean`
+/// does, but without needing a build to resolve the target directory) plus the
+/// optional categories listed in `include`.
+pub fn execute(path: Option<&str>, include: &[String], dry_run: bool) -> Result<()> {
+    let project_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()?,
+    };
+
+    let artifacts = find_artifacts(&project_dir, include)?;
+    if artifacts.is_empty() {
+        println!("{}", "Nothing to clean".green());
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for artifact in &artifacts {
+        let display = artifact
+            .path
+            .strip_prefix(&project_dir)
+            .unwrap_or(&artifact.path)
+            .display()
+            .to_string();
+
+        if dry_run {
+            println!("Would remove {} ({})", display.cyan(), format_bytes(artifact.bytes));
+        } else {
+            if artifact.path.is_dir() {
+                fs::remove_dir_all(&artifact.path)
+            } else {
+                fs::remove_file(&artifact.path)
+            }
+            .with_context(|| format!("Failed to remove {}", artifact.path.display()))?;
+            println!("Removed {} ({})", display.cyan(), format_bytes(artifact.bytes));
+        }
+        total += artifact.bytes;
+    }
+
+    if dry_run {
+        println!("\n{} {}", "Would reclaim".bold(), format_bytes(total).green().bold());
+    } else {
+        println!("\n{} {}", "Reclaimed".bold(), format_bytes(total).green().bold());
+    }
+
+    Ok(())
+}
+
+/// Collect everything `clean` would remove from the project at `project_dir`
+pub fn find_artifacts(project_dir: &Path, include: &[String]) -> Result<Vec<Artifact>> {
+    for category in include {
+        if !OPTIONAL_CATEGORIES.contains(&category.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Unknown --include category '{}'. Valid categories are: {}",
+                category,
+                OPTIONAL_CATEGORIES.join(", ")
+            ));
+        }
+    }
+    let included = |category: &str| include.iter().any(|c| c == category);
+
+    if !project_dir.join("Cargo.toml").exists() {
+        return Err(anyhow::anyhow!("No Cargo.toml found in {}", project_dir.display()));
+    }
+
+    let mut paths = BTreeSet::new();
+    for crate_dir in crate_dirs(project_dir)? {
+        let mut dirs = vec!["target"];
+        if included("dist") {
+            dirs.extend(["dist", "pkg"]);
+        }
+        if included("models") {
+            dirs.push("artifacts");
+        }
+        for dir in dirs {
+            let candidate = crate_dir.join(dir);
+            if candidate.is_dir() {
+                paths.insert(candidate);
+            }
+        }
+    }
+
+    // Model files and analysis results can live anywhere in the source tree
+    if included("models") || included("outputs") {
+        let walker = WalkDir::new(project_dir).into_iter().filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        });
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy();
+            let is_model = name.ends_with(".safetensors")
+                || (name.starts_with("model") && name.ends_with(".json"));
+            let is_output = entry
+                .path()
+                .file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().ends_with("_analyzed"));
+
+            if (included("models") && is_model) || (included("outputs") && is_output) {
+                paths.insert(entry.path().to_path_buf());
+            }
+        }
+    }
+
+    // Anything inside a directory that is already being removed is covered by it
+    let dirs: Vec<PathBuf> = paths.iter().filter(|p| p.is_dir()).cloned().collect();
+    Ok(paths
+        .into_iter()
+        .filter(|path| !dirs.iter().any(|dir| path != dir && path.starts_with(dir)))
+        .map(|path| Artifact {
+            bytes: size_of(&path),
+            path,
+        })
+        .collect())
+}
+
+/// The project directory followed by the directories of its workspace members
+fn crate_dirs(project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let content = fs::read_to_string(project_dir.join("Cargo.toml"))?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .context("Failed to parse Cargo.toml as TOML")?;
+
+    let mut dirs = vec![project_dir.to_path_buf()];
+    let members = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| members.iter().filter_map(|m| m.as_str()).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    for member in members {
+        // Expand `crates/*` style globs to the crates in that directory
+        if let Some(parent) = member.strip_suffix("/*") {
+            if let Ok(entries) = fs::read_dir(project_dir.join(parent)) {
+                let mut expanded: Vec<PathBuf> = entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.join("Cargo.toml").exists())
+                    .collect();
+                expanded.sort();
+                dirs.extend(expanded);
+            }
+        } else {
+            dirs.push(project_dir.join(member));
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// Size of a file, or of everything below a directory, in bytes
+fn size_of(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Format a byte count for display, e.g. `1.5 MiB`
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &str) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    fn relative_paths(root: &Path, artifacts: &[Artifact]) -> Vec<String> {
+        artifacts
+            .iter()
+            .map(|a| a.path.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_artifacts_in_workspace() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        write(&root.join("Cargo.toml"), "[workspace]\nmembers = [\"app\", \"crates/*\"]\n")?;
+        write(&root.join("target/debug/app"), "binary")?;
+        write(&root.join("app/Cargo.toml"), "[package]\nname = \"app\"\n")?;
+        write(&root.join("app/dist/index.html"), "<html></html>")?;
+        write(&root.join("crates/ml/Cargo.toml"), "[package]\nname = \"ml\"\n")?;
+        write(&root.join("crates/ml/target/debug/ml"), "binary")?;
+        write(&root.join("crates/ml/artifacts/model.safetensors"), "weights")?;
+        write(&root.join("crates/ml/model.json"), "{}")?;
+        write(&root.join("crates/ml/data/sales_analyzed.csv"), "a,b")?;
+        write(&root.join("crates/ml/data/sales.csv"), "a,b")?;
+
+        // Only target/ by default
+        let artifacts = find_artifacts(root, &[])?;
+        assert_eq!(relative_paths(root, &artifacts), vec!["crates/ml/target", "target"]);
+        assert_eq!(artifacts.iter().map(|a| a.bytes).sum::<u64>(), 12);
+
+        let include = vec!["dist".to_string(), "models".to_string(), "outputs".to_string()];
+        let artifacts = find_artifacts(root, &include)?;
+        assert_eq!(
+            relative_paths(root, &artifacts),
+            vec![
+                "app/dist",
+                "crates/ml/artifacts",
+                "crates/ml/data/sales_analyzed.csv",
+                "crates/ml/model.json",
+                "crates/ml/target",
+                "target",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run_keeps_files() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        write(&root.join("Cargo.toml"), "[package]\nname = \"app\"\n")?;
+        write(&root.join("target/debug/app"), "binary")?;
+
+        execute(root.to_str(), &[], true)?;
+        assert!(root.join("target").exists());
+
+        execute(root.to_str(), &[], false)?;
+        assert!(!root.join("target").exists());
+        assert!(root.join("Cargo.toml").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_category_is_rejected() {
+        let temp_dir = tempdir().unwrap();
+        let err = find_artifacts(temp_dir.path(), &["everything".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("dist"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
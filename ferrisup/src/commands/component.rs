@@ -1,23 +1,55 @@
-use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{Confirm, Input, Select};
+use serde::Serialize;
 use toml_edit::DocumentMut;
 
+use crate::commands::info::inspect_project;
+
 use ferrisup_common::cargo::{read_cargo_toml, update_workspace_members};
 
-type UsageSummary = HashMap<String, HashMap<String, Vec<(usize, String)>>>;
+/// A component of a project, as `component list --json` prints it
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ComponentSummary {
+    pub name: String,
+    /// Directory relative to the project root, with `/` separators
+    pub path: String,
+    pub component_type: Option<String>,
+    /// Framework, or the provider for serverless and edge components
+    pub framework: Option<String>,
+    /// The project handler that recognizes the component
+    pub handler: Option<String>,
+    pub dependency_count: usize,
+}
 
 /// Execute the component command for adding/removing components
-pub fn execute(action: Option<&str>, component_type: Option<&str>, project_path: Option<&str>) -> Result<()> {
-    println!("{}", "FerrisUp Component Manager".bold().green());
+///
+/// `force` and `dry_run` apply to `remove`: removing a component other workspace crates
+/// depend on, and only previewing the removal. `json` lists the components as JSON.
+pub fn execute(
+    action: Option<&str>,
+    component_type: Option<&str>,
+    project_path: Option<&str>,
+    force: bool,
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    if json && action.is_some_and(|action| action != "list") {
+        return Err(anyhow::anyhow!("--json only applies to the list action"));
+    }
+    // Only the JSON is printed, for tools that parse it
+    if !json {
+        println!("{}", "FerrisUp Component Manager".bold().green());
+    }
     
     // Get project path
     let project_dir = if let Some(path) = project_path {
         PathBuf::from(path)
+    } else if json {
+        std::env::current_dir()?
     } else {
         // Prompt for project path
         let use_current = Confirm::new()
@@ -44,6 +76,8 @@ pub fn execute(action: Option<&str>, component_type: Option<&str>, project_path:
     // Get action (add/remove)
     let action_str = if let Some(act) = action {
         act.to_string()
+    } else if json {
+        "list".to_string()
     } else {
         let options = ["add", "remove", "list"];
         let selection = Select::new()
@@ -58,7 +92,8 @@ pub fn execute(action: Option<&str>, component_type: Option<&str>, project_path:
     // Execute the selected action
     match action_str.as_str() {
         "add" => add_component(&project_dir, component_type)?,
-        "remove" => remove_component(&project_dir, component_type)?,
+        "remove" => remove_component(&project_dir, component_type, force, dry_run)?,
+        "list" if json => println!("{}", serde_json::to_string_pretty(&component_summaries(&project_dir)?)?),
         "list" => list_components(&project_dir)?,
         _ => return Err(anyhow::anyhow!("Invalid action. Use 'add', 'remove', or 'list'")),
     }
@@ -118,10 +153,14 @@ fn add_component(project_dir: &Path, component_type: Option<&str>) -> Result<()>
 }
 
 /// Remove a component from an existing project
-fn remove_component(project_dir: &Path, component_type: Option<&str>) -> Result<()> {
-    // List existing components
-    let components = discover_components(project_dir)?;
-    
+///
+/// In a workspace the component is found among the `[workspace] members`. Removing it is
+/// refused while other members depend on it, unless `force` is given, in which case those
+/// path dependencies are dropped too. With `dry_run` the removal is only shown as a diff.
+fn remove_component(project_dir: &Path, component_type: Option<&str>, force: bool, dry_run: bool) -> Result<()> {
+    let is_workspace = is_workspace_root(project_dir)?;
+    let components = project_components(project_dir)?;
+
     if components.is_empty() {
         println!("{}", "No components found to remove".yellow());
         return Ok(());
@@ -131,12 +170,16 @@ fn remove_component(project_dir: &Path, component_type: Option<&str>) -> Result<
     let component_path = if let Some(ctype) = component_type {
         // Find the component by type
         let matching: Vec<String> = components.iter()
-            .filter(|c| c.contains(ctype))
+            .filter(|c| member_name(c).contains(ctype))
             .cloned()
             .collect();
         
         if matching.is_empty() {
-            return Err(anyhow::anyhow!("No matching component found"));
+            return Err(anyhow::anyhow!(
+                "No {} component found, the components are: {}",
+                ctype,
+                components.join(", ")
+            ));
         } else if matching.len() == 1 {
             matching[0].clone()
         } else {
@@ -160,87 +203,30 @@ fn remove_component(project_dir: &Path, component_type: Option<&str>) -> Result<
         components[selection].clone()
     };
     
-    // Check if this is a workspace
-    let cargo_content = read_cargo_toml(project_dir)?;
-    let is_workspace = cargo_content.contains("[workspace]");
-    
-    // Before proceeding, check if the component is being used in other components
+    // Other members that still depend on the component would stop building
     if is_workspace {
-        let usage_summary = check_component_usage(project_dir, &component_path, &components)?;
-        
-        if !usage_summary.is_empty() {
-            println!("{}", "⚠️  Component is being used in other components:".yellow());
-            println!();
-            
-            // Print usage summary
-            for (component, files) in &usage_summary {
-                println!("In {} :", component.cyan());
-                
-                // Group by file and then by type (import, definite usage, potential usage)
-                for (file, lines) in files {
-                    println!("  📄 {}", file);
-                    
-                    // First show imports
-                    let imports: Vec<_> = lines.iter()
-                        .filter(|(_, content)| content.starts_with("use "))
-                        .collect();
-                    
-                    if !imports.is_empty() {
-                        println!("    Imports:");
-                        for (line_num, content) in &imports {
-                            println!("      Line {}: {}", line_num, content);
-                        }
-                    }
-                    
-                    // Then show direct references
-                    let direct_refs: Vec<_> = lines.iter()
-                        .filter(|(_, content)| content.starts_with("[DIRECT REFERENCE]"))
-                        .collect();
-                    
-                    if !direct_refs.is_empty() {
-                        println!("    Direct References:");
-                        for (line_num, content) in &direct_refs {
-                            let clean_content = content.replace("[DIRECT REFERENCE] ", "");
-                            println!("      Line {}: {}", line_num, clean_content);
-                        }
-                    }
-                    
-                    // Then show potential usage
-                    let potential: Vec<_> = lines.iter()
-                        .filter(|(_, content)| content.starts_with("[POTENTIAL USAGE]"))
-                        .collect();
-                    
-                    if !potential.is_empty() {
-                        println!("    Potential Usage:");
-                        for (line_num, content) in &potential {
-                            let clean_content = content.replace("[POTENTIAL USAGE] ", "");
-                            println!("      Line {}: {}", line_num, clean_content);
-                        }
-                    }
-                    
-                    // Then show definite usage
-                    let definite: Vec<_> = lines.iter()
-                        .filter(|(_, content)| !content.starts_with("use ") && 
-                                              !content.starts_with("[POTENTIAL USAGE]") &&
-                                              !content.starts_with("[DIRECT REFERENCE]"))
-                        .collect();
-                    
-                    if !definite.is_empty() {
-                        println!("    Definite Usage:");
-                        for (line_num, content) in &definite {
-                            println!("      Line {}: {}", line_num, content);
-                        }
-                    }
-                }
-                println!();
+        let dependents = find_dependents(project_dir, &components, &component_path)?;
+        if !dependents.is_empty() {
+            let listing = dependents
+                .iter()
+                .map(|(member, dependencies)| format!("  {} ({})", member, dependencies.join(", ")))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !force {
+                return Err(anyhow::anyhow!(
+                    "{} is still a dependency of other workspace crates:\n{}\nRemove those dependencies first, or pass --force to drop them along with the component",
+                    component_path,
+                    listing
+                ));
             }
-            
-            println!("{}", "You must manually remove these references before removing the component.".yellow());
-            println!("{}", "Component removal has been cancelled.".red());
-            return Ok(());
+            println!("{}\n{}", "⚠️  Dropping the dependencies of other crates on the component:".yellow(), listing);
         }
     }
-    
+
+    if dry_run {
+        return crate::commands::transform::dry_run::preview(project_dir, |copy| remove_member(copy, &component_path));
+    }
+
     // Confirm removal
     let confirm = Confirm::new()
         .with_prompt(format!("Remove component {}?", component_path))
@@ -252,616 +238,251 @@ fn remove_component(project_dir: &Path, component_type: Option<&str>) -> Result<
         return Ok(());
     }
     
-    if is_workspace {
-        // Remove component from other components' dependencies
-        remove_component_dependencies(project_dir, &component_path)?;
-        
-        // Update metadata to remove the component
-        remove_component_from_metadata(project_dir, &component_path)?;
-    }
-    
-    // Remove the component directory
-    let full_path = project_dir.join(&component_path);
-    fs::remove_dir_all(&full_path)
-        .context(format!("Failed to remove {}", full_path.display()))?;
-    
+    remove_member(project_dir, &component_path)?;
     println!("{} {}", "Successfully removed component:".green(), component_path);
     
-    // Update workspace members list if needed
-    if is_workspace {
-        // First update the workspace members list directly to ensure the removed component is gone
-        update_workspace_members_after_removal(project_dir, &component_path)?;
-        
-        // Then run the standard update to discover any new members
-        update_workspace_members(project_dir)?;
-    }
-    
-    Ok(())
-}
-
-/// Remove a component from the .ferrisup/metadata.toml file
-fn remove_component_from_metadata(project_dir: &Path, component_name: &str) -> Result<()> {
-    let ferrisup_dir = project_dir.join(".ferrisup");
-    if !ferrisup_dir.exists() {
-        // No metadata directory, nothing to update
-        return Ok(());
-    }
-    
-    let metadata_path = ferrisup_dir.join("metadata.toml");
-    if !metadata_path.exists() {
-        // No metadata file, nothing to update
-        return Ok(());
-    }
-    
-    // Read and parse the metadata file
-    let metadata_content = fs::read_to_string(&metadata_path)?;
-    let mut metadata_doc = metadata_content
-        .parse::<DocumentMut>()
-        .context("Failed to parse metadata.toml")?;
-    
-    // Remove the component entry from metadata
-    let component_key = format!("component.{}", component_name);
-    if metadata_doc.contains_key(&component_key) {
-        metadata_doc.remove(&component_key);
-        println!("{} {}", "Updated".green(), "metadata.toml to remove component".cyan());
-        
-        // Write the updated metadata back to the file
-        fs::write(metadata_path, metadata_doc.to_string())?;
-    }
-    
     Ok(())
 }
 
-/// Remove component dependencies from other components in the workspace
-fn remove_component_dependencies(project_dir: &Path, component_name: &str) -> Result<()> {
-    // Read the workspace Cargo.toml
-    let workspace_cargo_path = project_dir.join("Cargo.toml");
-    if !workspace_cargo_path.exists() {
-        return Ok(());
-    }
-    
-    let workspace_cargo_content = fs::read_to_string(&workspace_cargo_path)?;
-    let mut workspace_doc = workspace_cargo_content
-        .parse::<DocumentMut>()
-        .context("Failed to parse workspace Cargo.toml")?;
-    
-    // Remove from workspace.dependencies if it exists
-    if let Some(workspace) = workspace_doc.get_mut("workspace") {
-        if let Some(workspace_table) = workspace.as_table_mut() {
-            if let Some(deps) = workspace_table.get_mut("dependencies") {
-                if let Some(deps_table) = deps.as_table_mut() {
-                    // Check if the component is in workspace dependencies
-                    if deps_table.contains_key(component_name) {
-                        deps_table.remove(component_name);
-                        println!("{} {}", "Removed".green(), 
-                            format!("'{}' from workspace.dependencies", component_name).cyan());
-                    }
-                }
-            }
+/// Remove the component at `member` (relative to `project_dir`) from the project
+///
+/// In a workspace this also drops it from the `members` list and `[workspace.dependencies]`
+/// and removes every path dependency on it from the other members' manifests.
+fn remove_member(project_dir: &Path, member: &str) -> Result<()> {
+    if is_workspace_root(project_dir)? {
+        let members = workspace_members(project_dir)?;
+        for (dependent, _) in find_dependents(project_dir, &members, member)? {
+            let cargo_path = project_dir.join(&dependent).join("Cargo.toml");
+            let mut doc = read_manifest(&cargo_path)?;
+            remove_path_dependencies(&mut doc, &project_dir.join(&dependent), &project_dir.join(member));
+            fs::write(&cargo_path, doc.to_string())?;
+            println!("{} {}", "Removed".green(), format!("the dependency on {} from {}", member, dependent).cyan());
         }
-    }
-    
-    // Write updated workspace Cargo.toml
-    fs::write(&workspace_cargo_path, workspace_doc.to_string())?;
-    
-    // Now find all other component directories and remove the dependency
-    // from each component's Cargo.toml
-    let components = discover_components(project_dir)?;
-    
-    println!("{} {}", "Checking".blue(), 
-        format!("for dependencies and imports in other components").cyan());
-    
-    for comp in components {
-        // Skip the component being removed
-        if comp == component_name {
-            continue;
-        }
-        
-        let component_cargo_path = project_dir.join(&comp).join("Cargo.toml");
-        if component_cargo_path.exists() {
-            // Remove the dependency from this component
-            remove_dependency_from_component(&component_cargo_path, component_name)?;
-            
-            // Also remove any import statements from source files
-            remove_imports_from_component(&project_dir.join(&comp), component_name)?;
-        }
-    }
-    
-    Ok(())
-}
 
-/// Remove a dependency from a component's Cargo.toml
-fn remove_dependency_from_component(cargo_path: &Path, dependency_name: &str) -> Result<()> {
-    if !cargo_path.exists() {
-        return Ok(());
-    }
-    
-    let cargo_content = fs::read_to_string(cargo_path)?;
-    let mut cargo_doc = cargo_content
-        .parse::<DocumentMut>()
-        .context(format!("Failed to parse {}", cargo_path.display()))?;
-    
-    let mut updated = false;
-    
-    // Check standard dependencies
-    if let Some(deps) = cargo_doc.get_mut("dependencies") {
-        if let Some(deps_table) = deps.as_table_mut() {
-            if deps_table.contains_key(dependency_name) {
-                deps_table.remove(dependency_name);
-                updated = true;
+        let workspace_cargo_path = project_dir.join("Cargo.toml");
+        let mut workspace_doc = read_manifest(&workspace_cargo_path)?;
+        if let Some(deps) = workspace_doc
+            .get_mut("workspace")
+            .and_then(|w| w.get_mut("dependencies"))
+            .and_then(|d| d.as_table_like_mut())
+        {
+            let removed = path_dependencies_on(deps.iter(), project_dir, &project_dir.join(member));
+            for name in removed {
+                deps.remove(&name);
+                println!("{} {}", "Removed".green(), format!("'{}' from workspace.dependencies", name).cyan());
             }
         }
-    }
-    
-    // Check dev-dependencies
-    if let Some(deps) = cargo_doc.get_mut("dev-dependencies") {
-        if let Some(deps_table) = deps.as_table_mut() {
-            if deps_table.contains_key(dependency_name) {
-                deps_table.remove(dependency_name);
-                updated = true;
+        if let Some(members) = workspace_doc
+            .get_mut("workspace")
+            .and_then(|w| w.get_mut("members"))
+            .and_then(|m| m.as_array_mut())
+        {
+            let original_len = members.len();
+            // The first entry's leading whitespace goes to whichever entry is first afterwards
+            let first_prefix = members.iter().next().and_then(|m| m.decor().prefix().cloned());
+            members.retain(|m| m.as_str().map(|m| m.trim_end_matches('/')) != Some(member));
+            if let (Some(first), Some(prefix)) = (members.iter_mut().next(), first_prefix) {
+                first.decor_mut().set_prefix(prefix);
             }
-        }
-    }
-    
-    // Check build-dependencies
-    if let Some(deps) = cargo_doc.get_mut("build-dependencies") {
-        if let Some(deps_table) = deps.as_table_mut() {
-            if deps_table.contains_key(dependency_name) {
-                deps_table.remove(dependency_name);
-                updated = true;
+            if members.len() < original_len {
+                println!("{} {}", "Removed".green(), format!("'{}' from workspace members", member).cyan());
             }
         }
+        fs::write(&workspace_cargo_path, workspace_doc.to_string())?;
+
+        remove_component_from_metadata(project_dir, member)?;
     }
-    
-    if updated {
-        // Write updated Cargo.toml
-        fs::write(cargo_path, cargo_doc.to_string())?;
-        println!("{} {}", "Removed".green(), 
-            format!("'{}' dependency from {}", dependency_name, cargo_path.file_name().unwrap().to_string_lossy()).cyan());
-    }
-    
+
+    let full_path = project_dir.join(member);
+    fs::remove_dir_all(&full_path)
+        .context(format!("Failed to remove {}", full_path.display()))?;
     Ok(())
 }
 
-/// Remove import statements referencing the removed component
-fn remove_imports_from_component(component_dir: &Path, removed_component: &str) -> Result<()> {
-    // Process both src/main.rs and src/lib.rs if they exist
-    let src_dir = component_dir.join("src");
-    if !src_dir.exists() {
-        return Ok(());
-    }
-    
-    // Check main.rs
-    let main_rs = src_dir.join("main.rs");
-    if main_rs.exists() {
-        remove_imports_from_file(&main_rs, removed_component)?;
-    }
-    
-    // Check lib.rs
-    let lib_rs = src_dir.join("lib.rs");
-    if lib_rs.exists() {
-        remove_imports_from_file(&lib_rs, removed_component)?;
-    }
-    
-    // Recursively check all .rs files in the src directory
-    visit_rust_files(&src_dir, removed_component)?;
-    
-    Ok(())
+/// Whether the Cargo.toml in `project_dir` declares a `[workspace]`
+fn is_workspace_root(project_dir: &Path) -> Result<bool> {
+    Ok(read_manifest(&project_dir.join("Cargo.toml"))?.contains_key("workspace"))
 }
 
-/// Check if a component is being used in other components
-/// Returns a map of component name -> (file path -> (line number, line content))
-fn check_component_usage(project_dir: &Path, component_to_check: &str, all_components: &[String]) -> Result<UsageSummary> {
-    let mut usage_summary: UsageSummary = HashMap::new();
-    
-    println!("{} {}", "Checking for usage of".blue(), component_to_check.cyan());
-    
-    // For each component (except the one being removed)
-    for component in all_components {
-        if component == component_to_check {
-            continue;
-        }
-        
-        println!("{} {}", "Scanning component".blue(), component.cyan());
-        
-        let component_dir = project_dir.join(component);
-        let src_dir = component_dir.join("src");
-        
-        if !src_dir.exists() || !src_dir.is_dir() {
-            println!("{} {}", "No src directory found in".yellow(), component.cyan());
-            continue;
-        }
-        
-        // We'll use visit_rust_files_for_usage to check all .rs files including main.rs and lib.rs
-        visit_rust_files_for_usage(&src_dir, component_to_check, component, project_dir, &mut usage_summary)?;
-    }
-    
-    Ok(usage_summary)
+fn read_manifest(cargo_path: &Path) -> Result<DocumentMut> {
+    fs::read_to_string(cargo_path)?
+        .parse::<DocumentMut>()
+        .context(format!("Failed to parse {}", cargo_path.display()))
 }
 
-/// Check a file for actual usage of a component (beyond imports)
-fn check_file_for_usage(
-    file_path: &Path, 
-    component_to_check: &str, 
-    component_name: &str,
-    project_dir: &Path,
-    usage_summary: &mut UsageSummary
-) -> Result<()> {
-    if !file_path.exists() {
-        return Ok(());
-    }
-    
-    println!("  Checking file: {}", file_path.display());
-    
-    let content = fs::read_to_string(file_path)?;
-    
-    // Check if the file imports the component
-    let snake_case = component_to_check.replace('-', "_");
-    let import_patterns = [
-        format!("use {}::*", component_to_check),
-        format!("use {}::", component_to_check),
-        // Also check for snake_case variant
-        format!("use {}_::", component_to_check),
-        format!("use {}_::*", component_to_check),
-        // Check for snake case variant
-        format!("use {}::*", snake_case),
-        format!("use {}::", snake_case),
-    ];
-    
-    let mut has_import = false;
-    
-    // First pass: find all import lines
-    for (line_num, line) in content.lines().enumerate() {
-        let trimmed = line.trim();
-        
-        // Skip empty lines or comments
-        if trimmed.is_empty() || trimmed.starts_with("//") {
-            continue;
-        }
-        
-        // Check for import statements
-        if trimmed.starts_with("use ") {
-            for pattern in &import_patterns {
-                if trimmed.contains(pattern) {
-    
-                    has_import = true;
-                    
-                    // Found import, add to summary - but check for duplicates first
-                    let file_relative = file_path.strip_prefix(project_dir.join(component_name))
-                        .unwrap_or(file_path)
-                        .to_string_lossy()
-                        .to_string();
-                    
-                    let entry = (line_num + 1, line.to_string());
-                    
-                    // Get or create the entry for this component and file
-                    let file_entries = usage_summary
-                        .entry(component_name.to_string())
-                        .or_default()
-                        .entry(file_relative.clone())
-                        .or_default();
-                    
-                    // Only add if this exact entry doesn't already exist
-                    if !file_entries.iter().any(|(num, content)| *num == entry.0 && content == &entry.1) {
-                        file_entries.push(entry);
-                    }
-                }
+/// The last path segment of a member such as `crates/server`
+fn member_name(member: &str) -> &str {
+    member.trim_end_matches('/').rsplit('/').next().unwrap_or(member)
+}
+
+/// The members of the workspace in `project_dir`, with `dir/*` globs expanded
+fn workspace_members(project_dir: &Path) -> Result<Vec<String>> {
+    let doc = read_manifest(&project_dir.join("Cargo.toml"))?;
+    let declared: Vec<String> = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| members.iter().filter_map(|m| m.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    let mut members = Vec::new();
+    for member in declared {
+        match member.strip_suffix("/*") {
+            Some(dir) => {
+                let Ok(entries) = fs::read_dir(project_dir.join(dir)) else {
+                    continue;
+                };
+                let mut expanded: Vec<String> = entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().join("Cargo.toml").exists())
+                    .map(|entry| format!("{}/{}", dir, entry.file_name().to_string_lossy()))
+                    .collect();
+                expanded.sort();
+                members.extend(expanded);
             }
+            None => members.push(member.trim_end_matches('/').to_string()),
         }
     }
-    
-    // Always check for actual usage, regardless of imports
-    // Second pass: check for actual usage
-    let snake_case = component_to_check.replace('-', "_");
-    
-    for (line_num, line) in content.lines().enumerate() {
-        let trimmed = line.trim();
-        
-        // Skip empty lines, comments, or import lines
-        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with("use ") {
+    Ok(members)
+}
+
+/// The other members of the workspace that depend on `member`, with the names of those
+/// dependencies
+///
+/// A dependency counts when its `path` points at the member, directly or through a
+/// `workspace = true` entry of `[workspace.dependencies]`.
+fn find_dependents(project_dir: &Path, members: &[String], member: &str) -> Result<Vec<(String, Vec<String>)>> {
+    let target = project_dir.join(member);
+    let mut dependents = Vec::new();
+    for other in members.iter().filter(|other| other.as_str() != member) {
+        let cargo_path = project_dir.join(other).join("Cargo.toml");
+        if !cargo_path.exists() {
             continue;
         }
-        
-        // Check for actual usage of the component - both original and snake case
-        if trimmed.contains(&component_to_check) || trimmed.contains(&snake_case) {
-            println!("    Found usage on line {}: {}", line_num + 1, trimmed);
-            
-            // Found usage, add to summary
-            let file_relative = file_path.strip_prefix(project_dir.join(component_name))
-                .unwrap_or(file_path)
-                .to_string_lossy()
-                .to_string();
-            
-            usage_summary
-                .entry(component_name.to_string())
-                .or_default()
-                .entry(file_relative)
-                .or_default()
-                .push((line_num + 1, line.to_string()));
-        }
-        
-        // Check for actual component name usage in code
-        let component_name_snake = component_to_check.replace('-', "_");
-        if trimmed.contains(&component_name_snake) || trimmed.contains(component_to_check) {
-            // Found direct reference to component name
-            let file_relative = file_path.strip_prefix(project_dir.join(component_name))
-                .unwrap_or(file_path)
-                .to_string_lossy()
-                .to_string();
-            
-            let entry = (line_num + 1, format!("[DIRECT REFERENCE] {}", line.trim()));
-            
-            // Get or create the entry for this component and file
-            let file_entries = usage_summary
-                .entry(component_name.to_string())
-                .or_default()
-                .entry(file_relative.clone())
-                .or_default();
-            
-            // Only add if this exact entry doesn't already exist
-            if !file_entries.iter().any(|(num, content)| *num == entry.0 && content == &entry.1) {
-                file_entries.push(entry);
-            }
-            continue;
+        let mut doc = read_manifest(&cargo_path)?;
+        let dependencies = remove_path_dependencies(&mut doc, &project_dir.join(other), &target);
+        if !dependencies.is_empty() {
+            dependents.push((other.clone(), dependencies));
         }
-        
-        // Also check for function calls that might be from the component
-        // but only if we have an import AND the line contains a function call
-        if has_import {
-            // Get exported functions from the component by checking its lib.rs or main.rs
-            let component_dir = project_dir.join(component_to_check);
-            let lib_rs = component_dir.join("src/lib.rs");
-            let main_rs = component_dir.join("src/main.rs");
-            
-            // Try to find exported functions
-            let mut exported_functions = Vec::new();
-            
-            // Check lib.rs for exported functions
-            if lib_rs.exists() {
-                if let Ok(content) = fs::read_to_string(&lib_rs) {
-                    // Very basic function detection - could be improved
-                    for line in content.lines() {
-                        let line = line.trim();
-                        if line.starts_with("pub fn ") {
-                            if let Some(name_end) = line["pub fn ".len()..].find('(') {
-                                let fn_name = &line["pub fn ".len()..][..name_end].trim();
-                                exported_functions.push(fn_name.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // Also check main.rs for exported functions
-            if main_rs.exists() && exported_functions.is_empty() {
-                if let Ok(content) = fs::read_to_string(&main_rs) {
-                    for line in content.lines() {
-                        let line = line.trim();
-                        if line.starts_with("pub fn ") {
-                            if let Some(name_end) = line["pub fn ".len()..].find('(') {
-                                let fn_name = &line["pub fn ".len()..][..name_end].trim();
-                                exported_functions.push(fn_name.to_string());
-                            }
-                        }
-                    }
+    }
+    Ok(dependents)
+}
+
+/// Remove the dependencies on the crate in `target` from a member manifest, returning
+/// their names
+///
+/// Covers `[dependencies]`, `[dev-dependencies]` and `[build-dependencies]`, also under
+/// `[target.'cfg(..)']`. `workspace = true` entries are resolved against the workspace root
+/// above `member_dir`.
+fn remove_path_dependencies(doc: &mut DocumentMut, member_dir: &Path, target: &Path) -> Vec<String> {
+    let workspace_paths = workspace_dependency_paths(member_dir);
+    let mut removed = Vec::new();
+
+    let mut remove_from = |table: &mut dyn toml_edit::TableLike| {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(deps) = table.get_mut(section).and_then(|d| d.as_table_like_mut()) else {
+                continue;
+            };
+            let mut names = path_dependencies_on(deps.iter(), member_dir, target);
+            names.extend(
+                deps.iter()
+                    .filter(|(_, spec)| spec.get("workspace").and_then(|w| w.as_bool()) == Some(true))
+                    .filter(|(name, _)| workspace_paths.iter().any(|(dep, path)| dep == name && same_path(path, target)))
+                    .map(|(name, _)| name.to_string()),
+            );
+            for name in names {
+                deps.remove(&name);
+                if !removed.contains(&name) {
+                    removed.push(name);
                 }
             }
-            
-            // Check if line contains any of the exported functions
-            let words: Vec<&str> = trimmed.split(|c: char| !c.is_alphanumeric() && c != '_').collect();
-            for word in words {
-                if !word.is_empty() && !word.chars().next().unwrap().is_uppercase() {
-                    // Check if this word is one of the exported functions
-                    if exported_functions.contains(&word.to_string()) ||
-                       // Or if it looks like a function call and we couldn't determine exports
-                       (exported_functions.is_empty() && 
-                        (trimmed.contains(&format!("{}{{", word)) || 
-                         trimmed.contains(&format!("{} {{", word)) ||
-                         trimmed.contains(&format!("{}(", word)) ||
-                         trimmed.contains(&format!("{}!", word)))) {
-                        
-                        // Add to summary as potential usage
-                        let file_relative = file_path.strip_prefix(project_dir.join(component_name))
-                            .unwrap_or(file_path)
-                            .to_string_lossy()
-                            .to_string();
-                        
-                        let entry = (line_num + 1, format!("[POTENTIAL USAGE] {}", line.trim()));
-                        
-                        // Get or create the entry for this component and file
-                        let file_entries = usage_summary
-                            .entry(component_name.to_string())
-                            .or_default()
-                            .entry(file_relative.clone())
-                            .or_default();
-                        
-                        // Only add if this exact entry doesn't already exist
-                        if !file_entries.iter().any(|(num, content)| *num == entry.0 && content == &entry.1) {
-                            file_entries.push(entry);
-                        }
-                    }
-                }
+        }
+    };
+
+    remove_from(doc.as_table_mut());
+    if let Some(targets) = doc.get_mut("target").and_then(|t| t.as_table_like_mut()) {
+        for (_, platform) in targets.iter_mut() {
+            if let Some(platform) = platform.as_table_like_mut() {
+                remove_from(platform);
             }
         }
     }
-    
-    Ok(())
+    removed
 }
 
-/// Visit all Rust files in a directory and check for component usage
-fn visit_rust_files_for_usage(
-    dir: &Path, 
-    component_to_check: &str, 
-    component_name: &str,
-    project_dir: &Path,
-    usage_summary: &mut UsageSummary
-) -> Result<()> {
-    if !dir.exists() || !dir.is_dir() {
-        return Ok(());
-    }
-    
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.is_dir() {
-            // Recursively visit subdirectories
-            visit_rust_files_for_usage(&path, component_to_check, component_name, project_dir, usage_summary)?;
-        } else if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-            // Check .rs files
-            check_file_for_usage(&path, component_to_check, component_name, project_dir, usage_summary)?;
-        }
-    }
-    
-    Ok(())
+/// The names of the dependencies in `deps` whose `path`, relative to `base`, is `target`
+fn path_dependencies_on<'a>(
+    deps: impl Iterator<Item = (&'a str, &'a toml_edit::Item)>,
+    base: &Path,
+    target: &Path,
+) -> Vec<String> {
+    deps.filter(|(_, spec)| {
+        spec.get("path")
+            .and_then(|p| p.as_str())
+            .is_some_and(|path| same_path(&base.join(path), target))
+    })
+    .map(|(name, _)| name.to_string())
+    .collect()
 }
 
-/// Visit all Rust files in a directory and its subdirectories
-fn visit_rust_files(dir: &Path, removed_component: &str) -> Result<()> {
-    if !dir.exists() || !dir.is_dir() {
-        return Ok(());
-    }
-    
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.is_dir() {
-            // Recursively visit subdirectories
-            visit_rust_files(path.as_path(), removed_component)?;
-        } else if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-            // Process Rust files
-            remove_imports_from_file(path.as_path(), removed_component)?;
-        }
-    }
-    
-    Ok(())
+/// The `path` dependencies of the `[workspace.dependencies]` of the workspace containing
+/// `member_dir`, resolved to absolute paths
+fn workspace_dependency_paths(member_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Some(root) = member_dir.ancestors().skip(1).find(|dir| {
+        read_manifest(&dir.join("Cargo.toml")).is_ok_and(|doc| doc.contains_key("workspace"))
+    }) else {
+        return Vec::new();
+    };
+    let Ok(doc) = read_manifest(&root.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    doc.get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table_like())
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|(name, spec)| Some((name.to_string(), root.join(spec.get("path")?.as_str()?))))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
-/// Update workspace members list after component removal
-fn update_workspace_members_after_removal(project_dir: &Path, removed_component: &str) -> Result<()> {
-    let workspace_cargo_path = project_dir.join("Cargo.toml");
-    if !workspace_cargo_path.exists() {
-        return Ok(());
-    }
-    
-    let workspace_cargo_content = fs::read_to_string(&workspace_cargo_path)?;
-    let mut workspace_doc = workspace_cargo_content
-        .parse::<DocumentMut>()
-        .context("Failed to parse workspace Cargo.toml")?;
-    
-    // Get the workspace members array
-    if let Some(workspace) = workspace_doc.get_mut("workspace") {
-        if let Some(workspace_table) = workspace.as_table_mut() {
-            if let Some(members) = workspace_table.get_mut("members") {
-                if let Some(members_array) = members.as_array_mut() {
-                    // Check if the component is in the members list
-                    let original_len = members_array.len();
-                    
-                    // Remove the component from the members list
-                    members_array.retain(|member| {
-                        if let Some(member_str) = member.as_str() {
-                            member_str != removed_component
-                        } else {
-                            true
-                        }
-                    });
-                    
-                    if members_array.len() < original_len {
-                        println!("{} {}", "Removed".green(), 
-                            format!("'{}' from workspace members", removed_component).cyan());
-                    }
-                }
-            }
-        }
+/// Whether two paths name the same directory, resolving `..` and symlinks when they exist
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
     }
-    
-    // Write updated workspace Cargo.toml
-    fs::write(&workspace_cargo_path, workspace_doc.to_string())?;
-    
-    Ok(())
 }
 
-/// Remove import statements from a Rust file
-fn remove_imports_from_file(file_path: &Path, removed_component: &str) -> Result<()> {
-    if !file_path.exists() {
+/// Remove a component from the .ferrisup/metadata.toml file
+fn remove_component_from_metadata(project_dir: &Path, component_name: &str) -> Result<()> {
+    let ferrisup_dir = project_dir.join(".ferrisup");
+    if !ferrisup_dir.exists() {
+        // No metadata directory, nothing to update
         return Ok(());
     }
     
-    let content = fs::read_to_string(file_path)?;
-    
-    // Look for import statements that reference the removed component
-    // Match patterns like:
-    // use removed_component::*;
-    // use removed_component::some_module;
-    // use crate::removed_component::*;
-    // use {removed_component, other_component};
-    // use component_with_removed_component_as_prefix;
-    
-    let mut updated_content = String::new();
-    let mut updated = false;
+    let metadata_path = ferrisup_dir.join("metadata.toml");
+    if !metadata_path.exists() {
+        // No metadata file, nothing to update
+        return Ok(());
+    }
     
-    // Create various patterns to match different import styles
-    let patterns = [
-        format!("use {}::*", removed_component),        // use removed_component::*
-    ];
+    // Read and parse the metadata file
+    let metadata_content = fs::read_to_string(&metadata_path)?;
+    let mut metadata_doc = metadata_content
+        .parse::<DocumentMut>()
+        .context("Failed to parse metadata.toml")?;
     
-    for line in content.lines() {
-        let trimmed = line.trim();
-        
-        // Skip empty lines or comments
-        if trimmed.is_empty() || trimmed.starts_with("//") {
-            updated_content.push_str(line);
-            updated_content.push('\n');
-            continue;
-        }
-        
-        // Check if this line is importing from the removed component
-        let mut should_skip = false;
-        
-        if trimmed.starts_with("use ") || trimmed.starts_with("pub use ") {
-            // Check against our patterns
-            for pattern in &patterns {
-                if trimmed.contains(pattern) || 
-                   // Also check for exact component name in braces import
-                   (trimmed.contains("{") && trimmed.contains("}") && 
-                    trimmed.contains(&format!(", {}, ", removed_component)) || 
-                    trimmed.contains(&format!("{{{}", removed_component)) || 
-                    trimmed.contains(&format!("{}, ", removed_component)) || 
-                    trimmed.contains(&format!(", {}}}", removed_component))) {
-                    
-                    should_skip = true;
-                    updated = true;
-                    println!("{} {}", "Removing import:".yellow(), trimmed.cyan());
-                    break;
-                }
-            }
-            
-            // Also check for component name as part of a path segment
-            if !should_skip && 
-               (trimmed.contains(&format!("::{}", removed_component)) || 
-                trimmed.contains(&format!("{}::", removed_component))) {
-                should_skip = true;
-                updated = true;
-                println!("{} {}", "Removing path import:".yellow(), trimmed.cyan());
-            }
-        }
-        
-        if should_skip {
-            // Skip this line (don't add it to updated_content)
-            continue;
-        }
+    // Remove the component entry from metadata
+    let component_key = format!("component.{}", component_name);
+    if metadata_doc.contains_key(&component_key) {
+        metadata_doc.remove(&component_key);
+        println!("{} {}", "Updated".green(), "metadata.toml to remove component".cyan());
         
-        // Keep all other lines
-        updated_content.push_str(line);
-        updated_content.push('\n');
-    }
-    
-    if updated {
-        // Write the updated content back to the file
-        fs::write(file_path, updated_content)?;
-        println!("{} {}", "Updated".green(), 
-            format!("imports in {}", file_path.display()).cyan());
+        // Write the updated metadata back to the file
+        fs::write(metadata_path, metadata_doc.to_string())?;
     }
     
     Ok(())
@@ -869,7 +490,7 @@ fn remove_imports_from_file(file_path: &Path, removed_component: &str) -> Result
 
 /// List components in a project
 fn list_components(project_dir: &Path) -> Result<()> {
-    let components = discover_components(project_dir)?;
+    let components = component_summaries(project_dir)?;
     
     if components.is_empty() {
         println!("{}", "No components found".yellow());
@@ -878,12 +499,48 @@ fn list_components(project_dir: &Path) -> Result<()> {
     
     println!("{}", "Components:".green());
     for component in components {
-        println!("  - {}", component);
+        match &component.framework {
+            Some(framework) => println!("  - {} ({})", component.path, framework.cyan()),
+            None => println!("  - {}", component.path),
+        }
     }
     
     Ok(())
 }
 
+/// Describe every component of the project, sorted by path so the output is stable
+///
+/// The framework and handler are detected the same way `ferrisup info` detects them for
+/// a single crate.
+pub fn component_summaries(project_dir: &Path) -> Result<Vec<ComponentSummary>> {
+    let mut components = project_components(project_dir)?;
+    components.sort();
+    components
+        .into_iter()
+        .map(|path| {
+            let info = inspect_project(&project_dir.join(&path))?;
+            Ok(ComponentSummary {
+                name: info.name,
+                path,
+                component_type: info.component_type,
+                framework: info.framework,
+                handler: info.handler,
+                dependency_count: info.dependency_count,
+            })
+        })
+        .collect()
+}
+
+/// The components of the project: the members of a workspace, otherwise the
+/// subdirectories that have a Cargo.toml
+fn project_components(project_dir: &Path) -> Result<Vec<String>> {
+    if is_workspace_root(project_dir)? {
+        workspace_members(project_dir)
+    } else {
+        discover_components(project_dir)
+    }
+}
+
 /// Discover components in a project
 fn discover_components(project_dir: &Path) -> Result<Vec<String>> {
     let entries = fs::read_dir(project_dir)?
@@ -904,3 +561,70 @@ fn discover_components(project_dir: &Path) -> Result<Vec<String>> {
 // All component implementation functions have been removed as we now delegate to transform command's functions
 // This includes database, AI, edge, embedded, and library component implementations
 // The component command now uses the same component types and creation logic as the transform command
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_crate(dir: &Path, manifest: &str) -> Result<()> {
+        fs::create_dir_all(dir.join("src"))?;
+        fs::write(dir.join("Cargo.toml"), manifest)?;
+        fs::write(dir.join("src/lib.rs"), "")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_member_with_dependents() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\n# Keep this comment\nmembers = [\"crates/*\", \"tools\"]\n\n[workspace.dependencies]\nshared = { path = \"crates/shared\" }\nserde = \"1.0\"\n",
+        )?;
+        write_crate(&root.join("crates/shared"), "[package]\nname = \"shared\"\nversion = \"0.1.0\"\n")?;
+        write_crate(
+            &root.join("crates/server"),
+            "[package]\nname = \"server\"\nversion = \"0.1.0\"\n\n[dependencies]\nshared = { workspace = true }\nserde = { workspace = true }\n",
+        )?;
+        write_crate(
+            &root.join("tools"),
+            "[package]\nname = \"tools\"\nversion = \"0.1.0\"\n\n[target.'cfg(unix)'.dev-dependencies]\nshared-lib = { package = \"shared\", path = \"../crates/shared\" }\n",
+        )?;
+
+        let members = workspace_members(root)?;
+        assert_eq!(members, ["crates/server", "crates/shared", "tools"]);
+        let dependents = find_dependents(root, &members, "crates/shared")?;
+        assert_eq!(
+            dependents,
+            vec![
+                ("crates/server".to_string(), vec!["shared".to_string()]),
+                ("tools".to_string(), vec!["shared-lib".to_string()]),
+            ]
+        );
+        assert!(find_dependents(root, &members, "tools")?.is_empty());
+
+        // Refused without --force, and a dry run leaves everything in place
+        assert!(remove_component(root, Some("shared"), false, false).unwrap_err().to_string().contains("crates/server (shared)"));
+        remove_component(root, Some("shared"), true, true)?;
+        assert!(root.join("crates/shared").exists());
+
+        remove_member(root, "crates/shared")?;
+        assert!(!root.join("crates/shared").exists());
+        let server = fs::read_to_string(root.join("crates/server/Cargo.toml"))?;
+        assert!(!server.contains("shared") && server.contains("serde = { workspace = true }"), "{}", server);
+        assert!(!fs::read_to_string(root.join("tools/Cargo.toml"))?.contains("shared-lib"));
+        let workspace = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(workspace.contains("# Keep this comment"), "{}", workspace);
+        assert!(!workspace.contains("shared") && workspace.contains("serde = \"1.0\""), "{}", workspace);
+
+        // Removing a listed member drops it from `members`
+        remove_member(root, "tools")?;
+        assert!(fs::read_to_string(root.join("Cargo.toml"))?.contains("members = [\"crates/*\"]"));
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/shared\", \"crates/server\"]\n")?;
+        write_crate(&root.join("crates/shared"), "[package]\nname = \"shared\"\nversion = \"0.1.0\"\n")?;
+        remove_member(root, "crates/shared")?;
+        assert!(fs::read_to_string(root.join("Cargo.toml"))?.contains("members = [\"crates/server\"]"));
+        Ok(())
+    }
+}
@@ -2,15 +2,61 @@ use anyhow::{Result, Context};
 use colored::Colorize;
 use std::fs;
 use std::path::Path;
+use clap::ValueEnum;
 use serde_json;
+use crate::core::diff::write_with_diff;
 use crate::core::Config;
 
+/// Serialization format of an exported configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// The format implied by a file extension, if it names one
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Detect the format of a config file from its extension, falling back to its content
+    fn detect(path: &Path, content: &str) -> Self {
+        Self::from_extension(path).unwrap_or_else(|| {
+            if content.trim_start().starts_with('{') {
+                ConfigFormat::Json
+            } else {
+                ConfigFormat::Toml
+            }
+        })
+    }
+}
+
 /// Execute the config command for managing configurations
-pub fn execute(export: bool, import: Option<&str>, path: Option<&str>) -> Result<()> {
+///
+/// Exports are written as `format`, or as the format the export path's extension names
+/// (JSON otherwise). Imports detect the format of the file they read; with
+/// `validate_only` the imported file is only checked, not applied.
+pub fn execute(
+    export: bool,
+    import: Option<&str>,
+    path: Option<&str>,
+    format: Option<ConfigFormat>,
+    validate_only: bool,
+) -> Result<()> {
     if export {
-        export_config(path)?;
+        export_config(path, format)?;
     } else if let Some(import_path) = import {
-        import_config(import_path, path)?;
+        if validate_only {
+            read_config_file(import_path)?;
+            println!("{} {} is a valid FerrisUp config", "✓".green(), import_path);
+        } else {
+            import_config(import_path, path)?;
+        }
     } else {
         // Interactive mode
         run_interactive()?;
@@ -39,7 +85,7 @@ fn run_interactive() -> Result<()> {
                 .default("ferrisup-config.json".to_string())
                 .interact()?;
             
-            export_config(Some(&path))?;
+            export_config(Some(&path), None)?;
         },
         1 => {
             let import_path = dialoguer::Input::<String>::new()
@@ -74,10 +120,11 @@ fn run_interactive() -> Result<()> {
 }
 
 /// Export the current configuration to a file
-fn export_config(path: Option<&str>) -> Result<()> {
-    let config_path = match path {
-        Some(p) => p.to_string(),
-        None => "ferrisup-config.json".to_string(),
+fn export_config(path: Option<&str>, format: Option<ConfigFormat>) -> Result<()> {
+    let config_path = match (path, format) {
+        (Some(p), _) => p.to_string(),
+        (None, Some(ConfigFormat::Toml)) => "ferrisup-config.toml".to_string(),
+        (None, _) => "ferrisup-config.json".to_string(),
     };
     
     // Read current config or create default
@@ -91,7 +138,11 @@ fn export_config(path: Option<&str>) -> Result<()> {
     
     // Write to the specified path
     let path = Path::new(&config_path);
-    write_config(&config, path)?;
+    let format = format
+        .or_else(|| ConfigFormat::from_extension(path))
+        .unwrap_or(ConfigFormat::Json);
+    write_with_diff(path, &serialize_config(&config, format)?)
+        .context("Failed to write config file")?;
     
     println!("{} {}", "Configuration exported to:".green(), config_path);
     
@@ -100,12 +151,7 @@ fn export_config(path: Option<&str>) -> Result<()> {
 
 /// Import a configuration from a file
 fn import_config(import_path: &str, export_path: Option<&str>) -> Result<()> {
-    // Read the config to import
-    let content = fs::read_to_string(import_path)
-        .context(format!("Failed to read config from {}", import_path))?;
-    
-    let config: Config = serde_json::from_str(&content)
-        .context("Failed to parse config as JSON")?;
+    let config = read_config_file(import_path)?;
     
     if let Some(path) = export_path {
         // Write to the specified path
@@ -162,7 +208,85 @@ fn read_config() -> Result<Config> {
         .context("Failed to read config file")
 }
 
+/// Read and strictly parse a config file, in whichever format it is written
+fn read_config_file(path: &str) -> Result<Config> {
+    let content = fs::read_to_string(path)
+        .context(format!("Failed to read config from {}", path))?;
+    
+    let format = ConfigFormat::detect(Path::new(path), &content);
+    parse_config(&content, format).map_err(|e| anyhow::anyhow!("Invalid config {}: {}", path, e))
+}
+
+fn serialize_config(config: &Config, format: ConfigFormat) -> Result<String> {
+    match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(config)
+            .context("Failed to serialize config to JSON"),
+        ConfigFormat::Toml => toml::to_string_pretty(config)
+            .context("Failed to serialize config to TOML"),
+    }
+}
+
+/// Parse a config, reporting the offending field and its position on failure
+fn parse_config(content: &str, format: ConfigFormat) -> Result<Config> {
+    match format {
+        ConfigFormat::Json => serde_json::from_str(content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse config as JSON: {}", e)),
+        ConfigFormat::Toml => toml::from_str(content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse config as TOML: {}", e.to_string().trim_end())),
+    }
+}
+
 fn write_config(config: &Config, path: &Path) -> Result<()> {
-    fs::write(path, serde_json::to_string_pretty(config)?)
+    write_with_diff(path, &serde_json::to_string_pretty(config)?)
         .context("Failed to write config file")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Preferences;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_export_import_round_trip() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let config = Config {
+            templates_dir: PathBuf::from("/opt/ferrisup/templates"),
+            preferences: Preferences {
+                git: false,
+                build: true,
+                interactive: false,
+            },
+        };
+
+        for (file_name, format) in [("config.json", ConfigFormat::Json), ("config.toml", ConfigFormat::Toml)] {
+            let path = temp_dir.path().join(file_name);
+            fs::write(&path, serialize_config(&config, format)?)?;
+
+            let content = fs::read_to_string(&path)?;
+            assert_eq!(ConfigFormat::detect(&path, &content), format);
+            assert_eq!(parse_config(&content, ConfigFormat::detect(&path, &content))?, config);
+
+            // Without a telling extension the content decides
+            let unnamed = temp_dir.path().join("exported-config");
+            fs::write(&unnamed, &content)?;
+            assert_eq!(ConfigFormat::detect(&unnamed, &content), format);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let json = r#"{ "templates_dir": "templates", "preferences": { "git": true, "biuld": true } }"#;
+        let err = parse_config(json, ConfigFormat::Json).unwrap_err().to_string();
+        assert!(err.contains("unknown field `biuld`"), "{}", err);
+        assert!(err.contains("line 1"), "{}", err);
+
+        let toml = "templates_dir = \"templates\"\ntemplate_dir = \"other\"\n\n[preferences]\ngit = true\n";
+        let err = parse_config(toml, ConfigFormat::Toml).unwrap_err().to_string();
+        assert!(err.contains("unknown field `template_dir`"), "{}", err);
+        assert!(err.contains("line 2"), "{}", err);
+    }
+}
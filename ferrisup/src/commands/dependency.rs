@@ -2,10 +2,15 @@ use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
 use colored::Colorize;
 use dialoguer::{Confirm, Input, MultiSelect};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::process::Command;
-use ferrisup_common::cargo::update_cargo_with_dependencies;
+use toml_edit::DocumentMut;
+use ferrisup_common::cargo::{registry_configured, upsert_dependency, DependencySpec};
+use crate::commands::offline;
+use crate::core::crates_io;
+use crate::core::diff::print_diff;
+use crate::status;
 
 #[derive(Debug, Args)]
 pub struct DependencyArgs {
@@ -35,21 +40,38 @@ pub struct AddArgs {
     pub dependencies: Vec<String>,
     
     /// Add as development dependency
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "build")]
     pub dev: bool,
     
+    /// Add as build dependency
+    #[arg(long)]
+    pub build: bool,
+    
     /// Add with specific features (comma separated)
     #[arg(short, long)]
     pub features: Option<String>,
     
-    /// Add with specific version
-    #[arg(short, long)]
+    /// Add with specific version (no `-v` short form, that is the global `--verbose`)
+    #[arg(long)]
     pub version: Option<String>,
     
     /// Path to the project (defaults to current directory)
     #[arg(short, long)]
     pub path: Option<PathBuf>,
     
+    /// Use a local path dependency instead of crates.io
+    #[arg(long, conflicts_with = "git")]
+    pub dep_path: Option<String>,
+    
+    /// Use a git repository dependency instead of crates.io
+    #[arg(long)]
+    pub git: Option<String>,
+    
+    /// Take the dependency from this alternate registry, as configured for cargo, instead
+    /// of crates.io
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["dep_path", "git"])]
+    pub registry: Option<String>,
+    
     /// Disable interactive prompts
     #[arg(long)]
     pub no_interactive: bool,
@@ -107,6 +129,17 @@ pub fn add_dependencies(args: AddArgs) -> Result<()> {
     if !cargo_toml_path.exists() {
         return Err(anyhow::anyhow!("No Cargo.toml found in the specified directory. Are you sure this is a Rust project?"));
     }
+
+    // Cargo can't resolve a registry it has no index for
+    if let Some(registry) = &args.registry {
+        if !registry_configured(registry, &fs::canonicalize(&project_dir)?) {
+            return Err(anyhow::anyhow!(
+                "Registry '{}' isn't in the cargo configuration of this project. Declare its index under [registries.{}] in .cargo/config.toml first",
+                registry,
+                registry
+            ));
+        }
+    }
     
     // If no dependencies were provided, prompt for them
     let dependencies = if args.dependencies.is_empty() {
@@ -127,17 +160,23 @@ pub fn add_dependencies(args: AddArgs) -> Result<()> {
     }
 
     // Check if we need to handle dependencies that exist in the wrong section
-    let cargo_content = fs::read_to_string(&cargo_toml_path)
+    let original_content = fs::read_to_string(&cargo_toml_path)
         .context("Failed to read Cargo.toml")?;
-    let cargo_toml: toml::Value = toml::from_str(&cargo_content)
+    let cargo_toml: toml::Value = toml::from_str(&original_content)
         .context("Failed to parse Cargo.toml as valid TOML")?;
     
     // Check for dependencies in the wrong section
     let section_to_check = if args.dev { "dependencies" } else { "dev-dependencies" };
-    let target_section = if args.dev { "dev-dependencies" } else { "dependencies" };
+    let target_section = if args.dev {
+        "dev-dependencies"
+    } else if args.build {
+        "build-dependencies"
+    } else {
+        "dependencies"
+    };
     let section_flag = if section_to_check == "dev-dependencies" { "--dev" } else { "" };
     
-    if let Some(deps_table) = cargo_toml.get(section_to_check) {
+    if let Some(deps_table) = cargo_toml.get(section_to_check).filter(|_| !args.build) {
         if let Some(deps_table) = deps_table.as_table() {
             for dependency in &dependencies {
                 if deps_table.contains_key(dependency) {
@@ -172,39 +211,61 @@ pub fn add_dependencies(args: AddArgs) -> Result<()> {
         }
     }
     
-    // Process each dependency
-    let mut dependencies_to_add = Vec::new();
+    // Re-read the manifest, since moving dependencies may have changed it
+    let cargo_content = fs::read_to_string(&cargo_toml_path)
+        .context("Failed to read Cargo.toml")?;
+    let mut doc = cargo_content
+        .parse::<DocumentMut>()
+        .context("Failed to parse Cargo.toml as valid TOML")?;
     
+    // Process each dependency
     for dependency in dependencies {
-        let version = args.version.clone().unwrap_or_else(|| "*".to_string());
-        let mut features_option: Option<Vec<String>> = None;
+        let mut features = Vec::new();
         
         // Handle features if provided via command line
         if let Some(features_str) = &args.features {
-            let features: Vec<String> = features_str
+            features = features_str
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
                 .collect();
-                
-            if !features.is_empty() {
-                features_option = Some(features);
-            }
         } 
         // If no features provided via command line, suggest popular features
         else if !args.no_interactive {
             // Get suggested features for this dependency
-            let features = suggest_features_interactive(&dependency)?;
-            if !features.is_empty() {
-                features_option = Some(features);
+            features = suggest_features_interactive(&dependency)?;
+        }
+        
+        // Registry dependencies should exist on crates.io; warn but still add them
+        if args.dep_path.is_none() && args.git.is_none() && args.registry.is_none() && !offline::is_offline() {
+            if let Ok(None) = crates_io::find_crate(&dependency) {
+                println!("{} {}", 
+                    "Warning:".yellow().bold(), 
+                    format!("{} was not found on crates.io", dependency).yellow());
             }
         }
         
-        dependencies_to_add.push((dependency, version, features_option));
+        let spec = DependencySpec {
+            name: dependency.clone(),
+            version: args.version.clone(),
+            features,
+            path: args.dep_path.clone(),
+            git: args.git.clone(),
+            registry: args.registry.clone(),
+        };
+        
+        let existed = upsert_dependency(&mut doc, target_section, &spec)?;
+        let action = if existed { "Updated" } else { "Added" };
+        println!("{} {} {}", 
+            action.green(), 
+            dependency.bold(), 
+            format!("in [{}]", target_section).green());
     }
     
-    // Use our enhanced utility function
-    update_cargo_with_dependencies(&cargo_toml_path, dependencies_to_add, args.dev)?;
+    let content = doc.to_string();
+    fs::write(&cargo_toml_path, &content)
+        .context(format!("Failed to write {}", cargo_toml_path.display()))?;
+    print_diff(&original_content, &content, "Cargo.toml");
     
     Ok(())
 }
@@ -302,6 +363,8 @@ pub fn remove_dependencies(args: RemoveArgs) -> Result<()> {
     };
     
     // Remove each dependency
+    let original_content = fs::read_to_string(&cargo_toml_path)
+        .context("Failed to read Cargo.toml")?;
     for dependency in &dependencies {
         println!("{} {}", "Removing dependency:".yellow(), dependency);
         
@@ -318,12 +381,17 @@ pub fn remove_dependencies(args: RemoveArgs) -> Result<()> {
         
         println!("{} {}", "Successfully removed:".green(), dependency);
     }
+    let content = fs::read_to_string(&cargo_toml_path)
+        .context("Failed to read Cargo.toml")?;
+    print_diff(&original_content, &content, "Cargo.toml");
     
     Ok(())
 }
 
 /// Update dependencies in a project
 pub fn update_dependencies(args: UpdateArgs) -> Result<()> {
+    offline::ensure_online("update dependencies")?;
+
     let project_dir = args.path.unwrap_or_else(|| PathBuf::from("."));
     
     // Verify this is a Rust project
@@ -394,7 +462,7 @@ pub fn analyze_dependencies(args: AnalyzeArgs) -> Result<()> {
     
     if !audit_installed {
         println!("{}", "cargo-audit is not installed. It's recommended for security analysis.".yellow());
-        if Confirm::new()
+        if !offline::is_offline() && Confirm::new()
             .with_prompt("Would you like to install cargo-audit?")
             .interact()?
         {
@@ -435,8 +503,10 @@ pub fn analyze_dependencies(args: AnalyzeArgs) -> Result<()> {
         .unwrap_or(false)
     {
         println!("\n{}", "Security audit:".blue());
+        // Offline, audit against the advisory database fetched last time
+        let audit_args: &[&str] = if offline::is_offline() { &["audit", "--no-fetch"] } else { &["audit"] };
         let audit_output = Command::new("cargo")
-            .args(["audit"])
+            .args(audit_args)
             .current_dir(&project_dir)
             .stdout(std::process::Stdio::inherit())
             .stderr(std::process::Stdio::inherit())
@@ -450,3 +520,164 @@ pub fn analyze_dependencies(args: AnalyzeArgs) -> Result<()> {
     
     Ok(())
 }
+
+/// Manifest sections a dependency file can list crates in
+const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// The crates listed in a dependency file (`new --dependencies-from`), with their section
+///
+/// The file is laid out like the dependency sections of a Cargo.toml: `[dependencies]`,
+/// `[dev-dependencies]` and `[build-dependencies]`, each entry a version string or a table
+/// with `version`, `features`, `path` or `git`.
+pub fn load_dependency_file(path: &Path) -> Result<Vec<(String, DependencySpec)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read dependency file {}", path.display()))?;
+    let file: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse dependency file {}", path.display()))?;
+
+    let mut dependencies = Vec::new();
+    for (section, entries) in &file {
+        if !DEPENDENCY_SECTIONS.contains(&section.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Unknown section [{}] in {}, expected one of: {}",
+                section,
+                path.display(),
+                DEPENDENCY_SECTIONS.join(", ")
+            ));
+        }
+        let entries = entries
+            .as_table()
+            .ok_or_else(|| anyhow::anyhow!("[{}] in {} is not a table", section, path.display()))?;
+        for (name, entry) in entries {
+            let spec = dependency_spec(name, entry)
+                .with_context(|| format!("Invalid entry for {} in [{}] of {}", name, section, path.display()))?;
+            dependencies.push((section.clone(), spec));
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// A dependency file entry: `"1.0"` or `{ version = "1.0", features = ["derive"] }`
+fn dependency_spec(name: &str, entry: &toml::Value) -> Result<DependencySpec> {
+    let mut spec = DependencySpec { name: name.to_string(), ..Default::default() };
+    if let Some(version) = entry.as_str() {
+        spec.version = Some(version.to_string());
+        return Ok(spec);
+    }
+
+    let table = entry
+        .as_table()
+        .ok_or_else(|| anyhow::anyhow!("expected a version string or a table"))?;
+    let string = |key: &str| -> Result<Option<String>> {
+        table
+            .get(key)
+            .map(|value| value.as_str().map(String::from).ok_or_else(|| anyhow::anyhow!("`{}` must be a string", key)))
+            .transpose()
+    };
+    for key in table.keys() {
+        if !["version", "features", "path", "git"].contains(&key.as_str()) {
+            return Err(anyhow::anyhow!("unsupported key `{}`, only version, features, path and git are", key));
+        }
+    }
+    spec.version = string("version")?;
+    spec.path = string("path")?;
+    spec.git = string("git")?;
+    if let Some(features) = table.get("features") {
+        spec.features = features
+            .as_array()
+            .and_then(|features| features.iter().map(|f| f.as_str().map(String::from)).collect())
+            .ok_or_else(|| anyhow::anyhow!("`features` must be a list of strings"))?;
+    }
+
+    Ok(spec)
+}
+
+/// Merge `dependencies` into the manifest at `manifest_path`, like `dependency add` does
+///
+/// A crate the manifest already lists keeps its version, with a warning when another one was
+/// asked for, and only gains the requested features.
+pub fn merge_dependencies(manifest_path: &Path, dependencies: &[(String, DependencySpec)]) -> Result<()> {
+    if dependencies.is_empty() {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+    if doc.get("package").is_none() {
+        return Err(anyhow::anyhow!(
+            "{} is a workspace manifest without a package, add the dependencies to its members instead",
+            manifest_path.display()
+        ));
+    }
+
+    for (section, spec) in dependencies {
+        let current = doc.get(section).and_then(|table| table.get(&spec.name)).map(|entry| {
+            entry
+                .as_str()
+                .or_else(|| entry.get("version").and_then(|version| version.as_str()))
+                .map(String::from)
+        });
+        let mut spec = spec.clone();
+        if let Some(current) = current {
+            if spec.version.is_some() && spec.version != current {
+                status!(
+                    "{} {}",
+                    "Warning:".yellow().bold(),
+                    format!(
+                        "keeping the template's {} {} in [{}] instead of {}",
+                        spec.name,
+                        current.as_deref().unwrap_or("(no version)"),
+                        section,
+                        spec.version.as_deref().unwrap_or_default()
+                    )
+                    .yellow()
+                );
+            }
+            spec.version = None;
+        }
+        upsert_dependency(&mut doc, section, &spec)?;
+    }
+
+    fs::write(manifest_path, doc.to_string())
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_merge_dependencies() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let file = temp_dir.path().join("deps.toml");
+        fs::write(
+            &file,
+            "[dependencies]\nanyhow = \"1.0\"\nserde = { version = \"1.0.200\", features = [\"rc\"] }\n\n[dev-dependencies]\npretty_assertions = \"1\"\n",
+        )?;
+        let manifest = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = { version = \"1.0\", features = [\"derive\"] }\n",
+        )?;
+
+        merge_dependencies(&manifest, &load_dependency_file(&file)?)?;
+        let doc = fs::read_to_string(&manifest)?.parse::<DocumentMut>()?;
+        assert_eq!(doc["dependencies"]["anyhow"].as_str(), Some("1.0"));
+        // The template's pin wins, the features are merged
+        assert_eq!(doc["dependencies"]["serde"]["version"].as_str(), Some("1.0"));
+        assert_eq!(doc["dependencies"]["serde"]["features"].as_array().map(|f| f.len()), Some(2));
+        assert_eq!(doc["dev-dependencies"]["pretty_assertions"].as_str(), Some("1"));
+
+        fs::write(&file, "[target]\nanyhow = \"1.0\"\n")?;
+        assert!(load_dependency_file(&file).is_err());
+        fs::write(&file, "[dependencies]\nanyhow = { version = \"1.0\", optional = true }\n")?;
+        assert!(load_dependency_file(&file).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+use std::process::{Command, Stdio};
+use crate::commands::offline;
+use crate::core::rust_version::rustc_version;
+use crate::template_manager::{check_rust_version_against, rust_version_requirements, scan_user_templates, user_templates_dir};
+
+/// How the doctor tells whether a tool is installed
+enum Check {
+    /// The command runs and succeeds
+    Command(&'static [&'static str]),
+    /// rustup lists the target as installed
+    Target(&'static str),
+}
+
+/// A tool some templates need, and how `doctor --fix` installs it
+struct Tool {
+    name: &'static str,
+    needed_by: &'static str,
+    check: Check,
+    /// Command installing the tool. `None` when that needs administrator rights, which
+    /// `--fix` never asks for; `advice` says what to do instead
+    install: Option<&'static [&'static str]>,
+    advice: &'static str,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "wasm32-unknown-unknown target",
+        needed_by: "Leptos, Dioxus and Tauri clients, edge functions",
+        check: Check::Target("wasm32-unknown-unknown"),
+        install: Some(&["rustup", "target", "add", "wasm32-unknown-unknown"]),
+        advice: "rustup target add wasm32-unknown-unknown",
+    },
+    Tool {
+        name: "trunk",
+        needed_by: "Leptos clients",
+        check: Check::Command(&["trunk", "--version"]),
+        install: Some(&["cargo", "install", "trunk", "--locked"]),
+        advice: "cargo install trunk --locked",
+    },
+    Tool {
+        name: "wasm-pack",
+        needed_by: "edge functions",
+        check: Check::Command(&["wasm-pack", "--version"]),
+        install: Some(&["cargo", "install", "wasm-pack"]),
+        advice: "cargo install wasm-pack",
+    },
+    Tool {
+        name: "cargo-lambda",
+        needed_by: "AWS Lambda serverless functions",
+        check: Check::Command(&["cargo", "lambda", "--version"]),
+        install: Some(&["cargo", "install", "cargo-lambda", "--locked"]),
+        advice: "cargo install cargo-lambda --locked",
+    },
+    Tool {
+        name: "git",
+        needed_by: "Burn examples and templates from git repositories",
+        check: Check::Command(&["git", "--version"]),
+        install: None,
+        advice: "install git with your system's package manager, such as `sudo apt install git` or `brew install git`",
+    },
+];
+
+impl Tool {
+    fn is_installed(&self) -> bool {
+        match self.check {
+            Check::Command([program, args @ ..]) => Command::new(program)
+                .args(args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success()),
+            Check::Command([]) => true,
+            Check::Target(target) => Command::new("rustup")
+                .args(["target", "list", "--installed"])
+                .output()
+                .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).lines().any(|line| line.trim() == target)),
+        }
+    }
+}
+
+/// Execute the doctor command
+///
+/// Checks the FerrisUp setup for problems: the installed Rust toolchain against the
+/// `rust_version` templates require, the user template directory, where templates
+/// without a valid `ferrisup.toml` are not offered by `list` or `new`, and the tools and
+/// targets some templates need. Each problem is reported with the reason.
+///
+/// Missing tools are only reported, since not every project needs them. With `fix`, the
+/// doctor offers to install each one, asking first unless `yes`, and checks again. Without
+/// a terminal to ask on (`no_interactive`, CI or piped stdin) `fix` needs `yes`.
+pub fn execute(fix: bool, yes: bool, no_interactive: bool) -> Result<()> {
+    let interactive = !no_interactive && std::env::var_os("CI").is_none() && std::io::stdin().is_terminal();
+    if fix && !yes && !interactive {
+        return Err(anyhow!(
+            "doctor --fix asks before each fix, which it can't do without a terminal; pass --yes to run the fixes without asking"
+        ));
+    }
+
+    let mut problems = 0;
+
+    println!("\n{}", "Rust toolchain".bold());
+    match rustc_version() {
+        Some(found) => {
+            println!("  {} rustc {}", "✓".green(), found);
+            for (template, required) in rust_version_requirements() {
+                match check_rust_version_against(&template, &required, &found) {
+                    Ok(()) => println!("    • {} (Rust >= {})", template.cyan(), required),
+                    Err(e) => {
+                        println!("  {} {}", "✗".red(), e);
+                        problems += 1;
+                    }
+                }
+            }
+        }
+        None => {
+            println!("  {} rustc not found. Install Rust from https://rustup.rs", "✗".red());
+            problems += 1;
+        }
+    }
+
+    println!("\n{}", "User templates".bold());
+    match user_templates_dir() {
+        Some(dir) if dir.is_dir() => {
+            println!("  Directory: {}", dir.display());
+
+            let (valid, invalid) = scan_user_templates();
+            println!("  {} {} available", "✓".green(), valid.len());
+            for template in &valid {
+                println!("    • {} - {}", template.name.cyan(), template.description);
+            }
+
+            for template in &invalid {
+                println!("  {} {}: {}", "✗".red(), template.path.display(), template.error);
+            }
+            problems += invalid.len();
+        }
+        Some(dir) => println!("  Directory: {} (not present, no user templates)", dir.display()),
+        None => println!("  No home directory found; set FERRISUP_TEMPLATE_DIR to use user templates"),
+    }
+
+    println!("\n{}", "Tools".bold());
+    let missing = check_tools();
+    if fix && !missing.is_empty() {
+        if fix_tools(&missing, yes)? {
+            println!("\n{}", "Tools, checked again".bold());
+            problems += check_tools().len();
+        } else {
+            problems += missing.len();
+        }
+    } else if !missing.is_empty() {
+        println!("  Install them with: {}", "ferrisup doctor --fix".cyan());
+    }
+
+    if problems > 0 {
+        return Err(anyhow!("Found {} problem(s) in the FerrisUp setup", problems));
+    }
+
+    println!("\n{}", "No problems found".green());
+    Ok(())
+}
+
+/// Report which tools are installed, returning the missing ones
+fn check_tools() -> Vec<&'static Tool> {
+    let mut missing = Vec::new();
+    for tool in TOOLS {
+        if tool.is_installed() {
+            println!("  {} {}", "✓".green(), tool.name);
+        } else {
+            println!("  {} {} not found (needed by {}): {}", "⚠".yellow(), tool.name, tool.needed_by, tool.advice);
+            missing.push(tool);
+        }
+    }
+    missing
+}
+
+/// Offer to install the `missing` tools, asking before each one unless `yes`
+///
+/// Tools that need administrator rights only get their advice. Returns whether anything
+/// was installed, so the tools are worth checking again.
+fn fix_tools(missing: &[&Tool], yes: bool) -> Result<bool> {
+    println!("\n{}", "Fixing".bold());
+    let mut installed_any = false;
+    for tool in missing {
+        let Some(install) = tool.install else {
+            println!("  {} {} needs administrator rights to install, so it's left to you: {}", "ℹ".blue(), tool.name, tool.advice);
+            continue;
+        };
+        let command = install.join(" ");
+        if offline::is_offline() {
+            println!("  {} Offline mode: skipping `{}`", "⚠".yellow(), command);
+            continue;
+        }
+        if !yes && !Confirm::new().with_prompt(format!("Run `{}`?", command)).default(true).interact()? {
+            continue;
+        }
+
+        println!("  Running {}", command.cyan());
+        let (program, args) = install.split_first().expect("install commands are never empty");
+        match Command::new(program).args(args).status() {
+            Ok(status) if status.success() => {
+                println!("  {} {} installed", "✓".green(), tool.name);
+                installed_any = true;
+            }
+            Ok(status) => println!("  {} `{}` failed ({})", "✗".red(), command, status),
+            Err(e) => println!("  {} Failed to run `{}`: {}", "✗".red(), command, e),
+        }
+    }
+    Ok(installed_any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixes_never_need_administrator_rights() {
+        for tool in TOOLS {
+            match tool.install {
+                Some(install) => {
+                    assert!(matches!(install.first(), Some(&("rustup" | "cargo"))), "{}: {:?}", tool.name, install);
+                    assert!(tool.advice.contains(&install.join(" ")), "{}", tool.name);
+                }
+                None => assert!(tool.advice.contains("sudo"), "{}", tool.name),
+            }
+        }
+    }
+}
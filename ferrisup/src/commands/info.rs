@@ -0,0 +1,329 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item};
+
+use crate::project::handler_for_project;
+
+/// Dependency-based hints used when a project has no `.ferrisup/origin.toml` stamp.
+/// Each entry maps a dependency name to (template, component type, framework).
+const DEPENDENCY_HINTS: &[(&str, &str, &str, &str)] = &[
+    ("leptos", "client", "client", "leptos"),
+    ("dioxus", "dioxus", "client", "dioxus"),
+    ("tauri", "tauri", "client", "tauri"),
+    ("axum", "axum", "server", "axum"),
+    ("actix-web", "actix", "server", "actix"),
+    ("poem", "poem", "server", "poem"),
+    ("burn", "burn", "data-science", "burn"),
+    ("linfa", "linfa", "data-science", "linfa"),
+    ("polars", "data-science", "data-science", "polars"),
+    ("lambda_http", "serverless", "serverless", "aws"),
+    ("lambda_runtime", "serverless", "serverless", "aws"),
+    ("worker", "edge", "edge", "cloudflare-workers"),
+    ("fastly", "edge", "edge", "fastly"),
+    ("embassy-executor", "embedded-embassy", "embedded", "embassy"),
+    ("rp2040-hal", "embedded", "embedded", "rp2040"),
+    ("esp-hal", "embedded", "embedded", "esp32"),
+    ("cortex-m", "embedded", "embedded", "cortex-m"),
+];
+
+/// Summary of an existing project, as printed by `ferrisup info`
+#[derive(Debug, Serialize)]
+pub struct ProjectInfo {
+    pub name: String,
+    pub version: Option<String>,
+    pub path: String,
+    pub template: Option<String>,
+    pub component_type: Option<String>,
+    pub framework: Option<String>,
+    pub handler: Option<String>,
+    /// Suggested commands from the handler, so `info` and `new` never disagree
+    pub build_command: Option<String>,
+    pub run_command: Option<String>,
+    /// Where the template information came from: "stamp", "metadata", "heuristic" or "unknown"
+    pub detected_from: String,
+    pub is_workspace_root: bool,
+    pub workspace_members: Vec<String>,
+    pub workspace_root: Option<String>,
+    pub dependency_count: usize,
+    pub dev_dependency_count: usize,
+}
+
+/// Execute the info command to inspect an existing project
+pub fn execute(path: Option<&str>, json: bool) -> Result<()> {
+    let project_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()?,
+    };
+
+    let info = inspect_project(&project_dir)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        print_info(&info);
+    }
+
+    Ok(())
+}
+
+/// Collect information about the project located at `project_dir`
+pub fn inspect_project(project_dir: &Path) -> Result<ProjectInfo> {
+    let cargo_toml_path = project_dir.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        return Err(anyhow::anyhow!("No Cargo.toml found in {}", project_dir.display()));
+    }
+
+    let content = fs::read_to_string(&cargo_toml_path)?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .context("Failed to parse Cargo.toml as TOML")?;
+
+    let package = doc.get("package");
+    let name = package
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(|n| n.to_string())
+        .or_else(|| {
+            project_dir
+                .canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string()))
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    let version = package
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string());
+
+    let dependencies = dependency_names(doc.get("dependencies"));
+    let dev_dependencies = dependency_names(doc.get("dev-dependencies"));
+
+    let workspace_members = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut info = ProjectInfo {
+        name,
+        version,
+        path: project_dir.display().to_string(),
+        template: None,
+        component_type: None,
+        framework: None,
+        handler: None,
+        build_command: None,
+        run_command: None,
+        detected_from: "unknown".to_string(),
+        is_workspace_root: doc.get("workspace").is_some(),
+        workspace_members,
+        workspace_root: find_workspace_root(project_dir).map(|p| p.display().to_string()),
+        dependency_count: dependencies.len(),
+        dev_dependency_count: dev_dependencies.len(),
+    };
+
+    // Prefer the stamp written at generation time, then the component type stored
+    // by `transform` in [package.metadata.ferrisup], then dependency heuristics
+    let metadata_component_type = package
+        .and_then(|p| p.get("metadata"))
+        .and_then(|m| m.get("ferrisup"))
+        .and_then(|f| f.get("component_type"))
+        .and_then(|c| c.as_str())
+        .map(|c| c.to_string());
+    if let Some(stamp) = read_origin_stamp(project_dir)? {
+        info.template = stamp_value(&stamp, "template");
+        info.component_type = stamp_value(&stamp, "component_type").or(metadata_component_type);
+        info.framework = stamp_value(&stamp, "framework");
+        info.detected_from = "stamp".to_string();
+    } else if let Some(component_type) = metadata_component_type {
+        info.component_type = Some(component_type);
+        info.detected_from = "metadata".to_string();
+    } else if let Some((template, component_type, framework)) = detect_from_dependencies(&dependencies) {
+        info.template = Some(template.to_string());
+        info.component_type = Some(component_type.to_string());
+        info.framework = Some(framework.to_string());
+        info.detected_from = "heuristic".to_string();
+    }
+
+    let variables = serde_json::json!({
+        "template": info.template,
+        "framework": info.framework,
+    });
+    if let Some(handler) = handler_for_project(info.template.as_deref(), &variables, project_dir) {
+        info.handler = Some(handler.name().to_string());
+        info.build_command = Some(handler.build_command().join(" "));
+        info.run_command = handler.run_command().map(|run| run.join(" "));
+    }
+
+    Ok(info)
+}
+
+/// Read `.ferrisup/origin.toml` if the project has one
+fn read_origin_stamp(project_dir: &Path) -> Result<Option<DocumentMut>> {
+    let stamp_path = project_dir.join(".ferrisup").join("origin.toml");
+    if !stamp_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&stamp_path)?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", stamp_path.display()))?;
+    Ok(Some(doc))
+}
+
+fn stamp_value(stamp: &DocumentMut, key: &str) -> Option<String> {
+    stamp.get(key).and_then(|v| v.as_str()).map(|v| v.to_string())
+}
+
+/// Collect the dependency names declared in a dependency table
+pub(crate) fn dependency_names(table: Option<&Item>) -> Vec<String> {
+    table
+        .and_then(|t| t.as_table_like())
+        .map(|t| t.iter().map(|(name, _)| name.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Guess the template, component type and framework from a dependency set
+pub(crate) fn detect_from_dependencies(dependencies: &[String]) -> Option<(&'static str, &'static str, &'static str)> {
+    DEPENDENCY_HINTS
+        .iter()
+        .find(|(dep, _, _, _)| dependencies.iter().any(|d| d == dep))
+        .map(|(_, template, component_type, framework)| (*template, *component_type, *framework))
+}
+
+/// Walk up from `project_dir` looking for a parent Cargo.toml with a [workspace] section
+fn find_workspace_root(project_dir: &Path) -> Option<PathBuf> {
+    let start = project_dir.canonicalize().ok()?;
+    let mut current = start.parent();
+
+    while let Some(dir) = current {
+        let cargo_toml = dir.join("Cargo.toml");
+        if let Ok(content) = fs::read_to_string(&cargo_toml) {
+            if let Ok(doc) = content.parse::<DocumentMut>() {
+                if doc.get("workspace").is_some() {
+                    return Some(dir.to_path_buf());
+                }
+            }
+        }
+        current = dir.parent();
+    }
+
+    None
+}
+
+fn print_info(info: &ProjectInfo) {
+    let unknown = "unknown".dimmed().to_string();
+    let display = |value: &Option<String>| {
+        value.as_ref().map(|v| v.cyan().to_string()).unwrap_or_else(|| unknown.clone())
+    };
+
+    println!("{}", "Project Information".bold().green());
+    println!("Name: {}", info.name.cyan());
+    if let Some(version) = &info.version {
+        println!("Version: {}", version.cyan());
+    }
+    println!("Path: {}", info.path.cyan());
+    println!("Template: {}", display(&info.template));
+    println!("Component type: {}", display(&info.component_type));
+    println!("Framework: {}", display(&info.framework));
+    if let Some(handler) = &info.handler {
+        println!("Handler: {}", handler.cyan());
+    }
+    if let Some(build) = &info.build_command {
+        println!("Build: {}", build.cyan());
+    }
+    if let Some(run) = &info.run_command {
+        println!("Run: {}", run.cyan());
+    }
+    match info.detected_from.as_str() {
+        "stamp" => println!("{}", "Detected from .ferrisup/origin.toml".dimmed()),
+        "metadata" => println!("{}", "Detected from [package.metadata.ferrisup] in Cargo.toml".dimmed()),
+        "heuristic" => println!("{}", "Detected heuristically from dependencies (no .ferrisup/origin.toml found)".yellow()),
+        _ => println!("{}", "Could not detect the template used for this project".yellow()),
+    }
+
+    if info.is_workspace_root {
+        println!("Workspace: {} ({} members)", "root".cyan(), info.workspace_members.len());
+        for member in &info.workspace_members {
+            println!("  - {}", member);
+        }
+    } else if let Some(root) = &info.workspace_root {
+        println!("Workspace: member of {}", root.cyan());
+    } else {
+        println!("Workspace: {}", "none".cyan());
+    }
+
+    println!("Dependencies: {}", info.dependency_count.to_string().cyan());
+    println!("Dev dependencies: {}", info.dev_dependency_count.to_string().cyan());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detects_template_from_dependencies() -> Result<()> {
+        let temp_dir = tempdir()?;
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"api\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\naxum = \"0.7\"\n",
+        )?;
+
+        let info = inspect_project(temp_dir.path())?;
+        assert_eq!(info.name, "api");
+        assert_eq!(info.template.as_deref(), Some("axum"));
+        assert_eq!(info.component_type.as_deref(), Some("server"));
+        assert_eq!(info.handler.as_deref(), Some("Server"));
+        assert_eq!(info.build_command.as_deref(), Some("cargo build"));
+        assert_eq!(info.run_command.as_deref(), Some("cargo run"));
+        assert_eq!(info.detected_from, "heuristic");
+        assert_eq!(info.dependency_count, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_origin_stamp_takes_precedence() -> Result<()> {
+        let temp_dir = tempdir()?;
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\naxum = \"0.7\"\n",
+        )?;
+        fs::create_dir_all(temp_dir.path().join(".ferrisup"))?;
+        fs::write(
+            temp_dir.path().join(".ferrisup").join("origin.toml"),
+            "template = \"minimal\"\ncomponent_type = \"minimal\"\n",
+        )?;
+
+        let info = inspect_project(temp_dir.path())?;
+        assert_eq!(info.template.as_deref(), Some("minimal"));
+        assert_eq!(info.detected_from, "stamp");
+        assert_eq!(info.framework, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_transform_metadata_takes_precedence_over_heuristics() -> Result<()> {
+        let temp_dir = tempdir()?;
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[package.metadata.ferrisup]\ncomponent_type = \"library\"\n\n[dependencies]\naxum = \"0.7\"\n",
+        )?;
+
+        let info = inspect_project(temp_dir.path())?;
+        assert_eq!(info.component_type.as_deref(), Some("library"));
+        assert_eq!(info.template, None);
+        assert_eq!(info.detected_from, "metadata");
+        Ok(())
+    }
+}
@@ -1,30 +1,333 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use colored::Colorize;
+use regex::Regex;
+use semver::{Version, VersionReq};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use walkdir::WalkDir;
+
+use crate::commands::offline;
 use crate::project::templates::list_templates;
+use crate::template_manager::template_version;
+
+/// Crates.io index that cargo's sparse protocol reads; unlike the web API it isn't rate limited
+const SPARSE_INDEX: &str = "https://index.crates.io";
+
+/// Latest stable release of each crate looked up in this process, `None` for unknown crates
+static LATEST_VERSIONS: Mutex<BTreeMap<String, Option<Version>>> = Mutex::new(BTreeMap::new());
 
-pub fn execute() -> Result<()> {
+pub fn execute(outdated: bool) -> Result<()> {
     let templates = list_templates()?;
     
     println!("\n{}", "Available templates:".green().bold());
     
     for (template_name, template_description) in templates {
+        let version = template_version(&template_name)
+            .map(|version| format!(" v{}", version).dimmed().to_string())
+            .unwrap_or_default();
         match template_name.as_str() {
-            "minimal" => println!("  • {} - {}", template_name.cyan().bold(), template_description),
-            "full-stack" => println!("  • {} - {}", template_name.magenta().bold(), template_description),
-            "data-science" => println!("  • {} - {}", template_name.blue().bold(), template_description),
-            "library" => println!("  • {} - {}", template_name.yellow().bold(), template_description),
-            "web-server" => println!("  • {} - {}", template_name.green().bold(), template_description),
-            "cli" => println!("  • {} - {}", template_name.red().bold(), template_description),
-            "embedded-embassy" => println!("  • {} - {}", template_name.cyan().bold(), template_description),
-            "desktop-dioxus" => println!("  • {} - {}", template_name.magenta().bold(), template_description),
-            "wasm" => println!("  • {} - {}", template_name.yellow().bold(), template_description),
-            "tauri" => println!("  • {} - {}", template_name.green().bold(), template_description),
-            _ => println!("  • {} - {}", template_name.white().bold(), template_description)
+            "minimal" => println!("  • {}{} - {}", template_name.cyan().bold(), version, template_description),
+            "full-stack" => println!("  • {}{} - {}", template_name.magenta().bold(), version, template_description),
+            "data-science" => println!("  • {}{} - {}", template_name.blue().bold(), version, template_description),
+            "library" => println!("  • {}{} - {}", template_name.yellow().bold(), version, template_description),
+            "web-server" => println!("  • {}{} - {}", template_name.green().bold(), version, template_description),
+            "cli" => println!("  • {}{} - {}", template_name.red().bold(), version, template_description),
+            "embedded-embassy" => println!("  • {}{} - {}", template_name.cyan().bold(), version, template_description),
+            "desktop-dioxus" => println!("  • {}{} - {}", template_name.magenta().bold(), version, template_description),
+            "wasm" => println!("  • {}{} - {}", template_name.yellow().bold(), version, template_description),
+            "tauri" => println!("  • {}{} - {}", template_name.green().bold(), version, template_description),
+            _ => println!("  • {}{} - {}", template_name.white().bold(), version, template_description)
         }
     }
-    
+
+    if outdated {
+        report_outdated()?;
+        return Ok(());
+    }
+
     println!("\n{}", "For more information about a template, use:".blue());
     println!("  {}", "ferrisup preview <template-name>".cyan());
     
     Ok(())
 }
+
+/// A dependency a bundled template pins behind the latest release on crates.io
+#[derive(Debug, PartialEq)]
+pub struct OutdatedDependency {
+    pub name: String,
+    /// Version requirement as written in the template
+    pub requirement: String,
+    pub latest: Version,
+}
+
+impl OutdatedDependency {
+    /// How far the requirement is behind, counted in the first version component that differs
+    pub fn lag(&self) -> String {
+        let pinned = pinned_version(&self.requirement);
+        if self.latest.major != pinned.major {
+            format!("{} major", self.latest.major.saturating_sub(pinned.major))
+        } else if self.latest.minor != pinned.minor {
+            format!("{} minor", self.latest.minor.saturating_sub(pinned.minor))
+        } else {
+            format!("{} patch", self.latest.patch.saturating_sub(pinned.patch))
+        }
+    }
+}
+
+/// Print the bundled templates whose pinned dependencies can't resolve to the latest release
+fn report_outdated() -> Result<()> {
+    println!("\n{}", "Checking template dependencies against crates.io...".blue());
+    if offline::is_offline() {
+        println!("{} Skipping the check, crates.io can't be reached in offline mode", "⚠️".yellow());
+        return Ok(());
+    }
+
+    let templates_dir = format!("{}/templates", env!("CARGO_MANIFEST_DIR"));
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(concat!("ferrisup/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let mut outdated_templates = 0;
+    for (template, dependencies) in template_dependencies(Path::new(&templates_dir))? {
+        let mut outdated = Vec::new();
+        for (name, requirement) in dependencies {
+            let latest = match latest_version(&client, &name) {
+                Ok(Some(latest)) => latest,
+                Ok(None) => continue,
+                Err(e) => {
+                    println!("{} Stopping the check: {}", "⚠️".yellow(), e);
+                    return Ok(());
+                }
+            };
+            if is_behind(&requirement, &latest) {
+                outdated.push(OutdatedDependency { name, requirement, latest });
+            }
+        }
+
+        if outdated.is_empty() {
+            continue;
+        }
+        outdated_templates += 1;
+        println!("\n{}", template.cyan().bold());
+        for dependency in outdated {
+            println!(
+                "  {} {} -> {} ({} behind)",
+                dependency.name,
+                dependency.requirement.yellow(),
+                dependency.latest.to_string().green(),
+                dependency.lag()
+            );
+        }
+    }
+
+    if outdated_templates == 0 {
+        println!("{}", "All template dependencies are up to date".green());
+    } else {
+        println!("\n{} template(s) pin outdated dependencies", outdated_templates);
+    }
+    Ok(())
+}
+
+/// The registry dependencies of every bundled template, as (crate, version requirement)
+///
+/// Manifests are grouped under the nearest directory with a `template.json`, so variant
+/// manifests (`Cargo.toml.csv.template`) and nested crates count for their template.
+pub fn template_dependencies(templates_dir: &Path) -> Result<BTreeMap<String, Vec<(String, String)>>> {
+    let mut templates: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for entry in WalkDir::new(templates_dir).into_iter().filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy();
+        if !entry.file_type().is_file() || !file_name.starts_with("Cargo.toml") {
+            continue;
+        }
+        let Some(template_dir) = entry
+            .path()
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != templates_dir)
+            .find(|dir| dir.join("template.json").exists())
+        else {
+            continue;
+        };
+        let template = template_dir
+            .strip_prefix(templates_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let dependencies = templates.entry(template).or_default();
+        for dependency in manifest_dependencies(&fs::read_to_string(entry.path())?) {
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+    }
+    Ok(templates)
+}
+
+/// Registry dependencies of a (possibly templated) manifest, read line by line since
+/// handlebars blocks keep templates from parsing as TOML
+fn manifest_dependencies(manifest: &str) -> Vec<(String, String)> {
+    let inline = Regex::new(r#"^([A-Za-z0-9_-]+)\s*=\s*"(\d[^"]*)""#).unwrap();
+    let table = Regex::new(r#"^([A-Za-z0-9_-]+)\s*=\s*\{(.*)\}"#).unwrap();
+    let version = Regex::new(r#"\bversion\s*=\s*"(\d[^"]*)""#).unwrap();
+    let package = Regex::new(r#"\bpackage\s*=\s*"([^"]+)""#).unwrap();
+
+    let mut dependencies = Vec::new();
+    let mut in_dependencies = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_dependencies = line.trim_end_matches(']').ends_with("dependencies");
+            continue;
+        }
+        if !in_dependencies {
+            continue;
+        }
+        if let Some(captures) = inline.captures(line) {
+            dependencies.push((captures[1].to_string(), captures[2].to_string()));
+        } else if let Some(captures) = table.captures(line) {
+            let Some(requirement) = version.captures(&captures[2]) else {
+                continue;
+            };
+            let name = package
+                .captures(&captures[2])
+                .map_or(&captures[1], |package| package.get(1).unwrap().as_str());
+            dependencies.push((name.to_string(), requirement[1].to_string()));
+        }
+    }
+    dependencies
+}
+
+/// Whether `requirement` keeps cargo from picking `latest`
+fn is_behind(requirement: &str, latest: &Version) -> bool {
+    match VersionReq::parse(requirement) {
+        Ok(req) => !req.matches(latest) && pinned_version(requirement) < *latest,
+        Err(_) => false,
+    }
+}
+
+/// The version a requirement names, with missing components as zero (`0.8` is `0.8.0`)
+fn pinned_version(requirement: &str) -> Version {
+    let mut parts = requirement
+        .trim_start_matches(['^', '=', '~', ' '])
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0));
+    Version::new(
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// The latest stable, non-yanked release of `name`, looked up once per process
+fn latest_version(client: &reqwest::blocking::Client, name: &str) -> Result<Option<Version>> {
+    if let Some(cached) = LATEST_VERSIONS.lock().unwrap().get(name) {
+        return Ok(cached.clone());
+    }
+
+    let url = format!("{}/{}", SPARSE_INDEX, index_path(name));
+    let response = client
+        .get(&url)
+        .send()
+        .map_err(|e| anyhow!("could not reach crates.io ({})", e))?;
+    let latest = if response.status() == reqwest::StatusCode::NOT_FOUND {
+        None
+    } else {
+        let body = response.error_for_status()?.text()?;
+        latest_release(&body)
+    };
+
+    LATEST_VERSIONS.lock().unwrap().insert(name.to_string(), latest.clone());
+    Ok(latest)
+}
+
+/// Highest stable, non-yanked version in a sparse index file (one JSON release per line)
+fn latest_release(index: &str) -> Option<Version> {
+    index
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|release| !release["yanked"].as_bool().unwrap_or(false))
+        .filter_map(|release| Version::parse(release["vers"].as_str()?).ok())
+        .filter(|version| version.pre.is_empty())
+        .max()
+}
+
+/// Path of a crate's file in the sparse index
+fn index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_dependencies() {
+        let manifest = r#"
+[package]
+name = "{{project_name}}"
+version = "0.1.0"
+
+[dependencies]
+anyhow = "1.0"
+{{#if (eq data_source "CSV files")}}
+polars = { version = "0.46.0", features = ["lazy", "csv"] }
+{{/if}}
+shared = { path = "../shared" }
+rand_new = { package = "rand", version = "0.9" }
+
+[features]
+xlsx = ["dep:rust_xlsxwriter"]
+
+[target.'cfg(target_arch = "wasm32")'.dependencies]
+wasm-bindgen = "0.2.100"
+"#;
+        assert_eq!(
+            manifest_dependencies(manifest),
+            vec![
+                ("anyhow".to_string(), "1.0".to_string()),
+                ("polars".to_string(), "0.46.0".to_string()),
+                ("rand".to_string(), "0.9".to_string()),
+                ("wasm-bindgen".to_string(), "0.2.100".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_behind() {
+        let latest = Version::new(0, 51, 0);
+        assert!(is_behind("0.46.0", &latest));
+        assert!(!is_behind("0.51", &latest));
+        assert!(!is_behind("1.0", &Version::new(1, 9, 3)));
+        assert!(is_behind("=1.0.2", &Version::new(1, 0, 3)));
+
+        let outdated = OutdatedDependency {
+            name: "polars".to_string(),
+            requirement: "0.46.0".to_string(),
+            latest,
+        };
+        assert_eq!(outdated.lag(), "5 minor");
+    }
+
+    #[test]
+    fn test_latest_release_skips_yanked_and_prereleases() {
+        let index = [
+            r#"{"name":"linfa","vers":"0.7.1","yanked":false}"#,
+            r#"{"name":"linfa","vers":"0.8.0-rc.1","yanked":false}"#,
+            r#"{"name":"linfa","vers":"0.7.2","yanked":true}"#,
+            r#"{"name":"linfa","vers":"0.6.9","yanked":false}"#,
+        ]
+        .join("\n");
+        assert_eq!(latest_release(&index), Some(Version::new(0, 7, 1)));
+        assert_eq!(index_path("Polars"), "po/la/polars");
+        assert_eq!(index_path("syn"), "3/s/syn");
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+use log::kv::{self, Key, VisitSource};
+use log::{LevelFilter, Log, Metadata, Record};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Written instead of the value of a variable that looks like a secret
+pub const REDACTED: &str = "<redacted>";
+
+/// Parts of variable names whose values are never written to the log: secrets, and the
+/// author's name and email since logs get attached to public bug reports
+const SENSITIVE_NAMES: &[&str] = &[
+    "password", "passwd", "secret", "token", "api_key", "apikey", "credential", "private_key", "author", "email",
+];
+
+/// Logs to the console like `env_logger`, and with `--log-file` also to a JSON-lines file
+///
+/// The file gets FerrisUp's debug records whatever the verbosity, so a log attached to a
+/// bug report shows every template file rendered and variable resolved.
+struct TeeLogger {
+    console: env_logger::Logger,
+    file: Option<Mutex<File>>,
+}
+
+impl TeeLogger {
+    fn file_enabled(&self, metadata: &Metadata) -> bool {
+        self.file.is_some()
+            && (metadata.level() <= log::Level::Warn
+                || (metadata.level() <= log::Level::Debug && metadata.target().starts_with("ferrisup")))
+    }
+}
+
+impl Log for TeeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.console.enabled(metadata) || self.file_enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.console.matches(record) {
+            self.console.log(record);
+        }
+        if let (true, Some(file)) = (self.file_enabled(record.metadata()), &self.file) {
+            if let Ok(mut file) = file.lock() {
+                // Losing a log line is better than failing the command
+                let _ = writeln!(file, "{}", json_line(record));
+            }
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+        if let Some(Ok(mut file)) = self.file.as_ref().map(|f| f.lock()) {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Install the logger for this run, at `level` on the console and writing `log_file` if given
+pub fn init(level: LevelFilter, log_file: Option<&Path>) -> Result<()> {
+    let console = env_logger::Builder::from_default_env().filter_level(level).build();
+    let file = log_file
+        .map(|path| File::create(path).with_context(|| format!("Failed to create log file {}", path.display())))
+        .transpose()?;
+    let max_level = match file {
+        Some(_) => console.filter().max(LevelFilter::Debug),
+        None => console.filter(),
+    };
+
+    log::set_boxed_logger(Box::new(TeeLogger { console, file: file.map(Mutex::new) }))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// Whether the value of the variable `name` must be kept out of the log
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_NAMES.iter().any(|sensitive| name.contains(sensitive))
+}
+
+/// The value of the variable `name` as written to the log, [`REDACTED`] if it's sensitive
+pub fn loggable_value(name: &str, value: &Value) -> String {
+    if is_sensitive(name) {
+        return REDACTED.to_string();
+    }
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Collects the key-values of a record as JSON fields
+struct Fields<'a>(&'a mut Map<String, Value>);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: kv::Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.insert(key.to_string(), Value::String(value.to_string()));
+        Ok(())
+    }
+}
+
+/// `record` as one JSON object: time, level, target and message, then its key-values
+fn json_line(record: &Record) -> String {
+    let mut line = Map::new();
+    line.insert("time".into(), Value::String(chrono::Utc::now().to_rfc3339()));
+    line.insert("level".into(), Value::String(record.level().to_string()));
+    line.insert("target".into(), Value::String(record.target().to_string()));
+    line.insert("message".into(), Value::String(record.args().to_string()));
+    let _ = record.key_values().visit(&mut Fields(&mut line));
+    Value::Object(line).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_line() {
+        let kvs = [("file", "src/lib.rs")];
+        let record = Record::builder()
+            .args(format_args!("Rendered template file"))
+            .level(log::Level::Debug)
+            .target("ferrisup::template")
+            .key_values(&kvs)
+            .build();
+        let line: Value = serde_json::from_str(&json_line(&record)).unwrap();
+        assert_eq!(line["level"], "DEBUG");
+        assert_eq!(line["target"], "ferrisup::template");
+        assert_eq!(line["message"], "Rendered template file");
+        assert_eq!(line["file"], "src/lib.rs");
+
+        assert_eq!(loggable_value("database_password", &json!("hunter2")), REDACTED);
+        assert_eq!(loggable_value("GITHUB_TOKEN", &json!("ghp_x")), REDACTED);
+        assert_eq!(loggable_value("authors", &json!("Ferris <ferris@example.com>")), REDACTED);
+        assert_eq!(loggable_value("mcu_target", &json!("esp32")), "esp32");
+        assert_eq!(loggable_value("minimal", &json!(true)), "true");
+    }
+}
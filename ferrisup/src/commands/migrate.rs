@@ -0,0 +1,252 @@
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+use crate::commands::info::{dependency_names, detect_from_dependencies};
+use crate::commands::new::STAMP_VERSION;
+use crate::project::find_handler;
+
+/// Keys of the current stamp schema, in the order `new` writes them
+const STAMP_FIELDS: &[&str] = &[
+    "template",
+    "component_type",
+    "framework",
+    "provider",
+    "application_type",
+    "license",
+    "template_version",
+    "template_commit",
+    "target_dir",
+    "profile",
+];
+
+/// A value `migrate` added to a stamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct StampChange {
+    pub key: String,
+    pub value: String,
+}
+
+/// Execute the migrate command for the project at `path`
+pub fn execute(path: Option<&str>) -> Result<()> {
+    let project_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()?,
+    };
+
+    let changes = migrate_stamp(&project_dir)?;
+    match changes {
+        None => println!(
+            "{} is already at version {}, nothing to migrate",
+            ".ferrisup/origin.toml".cyan(),
+            STAMP_VERSION
+        ),
+        Some(changes) => {
+            println!(
+                "{} Migrated {} to version {}",
+                "✅".green(),
+                ".ferrisup/origin.toml".cyan(),
+                STAMP_VERSION
+            );
+            for change in &changes {
+                println!("  + {} = {}", change.key.cyan(), change.value);
+            }
+            if changes.is_empty() {
+                println!("  No fields could be filled in from the project");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite the stamp of the project in `project_dir` in the current schema
+///
+/// Returns `None` when the stamp is already at [`STAMP_VERSION`], so migrating twice
+/// changes nothing, and otherwise the fields that were filled in. A missing template is
+/// taken from the component type when that names a template, then guessed from the
+/// dependencies like `ferrisup info` does; the framework is guessed the same way and the
+/// license comes from Cargo.toml. What can't be recovered, such as the template version
+/// the project was generated from, stays out. Keys FerrisUp doesn't know are kept.
+pub fn migrate_stamp(project_dir: &Path) -> Result<Option<Vec<StampChange>>> {
+    let stamp_path = project_dir.join(".ferrisup").join("origin.toml");
+    if !stamp_path.exists() {
+        return Err(anyhow!(
+            "No .ferrisup/origin.toml in {}, only projects generated by FerrisUp can be migrated",
+            project_dir.display()
+        ));
+    }
+    let content = fs::read_to_string(&stamp_path)?;
+    let stamp = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", stamp_path.display()))?;
+
+    let version = match stamp.get("stamp_version") {
+        None => 1,
+        Some(item) => item
+            .as_integer()
+            .ok_or_else(|| anyhow!("stamp_version in {} must be a number", stamp_path.display()))?,
+    };
+    if version > STAMP_VERSION {
+        return Err(anyhow!(
+            "{} is at version {}, newer than the version {} this FerrisUp writes. Update FerrisUp to migrate it",
+            stamp_path.display(),
+            version,
+            STAMP_VERSION
+        ));
+    }
+    if version == STAMP_VERSION {
+        return Ok(None);
+    }
+
+    let manifest = read_manifest(project_dir)?;
+    let detected = manifest
+        .as_ref()
+        .and_then(|doc| detect_from_dependencies(&dependency_names(doc.get("dependencies"))));
+    let value = |key: &str| stamp.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+
+    let mut filled: Vec<(&str, Option<String>)> = STAMP_FIELDS.iter().map(|key| (*key, value(key))).collect();
+    let mut changes = Vec::new();
+    let mut fill = |key: &str, inferred: Option<String>| {
+        let slot = filled.iter_mut().find(|(k, _)| *k == key).map(|(_, v)| v).unwrap();
+        if slot.is_none() {
+            if let Some(inferred) = inferred.filter(|v| !v.is_empty()) {
+                changes.push(StampChange { key: key.to_string(), value: inferred.clone() });
+                *slot = Some(inferred);
+            }
+        }
+    };
+
+    let component_type = value("component_type");
+    let template = value("template").or_else(|| {
+        component_type
+            .clone()
+            .filter(|component_type| find_handler(component_type, &serde_json::json!({}), project_dir).is_some())
+            .or_else(|| detected.map(|(template, _, _)| template.to_string()))
+    });
+    fill("template", template.clone());
+    let component_type = component_type.or_else(|| {
+        template
+            .as_deref()
+            .map(|template| template.split('/').next().unwrap_or(template).to_string())
+            .or_else(|| detected.map(|(_, component_type, _)| component_type.to_string()))
+    });
+    fill("component_type", component_type.clone());
+    // Only trust the dependencies' framework when they point at the same kind of project
+    fill(
+        "framework",
+        detected
+            .filter(|(_, detected_type, _)| component_type.as_deref().is_none_or(|c| c == *detected_type))
+            .map(|(_, _, framework)| framework.to_string()),
+    );
+    fill(
+        "license",
+        manifest
+            .as_ref()
+            .and_then(|doc| doc.get("package")?.get("license")?.as_str().map(|l| l.to_string())),
+    );
+
+    // Known fields in schema order, then whatever else the stamp had
+    let mut migrated = DocumentMut::new();
+    migrated.insert("stamp_version", toml_edit::value(STAMP_VERSION));
+    for (key, value) in &filled {
+        match (value, stamp.get(key)) {
+            (Some(value), _) => {
+                migrated.insert(key, toml_edit::value(value.as_str()));
+            }
+            // Present but not a string: kept as written
+            (None, Some(item)) => {
+                migrated.insert(key, item.clone());
+            }
+            (None, None) => {}
+        }
+    }
+    for (key, item) in stamp.iter() {
+        if key != "stamp_version" && !STAMP_FIELDS.contains(&key) {
+            migrated.insert(key, item.clone());
+        }
+    }
+    fs::write(&stamp_path, migrated.to_string())
+        .with_context(|| format!("Failed to write {}", stamp_path.display()))?;
+
+    Ok(Some(changes))
+}
+
+/// The project's Cargo.toml, if it has one
+fn read_manifest(project_dir: &Path) -> Result<Option<DocumentMut>> {
+    let manifest_path = project_dir.join("Cargo.toml");
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&manifest_path)?;
+    let doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+    Ok(Some(doc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// A stamp from before `stamp_version`, with the template, component type and framework only
+    const V1_STAMP: &str = "template = \"server\"\ncomponent_type = \"server\"\nframework = \"axum\"\n";
+
+    fn project_with_stamp(stamp: &str, manifest: &str) -> Result<tempfile::TempDir> {
+        let temp_dir = tempdir()?;
+        fs::write(temp_dir.path().join("Cargo.toml"), manifest)?;
+        fs::create_dir_all(temp_dir.path().join(".ferrisup"))?;
+        fs::write(temp_dir.path().join(".ferrisup/origin.toml"), stamp)?;
+        Ok(temp_dir)
+    }
+
+    #[test]
+    fn test_migrate_v1_stamp() -> Result<()> {
+        let project = project_with_stamp(
+            V1_STAMP,
+            "[package]\nname = \"api\"\nversion = \"0.1.0\"\nlicense = \"MIT\"\n\n[dependencies]\naxum = \"0.7\"\n",
+        )?;
+
+        let changes = migrate_stamp(project.path())?.expect("a v1 stamp is migrated");
+        assert_eq!(changes, vec![StampChange { key: "license".to_string(), value: "MIT".to_string() }]);
+        let stamp = fs::read_to_string(project.path().join(".ferrisup/origin.toml"))?;
+        assert_eq!(
+            stamp,
+            "stamp_version = 2\ntemplate = \"server\"\ncomponent_type = \"server\"\nframework = \"axum\"\nlicense = \"MIT\"\n"
+        );
+
+        // A second run finds the current version and leaves the file alone
+        assert_eq!(migrate_stamp(project.path())?, None);
+        assert_eq!(fs::read_to_string(project.path().join(".ferrisup/origin.toml"))?, stamp);
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_infers_missing_fields() -> Result<()> {
+        let project = project_with_stamp(
+            "component_type = \"client\"\nnotes = \"kept\"\n",
+            "[package]\nname = \"web\"\nversion = \"0.1.0\"\n\n[dependencies]\nleptos = \"0.7\"\n",
+        )?;
+
+        let changes = migrate_stamp(project.path())?.expect("a v1 stamp is migrated");
+        let keys: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.value.as_str())).collect();
+        assert_eq!(keys, vec![("template", "client"), ("framework", "leptos")]);
+        let stamp = fs::read_to_string(project.path().join(".ferrisup/origin.toml"))?;
+        assert!(stamp.starts_with("stamp_version = 2\ntemplate = \"client\"\n"), "{}", stamp);
+        assert!(stamp.contains("notes = \"kept\""), "{}", stamp);
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_refuses_newer_and_missing_stamps() -> Result<()> {
+        let project = project_with_stamp("stamp_version = 99\n", "[package]\nname = \"x\"\n")?;
+        assert!(migrate_stamp(project.path()).unwrap_err().to_string().contains("newer"));
+
+        let empty = tempdir()?;
+        assert!(migrate_stamp(empty.path()).is_err());
+        Ok(())
+    }
+}
@@ -8,12 +8,27 @@ pub mod workspace;
 pub mod component;
 pub mod dependency;
 pub mod unused_features;
+pub mod info;
 pub mod import_fixer;
 pub mod test_mode;
+pub mod verbosity;
+pub mod log_file;
+pub mod offline;
+pub mod output_format;
+pub mod progress;
+pub mod clean;
+pub mod doctor;
+pub mod verify;
+pub mod scale;
+pub mod ci;
+pub mod template;
+pub mod migrate;
 // Removed reference to unused module
 
 // Re-export the Commands enum for the CLI
-use clap::{Subcommand, ValueEnum};
+use clap::builder::PossibleValuesParser;
+use clap::{ArgGroup, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, ValueEnum)]
 pub enum ComponentType {
@@ -45,17 +60,21 @@ impl std::fmt::Display for ComponentType {
     }
 }
 
+// Parsed once per run, so the size of `New` with all its flags doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Commands {
     /// Create a new Rust project with interactive configuration
+    #[command(group(ArgGroup::new("repository").args(["vcs", "git"])))]
     New {
         /// Component name (optional, will prompt if not provided)
         #[arg(required = false)]
         name: Option<String>,
 
-        /// Component type to use (optional, will prompt if not provided)
-        #[arg(short, long)]
-        component_type: Option<ComponentType>,
+        /// Component type to use (optional, will prompt if not provided): one of the
+        /// bundled types or the name of a user template
+        #[arg(short, long, alias = "template", value_parser = parse_component_type)]
+        component_type: Option<String>,
         
         /// Framework to use for client, server, or embedded components
         #[arg(long)]
@@ -73,32 +92,182 @@ pub enum Commands {
         #[arg(short, long)]
         project_type: Option<String>,
 
-        /// Initialize a git repository
+        /// Set a template variable, skipping its prompt (repeatable, e.g. --set mcu_target=esp32)
+        #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+        set: Vec<(String, String)>,
+
+        /// Answer prompts from a TOML, YAML or JSON file mapping variables to values; --set
+        /// wins over it. A project's `.ferrisup/origin.toml` works too, recreating its template choices
+        #[arg(long, value_name = "FILE")]
+        answers: Option<PathBuf>,
+
+        /// Rust edition written to every generated Cargo.toml: 2018, 2021 or 2024
+        /// (templates are written for 2021, which is kept when this isn't given)
+        #[arg(long, value_name = "EDITION", value_parser = PossibleValuesParser::new(crate::template_manager::EDITIONS))]
+        edition: Option<String>,
+
+        /// Cargo features the template declares to enable by default, comma separated
+        /// (e.g. `--features xlsx` for data-science/polars-cli)
+        #[arg(long, value_name = "FEATURES", value_delimiter = ',')]
+        features: Vec<String>,
+
+        /// Add the crates listed in this TOML file to the generated Cargo.toml. It has
+        /// `[dependencies]`, `[dev-dependencies]` and `[build-dependencies]` tables like a
+        /// manifest; crates the template already depends on keep the template's version
+        #[arg(long, value_name = "FILE")]
+        dependencies_from: Option<PathBuf>,
+
+        /// Take every registry dependency of the generated Cargo.toml from this alternate
+        /// registry instead of crates.io
+        #[arg(long, value_name = "NAME")]
+        registry: Option<String>,
+
+        /// Index URL of --registry, written to the project's .cargo/config.toml. Needed when
+        /// the registry isn't in your cargo configuration yet
+        #[arg(long, value_name = "URL", requires = "registry")]
+        registry_url: Option<String>,
+
+        /// Cargo target directory for the project, written to its .cargo/config.toml. Relative
+        /// to the project, so `../target` shares the target directory of a monorepo root
+        #[arg(long, value_name = "PATH")]
+        target_dir: Option<String>,
+
+        /// Build profile written as [profile.release] to the project's Cargo.toml: `min-size`
+        /// (small binaries), `release-lto` (fast binaries) or `dev` (cargo's defaults). Some
+        /// templates pick one by default, such as `min-size` for edge
+        #[arg(long, value_name = "PROFILE", value_parser = PossibleValuesParser::new(crate::template_manager::PROFILES))]
+        profile: Option<String>,
+
+        /// Leave the project without a README instead of generating the standard one
+        #[arg(long)]
+        no_readme: bool,
+
+        /// Put the project under version control with an initial commit: `git`, `jj` for a
+        /// Jujutsu repository colocated with git, or `none`
+        #[arg(long, value_name = "VCS", value_parser = PossibleValuesParser::new(new::Vcs::NAMES))]
+        vcs: Option<String>,
+
+        /// Deprecated alias for --vcs git
         #[arg(short, long)]
         git: bool,
 
+        /// Message for the initial commit created with --vcs
+        #[arg(long, default_value = new::DEFAULT_COMMIT_MESSAGE)]
+        commit_message: String,
+
+        /// Add this URL as the `origin` remote of the new repository (requires --vcs)
+        #[arg(long, requires = "repository")]
+        git_remote: Option<String>,
+
+        /// Branch of the initial commit created with --vcs, regardless of git's init.defaultBranch
+        #[arg(long, value_name = "NAME", default_value = new::DEFAULT_GIT_BRANCH, requires = "repository")]
+        git_branch: String,
+
+        /// Generate into this existing directory instead of creating `./<name>`
+        #[arg(long, value_name = "DIR")]
+        into: Option<String>,
+
+        /// Create the project in this directory instead of `./<name>`, creating missing
+        /// parent directories; the crate is still named after <name>
+        #[arg(long, value_name = "DIR", conflicts_with = "into")]
+        path: Option<String>,
+
+        /// Overwrite files the template generates in an existing project directory
+        /// (other files in it are left alone)
+        #[arg(long)]
+        force: bool,
+
+        /// What to do with files the template generates that already exist in the project
+        /// directory: `skip` keeps them, `overwrite` replaces them (like --force), `prompt`
+        /// asks for each one
+        #[arg(long, value_name = "POLICY", conflicts_with = "force", value_parser = PossibleValuesParser::new(ferrisup_common::fs::OverwritePolicy::NAMES))]
+        overwrite_policy: Option<String>,
+
         /// Run cargo build after project creation
         #[arg(short, long)]
         build: bool,
 
+        /// Don't build the project, even for a template that builds by default
+        /// (`build_by_default` in its manifest)
+        #[arg(long, conflicts_with = "build")]
+        no_build_check: bool,
+
+        /// Generate example data into data/ with the template's own generator, for templates
+        /// that declare one (the polars and linfa data-science templates)
+        #[arg(long)]
+        sample_data: bool,
+
+        /// Write a CI workflow for the template: `github` for GitHub Actions, `none` to skip it
+        #[arg(long, value_name = "PROVIDER", default_value = "none", value_parser = PossibleValuesParser::new(ci::CI_PROVIDERS))]
+        ci: String,
+
+        /// License of the project: sets `license` in Cargo.toml and writes its text to LICENSE
+        #[arg(long, value_name = "SPDX", default_value = "MIT")]
+        license: String,
+
+        /// Leave out the tutorial comments and example code of templates that support it
+        #[arg(long)]
+        minimal: bool,
+
+        /// Version of the template to generate, refused unless it's the version this FerrisUp
+        /// bundles; the version used is recorded in .ferrisup/origin.toml either way
+        #[arg(long, value_name = "VERSION")]
+        template_version: Option<String>,
+
+        /// Author of the project, such as "Ferris <ferris@example.com>" (defaults to git's
+        /// user.name and user.email)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Warn if a crate with the project's name is already published on crates.io
+        #[arg(long)]
+        check_name: bool,
+
         /// Skip interactive prompts (for automated testing)
         #[arg(long)]
         no_interactive: bool,
     },
 
     /// Transform an existing project with interactive configuration
+    #[command(group(ArgGroup::new("target").args(["template", "to"])))]
     Transform {
         /// Path to the project to transform (optional, will prompt if not provided)
         #[arg(short, long)]
         project: Option<String>,
 
-        /// Template to transform to (optional, will prompt if not provided)
+        /// Template to transform to (optional, will prompt if not provided): `to-workspace`,
+        /// or a component type to add to a workspace, optionally with a framework (`client/leptos`)
         #[arg(short, long)]
         template: Option<String>,
+
+        /// Name of the workspace member to add (defaults to the component type)
+        #[arg(long, conflicts_with = "to")]
+        name: Option<String>,
+
+        /// Template the project was generated from, for --to (defaults to the one recorded
+        /// in its .ferrisup/origin.toml)
+        #[arg(long, value_name = "TEMPLATE", requires = "to")]
+        from: Option<String>,
+
+        /// Move the project onto this sibling template (e.g. `client/leptos/router` or
+        /// `server/poem`), applying what changes between the two templates. Files you
+        /// modified are conflicts, never overwritten without asking
+        #[arg(long, value_name = "TEMPLATE")]
+        to: Option<String>,
+
+        /// Print the files the template would move, create, modify or delete as a diff,
+        /// without changing the project
+        #[arg(long, requires = "target")]
+        dry_run: bool,
     },
 
     /// List available component types
-    List,
+    List {
+        /// Check the dependencies the bundled templates pin against the latest releases
+        /// on crates.io and show which templates are behind (for template maintainers)
+        #[arg(long)]
+        outdated: bool,
+    },
 
     /// Preview a component type without creating files
     /// 
@@ -109,9 +278,10 @@ pub enum Commands {
     /// - Limited support for complex template combinations
     #[cfg(not(feature = "workspace_test"))]
     Preview {
-        /// Component type to preview (optional, will prompt if not provided)
-        #[arg(short, long)]
-        component_type: Option<ComponentType>,
+        /// Component type to preview (optional, will prompt if not provided): one of the
+        /// bundled types or the name of a user template
+        #[arg(short, long, alias = "template", value_parser = parse_component_type)]
+        component_type: Option<String>,
         
         /// Framework to use for client, server, or embedded components
         #[arg(long)]
@@ -124,6 +294,19 @@ pub enum Commands {
         /// Application type for edge components
         #[arg(long)]
         application_type: Option<String>,
+
+        /// Print the template's full file layout, annotating files that depend on an option
+        #[arg(long)]
+        tree: bool,
+
+        /// List the variables the template declares (name, prompt, type, default, choices),
+        /// which can be answered up front with `new --set`
+        #[arg(long)]
+        list_variables: bool,
+
+        /// With --list-variables, print the variables as JSON for scripting
+        #[arg(long, requires = "list_variables")]
+        json: bool,
     },
 
     /// Manage project components (add/remove/list) with consistent component types
@@ -140,6 +323,19 @@ pub enum Commands {
         /// Path to the project (optional, will use current directory if not provided)
         #[arg(short, long)]
         project: Option<String>,
+
+        /// Remove a component even though other workspace crates depend on it, dropping
+        /// those dependencies
+        #[arg(long)]
+        force: bool,
+
+        /// Show what `remove` would change as a diff without touching the project
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the components (`list`) as JSON with their detected frameworks
+        #[arg(long)]
+        json: bool,
     },
 
     /// Manage configurations (export/import)
@@ -156,17 +352,45 @@ pub enum Commands {
         /// Path to export/import configuration (optional)
         #[arg(short, long)]
         path: Option<String>,
+
+        /// Format to export in (defaults to the path's extension, or JSON)
+        #[arg(long, value_enum)]
+        format: Option<config::ConfigFormat>,
+
+        /// Only check the file given to --import, without applying it
+        #[arg(long, requires = "import")]
+        validate_only: bool,
     },
 
     /// Manage Cargo workspaces
     Workspace {
-        /// Action to perform: init, add, remove, list, or optimize
+        /// Action to perform: init, add, remove, list, optimize, or sync
         #[arg(short, long)]
         action: Option<String>,
 
         /// Path to the workspace (optional, will use current directory if not provided)
         #[arg(short, long)]
         path: Option<String>,
+
+        /// Member path for the add/remove actions, relative to the workspace root (e.g. crates/api)
+        #[arg(required = false)]
+        member: Option<String>,
+
+        /// Output format for the list action: tree (default) or dot for Graphviz
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Edition the sync action gives every member (defaults to the one most members use)
+        #[arg(long)]
+        edition: Option<String>,
+
+        /// Let the sync action move dependencies members share into [workspace.dependencies]
+        #[arg(long)]
+        hoist: bool,
+
+        /// Show the diff of what the sync action would change without writing it
+        #[arg(long)]
+        dry_run: bool,
     },
     
     /// Manage project dependencies
@@ -179,5 +403,123 @@ pub enum Commands {
         /// Path to the project (optional, will use current directory if not provided)
         #[arg(short, long)]
         path: Option<String>,
+
+        /// Remove the unused features from Cargo.toml (a backup is saved to Cargo.toml.bak)
+        #[arg(long)]
+        apply: bool,
+
+        /// Exit with an error if any unused features are found (useful in CI)
+        #[arg(long)]
+        fail_on_unused: bool,
+    },
+
+    /// Remove build artifacts (target/ by default) from a crate or every workspace member
+    Clean {
+        /// Path to the project (optional, will use current directory if not provided)
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Also remove riskier artifacts: dist, models, outputs (comma separated)
+        #[arg(long, value_delimiter = ',')]
+        include: Vec<String>,
+
+        /// List what would be deleted without removing anything
+        #[arg(long)]
+        dry_run: bool,
     },
+
+    /// Grow an existing project step by step: convert it to a workspace, add client and
+    /// server components and CI, previewing each change before it's applied
+    Scale {
+        /// Path to the project (optional, will use current directory if not provided)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+
+    /// Show information about an existing project (template, framework, workspace, dependencies)
+    Info {
+        /// Path to the project (optional, will use current directory if not provided)
+        #[arg(short, long)]
+        path: Option<String>,
+
+        /// Print the information as JSON for tooling
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the FerrisUp setup for problems, such as invalid user templates or missing tools
+    Doctor {
+        /// Offer to install the missing tools and targets, then check again
+        #[arg(long)]
+        fix: bool,
+
+        /// Run the fixes without asking first
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+
+        /// Never prompt; --fix then needs --yes
+        #[arg(long)]
+        no_interactive: bool,
+    },
+
+    /// Upgrade a project's .ferrisup/origin.toml written by an older FerrisUp to the current format
+    Migrate {
+        /// Path to the project (optional, will use current directory if not provided)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+
+    /// Generate a template with its default answers in a sandbox and check that it compiles
+    Verify {
+        /// Template to verify (a component type or user template name)
+        #[arg(required_unless_present = "all", value_parser = parse_component_type)]
+        template: Option<String>,
+
+        /// Verify every bundled template and print a pass/fail matrix
+        #[arg(long, conflicts_with = "template")]
+        all: bool,
+    },
+
+    /// Author user templates: scaffold one, or check its manifest
+    Template {
+        /// Action to perform: new (scaffold a template in the user template directory) or validate
+        #[arg(value_parser = PossibleValuesParser::new(["new", "validate"]))]
+        action: String,
+
+        /// Template name; validate also takes the path to a template directory
+        name: String,
+    },
+}
+
+/// Parse a component type given to `new` or `preview`, accepting the bundled templates
+/// that aren't components (`minimal-workspace`) and the names of user templates next to
+/// the bundled component types
+fn parse_component_type(s: &str) -> Result<String, String> {
+    if let Ok(component_type) = ComponentType::from_str(s, true) {
+        return Ok(component_type.to_string());
+    }
+    let bundled = crate::template_manager::get_all_templates().unwrap_or_default();
+    if bundled.iter().any(|t| t == s) || crate::template_manager::find_user_template(s).is_some() {
+        return Ok(s.to_string());
+    }
+
+    let mut possible: Vec<String> = ComponentType::value_variants().iter().map(|c| c.to_string()).collect();
+    for template in bundled {
+        if !possible.contains(&template) {
+            possible.push(template);
+        }
+    }
+    Err(format!(
+        "unknown component type `{}` (possible values: {}, or a user template name)",
+        s,
+        possible.join(", ")
+    ))
+}
+
+/// Parse a `KEY=VALUE` pair given to `--set`
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("invalid KEY=VALUE: no `=` found in `{}`", s)),
+    }
 }
@@ -2,10 +2,19 @@ use std::path::{Path, PathBuf};
 use std::fs;
 // Removed unused import
 use std::process::Command;
+use chrono::Datelike;
 use colored::Colorize;
 use anyhow::{Result, anyhow};
-use dialoguer::{Select, Input};
+use dialoguer::{FuzzySelect, Select, Input};
+use crate::core::{crates_io, Error};
 use crate::template_manager;
+use crate::commands::offline;
+use crate::commands::progress::{self, Progress};
+use crate::commands::output_format::OutputFormat;
+use crate::commands::clean::format_bytes;
+use crate::commands::verbosity::{is_quiet, is_verbose, status_stdio};
+use crate::status;
+use serde::Serialize;
 use serde_json::{self, json, Value};
 use handlebars::Handlebars;
 use ferrisup_common::{fs::*, to_pascal_case};
@@ -17,21 +26,112 @@ use ferrisup_common::{fs::*, to_pascal_case};
 // This ensures we're always using the most up-to-date project creation methods
 // and reduces maintenance burden.
 
-// Main execute function to handle project creation
-pub fn execute(
-    name: Option<&str>,
-    component_type: Option<&str>,
-    framework: Option<&str>,
-    provider: Option<&str>,
-    application_type: Option<&str>,
-    git: bool,
-    build: bool,
-    no_interactive: bool,
-    _project_type: Option<&str>,
-) -> Result<()> {
+/// Message used for the initial commit when `--commit-message` isn't given
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Initial commit from FerrisUp";
+
+/// Initial branch of the repository when `--git-branch` isn't given
+pub const DEFAULT_GIT_BRANCH: &str = "main";
+
+/// Version control system a generated project is put under (`--vcs`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Vcs {
+    /// No repository
+    #[default]
+    None,
+    /// A git repository
+    Git,
+    /// A Jujutsu repository colocated with a git one, so git tools keep working
+    Jujutsu,
+}
+
+impl Vcs {
+    /// Names accepted by `--vcs`
+    pub const NAMES: &'static [&'static str] = &["git", "none", "jj"];
+
+    /// How the repository is called in messages
+    fn label(self) -> &'static str {
+        match self {
+            Vcs::Jujutsu => "Jujutsu",
+            Vcs::None | Vcs::Git => "Git",
+        }
+    }
+}
+
+impl std::str::FromStr for Vcs {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        match name {
+            "git" => Ok(Vcs::Git),
+            "none" => Ok(Vcs::None),
+            "jj" => Ok(Vcs::Jujutsu),
+            other => Err(anyhow!("Unknown version control system '{}', expected one of: {}", other, Vcs::NAMES.join(", "))),
+        }
+    }
+}
+
+impl Serialize for Vcs {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl std::fmt::Display for Vcs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Vcs::Git => "git",
+            Vcs::None => "none",
+            Vcs::Jujutsu => "jj",
+        })
+    }
+}
+
+/// `ferrisup new` as given on the command line
+///
+/// The name and component type are asked for when they're missing. `options` holds the
+/// other flags; [`execute`] fills in its template and name once they're known, its target
+/// directory from `into` or `path`, and its author from git when `--author` isn't given.
+#[derive(Debug, Clone)]
+pub struct NewArgs {
+    pub name: Option<String>,
+    pub component_type: Option<String>,
+    /// Existing directory to generate into (`--into`)
+    pub into: Option<String>,
+    /// Directory to create the project in, with any missing parents (`--path`)
+    pub path: Option<String>,
+    pub output_format: OutputFormat,
+    pub options: GenerateOptions,
+}
+
+/// Execute the new command: prompt for what `args` leaves out, then [`generate`] the project
+pub fn execute(args: NewArgs) -> Result<()> {
+    let NewArgs { name, component_type, into, path, output_format, options } = args;
+    let no_interactive = !options.interactive;
+
+    if into.is_some() && path.is_some() {
+        return Err(anyhow!("--path and --into are mutually exclusive: --into generates into an existing directory, --path creates one"));
+    }
+
+    // Resolve the --into directory up front so "." gets a real directory name. It's meant
+    // to have files already, so only a file in its place is refused
+    let target_dir = match &into {
+        Some(dir) => {
+            let dir = fs::canonicalize(dir).map_err(|e| anyhow!("Cannot use {} with --into: {}", dir, e))?;
+            ensure_empty_dir(&dir, true)?;
+            Some(dir)
+        }
+        None => path.as_ref().map(PathBuf::from),
+    };
+
     // Get project name
     let name = match name {
-        Some(name) => name.to_string(),
+        Some(name) => name,
+        // Generating into a directory names the project after it
+        None if target_dir.is_some() => target_dir
+            .as_ref()
+            .and_then(|dir| dir.file_name())
+            .map(|dir_name| dir_name.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("Cannot derive a project name from the target directory, pass a name explicitly"))?,
         None => {
             if no_interactive {
                 return Err(anyhow!("Project name is required in non-interactive mode"));
@@ -42,65 +142,964 @@ pub fn execute(
         }
     };
 
-    // Create project directory
-    let app_path = Path::new(&name);
-    create_directory(app_path)?;
+    // Reject names that would produce an invalid crate before prompting for anything else
+    template_manager::validate_project_name(&name)?;
+
+    // A new project directory has to be empty unless --force or --overwrite-policy says
+    // what to do with the files in it; --into is for adding a project to existing files
+    if into.is_none() {
+        let target = target_dir.clone().unwrap_or_else(|| PathBuf::from(&name));
+        if let Err(e) = ensure_empty_dir(&target, options.force || options.overwrite_policy.is_some()) {
+            if !target.is_dir() {
+                return Err(e);
+            }
+            eprintln!("Files already in {}:\n{}", target.display(), existing_entries(&target)?);
+            return Err(anyhow!(
+                "{}. Pass --force to generate into it anyway, or --into to add the project without overwriting anything",
+                e
+            ));
+        }
+    }
 
     // Get component type
-    let mut template = match component_type {
-        Some(template) => template.to_string(),
+    let template = match component_type {
+        Some(template) => template,
         None => {
             if no_interactive {
-                return Err(anyhow!("Component type is required in non-interactive mode"));
+                return Err(anyhow!("Component type is required in non-interactive mode, pass --template"));
             }
-            
-            let templates_with_desc = template_manager::list_templates()?;
-            let mut templates: Vec<&str> = templates_with_desc.iter().map(|(name, _)| name.as_str()).collect();
-            
-            // Add edge template if not present
-            if !templates.contains(&"edge") {
-                templates.push("edge");
+            select_template()?
+        }
+    };
+
+    // The author for manifests and LICENSE: --author, git's identity, or a prompt
+    let author = match options.author.clone().or_else(git_author) {
+        Some(author) => author,
+        None if no_interactive => String::new(),
+        None => Input::<String>::new()
+            .with_prompt("Author (name <email>, empty to skip)")
+            .allow_empty(true)
+            .interact_text()?,
+    };
+
+    // Directories above --path that don't exist yet are created for the project, and
+    // removed again if generation fails
+    let created_dir = path
+        .as_deref()
+        .and_then(|path| Path::new(path).parent())
+        .and_then(first_missing_dir);
+
+    let options = GenerateOptions {
+        template,
+        name,
+        target_dir,
+        author: Some(author).filter(|author| !author.trim().is_empty()),
+        ..options
+    };
+    match generate(options) {
+        Ok(mut project) => {
+            if output_format == OutputFormat::Json {
+                // Tools don't know the directory FerrisUp ran in
+                project.path = fs::canonicalize(&project.path).unwrap_or(project.path);
+                println!("{}", serde_json::to_string_pretty(&project)?);
             }
-            
-            // Create a vector of template descriptions
-            let descriptions: Vec<String> = templates.iter().map(|&name| {
-                if name == "edge" {
-                    return "Edge computing applications (Cloudflare, Vercel, Fastly, AWS, etc.)".to_string();
+            Ok(())
+        }
+        Err(e) => {
+            // A failed build leaves the generated project in place to fix
+            if let Some(dir) = created_dir.filter(|_| !matches!(e, Error::HookFailed { .. })) {
+                let _ = fs::remove_dir_all(dir);
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// Categories of the template picker, in the order they're listed
+const TEMPLATE_CATEGORIES: &[&str] = &["Starters", "Web", "Cloud", "Data science", "Embedded", "Your templates", "Other"];
+
+/// The picker category of a template from `list_templates`
+fn template_category(name: &str, description: &str) -> &'static str {
+    if description.ends_with("(user template)") {
+        return "Your templates";
+    }
+    match name {
+        "minimal" | "library" | "minimal-workspace" => "Starters",
+        "server" | "client" => "Web",
+        "serverless" | "edge" => "Cloud",
+        "embedded" => "Embedded",
+        _ if name.starts_with("data-science") => "Data science",
+        _ => "Other",
+    }
+}
+
+/// Ask for the template with a picker that filters as the user types
+///
+/// Templates are grouped by category and the category is part of each entry, so typing
+/// `web` or `cloud` narrows the list down to that group too.
+fn select_template() -> Result<String> {
+    let mut templates = template_manager::list_templates()?;
+    if !templates.iter().any(|(name, _)| name == "edge") {
+        templates.push(("edge".to_string(), "Edge computing applications (Cloudflare, Vercel, Fastly, AWS, etc.)".to_string()));
+    }
+
+    let rank = |category: &str| TEMPLATE_CATEGORIES.iter().position(|c| *c == category);
+    let mut entries: Vec<(&'static str, String, String)> = templates
+        .into_iter()
+        .map(|(name, description)| (template_category(&name, &description), name, description))
+        .collect();
+    // A stable sort keeps the listed order within a category
+    entries.sort_by_key(|(category, _, _)| rank(category));
+
+    let items: Vec<String> = entries
+        .iter()
+        .map(|(category, name, description)| format!("[{}] {} - {}", category, name, description))
+        .collect();
+    let selection = FuzzySelect::new()
+        .with_prompt("Select a component type (type to filter)")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    Ok(entries.swap_remove(selection).1)
+}
+
+/// The entries at the top of `dir`, one indented line each, at most ten
+fn existing_entries(dir: &Path) -> Result<String> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().to_string()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    let more = entries.len().saturating_sub(10);
+    let mut listing: Vec<String> = entries.into_iter().take(10).map(|entry| format!("  {}", entry)).collect();
+    if more > 0 {
+        listing.push(format!("  ... and {} more", more));
+    }
+    Ok(listing.join("\n"))
+}
+
+/// How the next steps `cd` into `dir`: relative to the current directory when it's below it
+fn cd_path(dir: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| dir.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    match relative {
+        Some(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Some(relative) => relative.display().to_string(),
+        None => dir.display().to_string(),
+    }
+}
+
+/// The outermost directory of `dir` and its ancestors that doesn't exist, if any
+fn first_missing_dir(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .take_while(|ancestor| !ancestor.exists())
+        .last()
+        .map(Path::to_path_buf)
+}
+
+/// What to generate with [`generate`]
+///
+/// Built with [`GenerateOptions::new`] and the builder methods, which mirror the
+/// flags of `ferrisup new`:
+///
+/// ```no_run
+/// let project = ferrisup::generate(
+///     ferrisup::GenerateOptions::new("embedded", "blinky")
+///         .target_dir("/tmp/blinky")
+///         .variable("mcu_target", "esp32")
+///         .offline(true),
+/// )?;
+/// println!("Generated {} files in {}", project.files.len(), project.path.display());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    /// Component type, bundled template path (`client/leptos/counter`) or user template name
+    pub template: String,
+    pub name: String,
+    /// Directory to generate into; defaults to `./<name>`. An existing directory keeps
+    /// its other files, but files the template generates are only overwritten with `force`
+    /// or as `overwrite_policy` says
+    pub target_dir: Option<PathBuf>,
+    /// Template variables, answering their prompts like `--set`
+    pub variables: serde_json::Map<String, Value>,
+    pub framework: Option<String>,
+    pub provider: Option<String>,
+    pub application_type: Option<String>,
+    /// Rust edition written to every generated Cargo.toml
+    pub edition: Option<String>,
+    /// Cargo features the template declares, added to `[features] default`
+    pub features: Vec<String>,
+    /// TOML file of extra crates to add to the generated Cargo.toml (`--dependencies-from`)
+    pub dependencies_from: Option<PathBuf>,
+    /// Alternate registry every registry dependency is taken from (`--registry`)
+    pub registry: Option<String>,
+    /// Index of `registry`, declared in the project's `.cargo/config.toml`
+    pub registry_url: Option<String>,
+    /// Cargo target directory written to the project's `.cargo/config.toml`, relative to
+    /// the project (`--target-dir`)
+    pub build_target_dir: Option<String>,
+    /// Build profile written as `[profile.release]` to the root Cargo.toml (`--profile`);
+    /// the template's default profile when `None`
+    pub profile: Option<String>,
+    /// Write the standard README; `false` leaves the project without one (`--no-readme`)
+    pub readme: bool,
+    /// Version control to initialize, with an initial commit of the generated files
+    pub vcs: Vcs,
+    pub commit_message: String,
+    pub git_remote: Option<String>,
+    /// Branch the initial commit is made on, whatever git's `init.defaultBranch` says
+    pub git_branch: String,
+    pub force: bool,
+    /// What to do with generated files that already exist in the target directory; `None`
+    /// refuses to generate over them unless `force` is set
+    pub overwrite_policy: Option<OverwritePolicy>,
+    /// Run `cargo build` once the project is generated: always with `Some(true)`, never
+    /// with `Some(false)`, and with `None` when the template sets `build_by_default`.
+    /// Fails with [`Error::HookFailed`] when the project doesn't build
+    pub build: Option<bool>,
+    /// Run the template's sample-data generators (`hooks.sample_data` in its manifest)
+    /// once the project is generated, filling `data/` to work with right away
+    pub sample_data: bool,
+    /// CI provider to write a workflow for (`github`); `None` writes none
+    pub ci: Option<String>,
+    /// SPDX license (`MIT`, `Apache-2.0`, ...) set in the manifests and written to LICENSE;
+    /// `None` leaves the template's own
+    pub license: Option<String>,
+    /// Leave out tutorial comments and example code, for templates with `supports_minimal`
+    pub minimal: bool,
+    /// Version the template must be at, refusing to generate any other
+    pub template_version: Option<String>,
+    /// Author `{{author}}` renders to, in manifests and the LICENSE copyright line
+    pub author: Option<String>,
+    /// Warn when the crate name is already taken on crates.io
+    pub check_name: bool,
+    /// Don't use the network, as in offline mode, for this generation
+    pub offline: bool,
+    /// Prompt for choices the template would otherwise default, like `new` without
+    /// `--no-interactive`
+    pub interactive: bool,
+}
+
+impl GenerateOptions {
+    pub fn new(template: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            name: name.into(),
+            target_dir: None,
+            variables: serde_json::Map::new(),
+            framework: None,
+            provider: None,
+            application_type: None,
+            edition: None,
+            features: Vec::new(),
+            dependencies_from: None,
+            registry: None,
+            registry_url: None,
+            build_target_dir: None,
+            profile: None,
+            readme: true,
+            vcs: Vcs::None,
+            commit_message: DEFAULT_COMMIT_MESSAGE.to_string(),
+            git_remote: None,
+            git_branch: DEFAULT_GIT_BRANCH.to_string(),
+            force: false,
+            overwrite_policy: None,
+            build: None,
+            sample_data: false,
+            ci: None,
+            license: None,
+            minimal: false,
+            template_version: None,
+            author: None,
+            check_name: false,
+            offline: false,
+            interactive: false,
+        }
+    }
+
+    pub fn target_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.target_dir = Some(dir.into());
+        self
+    }
+
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn framework(mut self, framework: impl Into<String>) -> Self {
+        self.framework = Some(framework.into());
+        self
+    }
+
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    pub fn application_type(mut self, application_type: impl Into<String>) -> Self {
+        self.application_type = Some(application_type.into());
+        self
+    }
+
+    pub fn edition(mut self, edition: impl Into<String>) -> Self {
+        self.edition = Some(edition.into());
+        self
+    }
+
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn dependencies_from(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dependencies_from = Some(path.into());
+        self
+    }
+
+    pub fn registry(mut self, name: impl Into<String>) -> Self {
+        self.registry = Some(name.into());
+        self
+    }
+
+    pub fn registry_url(mut self, url: impl Into<String>) -> Self {
+        self.registry_url = Some(url.into());
+        self
+    }
+
+    pub fn build_target_dir(mut self, dir: impl Into<String>) -> Self {
+        self.build_target_dir = Some(dir.into());
+        self
+    }
+
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    pub fn readme(mut self, readme: bool) -> Self {
+        self.readme = readme;
+        self
+    }
+
+    pub fn vcs(mut self, vcs: Vcs) -> Self {
+        self.vcs = vcs;
+        self
+    }
+
+    /// Same as `vcs(Vcs::Git)`, or `vcs(Vcs::None)` with `false`
+    pub fn git(mut self, git: bool) -> Self {
+        self.vcs = if git { Vcs::Git } else { Vcs::None };
+        self
+    }
+
+    pub fn commit_message(mut self, message: impl Into<String>) -> Self {
+        self.commit_message = message.into();
+        self
+    }
+
+    pub fn git_remote(mut self, url: impl Into<String>) -> Self {
+        self.git_remote = Some(url.into());
+        self
+    }
+
+    pub fn git_branch(mut self, branch: impl Into<String>) -> Self {
+        self.git_branch = branch.into();
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn overwrite_policy(mut self, policy: OverwritePolicy) -> Self {
+        self.overwrite_policy = Some(policy);
+        self
+    }
+
+    pub fn build(mut self, build: bool) -> Self {
+        self.build = Some(build);
+        self
+    }
+
+    pub fn sample_data(mut self, sample_data: bool) -> Self {
+        self.sample_data = sample_data;
+        self
+    }
+
+    pub fn ci(mut self, provider: impl Into<String>) -> Self {
+        self.ci = Some(provider.into());
+        self
+    }
+
+    pub fn license(mut self, license: impl Into<String>) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    pub fn minimal(mut self, minimal: bool) -> Self {
+        self.minimal = minimal;
+        self
+    }
+
+    pub fn template_version(mut self, version: impl Into<String>) -> Self {
+        self.template_version = Some(version.into());
+        self
+    }
+
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    pub fn check_name(mut self, check_name: bool) -> Self {
+        self.check_name = check_name;
+        self
+    }
+
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+}
+
+/// A project created by [`generate`], printed by `new --output-format json`
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedProject {
+    /// Directory the project was generated into
+    pub path: PathBuf,
+    pub name: String,
+    /// The template that was applied, after framework and provider choices were resolved
+    pub template: String,
+    /// Generated files, relative to `path`
+    pub files: Vec<PathBuf>,
+    /// The template variables the project was rendered with
+    pub variables: serde_json::Map<String, Value>,
+    /// The version control asked for, see `git_initialized` for whether it worked
+    pub vcs: Vcs,
+    pub git_initialized: bool,
+    /// Whether the template's sample-data generators ran and succeeded
+    pub sample_data_generated: bool,
+    /// The template's post-generate hook, which FerrisUp doesn't run
+    pub post_gen_hook: Option<String>,
+}
+
+/// Generate a project from a template without going through the CLI
+///
+/// This is what `ferrisup new` runs once it has a template and a project name, exposed
+/// for tools that embed FerrisUp. Nothing is written to the target until generation
+/// succeeded. Templates still ask on the terminal for variables, frameworks or providers
+/// that `options` doesn't answer, so pass the ones the template declares
+/// (see [`template_manager::template_variables`]).
+///
+/// Every call keeps its settings in its own [`template_manager::GenerationContext`], so
+/// projects can be generated one after another or from several threads, each with its
+/// own options. Offline mode turned on for the whole process (`--offline` or
+/// FERRISUP_OFFLINE) applies to every call.
+///
+/// Failures that callers may want to handle come back as their own [`Error`] variant,
+/// such as [`Error::TemplateNotFound`], [`Error::InvalidProjectName`] or [`Error::TargetExists`].
+pub fn generate(options: GenerateOptions) -> crate::core::Result<GeneratedProject> {
+    let GenerateOptions {
+        template,
+        name,
+        target_dir,
+        variables,
+        framework,
+        provider,
+        application_type,
+        edition,
+        features,
+        dependencies_from,
+        registry,
+        registry_url,
+        build_target_dir,
+        profile,
+        readme,
+        vcs,
+        commit_message,
+        git_remote,
+        git_branch,
+        force,
+        overwrite_policy,
+        build,
+        sample_data,
+        ci,
+        license,
+        minimal,
+        template_version,
+        author,
+        check_name,
+        offline: offline_mode,
+        interactive,
+    } = options;
+    let no_interactive = !interactive;
+
+    template_manager::validate_project_name(&name)?;
+    match vcs {
+        Vcs::None if git_remote.is_some() => {
+            return Err("A remote needs a repository, pass --vcs git or --vcs jj".into());
+        }
+        Vcs::None => {}
+        Vcs::Git | Vcs::Jujutsu => validate_branch_name(&git_branch)?,
+    }
+    let extra_dependencies = match &dependencies_from {
+        Some(path) => crate::commands::dependency::load_dependency_file(path)?,
+        None => Vec::new(),
+    };
+    match (&registry, &registry_url) {
+        (Some(registry), url) => template_manager::validate_registry(registry, url.as_deref())?,
+        (None, Some(_)) => return Err("--registry-url needs the name of the registry, pass --registry".into()),
+        (None, None) => {}
+    }
+    if overwrite_policy == Some(OverwritePolicy::Prompt) && !interactive {
+        return Err("--overwrite-policy prompt needs an interactive terminal, use skip or overwrite with --no-interactive".into());
+    }
+
+    // Validate the template variables before anything is written to disk. The template's
+    // `required_variables` are checked by `apply_template` once prompts and defaults are resolved
+    template_manager::validate_variable_overrides(&template, &variables)?;
+
+    // Templates written for specific editions are refused before anything is generated
+    if let Some(edition) = &edition {
+        template_manager::validate_edition(edition)?;
+    }
+    let mut ctx = template_manager::GenerationContext {
+        variable_overrides: variables.clone(),
+        author: author.as_deref().unwrap_or_default().trim().to_string(),
+        edition: edition.clone(),
+        minimal,
+        offline: offline_mode || offline::is_offline(),
+        interactive,
+        ..Default::default()
+    };
+    if minimal && !template_manager::supports_minimal(&template) {
+        status!(
+            "{} Template '{}' has no minimal variant, generating it in full",
+            "Note:".blue(),
+            template
+        );
+    }
+    template_manager::check_requested_edition(&template, ctx.edition.as_deref())?;
+    template_manager::check_rust_version(&template)?;
+    if let Some(version) = &template_version {
+        template_manager::check_template_version(&template, version)?;
+    }
+    if let Some(provider) = ci.as_deref().filter(|p| *p != "none") {
+        if !crate::commands::ci::CI_PROVIDERS.contains(&provider) {
+            return Err(format!(
+                "Unknown CI provider '{}', expected one of: {}",
+                provider,
+                crate::commands::ci::CI_PROVIDERS.join(", ")
+            )
+            .into());
+        }
+    }
+    let license = license.as_deref().map(template_manager::validate_license).transpose()?;
+    if check_name {
+        warn_if_name_taken(&name, ctx.offline);
+    }
+
+    // Generate into a staging directory and only move it into place once everything
+    // succeeded, so a failure never leaves a half-written project behind
+    let target_path = target_dir.unwrap_or_else(|| PathBuf::from(&name));
+    ctx.project_dir = Some(cd_path(&target_path)).filter(|dir| *dir != name);
+    let build_target_dir = build_target_dir
+        .map(|dir| template_manager::project_target_dir(&target_path, &dir))
+        .transpose()?;
+    let staging = StagingDir::new(&target_path)?;
+    if progress::progress_enabled(is_quiet(), no_interactive) {
+        ctx.progress = Some(Progress::start(staging.path()));
+    }
+    let requested_template = template.clone();
+    let template = generate_project(
+        &mut ctx,
+        staging.path(),
+        &name,
+        template,
+        framework.as_deref(),
+        provider.as_deref(),
+        application_type.as_deref(),
+    );
+    if let Some(progress) = ctx.progress.take() {
+        progress.finish();
+    }
+    let template = template?;
+    if let Some(edition) = &edition {
+        template_manager::set_project_edition(staging.path(), edition)?;
+    }
+    template_manager::enable_features(
+        staging.path(),
+        template.as_deref().unwrap_or(&requested_template),
+        &features,
+    )?;
+    crate::commands::dependency::merge_dependencies(&staging.path().join("Cargo.toml"), &extra_dependencies)?;
+    if let Some(registry) = &registry {
+        template_manager::set_project_registry(staging.path(), registry, registry_url.as_deref())?;
+    }
+    if let Some(dir) = &build_target_dir {
+        template_manager::set_project_target_dir(staging.path(), dir)?;
+    }
+    let profile = profile.or_else(|| template_manager::template_profile(template.as_deref().unwrap_or(&requested_template)));
+    if let Some(profile) = &profile {
+        template_manager::set_project_profile(staging.path(), profile)?;
+    }
+    // Generators that hand over to external tooling keep the README that tooling wrote.
+    // Server projects are written by ferrisup itself, from the selected framework's template
+    let readme_template = template.clone().or_else(|| {
+        ctx.variable("server_framework")
+            .and_then(|framework| framework.as_str().map(|framework| format!("server/{}", framework)))
+    });
+    match &readme_template {
+        _ if !readme => template_manager::remove_readme(staging.path())?,
+        Some(template) => {
+            let (build_command, run_command) = handler_commands(template, framework.as_deref(), &ctx.variables, &features, staging.path())
+                .unwrap_or_else(|| (vec!["cargo".to_string(), "build".to_string()], None));
+            template_manager::write_readme(staging.path(), template, &name, &build_command, run_command.as_deref())?;
+        }
+        None => {}
+    }
+    template_manager::apply_permissions(template.as_deref().unwrap_or(&requested_template), staging.path())?;
+    // The workflow is part of the generated files, so it's in the initial commit too
+    if ci.as_deref() == Some("github") {
+        let handler_variables = json!({ "template": template, "framework": framework });
+        crate::commands::ci::write_github_workflow(
+            staging.path(),
+            Some(template.as_deref().unwrap_or(&requested_template)),
+            &handler_variables,
+        )?;
+    }
+    if let Some(license) = license {
+        template_manager::set_project_license(staging.path(), license)?;
+        template_manager::write_license(
+            staging.path(),
+            license,
+            author.as_deref().unwrap_or(&format!("The {} authors", name)),
+            chrono::Local::now().year(),
+        )?;
+    }
+    // Templates copied from a git repository are pinned to its commit, bundled ones to their version
+    let template_commit = ctx.template_commit.take();
+    let version = match &template_commit {
+        Some(_) => None,
+        None => template_manager::template_version(template.as_deref().unwrap_or(&requested_template)),
+    };
+    // Generators that pick a template themselves record the one they applied, so the
+    // stamp names it (`client/leptos/counter` rather than `client`)
+    let applied_template = ctx.applied_template.take();
+    write_origin_stamp(
+        staging.path(),
+        &[
+            ("template", template.as_deref().or(applied_template.as_deref()).unwrap_or(&requested_template)),
+            ("component_type", requested_template.split('/').next().unwrap_or(&requested_template)),
+            ("framework", framework.as_deref().unwrap_or_default()),
+            ("provider", provider.as_deref().unwrap_or_default()),
+            ("application_type", application_type.as_deref().unwrap_or_default()),
+            ("license", license.unwrap_or_default()),
+            ("template_version", version.as_deref().unwrap_or_default()),
+            ("template_commit", template_commit.as_deref().unwrap_or_default()),
+            ("target_dir", build_target_dir.as_deref().unwrap_or_default()),
+            ("profile", profile.as_deref().unwrap_or_default()),
+        ],
+    )?;
+    // Create a .gitignore tailored to the template, keeping any entries it already ships or
+    // the target directory already has. Jujutsu reads it too
+    match &template {
+        Some(template) if vcs != Vcs::None => {
+            let existing = target_path.join(".gitignore");
+            let staged = staging.path().join(".gitignore");
+            if existing.is_file() && !staged.exists() {
+                fs::copy(&existing, &staged)?;
+            }
+            write_gitignore(staging.path(), template)?;
+        }
+        _ => {}
+    }
+    // Collected once everything is staged, so the list matches what is persisted and committed
+    let files = staging.files()?;
+
+    // Files in an existing target directory are only overwritten with --force or as
+    // --overwrite-policy says, and only the ones the template generates
+    let overwrite_policy = overwrite_policy.or(force.then_some(OverwritePolicy::Overwrite));
+    let listing = |paths: &[PathBuf]| {
+        paths
+            .iter()
+            .map(|path| format!("  {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let mut policy = OverwritePolicy::Overwrite;
+    if target_path.exists() {
+        let conflicts = staging.conflicts()?;
+        if !conflicts.is_empty() {
+            match overwrite_policy {
+                None => {
+                    eprintln!("Files that would be overwritten:\n{}", listing(&conflicts));
+                    return Err(Error::TargetExists(target_path));
                 }
-                
-                // Find the description for this template
-                for (template_name, desc) in &templates_with_desc {
-                    if template_name == name {
-                        return desc.clone();
-                    }
+                Some(OverwritePolicy::Overwrite) => {
+                    status!("{} Overwriting existing files:\n{}", "⚠️".yellow(), listing(&conflicts))
                 }
-                
-                // Default description if not found
-                "".to_string()
-            }).collect();
-            
-            // Format the items for display
-            let template_items: Vec<String> = templates.iter()
-                .zip(descriptions.iter())
-                .map(|(&name, desc)| format!("{} - {}", name, desc))
-                .collect();
-            
-            let selection = Select::new()
-                .with_prompt("Select a component type")
-                .items(&template_items)
-                .default(0)
-                .interact()?;
-                
-            templates[selection].to_string()
+                Some(other) => policy = other,
+            }
+        }
+    }
+
+    let (app_path, skipped) = staging.persist_with(policy, |file| {
+        Ok(dialoguer::Confirm::new()
+            .with_prompt(format!("{} already exists. Overwrite it?", file.display()))
+            .default(false)
+            .interact()?)
+    })?;
+    if !skipped.is_empty() {
+        status!("{} Kept existing files:\n{}", "ℹ️".blue(), listing(&skipped));
+    }
+    let files = files.into_iter().filter(|file| !skipped.contains(file)).collect();
+
+    let mut project = GeneratedProject {
+        path: app_path.clone(),
+        name: name.clone(),
+        template: template.clone().unwrap_or(requested_template),
+        files,
+        variables: std::mem::take(&mut ctx.variables),
+        vcs,
+        git_initialized: false,
+        sample_data_generated: false,
+        post_gen_hook: None,
+    };
+
+    // Generators that hand over to external tooling print their own next steps
+    let Some(template) = template else {
+        return Ok(project);
+    };
+    let app_path = app_path.as_path();
+    let summary = GenerationSummary::collect(app_path);
+
+    // Sample data is generated before the initial commit so it's part of it
+    if sample_data {
+        project.sample_data_generated = generate_sample_data(&template, app_path, &project.variables, ctx.offline)?;
+    }
+
+    // Initialize the repository if requested
+    let mut git_initialized = false;
+    if vcs != Vcs::None {
+        let label = vcs.label();
+        status!("🔄 Initializing {} repository...", label.to_lowercase());
+        let result = match vcs {
+            Vcs::Jujutsu => init_jj_repository(app_path, &commit_message, git_remote.as_deref(), &git_branch),
+            _ => init_git_repository(app_path, &commit_message, git_remote.as_deref(), &git_branch),
+        };
+        match result {
+            Ok(()) => {
+                git_initialized = true;
+                status!("✅ {} repository initialized on branch {} with commit \"{}\"", label, git_branch, commit_message);
+                if let Some(url) = &git_remote {
+                    status!("✅ Added remote origin {}", url);
+                }
+            }
+            // The project itself was generated fine, so don't fail the whole command
+            Err(e) => {
+                status!("{} Failed to initialize {} repository: {}", "⚠️".yellow(), label.to_lowercase(), e);
+                let init = if vcs == Vcs::Jujutsu { "jj git init --colocate" } else { "git init" };
+                status!("You can run `{}` in {} yourself", init, app_path.display());
+            }
         }
+    }
+
+    // The features are default ones now, but spell them out so the commands show what gets built
+    let feature_args = if features.is_empty() {
+        Vec::new()
+    } else {
+        vec!["--features".to_string(), features.join(",")]
     };
 
-    // Declare additional_vars here
-    let mut additional_vars = None;
+    // Build project if requested, or if the template builds by default and --no-build-check
+    // wasn't given. Heavy templates (`build_by_default = false`) note that it can be slow
+    let build_by_default = template_manager::get_template_config(&template)
+        .ok()
+        .and_then(|config| config.get("build_by_default")?.as_bool());
+    if build.unwrap_or(build_by_default == Some(true)) {
+        if build_by_default == Some(false) {
+            status!("{} Building {} can take a while and may need extra toolchains", "Note:".blue(), template);
+        }
+        status!("🔄 Building project...");
+        let output = Command::new("cargo")
+            .arg("build")
+            .args(&feature_args)
+            .args(ctx.offline.then_some("--offline"))
+            .current_dir(app_path)
+            .output()?;
+        if output.status.success() {
+            status!("✅ Project built successfully");
+        } else {
+            // Builds also fail on missing toolchains (wasm targets, GPU drivers), so the
+            // generated project is kept and the build left for the user to retry
+            let command = ["cargo".to_string(), "build".to_string()]
+                .into_iter()
+                .chain(feature_args.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+            status!("{} Build failed, the generated project was kept in {}", "⚠️".yellow(), app_path.display());
+            status!("Fix the error above and build it with: cd {} && {}", app_path.display(), command.cyan());
+            return Err(Error::HookFailed { name: command, code: output.status.code() });
+        }
+    }
+
+    // Print success message with instructions
+    status!("\n🎉 Project {} created successfully!", name);
+    
+    // We don't need to print next steps here as they're already printed in apply_template
+    // The next steps include the static server command if applicable
+
+    let post_gen_hook = template_manager::get_template_config(&template)
+        .ok()
+        .and_then(|config| config.pointer("/hooks/post_gen").and_then(|h| h.as_str()).map(|h| h.to_string()));
+    if let Some(hook) = &post_gen_hook {
+        log::info!(target: "ferrisup::hook", template = template.as_str(), hook = hook.as_str(); "Post-generate hook not run");
+    }
+    if !is_quiet() {
+        summary.print(vcs, git_initialized, post_gen_hook.as_deref());
+    }
+
+    if let Some((build_command, run_command)) = handler_commands(&template, framework.as_deref(), &project.variables, &features, app_path) {
+        status!("Build with: {}", build_command.join(" ").cyan());
+        if let Some(run) = run_command {
+            status!("Run with: {}", run.join(" ").cyan());
+        }
+    }
+
+    project.git_initialized = git_initialized;
+    project.post_gen_hook = post_gen_hook;
+    Ok(project)
+}
+
+/// The build and run commands of the handler for `template`, the ones `ferrisup info` shows
+///
+/// Cargo builds get the enabled `features` spelled out, and template `variables` that change
+/// the tooling (the serverless `cloud_provider`) pick the handler. `None` when no handler
+/// knows the project.
+fn handler_commands(
+    template: &str,
+    framework: Option<&str>,
+    variables: &serde_json::Map<String, Value>,
+    features: &[String],
+    project_dir: &Path,
+) -> Option<(Vec<String>, Option<Vec<String>>)> {
+    let handler_variables = json!({
+        "template": template,
+        "framework": framework,
+        "cloud_provider": variables.get("cloud_provider"),
+    });
+    let handler = crate::project::handler_for_project(Some(template), &handler_variables, project_dir)?;
+    let mut build_command = handler.build_command();
+    if build_command.first().map(String::as_str) == Some("cargo") && !features.is_empty() {
+        build_command.extend(["--features".to_string(), features.join(",")]);
+    }
+    Some((build_command, handler.run_command()))
+}
+
+/// Run the sample-data generators `template` declares in `hooks.sample_data`
+///
+/// Each entry is a command as program and arguments, rendered with the template's
+/// variables and run in the project, such as `["cargo", "run", "--", "generate"]`; cargo
+/// commands get `--offline` in offline mode. A failing generator fails with
+/// [`Error::HookFailed`], keeping the project. Returns whether sample data was generated.
+fn generate_sample_data(
+    template: &str,
+    app_path: &Path,
+    variables: &serde_json::Map<String, Value>,
+    offline: bool,
+) -> Result<bool> {
+    let commands = template_manager::get_template_config(template)
+        .ok()
+        .and_then(|config| config.pointer("/hooks/sample_data").and_then(|c| c.as_array()).cloned())
+        .unwrap_or_default();
+    if commands.is_empty() {
+        status!("{} Template '{}' has no sample-data generator, --sample-data is ignored", "Note:".blue(), template);
+        return Ok(false);
+    }
+
+    let context = handlebars::Context::wraps(variables)?;
+    status!("🔄 Generating sample data...");
+    for command in &commands {
+        let mut args = command
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|arg| arg.as_str())
+            .map(|arg| {
+                render_template_str(arg, &context)
+                    .map_err(|e| anyhow!("Failed to render the sample-data command argument '{}': {}", arg, e))
+            })
+            .collect::<Result<Vec<String>>>()?;
+        if offline && args.first().map(String::as_str) == Some("cargo") && args.len() > 1 {
+            args.insert(2, "--offline".to_string());
+        }
+        let Some((program, rest)) = args.split_first() else {
+            continue;
+        };
+        let error = match Command::new(program).args(rest).current_dir(app_path).output() {
+            Ok(output) if output.status.success() => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                if !stdout.trim().is_empty() {
+                    status!("{}", stdout.trim_end());
+                }
+                continue;
+            }
+            Ok(output) => {
+                eprintln!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+                Error::HookFailed { name: args.join(" "), code: output.status.code() }.into()
+            }
+            Err(e) => anyhow!("Failed to run {}: {}", program, e),
+        };
+        status!("{} Generating sample data failed, the generated project was kept in {}", "⚠️".yellow(), app_path.display());
+        status!("Generate it yourself with: cd {} && {}", app_path.display(), args.join(" ").cyan());
+        return Err(error);
+    }
+    status!("✅ Sample data generated");
+    Ok(true)
+}
+
+/// Generate the project files for `template` into `app_path`
+///
+/// Returns the final template name when the shared git and build steps should run, or
+/// `None` when the project was handed over to external tooling that prints its own next steps.
+fn generate_project(
+    ctx: &mut template_manager::GenerationContext,
+    app_path: &Path,
+    name: &str,
+    mut template: String,
+    framework: Option<&str>,
+    provider: Option<&str>,
+    application_type: Option<&str>,
+) -> Result<Option<String>> {
+    let no_interactive = !ctx.interactive;
+    // Start from the --set variables so the prompts below can be skipped for them
+    let set_variables = ctx.apply_variable_overrides(None);
+    let mut additional_vars = set_variables.clone();
 
     // Get template configuration to check for options
     let template_config = template_manager::get_template_config(&template)?;
     
+    // User templates shadow bundled ones, so none of the special handling below applies
+    if template_manager::find_user_template(&template).is_some() {
+        if no_interactive {
+            additional_vars = Some(Value::Object(default_option_answers(&template_config, additional_vars)));
+        }
+        template_manager::apply_template(ctx, &template, app_path, name, additional_vars)?;
+        return Ok(Some(template));
+    }
+    
     // Handle special templates
     if template == "server" {
         // Server template handling
@@ -111,8 +1110,8 @@ pub fn execute(
             if framework_options.contains(&fw) {
                 fw.to_string()
             } else {
-                println!("Warning: Provided framework '{}' is not valid for server components", fw);
-                println!("Valid options are: axum, actix, poem");
+                status!("Warning: Provided framework '{}' is not valid for server components", fw);
+                status!("Valid options are: axum, actix, poem");
                 
                 let selection = Select::new()
                     .with_prompt("Which web framework would you like to use?")
@@ -132,7 +1131,11 @@ pub fn execute(
             framework_options[selection].to_string()
         };
         
-        println!("Using {} as the server_framework", framework_selected);
+        status!("Using {} as the server_framework", framework_selected);
+        ctx.record_variables(&json!({
+            "project_name": name,
+            "server_framework": framework_selected
+        }));
         
         // We completely bypass the normal template processing for server templates
         // to ensure only the selected framework is included
@@ -157,7 +1160,7 @@ pub fn execute(
             // Create template vars
             let template_vars = json!({
                 "project_name": name,
-                "project_name_pascal_case": to_pascal_case(&name)
+                "project_name_pascal_case": to_pascal_case(name)
             });
             
             // Apply templating
@@ -196,14 +1199,14 @@ pub fn execute(
                     // Create template vars
                     let template_vars = json!({
                         "project_name": name,
-                        "project_name_pascal_case": to_pascal_case(&name)
+                        "project_name_pascal_case": to_pascal_case(name)
                     });
                     
                     // Apply templating
                     let rendered = match handlebars.render_template(&content, &template_vars) {
                         Ok(result) => result,
                         Err(e) => {
-                            println!("Warning: Template parsing error in {}: {}", file_name.to_string_lossy(), e);
+                            status!("Warning: Template parsing error in {}: {}", file_name.to_string_lossy(), e);
                             content
                         }
                     };
@@ -228,7 +1231,7 @@ pub fn execute(
             // Create template vars
             let template_vars = json!({
                 "project_name": name,
-                "project_name_pascal_case": to_pascal_case(&name),
+                "project_name_pascal_case": to_pascal_case(name),
                 "server_framework": framework_selected
             });
             
@@ -236,7 +1239,7 @@ pub fn execute(
             let rendered = match handlebars.render_template(&content, &template_vars) {
                 Ok(result) => result,
                 Err(e) => {
-                    println!("Warning: Template parsing error in README.md: {}", e);
+                    status!("Warning: Template parsing error in README.md: {}", e);
                     content
                 }
             };
@@ -269,7 +1272,7 @@ pub fn execute(
                 // Create template vars
                 let template_vars = json!({
                     "project_name": name,
-                    "project_name_pascal_case": to_pascal_case(&name),
+                    "project_name_pascal_case": to_pascal_case(name),
                     "server_framework": framework_selected
                 });
                 
@@ -277,7 +1280,7 @@ pub fn execute(
                 let rendered = match handlebars.render_template(&content, &template_vars) {
                     Ok(result) => result,
                     Err(e) => {
-                        println!("Warning: Template parsing error in README.md: {}", e);
+                        status!("Warning: Template parsing error in README.md: {}", e);
                         content
                     }
                 };
@@ -315,7 +1318,7 @@ pub fn execute(
                 // Create template vars
                 let template_vars = json!({
                     "project_name": name,
-                    "project_name_pascal_case": to_pascal_case(&name),
+                    "project_name_pascal_case": to_pascal_case(name),
                     "server_framework": framework_selected
                 });
                 
@@ -323,7 +1326,7 @@ pub fn execute(
                 let rendered = match handlebars.render_template(&content, &template_vars) {
                     Ok(result) => result,
                     Err(e) => {
-                        println!("Warning: Template parsing error in {}: {}", file_name_str, e);
+                        status!("Warning: Template parsing error in {}: {}", file_name_str, e);
                         content
                     }
                 };
@@ -334,7 +1337,7 @@ pub fn execute(
         }
         
         // Display success message and next steps
-        println!("\n✅ {} project created successfully!", name);
+        status!("\n✅ {} project created successfully!", name);
         
         // Get next steps for the selected framework
         let template_json_path = PathBuf::from(format!("{}/templates/server/template.json", env!("CARGO_MANIFEST_DIR")));
@@ -343,30 +1346,30 @@ pub fn execute(
             let template_config: Value = serde_json::from_str(&template_json)?;
             let next_steps_key = format!("next_steps_{}", framework_selected);
             
-            println!("\n🚀 Next steps:");
+            status!("\n🚀 Next steps:");
             if let Some(next_steps) = template_config.get(&next_steps_key).and_then(|s| s.as_array()) {
                 for step in next_steps {
                     if let Some(step_str) = step.as_str() {
-                        let processed_step = step_str.replace("{{project_name}}", &name);
-                        println!("  {}", processed_step);
+                        let processed_step = ctx.resolve_cd(step_str, name).replace("{{project_name}}", name);
+                        status!("  {}", processed_step);
                     }
                 }
             } else {
                 // Generic next steps if framework-specific ones aren't found
-                println!("  1. cd {}", name);
-                println!("  2. cargo run");
-                println!("  3. Visit http://localhost:3000 in your browser");
+                status!("  1. cd {}", ctx.cd_dir(name));
+                status!("  2. cargo run");
+                status!("  3. Visit http://localhost:3000 in your browser");
             }
         } else {
             // Generic next steps if template.json isn't found
-            println!("\n🚀 Next steps:");
-            println!("  1. cd {}", name);
-            println!("  2. cargo run");
-            println!("  3. Visit http://localhost:3000 in your browser");
+            status!("\n🚀 Next steps:");
+            status!("  1. cd {}", ctx.cd_dir(name));
+            status!("  2. cargo run");
+            status!("  3. Visit http://localhost:3000 in your browser");
         }
         
         // Skip the rest of the template handling code
-        return Ok(());
+        return Ok(None);
     } else if template == "serverless" {
         // Get cloud provider for serverless function
         let providers = ["aws", "gcp", "azure", "vercel", "netlify"];
@@ -374,11 +1377,11 @@ pub fn execute(
         // Use the provider parameter if provided, otherwise prompt for selection
         let selected_provider = if let Some(prov) = provider {
             if providers.contains(&prov) {
-                println!("Using {} as the cloud provider for your serverless function", prov);
+                status!("Using {} as the cloud provider for your serverless function", prov);
                 prov.to_string()
             } else {
-                println!("Warning: Provided provider '{}' is not valid for serverless components", prov);
-                println!("Valid options are: aws, gcp, azure, vercel, netlify");
+                status!("Warning: Provided provider '{}' is not valid for serverless components", prov);
+                status!("Valid options are: aws, gcp, azure, vercel, netlify");
                 
                 let selection = Select::new()
                     .with_prompt("Which cloud provider would you like to target for your serverless function?")
@@ -403,7 +1406,7 @@ pub fn execute(
         vars.insert("cloud_provider".to_string(), json!(selected_provider));
         
         // Use the template manager for serverless template with the selected provider
-        template_manager::apply_template(&template, app_path, &name, Some(serde_json::Value::Object(vars)))?;
+        template_manager::apply_template(ctx, &template, app_path, name, Some(serde_json::Value::Object(vars)))?;
         
         // Clean up provider-specific directories that weren't selected
         let providers = ["aws", "gcp", "azure", "vercel", "netlify"];
@@ -444,7 +1447,7 @@ pub fn execute(
             fs::remove_file(root_main_rs)?;
         }
         
-        return Ok(());
+        return Ok(None);
     } else if template == "edge" {
         // Handle edge template specifically to support the hierarchical structure
         // Get the edge template configuration
@@ -489,16 +1492,16 @@ pub fn execute(
                             })
                             .collect();
                         
-                        println!("Edge computing applications allow you to run Rust code close to your users.");
+                        status!("Edge computing applications allow you to run Rust code close to your users.");
                             
                         // Use the application_type parameter if provided, otherwise prompt for selection
                         let selected_app_type = if let Some(app_type) = application_type {
                             if app_type_options.contains(&app_type) {
-                                println!("Using {} as the edge application type", app_type);
+                                status!("Using {} as the edge application type", app_type);
                                 app_type.to_string()
                             } else {
-                                println!("Warning: Provided application type '{}' is not valid for edge components", app_type);
-                                println!("Valid options are: {}", app_type_options.join(", "));
+                                status!("Warning: Provided application type '{}' is not valid for edge components", app_type);
+                                status!("Valid options are: {}", app_type_options.join(", "));
                                 
                                 // Let user select application type
                                 let app_type_selection = Select::new()
@@ -520,7 +1523,7 @@ pub fn execute(
                             app_type_options[app_type_selection].to_string()
                         };
 
-                        println!("Selected application type: {}", selected_app_type);
+                        status!("Selected application type: {}", selected_app_type);
 
                         // Second level: Get provider options for the selected type
                         let provider_field = match selected_app_type.as_str() {
@@ -557,15 +1560,37 @@ pub fn execute(
                                     })
                                     .collect();
                                 
-                                // Let user select provider
-                                let provider_selection = Select::new()
-                                    .with_prompt("Select provider")
-                                    .items(&provider_display)
-                                    .default(0)
-                                    .interact()?;
-                                    
-                                let selected_provider = provider_options[provider_selection];
-                                println!("Selected provider: {}", selected_provider);
+                                // Accept the aliases the option declares, like `cloudflare` for `cloudflare-workers`
+                                let provider = provider.map(|prov| {
+                                    provider_option.get("aliases")
+                                        .and_then(|aliases| aliases.get(prov))
+                                        .and_then(|target| target.as_str())
+                                        .unwrap_or(prov)
+                                });
+
+                                // Use the provider parameter if it is valid for this application type,
+                                // otherwise let the user select one
+                                let selected_provider = match provider.and_then(|prov| provider_options.iter().copied().find(|&opt| opt == prov)) {
+                                    Some(prov) => {
+                                        status!("Using {} as the edge provider", prov);
+                                        prov
+                                    }
+                                    None => {
+                                        if let Some(prov) = provider {
+                                            status!("Warning: Provided provider '{}' is not valid for {} edge components", prov, selected_app_type);
+                                            status!("Valid options are: {}", provider_options.join(", "));
+                                        }
+
+                                        let provider_selection = Select::new()
+                                            .with_prompt("Select provider")
+                                            .items(&provider_display)
+                                            .default(0)
+                                            .interact()?;
+
+                                        provider_options[provider_selection]
+                                    }
+                                };
+                                status!("Selected provider: {}", selected_provider);
                                 
                                 // Create variables for template
                                 let mut vars_map = serde_json::Map::new();
@@ -587,7 +1612,7 @@ pub fn execute(
                                             
                                             // Check if the template directory exists
                                             let full_template_path = format!("{}/templates/{}", env!("CARGO_MANIFEST_DIR"), template);
-                                            println!("Using template: {}", template);
+                                            status!("Using template: {}", template);
                                             
                                             // Check if the directory exists
                                             if !Path::new(&full_template_path).exists() {
@@ -595,8 +1620,8 @@ pub fn execute(
                                             }
                                             
                                             // Handle the edge template explicitly
-                                            handle_edge_template(&template, app_path, &name, additional_vars.clone())?;
-                                            return Ok(());
+                                            handle_edge_template(ctx, &template, app_path, name, additional_vars.clone())?;
+                                            return Ok(None);
                                         } else {
                                             return Err(anyhow!("No template configuration found for provider: {}", selected_provider));
                                         }
@@ -629,7 +1654,7 @@ pub fn execute(
         if template != "server" && template != "serverless" && !template.starts_with("data-science/") {
             // Check if the template has prompts that need user input
             if let Some(prompts) = template_config.get("prompts").and_then(|p| p.as_array()) {
-                println!("\n📊 Data Science Project Configuration\n");
+                status!("\n📊 Data Science Project Configuration\n");
                 
                 let mut vars = serde_json::Map::new();
                 
@@ -639,6 +1664,12 @@ pub fn execute(
                         prompt.get("question").and_then(|q| q.as_str()),
                         prompt.get("options").and_then(|o| o.as_array())
                     ) {
+                        // Variables given with --set don't need a prompt
+                        if let Some(value) = set_variables.as_ref().and_then(|v| v.get(name)) {
+                            vars.insert(name.to_string(), value.clone());
+                            continue;
+                        }
+
                         let option_values: Vec<&str> = options
                             .iter()
                             .filter_map(|v| v.as_str())
@@ -660,7 +1691,7 @@ pub fn execute(
                             vars.insert(name.to_string(), json!(selected_value));
                             
                             // Print the selection
-                            println!("\n📊 {} {}: {}", 
+                            status!("\n📊 {} {}: {}", 
                                 match name {
                                     "data_source" => "What type of data will you be working with?",
                                     "analysis_type" => "What type of analysis do you plan to perform?",
@@ -691,8 +1722,8 @@ pub fn execute(
 
     // For client template, prompt for framework selection
     if template == "client" {
-        println!("Template description: Custom template: client");
-        println!("Using template: client");
+        status!("Template description: Custom template: client");
+        status!("Using template: client");
         
         // Get client framework
         let frameworks = vec!["dioxus", "tauri", "leptos"];
@@ -702,8 +1733,8 @@ pub fn execute(
             if frameworks.contains(&fw) {
                 fw.to_string()
             } else {
-                println!("Warning: Provided framework '{}' is not valid for client components", fw);
-                println!("Valid options are: dioxus, tauri, leptos");
+                status!("Warning: Provided framework '{}' is not valid for client components", fw);
+                status!("Valid options are: dioxus, tauri, leptos");
                 
                 let selection = Select::new()
                     .with_prompt("Select Rust client framework")
@@ -725,53 +1756,26 @@ pub fn execute(
         
         // For Leptos, prompt for specific template type
         if framework_selected == "leptos" {
-            println!("📦 Using Leptos templates to bootstrap the project");
-            println!("🔧 Checking for required dependencies...");
+            status!("📦 Using Leptos templates to bootstrap the project");
+            status!("🔧 Checking for required dependencies...");
             
             // Check for wasm32-unknown-unknown target
-            println!("🔍 Checking for wasm32-unknown-unknown target...");
+            status!("🔍 Checking for wasm32-unknown-unknown target...");
             let wasm_check = Command::new("rustup")
                 .args(["target", "list", "--installed"])
                 .output()?;
             
             let wasm_output = String::from_utf8_lossy(&wasm_check.stdout);
             if !wasm_output.contains("wasm32-unknown-unknown") {
-                println!("⚠️ wasm32-unknown-unknown target not found. Installing...");
-                let status = Command::new("rustup")
-                    .args(["target", "add", "wasm32-unknown-unknown"])
-                    .status()?;
-                
-                if !status.success() {
-                    println!("❌ Failed to install wasm32-unknown-unknown target.");
-                    println!("Please install it manually with: rustup target add wasm32-unknown-unknown");
+                status!("⚠️ wasm32-unknown-unknown target not found. Installing...");
+                if !run_install(ctx.offline, "rustup", &["target", "add", "wasm32-unknown-unknown"])? {
+                    status!("❌ Failed to install wasm32-unknown-unknown target.");
+                    status!("Please install it manually with: rustup target add wasm32-unknown-unknown");
                 } else {
-                    println!("✅ wasm32-unknown-unknown target installed successfully");
+                    status!("✅ wasm32-unknown-unknown target installed successfully");
                 }
             } else {
-                println!("✅ wasm32-unknown-unknown target is already installed");
-            }
-            
-            // Check for trunk (needed for counter, router, todo templates)
-            println!("🔍 Checking for Trunk...");
-            let trunk_check = Command::new("trunk")
-                .arg("--version")
-                .output();
-            
-            match trunk_check {
-                Ok(_) => println!("✅ Trunk is already installed"),
-                Err(_) => {
-                    println!("⚠️ Trunk not found. Installing...");
-                    let status = Command::new("cargo")
-                        .args(["install", "trunk", "--locked"])
-                        .status()?;
-                    
-                    if !status.success() {
-                        println!("❌ Failed to install Trunk.");
-                        println!("Please install it manually with: cargo install trunk --locked");
-                    } else {
-                        println!("✅ Trunk installed successfully");
-                    }
-                }
+                status!("✅ wasm32-unknown-unknown target is already installed");
             }
             
             let leptos_templates = vec![
@@ -780,11 +1784,15 @@ pub fn execute(
                 "Todo - Todo application with filtering",
             ];
             
-            let leptos_selection = Select::new()
-                .with_prompt("✨ Which Leptos template would you like to use?")
-                .items(&leptos_templates)
-                .default(0)
-                .interact()?;
+            let leptos_selection = if no_interactive {
+                0
+            } else {
+                Select::new()
+                    .with_prompt("✨ Which Leptos template would you like to use?")
+                    .items(&leptos_templates)
+                    .default(0)
+                    .interact()?
+            };
                 
             // Map selection to template name
             template = match leptos_selection {
@@ -794,28 +1802,60 @@ pub fn execute(
                 _ => "counter".to_string(), // Default to counter if somehow none selected
             };
             
-            println!("🔧 Creating new Leptos project with {} template...", template);
-            
             // For Leptos templates, prepend "client/leptos/"
             let template_path = format!("client/leptos/{}", template);
+            additional_vars = select_rendering_mode(&template_path, additional_vars, no_interactive)?;
+            additional_vars = select_styling(&template_path, additional_vars, no_interactive)?;
+            
+            // Client-side rendering is served by Trunk, server-side rendering by cargo-leptos
+            if rendering_mode(&additional_vars) == "ssr" {
+                check_cargo_leptos(ctx.offline)?;
+            } else {
+                // Trunk runs the Tailwind CLI through npx, cargo-leptos downloads it by itself
+                let tailwind = additional_vars.as_ref().and_then(|v| v.get("styling")).and_then(|s| s.as_str()) == Some("tailwind");
+                if tailwind && Command::new("npx").arg("--version").output().is_err() {
+                    status!("⚠️ npx not found. Install Node.js so Trunk can run the Tailwind CLI before each build");
+                }
+
+                status!("🔍 Checking for Trunk...");
+                let trunk_check = Command::new("trunk")
+                    .arg("--version")
+                    .output();
+            
+                match trunk_check {
+                    Ok(_) => status!("✅ Trunk is already installed"),
+                    Err(_) => {
+                        status!("⚠️ Trunk not found. Installing...");
+                        if !run_install(ctx.offline, "cargo", &["install", "trunk", "--locked"])? {
+                            status!("❌ Failed to install Trunk.");
+                            status!("Please install it manually with: cargo install trunk --locked");
+                        } else {
+                            status!("✅ Trunk installed successfully");
+                        }
+                    }
+                }
+            }
+            
+            status!("🔧 Creating new Leptos project with {} template...", template);
             
             if let Err(e) = template_manager::apply_template(
+                ctx,
                 &template_path,
                 app_path,
-                &name,
+                name,
                 additional_vars,
             ) {
                 return Err(e);
             }
             
             // DO NOT print next steps here; let the template manager handle it
-            return Ok(());
+            return Ok(None);
             
         } else if framework_selected == "dioxus" {
-            println!("📦 Creating Dioxus project with dioxus-cli");
+            status!("📦 Creating Dioxus project with dioxus-cli");
             
             // Check if dioxus-cli is installed
-            println!("🔍 Checking for dioxus-cli...");
+            status!("🔍 Checking for dioxus-cli...");
             let dx_check = Command::new("dx")
                 .arg("--version")
                 .output();
@@ -826,53 +1866,46 @@ pub fn execute(
             };
             
             if !dx_installed {
-                println!("⚠️ dioxus-cli not found. Installing...");
-                let install_status = Command::new("cargo")
-                    .args(["install", "dioxus-cli"])
-                    .status()?;
-                
-                if !install_status.success() {
+                offline::ensure_online_when(ctx.offline, "install dioxus-cli")?;
+                status!("⚠️ dioxus-cli not found. Installing...");
+                if !run_install(ctx.offline, "cargo", &["install", "dioxus-cli"])? {
                     return Err(anyhow!("Failed to install dioxus-cli"));
                 }
-                println!("✅ dioxus-cli installed successfully");
+                status!("✅ dioxus-cli installed successfully");
             } else {
-                println!("✅ dioxus-cli is already installed");
+                status!("✅ dioxus-cli is already installed");
             }
             
             // Check for wasm32-unknown-unknown target (required for web)
-            println!("🔍 Checking for wasm32-unknown-unknown target...");
+            status!("🔍 Checking for wasm32-unknown-unknown target...");
             let wasm_check = Command::new("rustup")
                 .args(["target", "list", "--installed"])
                 .output()?;
             
             let wasm_output = String::from_utf8_lossy(&wasm_check.stdout);
             if !wasm_output.contains("wasm32-unknown-unknown") {
-                println!("⚠️ wasm32-unknown-unknown target not found. Installing...");
-                let status = Command::new("rustup")
-                    .args(["target", "add", "wasm32-unknown-unknown"])
-                    .status()?;
-                
-                if !status.success() {
-                    println!("❌ Failed to install wasm32-unknown-unknown target.");
-                    println!("Please install it manually with: rustup target add wasm32-unknown-unknown");
+                status!("⚠️ wasm32-unknown-unknown target not found. Installing...");
+                if !run_install(ctx.offline, "rustup", &["target", "add", "wasm32-unknown-unknown"])? {
+                    status!("❌ Failed to install wasm32-unknown-unknown target.");
+                    status!("Please install it manually with: rustup target add wasm32-unknown-unknown");
                 } else {
-                    println!("✅ wasm32-unknown-unknown target installed successfully");
+                    status!("✅ wasm32-unknown-unknown target installed successfully");
                 }
             } else {
-                println!("✅ wasm32-unknown-unknown target is already installed");
+                status!("✅ wasm32-unknown-unknown target is already installed");
             }
             
             // Check for aarch64-apple-ios-sim target (required for iOS simulator)
-            println!("🔍 Checking for aarch64-apple-ios-sim target...");
+            status!("🔍 Checking for aarch64-apple-ios-sim target...");
             let ios_check = Command::new("rustup")
                 .args(["target", "list", "--installed"])
                 .output()?;
             
             let ios_output = String::from_utf8_lossy(&ios_check.stdout);
             if !ios_output.contains("aarch64-apple-ios-sim") {
-                println!("⚠️ aarch64-apple-ios-sim target not found.");
-                println!("This is something that Rustup can do for you. Rustup is Rust's toolchain");
-                println!("installer and manager, and it can add the iOS simulator target to your Rust installation.");
+                status!("⚠️ aarch64-apple-ios-sim target not found.");
+                status!("This is something that Rustup can do for you. Rustup is Rust's toolchain");
+                status!("installer and manager, and it can add the iOS simulator target to your Rust installation.");
                 
                 // Ask if user wants to install iOS simulator target
                 let install_ios = dialoguer::Confirm::new()
@@ -881,37 +1914,33 @@ pub fn execute(
                     .interact()?;
                 
                 if install_ios {
-                    println!("Installing aarch64-apple-ios-sim target...");
-                    let status = Command::new("rustup")
-                        .args(["target", "add", "aarch64-apple-ios-sim"])
-                        .status()?;
-                    
-                    if !status.success() {
-                        println!("❌ Failed to install aarch64-apple-ios-sim target.");
-                        println!("Please install it manually with: rustup target add aarch64-apple-ios-sim");
+                    status!("Installing aarch64-apple-ios-sim target...");
+                    if !run_install(ctx.offline, "rustup", &["target", "add", "aarch64-apple-ios-sim"])? {
+                        status!("❌ Failed to install aarch64-apple-ios-sim target.");
+                        status!("Please install it manually with: rustup target add aarch64-apple-ios-sim");
                     } else {
-                        println!("✅ aarch64-apple-ios-sim target installed successfully");
+                        status!("✅ aarch64-apple-ios-sim target installed successfully");
                     }
                 } else {
-                    println!("Skipping iOS simulator target installation.");
-                    println!("You can install it later with: rustup target add aarch64-apple-ios-sim");
+                    status!("Skipping iOS simulator target installation.");
+                    status!("You can install it later with: rustup target add aarch64-apple-ios-sim");
                 }
             } else {
-                println!("✅ aarch64-apple-ios-sim target is already installed");
+                status!("✅ aarch64-apple-ios-sim target is already installed");
             }
             
             // Check for Android targets if on macOS or Linux
             #[cfg(any(target_os = "macos", target_os = "linux"))]
             {
-                println!("🔍 Checking for Android targets...");
+                status!("🔍 Checking for Android targets...");
                 let android_check = Command::new("rustup")
                     .args(["target", "list", "--installed"])
                     .output()?;
                 
                 let android_output = String::from_utf8_lossy(&android_check.stdout);
                 if !android_output.contains("aarch64-linux-android") {
-                    println!("⚠️ aarch64-linux-android target not found.");
-                    println!("For most modern Android devices, you'll want the aarch64-linux-android target.");
+                    status!("⚠️ aarch64-linux-android target not found.");
+                    status!("For most modern Android devices, you'll want the aarch64-linux-android target.");
                     
                     // Ask if user wants to install Android target
                     let install_android = dialoguer::Confirm::new()
@@ -920,23 +1949,19 @@ pub fn execute(
                         .interact()?;
                     
                     if install_android {
-                        println!("Installing aarch64-linux-android target...");
-                        let status = Command::new("rustup")
-                            .args(["target", "add", "aarch64-linux-android"])
-                            .status()?;
-                        
-                        if !status.success() {
-                            println!("❌ Failed to install aarch64-linux-android target.");
-                            println!("Please install it manually with: rustup target add aarch64-linux-android");
+                        status!("Installing aarch64-linux-android target...");
+                        if !run_install(ctx.offline, "rustup", &["target", "add", "aarch64-linux-android"])? {
+                            status!("❌ Failed to install aarch64-linux-android target.");
+                            status!("Please install it manually with: rustup target add aarch64-linux-android");
                         } else {
-                            println!("✅ aarch64-linux-android target installed successfully");
+                            status!("✅ aarch64-linux-android target installed successfully");
                         }
                     } else {
-                        println!("Skipping Android target installation.");
-                        println!("You can install it later with: rustup target add aarch64-linux-android");
+                        status!("Skipping Android target installation.");
+                        status!("You can install it later with: rustup target add aarch64-linux-android");
                     }
                 } else {
-                    println!("✅ aarch64-linux-android target is already installed");
+                    status!("✅ aarch64-linux-android target is already installed");
                 }
             }
             
@@ -944,11 +1969,12 @@ pub fn execute(
             create_directory(app_path)?;
             
             // Let dioxus-cli handle the project creation with its own prompts
-            println!("🔧 Creating Dioxus project...");
+            status!("🔧 Creating Dioxus project...");
             let create_status = Command::new("dx")
                 .args(["init"])
                 .arg(".")
                 .current_dir(app_path)
+                .stdout(status_stdio())
                 .status()?;
                 
             if !create_status.success() {
@@ -956,25 +1982,25 @@ pub fn execute(
             }
             
             // Print success message with instructions
-            println!("\n🎉 Project {} created successfully!", name);
-            println!("\nNext steps:");
-            println!("  cd {}", name);
+            status!("\n🎉 Project {} created successfully!", name);
+            status!("\nNext steps:");
+            status!("  cd {}", ctx.cd_dir(name));
             // Detect if this is a Dioxus workspace (web, desktop, mobile all exist)
-            let web_exists = std::path::Path::new(&format!("{}/web", name)).exists();
-            let desktop_exists = std::path::Path::new(&format!("{}/desktop", name)).exists();
-            let mobile_exists = std::path::Path::new(&format!("{}/mobile", name)).exists();
+            let web_exists = app_path.join("web").exists();
+            let desktop_exists = app_path.join("desktop").exists();
+            let mobile_exists = app_path.join("mobile").exists();
             if web_exists || desktop_exists || mobile_exists {
-                println!("  dx serve --package web    # For web application");
-                println!("  dx serve --package desktop    # For desktop application");
-                println!("  dx serve --package mobile    # For mobile application");
+                status!("  dx serve --package web    # For web application");
+                status!("  dx serve --package desktop    # For desktop application");
+                status!("  dx serve --package mobile    # For mobile application");
             } else {
-                println!("  dx serve");
+                status!("  dx serve");
             }
             
-            return Ok(());
+            return Ok(None);
             
         } else if framework_selected == "tauri" {
-            println!("📦 Creating Tauri project with create-tauri-app");
+            status!("📦 Creating Tauri project with create-tauri-app");
             
             // Get the parent directory
             let parent_dir = app_path.parent().unwrap_or(Path::new("."));
@@ -1011,6 +2037,7 @@ npx create-tauri-app {}
             
             // Run the script
             let create_status = Command::new(&script_path)
+                .stdout(status_stdio())
                 .status()?;
                 
             // Clean up the temporary script
@@ -1021,9 +2048,9 @@ npx create-tauri-app {}
             }
             
             // Print success message
-            println!("\n🎉 Project {} created successfully!", name);
+            status!("\n🎉 Project {} created successfully!", name);
             
-            return Ok(());
+            return Ok(None);
             
         } else {
             // If not Leptos, Dioxus, or Tauri, use the selected framework as the template
@@ -1035,20 +2062,25 @@ npx create-tauri-app {}
             match fw.to_lowercase().as_str() {
                 "polars" => {
                     template = "data-science/polars-cli".to_string();
-                    println!("📈 Selected: Data Analysis with Polars");
+                    status!("📈 Selected: Data Analysis with Polars");
                 },
                 "linfa" => {
                     template = "data-science/linfa-examples".to_string();
-                    println!("🔍 Selected: Machine Learning with Linfa");
+                    status!("🔍 Selected: Machine Learning with Linfa");
+                },
+                "candle" => {
+                    template = "data-science/candle-mnist".to_string();
+                    status!("🧠 Selected: Deep Learning with Candle");
                 },
                 _ => {
-                    println!("Warning: Provided framework '{}' is not valid for data-science components", fw);
-                    println!("Valid options are: polars, linfa");
+                    status!("Warning: Provided framework '{}' is not valid for data-science components", fw);
+                    status!("Valid options are: polars, linfa, candle");
                     
                     // If invalid framework is provided, fall back to interactive selection
                     let framework_options = vec![
                         "Data Analysis with Polars",
-                        "Machine Learning with Linfa"
+                        "Machine Learning with Linfa",
+                        "Deep Learning with Candle"
                     ];
                     
                     let selection = Select::new()
@@ -1060,15 +2092,19 @@ npx create-tauri-app {}
                     match selection {
                         0 => {
                             template = "data-science/polars-cli".to_string();
-                            println!("📈 Selected: Data Analysis with Polars");
+                            status!("📈 Selected: Data Analysis with Polars");
                         },
                         1 => {
                             template = "data-science/linfa-examples".to_string();
-                            println!("🔍 Selected: Machine Learning with Linfa");
+                            status!("🔍 Selected: Machine Learning with Linfa");
+                        },
+                        2 => {
+                            template = "data-science/candle-mnist".to_string();
+                            status!("🧠 Selected: Deep Learning with Candle");
                         },
                         _ => {
                             template = "data-science/polars-cli".to_string();
-                            println!("📈 Selected: Data Analysis with Polars (default)");
+                            status!("📈 Selected: Data Analysis with Polars (default)");
                         }
                     }
                 }
@@ -1077,7 +2113,8 @@ npx create-tauri-app {}
             // No framework provided, use interactive selection
             let framework_options = vec![
                 "Data Analysis with Polars",
-                "Machine Learning with Linfa"
+                "Machine Learning with Linfa",
+                "Deep Learning with Candle"
             ];
             
             // Create a selection without additional prompt text to avoid duplication
@@ -1092,29 +2129,34 @@ npx create-tauri-app {}
                 0 => {
                     // Polars selected
                     template = "data-science/polars-cli".to_string();
-                    println!("📈 Selected: Data Analysis with Polars");
+                    status!("📈 Selected: Data Analysis with Polars");
                 },
                 1 => {
                     // Linfa selected
                     template = "data-science/linfa-examples".to_string();
-                    println!("🔍 Selected: Machine Learning with Linfa");
+                    status!("🔍 Selected: Machine Learning with Linfa");
+                },
+                2 => {
+                    // Candle selected
+                    template = "data-science/candle-mnist".to_string();
+                    status!("🧠 Selected: Deep Learning with Candle");
                 },
                 _ => {
                     // Fallback
                     template = "data-science/polars-cli".to_string();
-                    println!("📈 Selected: Data Analysis with Polars (default)");
+                    status!("📈 Selected: Data Analysis with Polars (default)");
                 }
             }
         }
         
-        println!("🔍 Checking for wasm32-unknown-unknown target...");
-        check_dependencies(&template)?;
+        status!("🔍 Checking for wasm32-unknown-unknown target...");
+        check_dependencies(&template, ctx.offline)?;
         
-        println!("\n📊 Setting up {} data science project...", template.replace("data-science/", ""));
+        status!("\n📊 Setting up {} data science project...", template.replace("data-science/", ""));
     }
 
     // Check for required dependencies based on template
-    check_dependencies(&template)?;
+    check_dependencies(&template, ctx.offline)?;
 
     // Handle special cases for 
     // 
@@ -1127,7 +2169,7 @@ npx create-tauri-app {}
     
     // embedded templates
     if template == "embedded" {
-        println!("📦 Creating embedded project for microcontrollers");
+        status!("📦 Creating embedded project for microcontrollers");
         
         // Create a variable to store the framework selection for template variables
         let framework_selection;
@@ -1136,18 +2178,18 @@ npx create-tauri-app {}
         let use_embassy = if let Some(fw) = framework {
             match fw.to_lowercase().as_str() {
                 "embassy" => {
-                    println!("Using Embassy framework for embedded development");
+                    status!("Using Embassy framework for embedded development");
                     framework_selection = "Yes, use Embassy framework".to_string();
                     true
                 },
                 "none" | "standard" => {
-                    println!("Using standard embedded template without a framework");
+                    status!("Using standard embedded template without a framework");
                     framework_selection = "No, use standard embedded template".to_string();
                     false
                 },
                 _ => {
-                    println!("Warning: Provided framework '{}' is not valid for embedded components", fw);
-                    println!("Valid options are: embassy, none");
+                    status!("Warning: Provided framework '{}' is not valid for embedded components", fw);
+                    status!("Valid options are: embassy, none");
                     
                     // Prompt for framework selection
                     let frameworks = vec!["No, use standard embedded template", "Yes, use Embassy framework"];
@@ -1211,10 +2253,10 @@ npx create-tauri-app {}
         
         if use_embassy {
             // User selected Embassy framework
-            println!("📦 Creating Embassy project using cargo-embassy");
+            status!("📦 Creating Embassy project using cargo-embassy");
             
             // Check if cargo-embassy is installed
-            println!("🔍 Checking for cargo-embassy...");
+            status!("🔍 Checking for cargo-embassy...");
             let embassy_check = Command::new("cargo")
                 .args(["embassy", "--version"])
                 .output();
@@ -1225,20 +2267,17 @@ npx create-tauri-app {}
             };
             
             if !embassy_installed {
-                println!("⚠️ cargo-embassy not found. Installing...");
-                let status = Command::new("cargo")
-                    .args(["install", "cargo-embassy"])
-                    .status()?;
-                
-                if !status.success() {
-                    println!("❌ Failed to install cargo-embassy.");
-                    println!("Please install it manually with: cargo install cargo-embassy");
+                offline::ensure_online_when(ctx.offline, "install cargo-embassy")?;
+                status!("⚠️ cargo-embassy not found. Installing...");
+                if !run_install(ctx.offline, "cargo", &["install", "cargo-embassy"])? {
+                    status!("❌ Failed to install cargo-embassy.");
+                    status!("Please install it manually with: cargo install cargo-embassy");
                     return Err(anyhow!("Failed to install cargo-embassy"));
                 } else {
-                    println!("✅ cargo-embassy installed successfully");
+                    status!("✅ cargo-embassy installed successfully");
                 }
             } else {
-                println!("✅ cargo-embassy is already installed");
+                status!("✅ cargo-embassy is already installed");
             }
             
             // Get microcontroller chip for Embassy
@@ -1250,10 +2289,10 @@ npx create-tauri-app {}
                 .interact()?;
                 
             let mcu_chip = mcu_targets[selection];
-            println!("Using {} as the microcontroller chip", mcu_chip);
+            status!("Using {} as the microcontroller chip", mcu_chip);
             
             // Create the project using cargo-embassy
-            println!("🔄 Creating new Embassy project...");
+            status!("🔄 Creating new Embassy project...");
             
             // Create a parent directory for the Embassy project
             let parent_dir = Path::new(".").join("embassy_temp");
@@ -1261,19 +2300,20 @@ npx create-tauri-app {}
             
             // Run cargo-embassy init from the parent directory
             let status = Command::new("cargo")
-                .args(["embassy", "init", "--chip", mcu_chip, &name])
+                .args(["embassy", "init", "--chip", mcu_chip, name])
                 .current_dir(&parent_dir)
+                .stdout(status_stdio())
                 .status()?;
                 
             if !status.success() {
-                println!("❌ Failed to create Embassy project.");
+                status!("❌ Failed to create Embassy project.");
                 return Err(anyhow!("Failed to create Embassy project"));
             }
             
             // Check if ESP toolchain is needed (for ESP32 chips)
             let is_esp_chip = mcu_chip.starts_with("esp");
             if is_esp_chip {
-                println!("🔍 Checking for ESP Rust toolchain...");
+                status!("🔍 Checking for ESP Rust toolchain...");
                 
                 // Check if ESP toolchain is installed
                 let esp_check = Command::new("rustup")
@@ -1284,68 +2324,75 @@ npx create-tauri-app {}
                 let esp_installed = toolchains.contains("esp");
                 
                 if !esp_installed {
-                    println!("⚠️ ESP Rust toolchain not found. Installing...");
+                    status!("⚠️ ESP Rust toolchain not found. Installing...");
                     
                     // Install ESP toolchain
-                    let install_status = Command::new("rustup")
-                        .args(["toolchain", "install", "esp"])
-                        .status()?;
-                    
-                    if !install_status.success() {
-                        println!("❌ Failed to install ESP toolchain.");
-                        println!("Please install it manually with: rustup toolchain install esp");
-                        println!("See https://esp-rs.github.io/book/installation/index.html for more information.");
+                    if !run_install(ctx.offline, "rustup", &["toolchain", "install", "esp"])? {
+                        status!("❌ Failed to install ESP toolchain.");
+                        status!("Please install it manually with: rustup toolchain install esp");
+                        status!("See https://esp-rs.github.io/book/installation/index.html for more information.");
                     } else {
-                        println!("✅ ESP toolchain installed successfully");
+                        status!("✅ ESP toolchain installed successfully");
                     }
                 } else {
-                    println!("✅ ESP toolchain is already installed");
+                    status!("✅ ESP toolchain is already installed");
                 }
             }
             
             // Move the generated project to the target directory
-            let project_dir = parent_dir.join(&name);
+            let project_dir = parent_dir.join(name);
             if project_dir.exists() {
                 // Copy all files from the generated project to the target directory
-                let target_dir = Path::new(&name);
-                if !target_dir.exists() {
-                    fs::create_dir_all(target_dir)?;
-                }
-                
-                copy_directory(&project_dir, target_dir)?;
+                copy_directory(&project_dir, app_path)?;
                 
                 // Clean up the temporary directory
                 fs::remove_dir_all(parent_dir)?;
                 
-                println!("🎉 Embassy project {} created successfully!", name);
-                println!("\nNext steps:");
-                println!("  cd {}", name);
+                status!("🎉 Embassy project {} created successfully!", name);
+                status!("\nNext steps:");
+                status!("  cd {}", ctx.cd_dir(name));
                 
                 // Add ESP-specific instructions if needed
                 if mcu_chip.starts_with("esp") {
-                    println!("\nℹ️ This project uses the ESP Rust toolchain");
-                    println!("  Make sure it's installed with: rustup toolchain install esp");
-                    println!("  For more information: https://esp-rs.github.io/book/installation/index.html");
-                    println!("\n  # Build the project");
-                    println!("  cargo build --release");
-                    println!("  # Flash to device");
-                    println!("  cargo run --release");
-                    println!("\nℹ️ If you see an error about custom toolchain, run:");
-                    println!("  rustup toolchain install esp");
+                    status!("\nℹ️ This project uses the ESP Rust toolchain");
+                    status!("  Make sure it's installed with: rustup toolchain install esp");
+                    status!("  For more information: https://esp-rs.github.io/book/installation/index.html");
+                    status!("\n  # Build the project");
+                    status!("  cargo build --release");
+                    status!("  # Flash to device");
+                    status!("  cargo run --release");
+                    status!("\nℹ️ If you see an error about custom toolchain, run:");
+                    status!("  rustup toolchain install esp");
                 } else {
-                    println!("  # Build the project");
-                    println!("  cargo build --release");
-                    println!("  # Run the project");
-                    println!("  cargo run --release");
+                    status!("  # Build the project");
+                    status!("  cargo build --release");
+                    status!("  # Run the project");
+                    status!("  cargo run --release");
                 }
                 
-                return Ok(());
+                return Ok(None);
             } else {
-                println!("❌ Failed to create Embassy project.");
+                status!("❌ Failed to create Embassy project.");
                 return Err(anyhow!("Failed to create Embassy project: Project directory not found"));
             }
         } else {
             // Standard embedded template
+            // Use the provider parameter as the microcontroller target if it is valid
+            if let Some(prov) = provider {
+                let mcu_targets = ["rp2040", "stm32", "esp32", "arduino"];
+                if mcu_targets.contains(&prov) {
+                    let mut vars_map = additional_vars
+                        .take()
+                        .and_then(|v| v.as_object().cloned())
+                        .unwrap_or_default();
+                    vars_map.insert("mcu_target".to_string(), json!(prov));
+                    additional_vars = Some(Value::Object(vars_map));
+                } else {
+                    status!("Warning: Provided provider '{}' is not a valid microcontroller target", prov);
+                    status!("Valid options are: {}", mcu_targets.join(", "));
+                }
+            }
+
             // Get microcontroller target from options
             let mcu_target = if let Some(vars) = &additional_vars {
                 if vars.get("mcu_target").is_some() {
@@ -1392,13 +2439,13 @@ npx create-tauri-app {}
                 selected_target
             };
             
-            println!("Using {} as the microcontroller target", mcu_target);
+            status!("Using {} as the microcontroller target", mcu_target);
             
             // Create target-specific dependencies string
             let mcu_target_deps = match mcu_target.as_str() {
                 "rp2040" => "rp2040-hal = \"0.9\"\nrp2040-boot2 = \"0.3\"\nusb-device = \"0.2\"\nusbd-serial = \"0.1\"",
                 "stm32" => "stm32f4xx-hal = { version = \"0.17\", features = [\"stm32f411\"] }",
-                "esp32" => "esp32-hal = \"0.16\"\nesp-backtrace = \"0.9\"\nesp-println = \"0.6\"",
+                "esp32" => "esp-hal = { version = \"1.0\", features = [\"esp32\"] }\nesp-bootloader-esp-idf = { version = \"0.4\", features = [\"esp32\"] }\nesp-backtrace = { version = \"0.18\", features = [\"esp32\", \"panic-handler\", \"println\"] }\nesp-println = { version = \"0.16\", features = [\"esp32\"] }",
                 "arduino" => "arduino-hal = { git = \"https://github.com/rahix/avr-hal\", rev = \"7dfa6d322b9df98b2d98afe0e14a97afe0187ac1\" }\navr-device = \"0.5\"\nufmt = \"0.2\"",
                 _ => "",
             };
@@ -1418,9 +2465,17 @@ npx create-tauri-app {}
                     }
                 }
             }
+
+            // The RP2040 template blinks a configurable LED
+            if mcu_target == "rp2040" {
+                let (led_pin, blink_ms) = rp2040_led_settings(&additional_vars, no_interactive)?;
+                status!("Blinking GPIO{} every {}ms", led_pin, blink_ms);
+                vars.insert("led_pin".to_string(), json!(led_pin));
+                vars.insert("blink_ms".to_string(), json!(blink_ms));
+            }
             
             // Apply the template using the template manager
-            template_manager::apply_template(&template, app_path, &name, Some(serde_json::Value::Object(vars)))?;
+            template_manager::apply_template(ctx, &template, app_path, name, Some(serde_json::Value::Object(vars)))?;
             
             // Suggest installing the appropriate Rust target
             let rust_target = match mcu_target.as_str() {
@@ -1431,18 +2486,23 @@ npx create-tauri-app {}
                 _ => "thumbv6m-none-eabi",
             };
             
-            println!("\nℹ️ You'll need to install the appropriate Rust target:");
-            println!("  rustup target add {}", rust_target);
+            status!("\nℹ️ You'll need to install the appropriate Rust target:");
+            if mcu_target == "esp32" {
+                // Xtensa targets aren't distributed through rustup, espup installs the `esp` toolchain
+                status!("  cargo install espup && espup install");
+            } else {
+                status!("  rustup target add {}", rust_target);
+            }
             
             match mcu_target.as_str() {
                 "rp2040" => {
-                    println!("  cargo install probe-run");
-                    println!("  cargo run --target {}", rust_target);
+                    status!("  cargo install probe-run");
+                    status!("  cargo run --target {}", rust_target);
                 },
                 "esp32" => {
-                    println!("  cargo install espflash");
-                    println!("  cargo build --target {}", rust_target);
-                    println!("  espflash flash --monitor target/{}/debug/{}", rust_target, name);
+                    // .cargo/config.toml sets the build target and uses espflash as the runner
+                    status!("  cargo install espflash");
+                    status!("  cargo run --release");
                 },
                 "arduino" => {
                     // Check if the target is installed
@@ -1455,36 +2515,38 @@ npx create-tauri-app {}
                     let target_installed = installed_targets.contains(rust_target);
                     
                     if !target_installed {
-                        println!("🔍 Checking for {} target...", rust_target);
-                        println!("❌ {} target is not installed", rust_target);
+                        status!("🔍 Checking for {} target...", rust_target);
+                        status!("❌ {} target is not installed", rust_target);
                     } else {
-                        println!("🔍 Checking for {} target...", rust_target);
-                        println!("✅ {} target is already installed", rust_target);
+                        status!("🔍 Checking for {} target...", rust_target);
+                        status!("✅ {} target is already installed", rust_target);
                     }
                     
-                    println!("\nℹ️ Setup instructions for Arduino development:");
-                    println!("  1. Install the AVR target:  rustup target add {}", rust_target);
-                    println!("  2. Install ravedude:       cargo install ravedude");
-                    println!("  3. Build the project:      cargo build --target {}", rust_target);
-                    println!("  4. Flash to Arduino:       cargo run --target {}", rust_target);
-                    println!("\nNote: arduino-hal is sourced from GitHub, not crates.io");
+                    status!("\nℹ️ Setup instructions for Arduino development:");
+                    status!("  1. Install the AVR target:  rustup target add {}", rust_target);
+                    status!("  2. Install ravedude:       cargo install ravedude");
+                    status!("  3. Build the project:      cargo build --target {}", rust_target);
+                    status!("  4. Flash to Arduino:       cargo run --target {}", rust_target);
+                    status!("\nNote: arduino-hal is sourced from GitHub, not crates.io");
                 },
                 _ => {
-                    println!("  cargo build --target {}", rust_target);
+                    status!("  cargo build --target {}", rust_target);
                 }
             }
         }
     } else if template == "counter" || template == "router" || template == "todo" {
         // For Leptos templates, prepend "client/leptos/"
         let template_path = format!("client/leptos/{}", template);
-        template_manager::apply_template(&template_path, app_path, &name, additional_vars.clone())?;
+        additional_vars = select_rendering_mode(&template_path, additional_vars, no_interactive)?;
+        additional_vars = select_styling(&template_path, additional_vars, no_interactive)?;
+        template_manager::apply_template(ctx, &template_path, app_path, name, additional_vars.clone())?;
     } else {
         // For data science templates, handle the prompts directly
         if template.starts_with("data-science/") {
             // Get the template configuration to access prompts
             let template_config = template_manager::get_template_config(&template)?;
             
-            println!("\n📊 Data Science Project Configuration\n");
+            status!("\n📊 Data Science Project Configuration\n");
             
             // Create a map to store the user's selections
             let mut template_vars = serde_json::Map::new();
@@ -1497,6 +2559,12 @@ npx create-tauri-app {}
                         prompt.get("question").and_then(|q| q.as_str()),
                         prompt.get("options").and_then(|o| o.as_array())
                     ) {
+                        // Variables given with --set don't need a prompt
+                        if let Some(value) = set_variables.as_ref().and_then(|v| v.get(name)) {
+                            template_vars.insert(name.to_string(), value.clone());
+                            continue;
+                        }
+
                         let option_values: Vec<&str> = options
                             .iter()
                             .filter_map(|v| v.as_str())
@@ -1534,173 +2602,481 @@ npx create-tauri-app {}
             }
             
             // Debug output only when in verbose mode
-            if std::env::var("FERRISUP_VERBOSE").is_ok() {
-                println!("Template variables: {}", json!(template_vars));
+            if is_verbose() {
+                status!("Template variables: {}", json!(template_vars));
             }
             
             // Apply the template with the user's selections
-            template_manager::apply_template(&template, app_path, &name, Some(json!(template_vars)))?;
+            template_manager::apply_template(ctx, &template, app_path, name, Some(json!(template_vars)))?;
         } else {
             // For non-data-science templates, use the original approach
-            template_manager::apply_template(&template, app_path, &name, additional_vars)?;
+            template_manager::apply_template(ctx, &template, app_path, name, additional_vars)?;
         }
     }
 
-    // Initialize git repository if requested
-    if git {
-        println!("🔄 Initializing git repository...");
-        let status = Command::new("git")
-            .args(["init"])
-            .current_dir(app_path)
-            .status()?;
-        if !status.success() {
-            return Err(anyhow!("Failed to initialize git repository"));
+    Ok(Some(template))
+}
+
+/// Ask whether a template that supports both client-side and server-side rendering
+/// should be generated as CSR or SSR, storing the answer in the `rendering` variable
+fn select_rendering_mode(
+    template_path: &str,
+    additional_vars: Option<Value>,
+    no_interactive: bool,
+) -> Result<Option<Value>> {
+    let template_config = template_manager::get_template_config(template_path)?;
+    if template_config.get("rendering").is_none() || additional_vars.as_ref().and_then(|v| v.get("rendering")).is_some() {
+        return Ok(additional_vars);
+    }
+
+    let options = [
+        "CSR - Client-side rendering, served by Trunk",
+        "SSR - Server-side rendering with Axum and hydration, built with cargo-leptos",
+    ];
+    let selection = if no_interactive {
+        0
+    } else {
+        Select::new()
+            .with_prompt("Which rendering mode would you like to use?")
+            .items(&options)
+            .default(0)
+            .interact()?
+    };
+    let rendering = if selection == 1 { "ssr" } else { "csr" };
+
+    let mut vars = additional_vars
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    vars.insert("rendering".to_string(), json!(rendering));
+    Ok(Some(Value::Object(vars)))
+}
+
+/// Ask whether a template that ships several styling setups should use plain CSS or
+/// Tailwind, storing the answer in the `styling` variable
+fn select_styling(
+    template_path: &str,
+    additional_vars: Option<Value>,
+    no_interactive: bool,
+) -> Result<Option<Value>> {
+    let template_config = template_manager::get_template_config(template_path)?;
+    if template_config.get("styling").is_none() || additional_vars.as_ref().and_then(|v| v.get("styling")).is_some() {
+        return Ok(additional_vars);
+    }
+
+    let options = [
+        "Plain CSS - a single style.css",
+        "Tailwind CSS - utility classes compiled by the Tailwind CLI",
+    ];
+    let selection = if no_interactive {
+        0
+    } else {
+        Select::new()
+            .with_prompt("How would you like to style the app?")
+            .items(&options)
+            .default(0)
+            .interact()?
+    };
+    let styling = if selection == 1 { "tailwind" } else { "plain-css" };
+
+    let mut vars = additional_vars
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    vars.insert("styling".to_string(), json!(styling));
+    Ok(Some(Value::Object(vars)))
+}
+
+/// What `new` generated, counted once the project has been moved into place
+struct GenerationSummary {
+    files: usize,
+    dirs: usize,
+    bytes: u64,
+}
+
+impl GenerationSummary {
+    /// Count the files, directories and bytes below `app_path`
+    fn collect(app_path: &Path) -> Self {
+        let mut summary = GenerationSummary { files: 0, dirs: 0, bytes: 0 };
+        for entry in walkdir::WalkDir::new(app_path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                summary.dirs += 1;
+            } else if entry.file_type().is_file() {
+                summary.files += 1;
+                summary.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
         }
-        
-        // Create .gitignore file
-        let gitignore = r#"/target
-/dist
-/Cargo.lock
-**/*.rs.bk
-"#;
-        std::fs::write(app_path.join(".gitignore"), gitignore)?;
-        println!("✅ Git repository initialized");
-    }
-
-    // Build project if requested
-    if build {
-        println!("🔄 Building project...");
-        let status = Command::new("cargo")
-            .args(["build"])
+        summary
+    }
+
+    fn print(&self, vcs: Vcs, git_initialized: bool, post_gen_hook: Option<&str>) {
+        status!(
+            "Created {} {} in {} {} ({})",
+            self.files.to_string().cyan(),
+            if self.files == 1 { "file" } else { "files" },
+            self.dirs.to_string().cyan(),
+            if self.dirs == 1 { "directory" } else { "directories" },
+            format_bytes(self.bytes).cyan()
+        );
+
+        let git_status = match (vcs, git_initialized) {
+            (Vcs::None, _) => "not initialized (pass --vcs git or --vcs jj)".dimmed(),
+            (_, true) => "initialized".green(),
+            (_, false) => "failed to initialize".yellow(),
+        };
+        status!("{} repository: {}", vcs.label(), git_status);
+
+        // FerrisUp doesn't execute template hooks, so point at the script instead
+        match post_gen_hook {
+            Some(hook) => status!("Post-generate hook: {} (run {} yourself)", "not run".yellow(), hook.cyan()),
+            None => status!("Post-generate hook: {}", "none".dimmed()),
+        }
+    }
+}
+
+/// The author from git's `user.name` and `user.email`, as `Name <email>`
+pub(crate) fn git_author() -> Option<String> {
+    let config = git2::Config::open_default().ok()?;
+    let setting = |key: &str| config.get_string(key).ok().filter(|value| !value.trim().is_empty());
+    match (setting("user.name"), setting("user.email")) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name.trim(), email.trim())),
+        (Some(name), None) => Some(name.trim().to_string()),
+        _ => None,
+    }
+}
+
+/// Schema version of `.ferrisup/origin.toml`, written as its `stamp_version` key
+///
+/// Stamps without the key are version 1, the template, component type and framework
+/// only. `ferrisup migrate` brings older stamps up to this version.
+pub const STAMP_VERSION: i64 = 2;
+
+/// Record the choices a project was generated with in `.ferrisup/origin.toml`
+///
+/// `ferrisup info` reads the stamp back, and `new --answers` recreates the project from it.
+/// Empty values are left out.
+pub(crate) fn write_origin_stamp(project_dir: &Path, entries: &[(&str, &str)]) -> Result<()> {
+    let mut stamp = toml_edit::DocumentMut::new();
+    stamp.insert("stamp_version", toml_edit::value(STAMP_VERSION));
+    for (key, value) in entries.iter().filter(|(_, value)| !value.is_empty()) {
+        stamp.insert(key, toml_edit::value(*value));
+    }
+    let dir = project_dir.join(".ferrisup");
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("origin.toml"), stamp.to_string())?;
+    Ok(())
+}
+
+/// Warn when a crate named like the project is already published on crates.io
+///
+/// Only a warning: the name still works for a project that's never published. Skipped
+/// in offline mode, and a failed lookup only prints a note.
+fn warn_if_name_taken(name: &str, offline: bool) {
+    let crate_name = name.replace('-', "_");
+    if offline {
+        status!("{} Offline mode: skipping the crates.io check of {}", "Note:".blue(), crate_name.cyan());
+        return;
+    }
+    match crates_io::find_crate(&crate_name) {
+        Ok(Some(published)) => status!(
+            "{} {} is already taken on crates.io by {} {}, pick another name before publishing: {}",
+            "Warning:".yellow().bold(),
+            crate_name.cyan(),
+            published.name,
+            published.version,
+            published.url()
+        ),
+        Ok(None) => status!("{} {} is available on crates.io", "✅".green(), crate_name.cyan()),
+        Err(e) => status!("{} Couldn't check {} on crates.io: {}", "Note:".blue(), crate_name.cyan(), e),
+    }
+}
+
+/// Run the command that installs a missing tool, returning whether it succeeded
+///
+/// In offline mode nothing is run, so generating from the bundled templates never
+/// waits on the network; the caller then prints how to install the tool manually.
+fn run_install(offline: bool, program: &str, args: &[&str]) -> Result<bool> {
+    if offline {
+        status!("⚠️ Offline mode: skipping `{} {}`", program, args.join(" "));
+        return Ok(false);
+    }
+    Ok(Command::new(program).args(args).stdout(status_stdio()).status()?.success())
+}
+
+/// Fail unless `branch` is a valid git branch name, such as `main` or `release/1.0`
+fn validate_branch_name(branch: &str) -> crate::core::Result<()> {
+    if git2::Branch::name_is_valid(branch).unwrap_or(false) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid git branch name (see `git check-ref-format --branch`)", branch).into())
+    }
+}
+
+/// Initialize a repository in `app_path` on `branch`, commit all generated files and
+/// optionally add an `origin` remote
+fn init_git_repository(app_path: &Path, commit_message: &str, git_remote: Option<&str>, branch: &str) -> Result<()> {
+    // HEAD points at the branch before the first commit creates it, so git's own
+    // init.defaultBranch doesn't matter
+    let mut init_options = git2::RepositoryInitOptions::new();
+    init_options.initial_head(branch);
+    let repo = git2::Repository::init_opts(app_path, &init_options)?;
+
+    // Stage everything that isn't ignored by the generated .gitignore
+    let mut index = repo.index()?;
+    index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    // Fall back to a FerrisUp identity when user.name/user.email aren't configured
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("FerrisUp", "ferrisup@users.noreply.github.com"))?;
+    repo.commit(Some("HEAD"), &signature, &signature, commit_message, &tree, &[])?;
+
+    if let Some(url) = git_remote {
+        repo.remote("origin", url)?;
+    }
+
+    Ok(())
+}
+
+/// Initialize a Jujutsu repository colocated with git in `app_path`, commit all generated
+/// files and point `branch` at the commit
+///
+/// Runs the `jj` CLI, so a missing `jj` is reported like any other failure and the
+/// project stays as generated.
+fn init_jj_repository(app_path: &Path, commit_message: &str, git_remote: Option<&str>, branch: &str) -> Result<()> {
+    let jj = |args: &[&str]| -> Result<()> {
+        let output = Command::new("jj")
+            .args(args)
             .current_dir(app_path)
-            .status()?;
-        if !status.success() {
-            return Err(anyhow!("Failed to build project"));
+            .output()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => anyhow!("jj is not installed, see https://jj-vcs.github.io/jj/latest/install-and-setup/"),
+                _ => anyhow!("Failed to run jj: {}", e),
+            })?;
+        if !output.status.success() {
+            return Err(anyhow!("`jj {}` failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
         }
-        println!("✅ Project built successfully");
+        Ok(())
+    };
+
+    jj(&["git", "init", "--colocate"])?;
+    // The working-copy commit becomes the initial commit, and the branch (a bookmark in
+    // jj) points at it so git sees it too
+    jj(&["commit", "--message", commit_message])?;
+    jj(&["bookmark", "create", branch, "--revision", "@-"])?;
+    if let Some(url) = git_remote {
+        jj(&["git", "remote", "add", "origin", url])?;
     }
 
-    // Print success message with instructions
-    println!("\n🎉 Project {} created successfully!", name);
-    
-    // We don't need to print next steps here as they're already printed in apply_template
-    // The next steps include the static server command if applicable
+    Ok(())
+}
+
+/// Highest GPIO number on the RP2040 (GPIO0-GPIO29)
+const RP2040_MAX_GPIO: u64 = 29;
+
+/// Resolve the LED pin and blink interval for the RP2040 template, prompting for any
+/// value not already provided. The pin becomes a `pins.gpioN` field access, so it is
+/// validated here rather than surfacing as a compile error in the generated project.
+/// Answer the `options` of a template manifest that `variables` doesn't with their defaults:
+/// the `default`, else the first choice of a select, an empty input or `false`
+fn default_option_answers(template_config: &Value, variables: Option<Value>) -> serde_json::Map<String, Value> {
+    let mut answers = match variables {
+        Some(Value::Object(variables)) => variables,
+        _ => serde_json::Map::new(),
+    };
+    let options = template_config.get("options").and_then(|o| o.as_array()).into_iter().flatten();
+    for option in options {
+        let Some(name) = option.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        if answers.contains_key(name) {
+            continue;
+        }
+        let default = option.get("default").cloned().unwrap_or_else(|| {
+            match option.get("type").and_then(|t| t.as_str()) {
+                Some("select") => option.pointer("/options/0").cloned().unwrap_or(json!("")),
+                Some("boolean") => json!(false),
+                _ => json!(""),
+            }
+        });
+        answers.insert(name.to_string(), default);
+    }
+    answers
+}
+
+fn rp2040_led_settings(additional_vars: &Option<Value>, no_interactive: bool) -> Result<(u64, u64)> {
+    let provided = |key: &str| -> Result<Option<u64>> {
+        match additional_vars.as_ref().and_then(|v| v.get(key)) {
+            None => Ok(None),
+            Some(Value::Number(n)) => n.as_u64().map(Some).ok_or_else(|| anyhow!("{} must be a non-negative integer", key)),
+            Some(Value::String(s)) => s.trim().parse().map(Some).map_err(|_| anyhow!("{} must be a non-negative integer, got '{}'", key, s)),
+            Some(other) => Err(anyhow!("{} must be a non-negative integer, got {}", key, other)),
+        }
+    };
+
+    let led_pin = match provided("led_pin")? {
+        Some(pin) => pin,
+        None if no_interactive => 25,
+        None => Input::<u64>::new()
+            .with_prompt("LED GPIO pin (0-29)")
+            .default(25)
+            .interact_text()?,
+    };
+    if led_pin > RP2040_MAX_GPIO {
+        return Err(anyhow!("Invalid led_pin {}: the RP2040 only has GPIO0-GPIO{}", led_pin, RP2040_MAX_GPIO));
+    }
+
+    let blink_ms = match provided("blink_ms")? {
+        Some(ms) => ms,
+        None if no_interactive => 500,
+        None => Input::<u64>::new()
+            .with_prompt("LED blink interval in milliseconds")
+            .default(500)
+            .interact_text()?,
+    };
+    // `delay_ms` takes a u32
+    if blink_ms == 0 || blink_ms > u64::from(u32::MAX) {
+        return Err(anyhow!("Invalid blink_ms {}: expected a value between 1 and {}", blink_ms, u32::MAX));
+    }
+
+    Ok((led_pin, blink_ms))
+}
+
+fn rendering_mode(additional_vars: &Option<Value>) -> &str {
+    additional_vars
+        .as_ref()
+        .and_then(|v| v.get("rendering"))
+        .and_then(|r| r.as_str())
+        .unwrap_or("csr")
+}
+
+// Helper function to check for cargo-leptos, used to build server-side rendered Leptos apps
+fn check_cargo_leptos(offline: bool) -> Result<()> {
+    status!("🔍 Checking for cargo-leptos...");
+    let cargo_leptos_check = Command::new("cargo")
+        .args(["leptos", "--version"])
+        .output();
+
+    match cargo_leptos_check {
+        Ok(output) if output.status.success() => status!("✅ cargo-leptos is already installed"),
+        _ => {
+            status!("⚠️ cargo-leptos not found. Installing...");
+            if !run_install(offline, "cargo", &["install", "cargo-leptos", "--locked"])? {
+                status!("❌ Failed to install cargo-leptos.");
+                status!("Please install it manually with: cargo install cargo-leptos --locked");
+            } else {
+                status!("✅ cargo-leptos installed successfully");
+            }
+        }
+    }
 
     Ok(())
 }
 
 // Helper function to check and install required dependencies
-fn check_dependencies(template: &str) -> Result<()> {
+fn check_dependencies(template: &str, offline: bool) -> Result<()> {
     // Check for wasm32-unknown-unknown target
-    println!("🔍 Checking for wasm32-unknown-unknown target...");
+    status!("🔍 Checking for wasm32-unknown-unknown target...");
     let wasm_check = Command::new("rustup")
         .args(["target", "list", "--installed"])
         .output()?;
     
     let wasm_output = String::from_utf8_lossy(&wasm_check.stdout);
     if !wasm_output.contains("wasm32-unknown-unknown") {
-        println!("⚠️ wasm32-unknown-unknown target not found. Installing...");
-        let status = Command::new("rustup")
-            .args(["target", "add", "wasm32-unknown-unknown"])
-            .status()?;
-        
-        if !status.success() {
-            println!("❌ Failed to install wasm32-unknown-unknown target.");
-            println!("Please install it manually with: rustup target add wasm32-unknown-unknown");
+        status!("⚠️ wasm32-unknown-unknown target not found. Installing...");
+        if !run_install(offline, "rustup", &["target", "add", "wasm32-unknown-unknown"])? {
+            status!("❌ Failed to install wasm32-unknown-unknown target.");
+            status!("Please install it manually with: rustup target add wasm32-unknown-unknown");
         } else {
-            println!("✅ wasm32-unknown-unknown target installed successfully");
+            status!("✅ wasm32-unknown-unknown target installed successfully");
         }
     } else {
-        println!("✅ wasm32-unknown-unknown target is already installed");
+        status!("✅ wasm32-unknown-unknown target is already installed");
     }
     
     // Check if we're using an edge template that needs wasm-pack
     if template.starts_with("edge/") {
-        println!("🔍 Checking for wasm-pack...");
+        status!("🔍 Checking for wasm-pack...");
         let wasm_pack_check = Command::new("wasm-pack")
             .arg("--version")
             .output();
         
         match wasm_pack_check {
-            Ok(_) => println!("✅ wasm-pack is already installed"),
+            Ok(_) => status!("✅ wasm-pack is already installed"),
             Err(_) => {
-                println!("⚠️ wasm-pack not found. Installing...");
-                let status = Command::new("cargo")
-                    .args(["install", "wasm-pack"])
-                    .status()?;
-                
-                if !status.success() {
-                    println!("❌ Failed to install wasm-pack.");
-                    println!("Please install it manually with: cargo install wasm-pack");
+                status!("⚠️ wasm-pack not found. Installing...");
+                if !run_install(offline, "cargo", &["install", "wasm-pack"])? {
+                    status!("❌ Failed to install wasm-pack.");
+                    status!("Please install it manually with: cargo install wasm-pack");
                 } else {
-                    println!("✅ wasm-pack installed successfully");
+                    status!("✅ wasm-pack installed successfully");
                 }
             }
         }
         
         // For Cloudflare Workers templates, check for Wrangler CLI
-        if template.contains("cloudflare-workers") || template.contains("cloudflare-pages") {
-            println!("🔍 Checking for Cloudflare Wrangler CLI...");
+        if template.contains("cloudflare") {
+            status!("🔍 Checking for Cloudflare Wrangler CLI...");
             let wrangler_check = Command::new("wrangler")
                 .arg("--version")
                 .output();
             
             match wrangler_check {
-                Ok(_) => println!("✅ Wrangler CLI is already installed"),
+                Ok(_) => status!("✅ Wrangler CLI is already installed"),
                 Err(_) => {
-                    println!("ℹ️ Wrangler CLI not found.");
-                    println!("You may need to install it with: npm install -g wrangler");
-                    println!("Learn more at: https://developers.cloudflare.com/workers/wrangler/install-and-update/");
+                    status!("ℹ️ Wrangler CLI not found.");
+                    status!("You may need to install it with: npm install -g wrangler");
+                    status!("Learn more at: https://developers.cloudflare.com/workers/wrangler/install-and-update/");
                 }
             }
         }
         
         // For Vercel templates, check for Vercel CLI
         if template.contains("vercel") {
-            println!("🔍 Checking for Vercel CLI...");
+            status!("🔍 Checking for Vercel CLI...");
             let vercel_check = Command::new("vercel")
                 .arg("--version")
                 .output();
             
             match vercel_check {
-                Ok(_) => println!("✅ Vercel CLI is already installed"),
+                Ok(_) => status!("✅ Vercel CLI is already installed"),
                 Err(_) => {
-                    println!("ℹ️ Vercel CLI not found.");
-                    println!("You may need to install it with: npm install -g vercel");
-                    println!("Learn more at: https://vercel.com/docs/cli");
+                    status!("ℹ️ Vercel CLI not found.");
+                    status!("You may need to install it with: npm install -g vercel");
+                    status!("Learn more at: https://vercel.com/docs/cli");
                 }
             }
         }
         
         // For Netlify templates, check for Netlify CLI
         if template.contains("netlify") {
-            println!("🔍 Checking for Netlify CLI...");
+            status!("🔍 Checking for Netlify CLI...");
             let netlify_check = Command::new("netlify")
                 .arg("--version")
                 .output();
             
             match netlify_check {
-                Ok(_) => println!("✅ Netlify CLI is already installed"),
+                Ok(_) => status!("✅ Netlify CLI is already installed"),
                 Err(_) => {
-                    println!("ℹ️ Netlify CLI not found.");
-                    println!("You may need to install it with: npm install -g netlify-cli");
-                    println!("Learn more at: https://docs.netlify.com/cli/get-started/");
+                    status!("ℹ️ Netlify CLI not found.");
+                    status!("You may need to install it with: npm install -g netlify-cli");
+                    status!("Learn more at: https://docs.netlify.com/cli/get-started/");
                 }
             }
         }
         
         // For Fastly templates, check for Fastly CLI
         if template.contains("fastly") {
-            println!("🔍 Checking for Fastly CLI...");
+            status!("🔍 Checking for Fastly CLI...");
             let fastly_check = Command::new("fastly")
                 .arg("--version")
                 .output();
             
             match fastly_check {
-                Ok(_) => println!("✅ Fastly CLI is already installed"),
+                Ok(_) => status!("✅ Fastly CLI is already installed"),
                 Err(_) => {
-                    println!("ℹ️ Fastly CLI not found.");
-                    println!("You may need to install it from: https://developer.fastly.com/learning/tools/cli/");
+                    status!("ℹ️ Fastly CLI not found.");
+                    status!("You may need to install it from: https://developer.fastly.com/learning/tools/cli/");
                 }
             }
         }
@@ -1708,24 +3084,20 @@ fn check_dependencies(template: &str) -> Result<()> {
     
     // Check for trunk (needed for counter, router, todo templates)
     if template == "counter" || template == "router" || template == "todo" {
-        println!("🔍 Checking for Trunk...");
+        status!("🔍 Checking for Trunk...");
         let trunk_check = Command::new("trunk")
             .arg("--version")
             .output();
         
         match trunk_check {
-            Ok(_) => println!("✅ Trunk is already installed"),
+            Ok(_) => status!("✅ Trunk is already installed"),
             Err(_) => {
-                println!("⚠️ Trunk not found. Installing...");
-                let status = Command::new("cargo")
-                    .args(["install", "trunk", "--locked"])
-                    .status()?;
-                
-                if !status.success() {
-                    println!("❌ Failed to install Trunk.");
-                    println!("Please install it manually with: cargo install trunk --locked");
+                status!("⚠️ Trunk not found. Installing...");
+                if !run_install(offline, "cargo", &["install", "trunk", "--locked"])? {
+                    status!("❌ Failed to install Trunk.");
+                    status!("Please install it manually with: cargo install trunk --locked");
                 } else {
-                    println!("✅ Trunk installed successfully");
+                    status!("✅ Trunk installed successfully");
                 }
             }
         }
@@ -1734,14 +3106,84 @@ fn check_dependencies(template: &str) -> Result<()> {
     Ok(())
 }
 
+/// Fill in the boolean options an edge provider's `template.json` declares, like `websockets`
+///
+/// `--set websockets=true` arrives as a string, which Handlebars' `#if` would take as true
+/// even for "false", so answers become booleans; options that weren't given get their default.
+fn with_edge_options(template_dir: &Path, vars: Option<Value>) -> Option<Value> {
+    let config: Option<Value> = fs::read_to_string(template_dir.join("template.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    let Some(options) = config.as_ref().and_then(|c| c.get("options")).and_then(Value::as_object) else {
+        return vars;
+    };
+
+    let mut vars = vars.and_then(|v| v.as_object().cloned()).unwrap_or_default();
+    for (name, option) in options {
+        if option.get("type").and_then(Value::as_str) != Some("boolean") {
+            continue;
+        }
+        let enabled = match vars.get(name) {
+            Some(Value::String(answer)) => matches!(answer.trim().to_lowercase().as_str(), "true" | "yes" | "y" | "1" | "on"),
+            Some(Value::Bool(answer)) => *answer,
+            _ => option.get("default").and_then(Value::as_bool).unwrap_or(false),
+        };
+        vars.insert(name.clone(), Value::Bool(enabled));
+    }
+    Some(Value::Object(vars))
+}
+
+/// Render every file below `source` into `target` with the edge template's variables
+fn render_edge_directory(ctx: &template_manager::GenerationContext, source: &Path, target: &Path, name: &str, additional_vars: Option<&Value>) -> Result<()> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.set_strict_mode(false);
+
+    let mut template_vars = json!({
+        "project_name": name,
+        "crate_name": name.replace("-", "_"),
+        "project_name_pascal_case": to_pascal_case(name),
+        "author": ctx.author,
+        "authors": ctx.authors()
+    });
+    if let (Some(vars), Some(obj)) = (additional_vars.and_then(Value::as_object), template_vars.as_object_mut()) {
+        obj.extend(vars.clone());
+    }
+
+    for entry in walkdir::WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+        let relative = entry.path().strip_prefix(source)?;
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)?;
+            continue;
+        }
+        match fs::read_to_string(entry.path()) {
+            Ok(content) => {
+                let rendered = handlebars
+                    .render_template(&content, &template_vars)
+                    .map_err(|e| anyhow!("Failed to render {}: {}", entry.path().display(), e))?;
+                fs::write(&destination, rendered)?;
+            }
+            // Binary files are copied as they are
+            Err(_) => {
+                fs::copy(entry.path(), &destination)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 // Helper function to handle edge templates
-fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_vars: Option<serde_json::Value>) -> Result<()> {
+fn handle_edge_template(ctx: &template_manager::GenerationContext, template: &str, app_path: &Path, name: &str, additional_vars: Option<serde_json::Value>) -> Result<()> {
+    let additional_vars = ctx.apply_variable_overrides(additional_vars);
+
     // Handle edge template creation manually
     let template_dir_path = PathBuf::from(format!("{}/templates/{}", env!("CARGO_MANIFEST_DIR"), template));
     
     if !template_dir_path.exists() {
         return Err(anyhow::anyhow!("Could not find template directory for {} template", template));
     }
+    let additional_vars = with_edge_options(&template_dir_path, additional_vars);
     
     // Create src directory if needed
     fs::create_dir_all(app_path.join("src"))?;
@@ -1763,7 +3205,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
             "project_name": name,
             "crate_name": name.replace("-", "_"),
             "project_name_pascal_case": to_pascal_case(&name),
-            "authors": "Your Name <your.email@example.com>"
+            "author": ctx.author,
+            "authors": ctx.authors()
         });
         
         // Merge additional variables if provided
@@ -1781,8 +3224,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         let rendered = match handlebars.render_template(&content, &template_vars) {
             Ok(result) => result,
             Err(e) => {
-                println!("Warning: Template parsing error in lib.rs: {}", e);
-                println!("Proceeding with unmodified template content");
+                status!("Warning: Template parsing error in lib.rs: {}", e);
+                status!("Proceeding with unmodified template content");
                 content
             }
         };
@@ -1818,7 +3261,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         "project_name": name,
         "crate_name": name.replace("-", "_"),
         "project_name_pascal_case": to_pascal_case(&name),
-        "authors": "Your Name <your.email@example.com>"
+        "author": ctx.author,
+        "authors": ctx.authors()
     });
     
     // Merge additional variables if provided
@@ -1836,8 +3280,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
     let rendered = match handlebars.render_template(&content, &template_vars) {
         Ok(result) => result,
         Err(e) => {
-            println!("Warning: Template parsing error in Cargo.toml: {}", e);
-            println!("Proceeding with unmodified template content");
+            status!("Warning: Template parsing error in Cargo.toml: {}", e);
+            status!("Proceeding with unmodified template content");
             content
         }
     };
@@ -1863,7 +3307,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
             "project_name": name,
             "crate_name": name.replace("-", "_"),
             "project_name_pascal_case": to_pascal_case(&name),
-            "authors": "Your Name <your.email@example.com>"
+            "author": ctx.author,
+            "authors": ctx.authors()
         });
         
         // Merge additional variables if provided
@@ -1881,8 +3326,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         let rendered = match handlebars.render_template(&content, &template_vars) {
             Ok(result) => result,
             Err(e) => {
-                println!("Warning: Template parsing error in README.md: {}", e);
-                println!("Proceeding with unmodified template content");
+                status!("Warning: Template parsing error in README.md: {}", e);
+                status!("Proceeding with unmodified template content");
                 content
             }
         };
@@ -1907,7 +3352,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         let target_path = app_path.join(&file_name);
         
         if source_path.is_dir() {
-            copy_directory(&source_path, &target_path)?;
+            // Sources are rendered too: they use the project name and options like `websockets`
+            render_edge_directory(ctx, &source_path, &target_path, name, additional_vars.as_ref())?;
         } else {
             // Read the source file
             let content = fs::read_to_string(&source_path)?;
@@ -1924,7 +3370,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
                 "project_name": name,
                 "crate_name": name.replace("-", "_"),
                 "project_name_pascal_case": to_pascal_case(&name),
-                "authors": "Your Name <your.email@example.com>"
+                "author": ctx.author,
+                "authors": ctx.authors()
             });
             
             // Merge additional variables if provided
@@ -1942,8 +3389,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
             let rendered = match handlebars.render_template(&content, &template_vars) {
                 Ok(result) => result,
                 Err(e) => {
-                    println!("Warning: Template parsing error in {}: {}", file_name_str, e);
-                    println!("Proceeding with unmodified template content");
+                    status!("Warning: Template parsing error in {}: {}", file_name_str, e);
+                    status!("Proceeding with unmodified template content");
                     content
                 }
             };
@@ -1958,8 +3405,8 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         let template_json_content = fs::read_to_string(&template_json_path)?;
         if let Ok(template_config) = serde_json::from_str::<serde_json::Value>(&template_json_content) {
             if let Some(next_steps) = template_config.get("next_steps").and_then(|s| s.as_array()) {
-                println!("\n✅ {} project created successfully!", name.green());
-                println!("\n{}", "Next steps:".bold().green());
+                status!("\n✅ {} project created successfully!", name.green());
+                status!("\n{}", "Next steps:".bold().green());
                 
                 // Create a handlebars registry for processing templates
                 let mut handlebars = Handlebars::new();
@@ -1982,11 +3429,12 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
                 for step in next_steps {
                     if let Some(step_str) = step.as_str() {
                         // Replace {{project_name}} with the actual project name
-                        let step_text = match handlebars.render_template(step_str, &json!(data)) {
+                        let step_str = ctx.resolve_cd(step_str, name);
+                        let step_text = match handlebars.render_template(&step_str, &json!(data)) {
                             Ok(rendered) => rendered,
                             Err(_) => step_str.replace("{{project_name}}", name),
                         };
-                        println!("- {}", step_text);
+                        status!("- {}", step_text);
                     }
                 }
                 return Ok(());
@@ -1994,7 +3442,7 @@ fn handle_edge_template(template: &str, app_path: &Path, name: &str, additional_
         }
     }
     
-    println!("\n🎉 Project {} created successfully!", name);
+    status!("\n🎉 Project {} created successfully!", name);
     
     Ok(())
 }
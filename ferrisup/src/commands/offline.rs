@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+use std::sync::OnceLock;
+
+static OFFLINE: OnceLock<bool> = OnceLock::new();
+
+/// Whether the FERRISUP_OFFLINE environment variable asks for offline mode
+fn offline_from_env() -> bool {
+    std::env::var("FERRISUP_OFFLINE")
+        .map(|value| !matches!(value.trim(), "" | "0" | "false"))
+        .unwrap_or(false)
+}
+
+/// Record whether the global `--offline` flag was given
+///
+/// FERRISUP_OFFLINE=1 enables offline mode as well. When enabled, CARGO_NET_OFFLINE is
+/// exported too, so the cargo commands FerrisUp runs (`cargo add`, `cargo build`, ...)
+/// only use the local registry cache. Only the first call has an effect.
+pub fn set_offline(offline: bool) {
+    let offline = offline || offline_from_env();
+    if OFFLINE.set(offline).is_ok() && offline {
+        std::env::set_var("CARGO_NET_OFFLINE", "true");
+    }
+}
+
+/// Returns true if FerrisUp must not access the network
+pub fn is_offline() -> bool {
+    *OFFLINE.get_or_init(offline_from_env)
+}
+
+/// Fail with a clear message if `action` needs the network but offline mode is on
+pub fn ensure_online(action: &str) -> Result<()> {
+    ensure_online_when(is_offline(), action)
+}
+
+/// Like [`ensure_online`], for work with its own `offline` setting such as one generation
+pub fn ensure_online_when(offline: bool, action: &str) -> Result<()> {
+    if offline {
+        return Err(anyhow!(
+            "Cannot {} in offline mode. Run without --offline (and unset FERRISUP_OFFLINE) once network access is available",
+            action
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use clap::ValueEnum;
+
+/// What the CLI prints its results as, set with the global `--output-format` flag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Status lines and summaries for people
+    #[default]
+    Text,
+    /// A single JSON object on stdout, for tools wrapping the CLI
+    Json,
+}
+
+/// Print `error` as `{"error": ...}` on stdout, the JSON counterpart of the usual `Error: ...`
+pub fn print_json_error(error: &anyhow::Error) {
+    let error = serde_json::json!({ "error": format!("{:#}", error) });
+    println!("{}", error);
+}
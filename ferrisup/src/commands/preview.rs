@@ -1,9 +1,11 @@
 use anyhow::{Result, anyhow, Context};
 use colored::Colorize;
-use std::collections::HashMap;
-use dialoguer::{Confirm, Select};
-use crate::project::templates::{get_template, list_templates, find_template_directory};
+use std::collections::{BTreeMap, HashMap};
+use dialoguer::{Confirm, Input, Select};
+use crate::commands::new::{generate, git_author, GenerateOptions};
+use crate::project::templates::{get_template, get_template_config, list_templates, find_template_directory};
 use crate::core::Config;
+use crate::template_manager::{find_user_template, template_layout, template_variables, template_version, LayoutEntry};
 
 /// Component structures for preview functionality
 #[derive(Default, Debug)]
@@ -109,11 +111,15 @@ struct PreviewOptions {
 /// * Not all component options are fully supported
 /// 
 /// Future improvements will address these limitations to provide a more accurate preview experience.
+///
+/// With `tree`, the file layout is instead worked out from the template's manifest and
+/// printed as a tree, with conditional files annotated with the option that selects them.
 pub fn execute(
     component_type: Option<&str>,
     framework: Option<&str>,
     provider: Option<&str>,
-    application_type: Option<&str>
+    application_type: Option<&str>,
+    tree: bool,
 ) -> Result<()> {
     println!("{}", "FerrisUp Template Preview".bold().green());
     println!("Preview template structure without creating files\n");
@@ -149,17 +155,25 @@ pub fn execute(
     };
     
     // Get template metadata
-    let _template_content = get_template(&selected_template)
+    let template_name = get_template(&selected_template)
         .context(format!("Failed to find template '{}'", selected_template))?;
     
     // Create a temporary representation of the project structure
     println!("\n{} {}", "Template:".bold(), selected_template.green());
+    if let Some(version) = template_version(&selected_template) {
+        println!("{} {}", "Version:".bold(), version);
+    }
     
     // Create a local components structure for preview
     let mut components = Components::default();
     
+    // User templates and the workspace starter have no built-in component model, their
+    // layout is shown instead
+    let layout_only = find_user_template(&selected_template).is_some() || selected_template == "minimal-workspace";
+    
     // Set up different components based on template type
     match selected_template.as_str() {
+        _ if layout_only => {},
         "client" => {
             // Use the specified framework if provided, otherwise show default options
             let frameworks = if let Some(framework) = &options.framework {
@@ -284,15 +298,26 @@ pub fn execute(
         }
     }
     
+    if tree || layout_only {
+        let layout = template_layout(&template_name, &preview_selections(&options))?;
+        println!("\n{}", "Project Structure:".bold());
+        println!("{}", render_layout_tree("example_project", &layout));
+        if tree {
+            return Ok(());
+        }
+    }
+    
     // Create a virtual configuration for the preview
     let config = Config::default();
     
-    // Generate the project structure tree
-    let tree = generate_project_tree(&components, &config);
-    
-    // Display the project structure tree
-    println!("\n{}", "Project Structure:".bold());
-    println!("{}", tree);
+    if !layout_only {
+        // Generate the project structure tree
+        let tree = generate_project_tree(&components, &config);
+        
+        // Display the project structure tree
+        println!("\n{}", "Project Structure:".bold());
+        println!("{}", tree);
+    }
     
     // Display notable components and features
     println!("\n{}", "Notable Features:".bold());
@@ -325,14 +350,14 @@ pub fn execute(
         }
         
         // Create a project with the selected template and options
-        if let Err(e) = crate::commands::new::execute(
-            None, 
-            Some(&selected_template), 
-            options.framework.as_deref(),
-            options.provider.as_deref(), 
-            options.application_type.as_deref(),
-            false, false, false, None
-        ) {
+        let name = Input::<String>::new().with_prompt("Component name").interact()?;
+        if let Err(e) = generate(GenerateOptions {
+            framework: options.framework.clone(),
+            provider: options.provider.clone(),
+            application_type: options.application_type.clone(),
+            author: git_author(),
+            ..GenerateOptions::new(selected_template.clone(), name).build(false).interactive(true)
+        }) {
             return Err(anyhow!("Failed to create preview: {}", e));
         }
     }
@@ -340,6 +365,123 @@ pub fn execute(
     Ok(())
 }
 
+/// Print the variables `template` declares, for answering them with `new --set`
+pub fn list_variables(template: Option<&str>, json: bool) -> Result<()> {
+    let template = template
+        .ok_or_else(|| anyhow!("--list-variables needs a template, e.g. --template server"))?;
+    let variables = template_variables(template);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&variables)?);
+        return Ok(());
+    }
+
+    if variables.is_empty() {
+        println!("The {} template does not declare any variables", template.green());
+        return Ok(());
+    }
+
+    println!("{} {}\n", "Variables of the template".bold(), template.green());
+    for variable in &variables {
+        let mut details = vec![variable.kind.clone()];
+        if let Some(default) = &variable.default {
+            let default = default.as_str().map(|d| d.to_string()).unwrap_or_else(|| default.to_string());
+            details.push(format!("default: {}", default));
+        }
+        if variable.required {
+            details.push("required".to_string());
+        }
+
+        println!("  {} ({})", variable.name.cyan().bold(), details.join(", "));
+        if let Some(prompt) = &variable.prompt {
+            println!("    {}", prompt);
+        }
+        if !variable.choices.is_empty() {
+            println!("    choices: {}", variable.choices.join(", "));
+        }
+    }
+
+    let example = &variables[0];
+    let value = example.choices.first().cloned().unwrap_or_else(|| "VALUE".to_string());
+    let mut set = format!("{}={}", example.name, value);
+    if set.contains(char::is_whitespace) {
+        set = format!("\"{}\"", set);
+    }
+    println!("\n{}", "Answer them up front with:".blue());
+    println!(
+        "  {}",
+        format!("ferrisup new my_app --component-type {} --set {}", template, set).cyan()
+    );
+    Ok(())
+}
+
+/// Template variables the preview options select, for working out a template's layout
+fn preview_selections(options: &PreviewOptions) -> serde_json::Map<String, serde_json::Value> {
+    let mut selections = serde_json::Map::new();
+    let mut select = |variables: &[&str], value: &Option<String>| {
+        if let Some(value) = value {
+            for variable in variables {
+                selections.insert(variable.to_string(), serde_json::json!(value));
+            }
+        }
+    };
+    
+    select(&["framework", "client_framework", "server_framework"], &options.framework);
+    select(&["cloud_provider", "static_site_provider", "api_function_provider"], &options.provider);
+    select(&["application_type", "edge_type"], &options.application_type);
+    selections
+}
+
+/// Render a template layout as a directory tree, annotating conditional files
+fn render_layout_tree(project_name: &str, layout: &[LayoutEntry]) -> String {
+    #[derive(Default)]
+    struct Node {
+        children: BTreeMap<String, Node>,
+        annotation: Option<String>,
+    }
+    
+    fn render(node: &Node, prefix: &str, out: &mut String) {
+        // Directories first, then files, each in alphabetical order
+        let mut children: Vec<(&String, &Node)> = node.children.iter().collect();
+        children.sort_by_key(|(name, child)| (child.children.is_empty(), name.as_str()));
+        
+        for (i, (name, child)) in children.iter().enumerate() {
+            let last = i == children.len() - 1;
+            let branch = if last { "└── " } else { "├── " };
+            if child.children.is_empty() {
+                match &child.annotation {
+                    Some(annotation) => out.push_str(&format!("{}{}{} {}\n", prefix, branch, name, annotation.dimmed())),
+                    None => out.push_str(&format!("{}{}{}\n", prefix, branch, name)),
+                }
+            } else {
+                out.push_str(&format!("{}{}{}/\n", prefix, branch, name));
+                let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                render(child, &child_prefix, out);
+            }
+        }
+    }
+    
+    let mut root = Node::default();
+    for entry in layout {
+        let mut node = &mut root;
+        for part in entry.path.split('/') {
+            node = node.children.entry(part.to_string()).or_default();
+        }
+        if !entry.conditions.is_empty() {
+            let conditions: Vec<String> = entry
+                .conditions
+                .iter()
+                .map(|(variable, values)| format!("{}={}", variable, values.join("|")))
+                .collect();
+            node.annotation = Some(format!("[{}]", conditions.join(", ")));
+        }
+    }
+    
+    let mut tree = format!("{}/\n", project_name);
+    render(&root, "", &mut tree);
+    tree.trim_end().to_string()
+}
+
 /// Generate a text-based tree representation of the project structure
 fn generate_project_tree(components: &Components, _config: &Config) -> String {
     // Since Config no longer has project_name and template fields,
@@ -511,18 +653,11 @@ fn display_template_features(template_name: &str, components: &Components, _conf
         }
     }
     
-    if let Ok(template_dir) = find_template_directory(template_name) {
-        let template_json_path = template_dir.join("template.json");
-        if template_json_path.exists() {
-            if let Ok(content) = std::fs::read_to_string(&template_json_path) {
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                    if let Some(features) = json.get("features").and_then(|f| f.as_array()) {
-                        for feature in features {
-                            if let Some(feature_str) = feature.as_str() {
-                                features_from_metadata.push(feature_str.to_string());
-                            }
-                        }
-                    }
+    if let Ok(json) = get_template_config(template_name) {
+        if let Some(features) = json.get("features").and_then(|f| f.as_array()) {
+            for feature in features {
+                if let Some(feature_str) = feature.as_str() {
+                    features_from_metadata.push(feature_str.to_string());
                 }
             }
         }
@@ -999,6 +1134,34 @@ mod tests {
         assert!(tree.contains("main.rs"));
     }
     
+    #[test]
+    fn test_render_layout_tree() {
+        colored::control::set_override(false);
+        let entry = |path: &str, conditions: &[(&str, &str)]| LayoutEntry {
+            path: path.to_string(),
+            conditions: conditions
+                .iter()
+                .map(|(variable, value)| (variable.to_string(), vec![value.to_string()]))
+                .collect(),
+        };
+        let layout = vec![
+            entry("Cargo.toml", &[]),
+            entry("index.html", &[("rendering", "csr")]),
+            entry("src/lib.rs", &[]),
+            entry("src/server.rs", &[("rendering", "ssr")]),
+        ];
+        
+        assert_eq!(
+            render_layout_tree("example_project", &layout),
+            "example_project/\n\
+             ├── src/\n\
+             │   ├── lib.rs\n\
+             │   └── server.rs [rendering=ssr]\n\
+             ├── Cargo.toml\n\
+             └── index.html [rendering=csr]"
+        );
+    }
+    
     #[test]
     fn test_display_template_features() {
         let components = Components::default();
@@ -0,0 +1,39 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Whether a progress line should be shown: never under `--quiet` or `--no-interactive`,
+/// and only when stdout is a terminal so piped output and logs stay clean
+pub fn progress_enabled(quiet: bool, no_interactive: bool) -> bool {
+    !quiet && !no_interactive && std::io::stdout().is_terminal()
+}
+
+/// The progress line of a project being generated, naming each file written below its root
+#[derive(Debug)]
+pub struct Progress {
+    bar: ProgressBar,
+    root: PathBuf,
+}
+
+impl Progress {
+    /// Start a progress line naming each file generated below `root`
+    pub fn start(root: &Path) -> Self {
+        let bar = ProgressBar::new_spinner();
+        bar.set_style(
+            ProgressStyle::with_template("{spinner:.green} {pos} files {wide_msg:.dim}")
+                .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+        );
+        Self { bar, root: root.to_path_buf() }
+    }
+
+    /// Report a generated file
+    pub fn file_written(&self, path: &Path) {
+        self.bar.inc(1);
+        self.bar.set_message(path.strip_prefix(&self.root).unwrap_or(path).display().to_string());
+    }
+
+    /// Remove the progress line
+    pub fn finish(self) {
+        self.bar.finish_and_clear();
+    }
+}
@@ -1,396 +1,200 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use colored::Colorize;
-use crate::project::templates::list_templates;
-use dialoguer::{Confirm, Input, MultiSelect, Select};
-use std::path::PathBuf;
-use std::fs;
+use dialoguer::Select;
+use serde_json::json;
+use std::path::{Path, PathBuf};
 
-pub fn execute() -> Result<()> {
-    println!("{}", "FerrisUp Interactive Project Builder".bold().green());
-    println!("{}", "Scale your Rust project with the features you need".blue());
-    
-    // Step 1: Get project directory (current or new)
-    let use_current_dir = Confirm::new()
-        .with_prompt("Use current directory for your project?")
-        .default(true)
-        .interact()?;
-    
-    let project_dir = if use_current_dir {
-        std::env::current_dir()?
-    } else {
-        let dir: String = Input::new()
-            .with_prompt("Enter the path for your new project")
-            .interact_text()?;
-        
-        let path = PathBuf::from(&dir);
-        if !path.exists() {
-            fs::create_dir_all(&path)?;
-        }
-        path
-    };
-    
-    // New Step: Offer templates or custom setup
-    let use_template = Confirm::new()
-        .with_prompt("Would you like to use a predefined template?")
-        .default(false)
-        .interact()?;
-    
-    if use_template {
-        // Get available templates
-        let templates = list_templates()?;
-        let template_names: Vec<String> = templates.iter().map(|(name, desc)| {
-            format!("{} - {}", name, desc)
-        }).collect();
-        
-        // Present template options
-        let template_idx = Select::new()
-            .with_prompt("Select a template")
-            .items(&template_names)
-            .default(0)
-            .interact()?;
-        
-        let selected_template = templates[template_idx].0.clone();
-        
-        // Confirm template selection
-        println!("\n{}", "Template Configuration Summary".bold().yellow());
-        println!("Project directory: {}", project_dir.display().to_string().cyan());
-        println!("Template: {}", selected_template.cyan());
-        
-        let proceed = Confirm::new()
-            .with_prompt("Proceed with template-based project generation?")
-            .default(true)
-            .interact()?;
-        
-        if !proceed {
-            println!("{}", "Project generation cancelled.".yellow());
-            return Ok(());
+use crate::commands::ci::{write_github_workflow, GITHUB_WORKFLOW};
+use crate::commands::info::{inspect_project, ProjectInfo};
+use crate::commands::transform::{component, dry_run, workspace};
+
+/// Components `scale` offers to add, as (component spec, member name)
+const COMPONENTS: &[(&str, &str)] = &[("client/leptos", "client"), ("server/axum", "server")];
+
+/// A way `scale` can grow a project
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScaleStep {
+    /// Move the crate below `crates/` in a new workspace
+    ToWorkspace,
+    /// Add a workspace member generated from a component spec such as `client/leptos`
+    AddComponent { spec: &'static str, name: &'static str },
+    /// Add a GitHub Actions workflow that checks formatting and lints and runs the tests
+    AddCi,
+}
+
+impl ScaleStep {
+    pub fn description(&self) -> String {
+        match self {
+            ScaleStep::ToWorkspace => "Convert the project to a workspace".to_string(),
+            ScaleStep::AddComponent { spec, name } => format!("Add a {} component ({})", name, spec),
+            ScaleStep::AddCi => format!("Add a GitHub Actions CI workflow ({})", GITHUB_WORKFLOW),
         }
-        
-        // Generate project from template
-        println!("\n{}", "Generating project from template...".green());
-        
-        // Here we would call the new::execute function with the selected template
-        // commands::new::execute(&project_dir.file_name().unwrap().to_string_lossy(), &selected_template, false, false, false, None)?;
-        
-        println!("{}", "Project successfully generated!".bold().green());
-        println!("Your new Rust project is ready at: {}", project_dir.display().to_string().cyan());
-        
-        return Ok(());
-    }
-    
-    // Step 2: Project type selection
-    let project_types = vec![
-        "Binary (Simple application)",
-        "Library (Reusable code)",
-        "Workspace (Multi-crate project)",
-    ];
-    
-    let project_type_idx = Select::new()
-        .with_prompt("Select project type")
-        .items(&project_types)
-        .default(0)
-        .interact()?;
-    
-    // Step 3: If workspace, ask about components
-    let mut has_client = false;
-    let mut has_server = false;
-    let mut has_libs = false;
-    let mut has_ai = false;
-    let mut has_edge = false;
-    let mut has_embedded = false;
-    
-    if project_type_idx == 2 {  // Workspace
-        println!("\n{}", "Workspace Components".bold().cyan());
-        
-        let component_options = vec![
-            "Client Applications (Web/Desktop UI)",
-            "Server Services (APIs/Backend)",
-            "Libraries (Shared code)",
-            "AI Components (ML models, inference)",
-            "Edge Computing (WASM, Serverless)",
-            "Embedded Systems (IoT, Hardware)",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select the components for your workspace")
-            .items(&component_options)
-            .interact()?;
-        
-        has_client = selections.contains(&0);
-        has_server = selections.contains(&1);
-        has_libs = selections.contains(&2);
-        has_ai = selections.contains(&3);
-        has_edge = selections.contains(&4);
-        has_embedded = selections.contains(&5);
     }
-    
-    // Step 4: Database selection
-    let use_database = Confirm::new()
-        .with_prompt("Include database support?")
-        .default(false)
-        .interact()?;
-    
-    let db_type = if use_database {
-        let db_options = vec![
-            "PostgreSQL",
-            "MySQL",
-            "SQLite",
-            "MongoDB",
-            "Redis",
-            "DynamoDB",
-            "None (will configure later)",
-        ];
-        
-        let db_idx = Select::new()
-            .with_prompt("Select database type")
-            .items(&db_options)
-            .default(0)
-            .interact()?;
-        
-        match db_idx {
-            0 => "postgres",
-            1 => "mysql",
-            2 => "sqlite",
-            3 => "mongodb",
-            4 => "redis",
-            5 => "dynamodb",
-            _ => "none",
+
+    /// Apply the step to the project in `project_dir`, the same way `transform` would
+    pub fn apply(&self, project_dir: &Path) -> Result<()> {
+        match self {
+            ScaleStep::ToWorkspace => workspace::to_workspace(project_dir).map(|_| ()),
+            ScaleStep::AddComponent { spec, name } => {
+                component::add_component_from_spec(project_dir, spec, Some(name)).map(|_| ())
+            }
+            ScaleStep::AddCi => {
+                let template = inspect_project(project_dir)?.template;
+                write_github_workflow(project_dir, template.as_deref(), &json!({})).map(|_| ())
+            }
         }
-    } else {
-        "none"
-    };
-    
-    // Step 5: Client framework selection (if applicable)
-    let client_frameworks = if has_client {
-        println!("\n{}", "Client Framework Setup".bold().cyan());
-        
-        let framework_options = vec![
-            "Dioxus (React-like, Web/Desktop/Mobile)",
-            "Tauri (Desktop with web technologies)",
-            "Leptos (Web with fine-grained reactivity)",
-            "Yew (Component-based framework)",
-            "Vanilla (No framework)",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select client frameworks to use")
-            .items(&framework_options)
-            .interact()?;
-        
-        let mut frameworks = Vec::new();
-        if selections.contains(&0) { frameworks.push("dioxus"); }
-        if selections.contains(&1) { frameworks.push("tauri"); }
-        if selections.contains(&2) { frameworks.push("leptos"); }
-        if selections.contains(&3) { frameworks.push("yew"); }
-        if selections.contains(&4) { frameworks.push("vanilla"); }
-        
-        frameworks
-    } else {
-        Vec::new()
-    };
-    
-    // Step 6: Server framework selection (if applicable)
-    let server_frameworks = if has_server {
-        println!("\n{}", "Server Framework Setup".bold().cyan());
-        
-        let framework_options = vec![
-            "Poem (Simple and flexible)",
-            "Axum (Modular and performant)",
-            "Actix Web (Powerful and mature)",
-            "Rocket (Ergonomic and boilerplate-free)",
-            "Warp (Composable and fast)",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select server frameworks to use")
-            .items(&framework_options)
-            .interact()?;
-        
-        let mut frameworks = Vec::new();
-        if selections.contains(&0) { frameworks.push("poem"); }
-        if selections.contains(&1) { frameworks.push("axum"); }
-        if selections.contains(&2) { frameworks.push("actix-web"); }
-        if selections.contains(&3) { frameworks.push("rocket"); }
-        if selections.contains(&4) { frameworks.push("warp"); }
-        
-        frameworks
-    } else {
-        Vec::new()
-    };
-    
-    // Step 7: AI components (if applicable)
-    let ai_components = if has_ai {
-        println!("\n{}", "AI Components Setup".bold().cyan());
-        
-        let ai_options = vec![
-            "Text Generation (LLaMA, GPT)",
-            "Image Generation (Stable Diffusion)",
-            "Speech Recognition (Whisper)",
-            "Embeddings (BERT, Sentence transformers)",
-            "Computer Vision (Object detection, classification)",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select AI capabilities to include")
-            .items(&ai_options)
-            .interact()?;
-        
-        let mut components = Vec::new();
-        if selections.contains(&0) { components.push("text-generation"); }
-        if selections.contains(&1) { components.push("image-generation"); }
-        if selections.contains(&2) { components.push("speech-recognition"); }
-        if selections.contains(&3) { components.push("embeddings"); }
-        if selections.contains(&4) { components.push("computer-vision"); }
-        
-        components
-    } else {
-        Vec::new()
-    };
-    
-    // Step 8: Edge components (if applicable)
-    let edge_components = if has_edge {
-        println!("\n{}", "Edge Computing Setup".bold().cyan());
-        
-        let edge_options = vec![
-            "WebAssembly (WASM)",
-            "Cloudflare Workers",
-            "Deno Deploy",
-            "Netlify Functions",
-            "Vercel Edge Functions",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select edge computing targets")
-            .items(&edge_options)
-            .interact()?;
-        
-        let mut components = Vec::new();
-        if selections.contains(&0) { components.push("wasm"); }
-        if selections.contains(&1) { components.push("cloudflare-workers"); }
-        if selections.contains(&2) { components.push("deno-deploy"); }
-        if selections.contains(&3) { components.push("netlify-functions"); }
-        if selections.contains(&4) { components.push("vercel-edge"); }
-        
-        components
-    } else {
-        Vec::new()
-    };
-    
-    // Step 9: Embedded components (if applicable)
-    let embedded_components = if has_embedded {
-        println!("\n{}", "Embedded Systems Setup".bold().cyan());
-        
-        let embedded_options = vec![
-            "Raspberry Pi Pico (RP2040)",
-            "ESP32",
-            "STM32",
-            "Arduino",
-            "Generic Microcontroller",
-        ];
-        
-        let selections = MultiSelect::new()
-            .with_prompt("Select embedded targets")
-            .items(&embedded_options)
-            .interact()?;
-        
-        let mut components = Vec::new();
-        if selections.contains(&0) { components.push("rp2040"); }
-        if selections.contains(&1) { components.push("esp32"); }
-        if selections.contains(&2) { components.push("stm32"); }
-        if selections.contains(&3) { components.push("arduino"); }
-        if selections.contains(&4) { components.push("generic"); }
-        
-        components
-    } else {
-        Vec::new()
+    }
+}
+
+/// Execute the scale command
+///
+/// Inspects the project in `project_path` (or the current directory) and offers the steps
+/// that would grow it, one at a time. Each step can be previewed as a diff, applied or
+/// skipped. Ends with a summary of every file that changed.
+pub fn execute(project_path: Option<&str>) -> Result<()> {
+    let project_dir = match project_path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
     };
-    
-    // Step 10: Additional features
-    println!("\n{}", "Additional Features".bold().cyan());
-    
-    let feature_options = vec![
-        "GitHub Actions CI/CD",
-        "Docker containerization",
-        "Kubernetes manifests",
-        "Observability setup (metrics/tracing)",
-        "Authentication boilerplate",
-        "Testing infrastructure",
-        "Documentation generation",
-    ];
-    
-    let feature_selections = MultiSelect::new()
-        .with_prompt("Select additional features to include")
-        .items(&feature_options)
-        .interact()?;
-    
-    // Step 11: Confirmation
-    println!("\n{}", "Project Configuration Summary".bold().yellow());
-    println!("Project directory: {}", project_dir.display().to_string().cyan());
-    println!("Project type: {}", project_types[project_type_idx].cyan());
-    
-    if project_type_idx == 2 {
-        println!("Components:");
-        if has_client { println!("  - {}", "Client Applications".cyan()); }
-        if has_server { println!("  - {}", "Server Services".cyan()); }
-        if has_libs { println!("  - {}", "Libraries".cyan()); }
-        if has_ai { println!("  - {}", "AI Components".cyan()); }
-        if has_edge { println!("  - {}", "Edge Computing".cyan()); }
-        if has_embedded { println!("  - {}", "Embedded Systems".cyan()); }
+    if !project_dir.join("Cargo.toml").exists() {
+        return Err(anyhow!(
+            "{} is not a Cargo project (no Cargo.toml found). Create one with `ferrisup new` first",
+            project_dir.display()
+        ));
     }
-    
-    if use_database {
-        println!("Database: {}", db_type.cyan());
-    } else {
-        println!("Database: {}", "None".cyan());
+    let info = inspect_project(&project_dir)?;
+    if let Some(root) = &info.workspace_root {
+        return Err(anyhow!(
+            "{} is a member of the workspace at {}. Run `ferrisup scale` from the workspace root",
+            project_dir.display(),
+            root
+        ));
+    }
+
+    println!("{}", "FerrisUp Project Scaling".bold().green());
+    println!("Project: {} ({})", info.name.cyan(), project_dir.display());
+    println!(
+        "Detected: {}{}",
+        if info.is_workspace_root { "workspace" } else { "single crate" }.cyan(),
+        info.handler
+            .as_ref()
+            .or(info.template.as_ref())
+            .map(|kind| format!(", {}", kind))
+            .unwrap_or_default()
+    );
+
+    let steps = growth_steps(&info, &project_dir);
+    if steps.is_empty() {
+        println!("{}", "Nothing left to add: the project is a workspace with client, server and CI".green());
+        return Ok(());
     }
-    
-    if !client_frameworks.is_empty() {
-        println!("Client frameworks: {}", client_frameworks.join(", ").cyan());
+
+    let before = dry_run::Snapshot::take(&project_dir)?;
+    let mut is_workspace = info.is_workspace_root;
+    let mut applied = Vec::new();
+    let mut skipped = Vec::new();
+    for step in steps {
+        // Components are workspace members, so they need the workspace step
+        if matches!(step, ScaleStep::AddComponent { .. }) && !is_workspace {
+            println!("{} {} (needs a workspace)", "Skipping:".yellow(), step.description());
+            skipped.push(step);
+            continue;
+        }
+
+        println!("\n{}", step.description().bold());
+        loop {
+            let choice = Select::new()
+                .with_prompt("What would you like to do?")
+                .items(&["Apply", "Preview the changes", "Skip"])
+                .default(0)
+                .interact()?;
+            match choice {
+                0 => {
+                    step.apply(&project_dir)?;
+                    println!("{} {}", "✓".green(), step.description());
+                    if step == ScaleStep::ToWorkspace {
+                        is_workspace = true;
+                    }
+                    applied.push(step);
+                    break;
+                }
+                1 => dry_run::preview(&project_dir, |copy| step.apply(copy))?,
+                _ => {
+                    skipped.push(step);
+                    break;
+                }
+            }
+        }
     }
-    
-    if !server_frameworks.is_empty() {
-        println!("Server frameworks: {}", server_frameworks.join(", ").cyan());
+
+    println!("\n{}", "Scaling summary".bold().green());
+    for step in &applied {
+        println!("  {} {}", "applied".green(), step.description());
     }
-    
-    if !ai_components.is_empty() {
-        println!("AI components: {}", ai_components.join(", ").cyan());
+    for step in &skipped {
+        println!("  {} {}", "skipped".dimmed(), step.description());
     }
-    
-    if !edge_components.is_empty() {
-        println!("Edge components: {}", edge_components.join(", ").cyan());
+    let changes = before.changes(&project_dir)?;
+    if !changes.is_empty() {
+        println!("\n{}", "Changed files:".bold());
+        changes.print_changes();
     }
-    
-    if !embedded_components.is_empty() {
-        println!("Embedded components: {}", embedded_components.join(", ").cyan());
+
+    Ok(())
+}
+
+/// The steps that would grow the project, in the order they're offered
+pub fn growth_steps(info: &ProjectInfo, project_dir: &Path) -> Vec<ScaleStep> {
+    let mut steps = Vec::new();
+    if !info.is_workspace_root {
+        steps.push(ScaleStep::ToWorkspace);
     }
-    
-    if !feature_selections.is_empty() {
-        println!("Additional features:");
-        for &idx in &feature_selections {
-            println!("  - {}", feature_options[idx].cyan());
+    for (spec, name) in COMPONENTS {
+        let is_member = info
+            .workspace_members
+            .iter()
+            .any(|member| member.rsplit('/').next() == Some(*name));
+        if !is_member && !project_dir.join(name).exists() && !project_dir.join("crates").join(name).exists() {
+            steps.push(ScaleStep::AddComponent { spec, name });
         }
     }
-    
-    let proceed = Confirm::new()
-        .with_prompt("Proceed with project generation?")
-        .default(true)
-        .interact()?;
-    
-    if !proceed {
-        println!("{}", "Project generation cancelled.".yellow());
-        return Ok(());
+    if !project_dir.join(GITHUB_WORKFLOW).exists() {
+        steps.push(ScaleStep::AddCi);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_growth_steps() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join("src"))?;
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\"\n")?;
+        fs::write(project.join("src/main.rs"), "fn main() {}\n")?;
+
+        let steps = growth_steps(&inspect_project(project)?, project);
+        assert_eq!(
+            steps,
+            vec![
+                ScaleStep::ToWorkspace,
+                ScaleStep::AddComponent { spec: "client/leptos", name: "client" },
+                ScaleStep::AddComponent { spec: "server/axum", name: "server" },
+                ScaleStep::AddCi,
+            ]
+        );
+
+        // Applied steps aren't offered again
+        ScaleStep::ToWorkspace.apply(project)?;
+        ScaleStep::AddCi.apply(project)?;
+        fs::create_dir_all(project.join("crates/server"))?;
+        let steps = growth_steps(&inspect_project(project)?, project);
+        assert_eq!(steps, vec![ScaleStep::AddComponent { spec: "client/leptos", name: "client" }]);
+        Ok(())
     }
-    
-    // Step 12: Generate the project
-    println!("\n{}", "Generating project...".green());
-    
-    // Here we would call functions to create the actual project structure
-    // based on the selected options. This would involve creating directories,
-    // writing Cargo.toml files, copying template files, etc.
-    
-    // For now, we'll just print a success message
-    println!("{}", "Project successfully generated!".bold().green());
-    println!("Your new Rust project is ready at: {}", project_dir.display().to_string().cyan());
-    
-    Ok(())
 }
@@ -0,0 +1,196 @@
+// Authoring user templates: scaffold a new one and validate its manifest
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::template_manager::{user_templates_dir, validate_user_template, AUTHORING_GUIDE, USER_MANIFEST};
+
+/// Files of a new template, as (path, content); `__TEMPLATE__` is replaced with its name
+const SKELETON: &[(&str, &str)] = &[
+    (
+        USER_MANIFEST,
+        r#"description = "__TEMPLATE__ template"
+# Optional: shown by `ferrisup list` and recorded in generated projects
+# version = "0.1.0"
+# Optional: refuse to generate on older toolchains
+# rust_version = "1.75"
+# Optional: run `cargo build` right after generating (`false` marks a heavy template)
+# build_by_default = false
+
+# Questions asked by `ferrisup new`; `--set name=value` answers them up front
+[[options]]
+name = "greeting"
+description = "Greeting the program prints"
+type = "input"
+default = "Hello"
+
+[[options]]
+name = "output"
+description = "Where the greeting goes"
+type = "select"
+options = ["stdout", "stderr"]
+default = "stdout"
+
+# Variables that are never asked for, with their values
+[variables]
+punctuation = "!"
+
+# Where files of this directory go in the project; files that aren't listed keep their path
+[[files]]
+source = "Cargo.toml.template"
+target = "Cargo.toml"
+
+[[files]]
+source = "src/main.rs"
+target = "src/main.rs"
+"#,
+    ),
+    (
+        "Cargo.toml.template",
+        r#"[package]
+name = "{{crate_name}}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+"#,
+    ),
+    (
+        "src/main.rs",
+        r#"// {{project_name}}, generated from the __TEMPLATE__ template
+fn main() {
+    {{#if (eq output "stderr")}}
+    eprintln!("{{greeting}} from {{project_name}}{{punctuation}}");
+    {{else}}
+    println!("{{greeting}} from {{project_name}}{{punctuation}}");
+    {{/if}}
+}
+"#,
+    ),
+    (
+        AUTHORING_GUIDE,
+        r#"# __TEMPLATE__
+
+A FerrisUp user template. Generate a project from it with:
+
+```bash
+ferrisup new my-app --component-type __TEMPLATE__
+```
+
+and check it after editing with `ferrisup template validate __TEMPLATE__`.
+
+## Manifest
+
+`ferrisup.toml` describes the template:
+
+- `description` (required) is what `ferrisup list` shows
+- `[[options]]` are the questions `new` asks, each with a `name`, a `description` (the
+  question) and a `type`: `input`, `select` (with `options`) or `boolean`. `--set name=value`
+  answers them without a prompt
+- `[variables]` holds values that are never asked for
+- `[[files]]` maps files from `source` in this directory to `target` in the project. An
+  entry with `condition = 'output == "stderr"'` is only rendered when the variable has
+  that value
+- `[permissions]` lists the generated files to make executable, such as
+  `executable = ["scripts/setup.sh"]`
+- `version`, `rust_version`, `build_by_default` and `supports_minimal` are optional, see the FerrisUp README
+
+Files that aren't listed are copied into generated projects at the same path (`.rs`,
+`.toml`, `.md` and other text files are rendered, a `.template` suffix is dropped). This
+`TEMPLATE.md` is the exception: it's only for the template's authors.
+
+## Rendering
+
+Files are rendered with Handlebars in strict mode, so a variable that isn't defined is an
+error instead of an empty string. Besides your options and variables, every template gets
+`{{project_name}}`, `{{crate_name}}` (the name with `-` turned into `_`), `{{author}}` (from
+`--author` or git, empty when unknown) and `{{authors}}` (the author or a placeholder).
+
+Conditionals:
+
+```handlebars
+{{#if greeting}}set and not empty{{else}}missing, empty or false{{/if}}
+{{#unless greeting}}the opposite of #if{{/unless}}
+{{#if (eq output "stderr")}}compares a variable with a value{{/if}}
+```
+
+Prefer `(eq ...)` for `select` options; values given with `--set` are strings, so
+`--set flag=false` counts as set for `{{#if flag}}`. Variables that may be missing can
+be tested with `{{#if}}` and `(eq ...)`, but not printed.
+
+A whole directory can depend on a variable: `server__if_ssr/` is only generated when
+`{{#if ssr}}` holds, and then as `server/`. Unlike `{{#if}}`, the answers `no` and
+`false` leave it out.
+"#,
+    ),
+];
+
+/// Execute the template command: `new` scaffolds a template, `validate` checks one
+pub fn execute(action: &str, name: &str) -> Result<()> {
+    match action {
+        "new" => {
+            let dir = new_template(name)?;
+            println!("{} Created template {} in {}", "✅".green(), name.cyan(), dir.display());
+            println!("Edit its files, then check it with: {}", format!("ferrisup template validate {}", name).cyan());
+            println!("Generate a project from it with: {}", format!("ferrisup new my-app --component-type {}", name).cyan());
+            Ok(())
+        }
+        "validate" => {
+            let dir = template_dir(name)?;
+            let problems = validate_user_template(&dir);
+            if problems.is_empty() {
+                println!("{} {} is a valid template", "✅".green(), dir.display());
+                return Ok(());
+            }
+            for problem in &problems {
+                println!("  {} {}", "✗".red(), problem);
+            }
+            Err(anyhow!(
+                "Template {} has {} problem{}",
+                dir.display(),
+                problems.len(),
+                if problems.len() == 1 { "" } else { "s" }
+            ))
+        }
+        _ => Err(anyhow!("Unknown template action '{}', expected new or validate", action)),
+    }
+}
+
+/// Scaffold the template `name` in the user template directory, returning its directory
+pub fn new_template(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(anyhow!("Invalid template name '{}': use a plain directory name such as my-service", name));
+    }
+    let registry = user_templates_dir().ok_or_else(|| anyhow!("Cannot find the home directory, set FERRISUP_TEMPLATE_DIR"))?;
+    let dir = registry.join(name);
+    if dir.exists() {
+        return Err(anyhow!("{} already exists", dir.display()));
+    }
+    write_skeleton(&dir, name)?;
+    Ok(dir)
+}
+
+/// The directory `validate` checks: a path to a template, or a name in the user template directory
+fn template_dir(name: &str) -> Result<PathBuf> {
+    let path = Path::new(name);
+    if path.join(USER_MANIFEST).exists() {
+        return Ok(path.to_path_buf());
+    }
+    match user_templates_dir().map(|registry| registry.join(name)) {
+        Some(dir) if dir.is_dir() => Ok(dir),
+        _ if path.is_dir() => Ok(path.to_path_buf()),
+        _ => Err(anyhow!("No template '{}' in the user template directory, and no such directory", name)),
+    }
+}
+
+fn write_skeleton(dir: &Path, name: &str) -> Result<()> {
+    for (path, content) in SKELETON {
+        let file = dir.join(path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file, content.replace("__TEMPLATE__", name))?;
+    }
+    Ok(())
+}
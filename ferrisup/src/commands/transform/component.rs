@@ -6,7 +6,10 @@ use ferrisup_common::fs::create_directory;
 use ferrisup_common::cargo;
 use toml_edit::{DocumentMut};
 
+use crate::commands::offline;
+use crate::commands::new::{generate, git_author, GenerateOptions};
 use crate::commands::test_mode::is_test_mode;
+use crate::template_manager::validate_project_name;
 use super::project_structure::{analyze_project_structure, map_component_to_template};
 use super::utils::{store_transformation_metadata, store_component_type_in_cargo, make_shared_component_accessible, update_root_file_references, add_component_to_workspace};
 use super::ui::{get_input_with_default, select_option};
@@ -40,7 +43,7 @@ pub fn add_component(project_dir: &Path) -> Result<()> {
     )?;
     
     // For shared components, check if the crate name is available on crates.io
-    if component_type == "shared" {
+    if component_type == "shared" && !offline::is_offline() {
         // Keep prompting until we get an available name
         let mut is_available = false;
         while !is_available {
@@ -120,18 +123,12 @@ pub fn add_component(project_dir: &Path) -> Result<()> {
     // Change to project directory to create component at the right location
     std::env::set_current_dir(project_dir)?;
 
-    // Call the new command to create the component
-    let result = crate::commands::new::execute(
-        Some(&component_name),
-        Some(template),
-        framework.as_deref(),
-        None,
-        None,
-        false,
-        false,
-        false,
-        None,
-    );
+    // Generate the component the way the new command does
+    let result = generate(GenerateOptions {
+        framework: framework.clone(),
+        author: git_author(),
+        ..GenerateOptions::new(template, &component_name).build(false).interactive(true)
+    });
 
     // Change back to original directory
     std::env::set_current_dir(current_dir)?;
@@ -169,6 +166,112 @@ pub fn add_component(project_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Add a component to a workspace without prompting
+///
+/// `spec` is a component type optionally followed by a framework, e.g. `client/leptos`
+/// or `server/axum`. The component is generated by the same code path as `ferrisup new`
+/// into a new member named `name` (the component type by default), placed next to the
+/// existing members, and registered in the root `Cargo.toml`. Returns the member path
+/// relative to the workspace root.
+pub fn add_component_from_spec(project_dir: &Path, spec: &str, name: Option<&str>) -> Result<String> {
+    let (component_type, framework) = match spec.split_once('/') {
+        Some((component_type, framework)) => (component_type, Some(framework)),
+        None => (spec, None),
+    };
+    if !get_component_type_names().contains(&component_type) {
+        return Err(anyhow!(
+            "Unknown component type '{}'. Valid types are: {}",
+            component_type,
+            get_component_type_names().join(", ")
+        ));
+    }
+
+    let manifest_path = project_dir.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", manifest_path.display(), e))?
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow!("Failed to parse {}: {}", manifest_path.display(), e))?;
+    let Some(workspace) = manifest.get("workspace") else {
+        return Err(anyhow!(
+            "{} is not a workspace. Convert it first with `ferrisup transform --project {} --template to-workspace`",
+            project_dir.display(),
+            project_dir.display()
+        ));
+    };
+    let members: Vec<String> = workspace
+        .get("members")
+        .and_then(|m| m.as_array())
+        .map(|m| m.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
+        .unwrap_or_default();
+
+    let component_name = name.unwrap_or(component_type);
+    validate_project_name(component_name)?;
+
+    // New members go where the existing ones live
+    let in_crates_dir = !members.is_empty() && members.iter().all(|m| m.starts_with("crates/"));
+    let member_path = if in_crates_dir {
+        format!("crates/{}", component_name)
+    } else {
+        component_name.to_string()
+    };
+
+    let duplicate = members
+        .iter()
+        .any(|m| m == &member_path || m.rsplit('/').next() == Some(component_name));
+    if duplicate {
+        return Err(anyhow!(
+            "The workspace already has a member named '{}'. Pick another name with --name",
+            component_name
+        ));
+    }
+    let component_dir = project_dir.join(&member_path);
+    if component_dir.exists() {
+        return Err(anyhow!(
+            "{} already exists. Pick another name with --name",
+            component_dir.display()
+        ));
+    }
+
+    let template = if component_type == "shared" {
+        "library"
+    } else {
+        map_component_to_template(component_type)
+    };
+
+    create_directory(&component_dir)?;
+    let result = generate(GenerateOptions {
+        framework: framework.map(String::from),
+        author: git_author(),
+        ..GenerateOptions::new(template, component_name).target_dir(&component_dir).build(false)
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&component_dir);
+        return Err(anyhow!("Failed to create {} component: {}", component_type, e));
+    }
+
+    store_transformation_metadata(project_dir, component_name, template, framework)?;
+    store_component_type_in_cargo(&component_dir, template)?;
+    add_component_to_workspace(project_dir, &member_path)?;
+    if component_type == "shared" {
+        make_shared_component_accessible(project_dir, &member_path)?;
+    }
+
+    let package_name = cargo::read_cargo_toml(&component_dir)
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+        .and_then(|doc| doc.get("package")?.get("name")?.as_str().map(|n| n.to_string()))
+        .unwrap_or_else(|| component_name.to_string());
+
+    println!("\n{}", "Workspace members:".bold());
+    for member in members.iter().chain(std::iter::once(&member_path)) {
+        println!("  - {}", member.cyan());
+    }
+    println!("\n{}", "Build the new component with:".bold());
+    println!("  cargo build -p {}", package_name);
+
+    Ok(member_path)
+}
+
 // Function to add a component without converting to workspace
 pub fn add_component_without_workspace(project_dir: &Path) -> Result<()> {
     println!(
@@ -0,0 +1,256 @@
+// Previewing transforms (`transform --dry-run`)
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::core::diff;
+use ferrisup_common::fs::copy_dir_filtered;
+
+/// Directories that aren't copied for a dry run: they can be large and transforms only
+/// ever keep or remove them as a whole
+const UNCOPIED_DIRS: &[&str] = &["target", ".git"];
+
+/// The changes a transform would make to a project
+#[derive(Debug, Default)]
+pub struct TransformPlan {
+    /// Files moved unchanged, as (from, to)
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    pub created: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub deleted: Vec<PathBuf>,
+    /// Unified diff of every created, modified and deleted text file
+    pub diff: String,
+}
+
+impl TransformPlan {
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty() && self.created.is_empty() && self.modified.is_empty() && self.deleted.is_empty()
+    }
+
+    /// Print the planned changes followed by the diff
+    pub fn print(&self) {
+        if self.is_empty() {
+            println!("{}", "The transform would not change any files".yellow());
+            return;
+        }
+
+        println!("{}", "Planned changes (dry run, nothing was written):".bold());
+        self.print_changes();
+
+        if !self.diff.is_empty() {
+            println!();
+            diff::print_unified(&self.diff);
+        }
+    }
+
+    /// Print one line per moved, created, modified and deleted file
+    pub fn print_changes(&self) {
+        for (from, to) in &self.moved {
+            println!("  {} {} -> {}", "move".cyan(), from.display(), to.display());
+        }
+        for path in &self.created {
+            println!("  {} {}", "create".green(), path.display());
+        }
+        for path in &self.modified {
+            println!("  {} {}", "modify".yellow(), path.display());
+        }
+        for path in &self.deleted {
+            println!("  {} {}", "delete".red(), path.display());
+        }
+    }
+}
+
+/// Print what `transform` would change in `project_dir`, leaving the project untouched
+pub fn preview(project_dir: &Path, transform: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    println!(
+        "{}",
+        "Dry run: applying the transform to a temporary copy of the project".dimmed()
+    );
+    let plan = plan(project_dir, transform)?;
+    println!();
+    plan.print();
+    Ok(())
+}
+
+/// Work out what `transform` would change in `project_dir` without touching it
+///
+/// The transform runs against a copy of the project in a temporary directory, so the
+/// plan is exactly what applying it would do. The copy is then compared with the project.
+pub fn plan(project_dir: &Path, transform: impl FnOnce(&Path) -> Result<()>) -> Result<TransformPlan> {
+    let copy = Snapshot::take(project_dir)?;
+    transform(copy.path())?;
+
+    let mut plan = compare(project_dir, copy.path())?;
+    for dir in UNCOPIED_DIRS {
+        if project_dir.join(dir).is_dir() && !copy.path().join(dir).exists() {
+            plan.deleted.push(PathBuf::from(format!("{}/", dir)));
+        }
+    }
+    Ok(plan)
+}
+
+/// A copy of a project in a temporary directory, removed when dropped
+pub struct Snapshot {
+    _sandbox: tempfile::TempDir,
+    copy: PathBuf,
+}
+
+impl Snapshot {
+    /// Copy `project_dir`, leaving out the contents of the uncopied directories
+    pub fn take(project_dir: &Path) -> Result<Self> {
+        let sandbox = tempfile::tempdir().context("Failed to create a directory for the project copy")?;
+        // Keep the project's directory name, transforms may derive names from it
+        let file_name = project_dir
+            .canonicalize()?
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_else(|| "project".into());
+        let copy = sandbox.path().join(file_name);
+
+        let ignore: Vec<String> = UNCOPIED_DIRS.iter().map(|d| d.to_string()).collect();
+        copy_dir_filtered(project_dir, &copy, &ignore)?;
+        fs::create_dir_all(&copy)?;
+        // Stand-ins for the directories that weren't copied, to notice a transform removing them
+        for dir in UNCOPIED_DIRS {
+            if project_dir.join(dir).is_dir() {
+                fs::create_dir_all(copy.join(dir))?;
+            }
+        }
+
+        Ok(Self { _sandbox: sandbox, copy })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.copy
+    }
+
+    /// What changed in `project_dir` since the snapshot was taken
+    pub fn changes(&self, project_dir: &Path) -> Result<TransformPlan> {
+        compare(&self.copy, project_dir)
+    }
+
+    /// Print a diff of every text file in `project_dir` that was edited since the snapshot
+    ///
+    /// Created, moved and deleted files are left out, transforms report those themselves.
+    pub fn print_edits(&self, project_dir: &Path) -> Result<()> {
+        for path in self.changes(project_dir)?.modified {
+            let old = fs::read_to_string(self.copy.join(&path));
+            let new = fs::read_to_string(project_dir.join(&path));
+            if let (Ok(old), Ok(new)) = (old, new) {
+                diff::print_diff(&old, &new, &path.to_string_lossy());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare the files of `before` and `after`, pairing up deleted and created files with
+/// the same contents as moves
+fn compare(before: &Path, after: &Path) -> Result<TransformPlan> {
+    let old = read_files(before)?;
+    let new = read_files(after)?;
+
+    let mut plan = TransformPlan::default();
+    let mut created: Vec<&PathBuf> = new.keys().filter(|path| !old.contains_key(*path)).collect();
+    for (path, content) in &old {
+        match new.get(path) {
+            Some(updated) if updated == content => {}
+            Some(_) => plan.modified.push(path.clone()),
+            None => {
+                let moved_to = created
+                    .iter()
+                    .position(|candidate| new[*candidate] == *content && candidate.file_name() == path.file_name());
+                match moved_to {
+                    Some(index) => plan.moved.push((path.clone(), created.remove(index).clone())),
+                    None => plan.deleted.push(path.clone()),
+                }
+            }
+        }
+    }
+    plan.created = created.into_iter().cloned().collect();
+
+    let empty = Vec::new();
+    let mut changed: Vec<&PathBuf> = plan.created.iter().chain(&plan.modified).chain(&plan.deleted).collect();
+    changed.sort();
+    for path in changed {
+        let before = old.get(path).unwrap_or(&empty);
+        let after = new.get(path).unwrap_or(&empty);
+        plan.diff.push_str(&file_diff(path, old.contains_key(path), before, new.contains_key(path), after));
+    }
+
+    Ok(plan)
+}
+
+/// Unified diff of one file, `/dev/null` standing in for a side where it doesn't exist
+fn file_diff(path: &Path, existed: bool, before: &[u8], exists: bool, after: &[u8]) -> String {
+    let shown = path.to_string_lossy().replace('\\', "/");
+    let old_header = if existed { format!("a/{}", shown) } else { "/dev/null".to_string() };
+    let new_header = if exists { format!("b/{}", shown) } else { "/dev/null".to_string() };
+
+    match (std::str::from_utf8(before), std::str::from_utf8(after)) {
+        (Ok(before), Ok(after)) => diff::unified_diff(before, after, &old_header, &new_header),
+        _ => format!("Binary file {} differs\n", shown),
+    }
+}
+
+/// Every file below `root` by relative path, skipping the uncopied directories
+pub(super) fn read_files(root: &Path) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    let walker = WalkDir::new(root).into_iter().filter_entry(|entry| {
+        let name = entry.file_name().to_string_lossy();
+        !(entry.depth() > 0 && entry.file_type().is_dir() && UNCOPIED_DIRS.contains(&name.as_ref()))
+    });
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let content = fs::read(entry.path()).with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        files.insert(entry.path().strip_prefix(root)?.to_path_buf(), content);
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_plan_leaves_the_project_alone() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let project = temp_dir.path().join("app");
+        fs::create_dir_all(project.join("src"))?;
+        fs::create_dir_all(project.join("target/debug"))?;
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"app\"\n")?;
+        fs::write(project.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(project.join("notes.txt"), "old\n")?;
+
+        let plan = plan(&project, |copy| {
+            fs::create_dir_all(copy.join("crates/app"))?;
+            fs::rename(copy.join("src"), copy.join("crates/app/src"))?;
+            fs::write(copy.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/app\"]\n")?;
+            fs::write(copy.join("crates/app/Cargo.toml"), "[package]\nname = \"app\"\n")?;
+            fs::remove_file(copy.join("notes.txt"))?;
+            fs::remove_dir_all(copy.join("target"))?;
+            Ok(())
+        })?;
+
+        assert_eq!(plan.moved, vec![(PathBuf::from("src/main.rs"), PathBuf::from("crates/app/src/main.rs"))]);
+        assert_eq!(plan.created, vec![PathBuf::from("crates/app/Cargo.toml")]);
+        assert_eq!(plan.modified, vec![PathBuf::from("Cargo.toml")]);
+        assert_eq!(plan.deleted, vec![PathBuf::from("notes.txt"), PathBuf::from("target/")]);
+        assert!(plan.diff.contains("--- a/Cargo.toml\n+++ b/Cargo.toml\n"), "{}", plan.diff);
+        assert!(plan.diff.contains("+[workspace]"), "{}", plan.diff);
+        assert!(plan.diff.contains("--- /dev/null\n+++ b/crates/app/Cargo.toml"), "{}", plan.diff);
+        assert!(plan.diff.contains("--- a/notes.txt\n+++ /dev/null\n"), "{}", plan.diff);
+
+        assert!(project.join("src/main.rs").exists());
+        assert!(project.join("target/debug").exists());
+        assert_eq!(fs::read_to_string(project.join("Cargo.toml"))?, "[package]\nname = \"app\"\n");
+        Ok(())
+    }
+}
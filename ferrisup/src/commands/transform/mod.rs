@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use colored::Colorize;
 use std::path::Path;
 use dialoguer::Select;
 use crate::commands::test_mode::is_test_mode;
+use crate::commands::verbosity::is_quiet;
 
 // Re-export component functions for backward compatibility
 pub use self::component::add_component;
@@ -16,8 +17,25 @@ pub mod utils;
 pub mod ui;
 pub mod workspace_utils;
 pub mod constants;
+pub mod dry_run;
+pub mod retheme;
 
-pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Result<()> {
+/// Execute the transform command
+///
+/// Without a template this runs the interactive transformation menu. The `to-workspace`
+/// template converts a single crate into a workspace, and a component type such as
+/// `client/leptos` adds that component to a workspace as a member called `component_name`.
+/// With `to_template` the project moves from the template it was generated from (or
+/// `from_template`) onto a sibling template, see [`retheme::execute`]. With `dry_run` the
+/// changes a template would make are printed as a diff instead.
+pub fn execute(
+    project_path: Option<&str>,
+    template_name: Option<&str>,
+    component_name: Option<&str>,
+    from_template: Option<&str>,
+    to_template: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
     ui::print_banner();
 
     // Get project path from argument or use current directory
@@ -36,7 +54,7 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
 
         // Ask if user wants to specify a different path
         if ui::confirm_action("Would you like to specify a different path?", true)? {
-            return execute(None, template_name);
+            return execute(None, template_name, component_name, from_template, to_template, dry_run);
         } else {
             return Ok(());
         }
@@ -52,12 +70,48 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
 
         // Ask if user wants to specify a different path
         if ui::confirm_action("Would you like to specify a different path?", true)? {
-            return execute(None, template_name);
+            return execute(None, template_name, component_name, from_template, to_template, dry_run);
         } else {
             return Ok(());
         }
     }
 
+    // Non-interactive transforms
+    if let Some(to_template) = to_template {
+        return retheme::execute(project_dir, from_template, to_template, dry_run);
+    }
+    if from_template.is_some() {
+        return Err(anyhow!("--from needs --to, the template to move the project onto"));
+    }
+    if template_name == Some("to-workspace") {
+        if dry_run {
+            dry_run::preview(project_dir, |copy| workspace::to_workspace(copy).map(|_| ()))?;
+        } else {
+            with_diff(project_dir, |dir| workspace::to_workspace(dir).map(|_| ()))?;
+        }
+        return Ok(());
+    }
+    if let Some(spec) = template_name {
+        let component_type = spec.split('/').next().unwrap_or(spec);
+        if constants::get_component_type_names().contains(&component_type) {
+            if dry_run {
+                dry_run::preview(project_dir, |copy| {
+                    component::add_component_from_spec(copy, spec, component_name).map(|_| ())
+                })?;
+            } else {
+                with_diff(project_dir, |dir| {
+                    component::add_component_from_spec(dir, spec, component_name).map(|_| ())
+                })?;
+            }
+            return Ok(());
+        }
+    }
+    if dry_run {
+        return Err(anyhow!(
+            "--dry-run needs a template to preview: `to-workspace`, a component type such as `client/leptos` or --to"
+        ));
+    }
+
     // Analyze project structure
     let structure = project_structure::analyze_project_structure(project_dir)?;
 
@@ -104,7 +158,7 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
             match option_idx {
                 0 => {
                     // Add a component
-                    component::add_component(project_dir)?;
+                    with_diff(project_dir, component::add_component)?;
                 }
                 1 => {
                     // Exit
@@ -118,7 +172,7 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
             match option_idx {
                 0 => {
                     // Convert to workspace
-                    workspace::convert_to_workspace(project_dir)?;
+                    with_diff(project_dir, workspace::convert_to_workspace)?;
                     is_workspace = true;
                     // Continue to the next iteration with workspace options
                     continue;
@@ -126,7 +180,7 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
                 1 => {
                     // Use current structure
                     println!("{}", "Using current structure.".blue());
-                    component::add_component_without_workspace(project_dir)?;
+                    with_diff(project_dir, component::add_component_without_workspace)?;
                 }
                 2 => {
                     // Exit
@@ -140,3 +194,14 @@ pub fn execute(project_path: Option<&str>, template_name: Option<&str>) -> Resul
 
     Ok(())
 }
+
+/// Run `transform` on `project_dir`, then print a diff of the files it edited
+fn with_diff<T>(project_dir: &Path, transform: impl FnOnce(&Path) -> Result<T>) -> Result<T> {
+    if is_quiet() {
+        return transform(project_dir);
+    }
+    let snapshot = dry_run::Snapshot::take(project_dir)?;
+    let result = transform(project_dir)?;
+    snapshot.print_edits(project_dir)?;
+    Ok(result)
+}
@@ -0,0 +1,336 @@
+// Moving a project onto a sibling template (`transform --from/--to`)
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use toml_edit::DocumentMut;
+
+use super::{dry_run, ui, with_diff};
+use crate::commands::offline;
+use crate::commands::test_mode::is_test_mode;
+use crate::core::diff;
+use crate::template_manager;
+
+/// Where `new` records the template a project came from
+const STAMP_PATH: &str = ".ferrisup/origin.toml";
+
+/// Stamp keys describing the original template's choices, dropped when another template is named
+const TEMPLATE_CHOICES: &[&str] = &["template", "component_type", "framework", "provider", "application_type"];
+
+/// Suffix of the copies of conflicting files holding the target template's version
+const CONFLICT_SUFFIX: &str = "ferrisup-new";
+
+/// The files of a project, by path relative to its root
+type Files = BTreeMap<PathBuf, Vec<u8>>;
+
+/// What moving a project from one template to another changes
+#[derive(Debug, Default, PartialEq)]
+pub struct Retheme {
+    /// Files the user left as generated, with the target template's version (`None` deletes them)
+    pub updates: BTreeMap<PathBuf, Option<Vec<u8>>>,
+    /// Files the two templates disagree on that the user changed since they were generated,
+    /// with the target template's version
+    pub conflicts: BTreeMap<PathBuf, Option<Vec<u8>>>,
+}
+
+/// Work out how the project's files change when the templates go from `from` to `to`
+///
+/// Files both templates generate the same way, and files already as `to` has them, are
+/// left alone. The others take the `to` version when the project still has them as `from`
+/// generated them (or lacks them like `from` does); otherwise the user changed them, so
+/// they're conflicts.
+pub fn plan_retheme(project: &Files, from: &Files, to: &Files) -> Retheme {
+    let mut retheme = Retheme::default();
+    let paths: BTreeSet<&PathBuf> = from.keys().chain(to.keys()).collect();
+    for path in paths {
+        let (base, target, current) = (from.get(path), to.get(path), project.get(path));
+        if base == target || current == target {
+            continue;
+        }
+        let slot = if current == base { &mut retheme.updates } else { &mut retheme.conflicts };
+        slot.insert(path.clone(), target.cloned());
+    }
+    retheme
+}
+
+/// Re-theme the project in `project_dir` from the template it was generated from onto
+/// `to_template`
+///
+/// `from_template` defaults to the template recorded in the project's `.ferrisup/origin.toml`.
+/// Both templates are generated with the project's name and stamped choices, and their
+/// differences are applied to the project. Files the user modified are conflicts: each one
+/// is only overwritten after confirming, and otherwise the `to` version is written next to
+/// it as `<file>.ferrisup-new` to merge by hand. With `dry_run` the changes and conflicts
+/// are printed as a diff instead.
+pub fn execute(project_dir: &Path, from_template: Option<&str>, to_template: &str, dry_run: bool) -> Result<()> {
+    let stamp_path = project_dir.join(STAMP_PATH);
+    let stamp = match fs::read_to_string(&stamp_path) {
+        Ok(content) => content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", stamp_path.display()))?,
+        Err(_) if from_template.is_some() => DocumentMut::new(),
+        Err(_) => {
+            return Err(anyhow!(
+                "{} has no {} recording the template it was generated from; name it with --from",
+                project_dir.display(),
+                STAMP_PATH
+            ))
+        }
+    };
+    let stamped_template = stamp.get("template").and_then(|t| t.as_str()).map(|t| t.to_string());
+    let from_label = match (from_template, &stamped_template) {
+        (Some(from), _) => from.to_string(),
+        (None, Some(stamped)) => stamped.clone(),
+        (None, None) => return Err(anyhow!("{} doesn't record a template; name it with --from", stamp_path.display())),
+    };
+    for template in from_template.into_iter().chain([to_template]) {
+        if template_manager::get_template_config(template).is_err() {
+            return Err(anyhow!("Unknown template '{}'", template));
+        }
+    }
+    if from_label == to_template {
+        println!("{} The project is already generated from {}", "Note:".blue(), to_template.cyan());
+        return Ok(());
+    }
+
+    let name = project_name(project_dir)?;
+    println!(
+        "{}",
+        format!("Generating {} and {} as {} to compare them", from_label, to_template, name).dimmed()
+    );
+    let sandbox = tempfile::tempdir().context("Failed to create a directory for the template baselines")?;
+    let from_files = generate_baseline(&stamp, from_template, &name, &sandbox.path().join("from"))
+        .with_context(|| format!("Failed to generate {} to compare with", from_label))?;
+    let mut to_files = generate_baseline(&stamp, Some(to_template), &name, &sandbox.path().join("to"))
+        .with_context(|| format!("Failed to generate {} to compare with", to_template))?;
+
+    // The stamp belongs to FerrisUp rather than the user: it always records the new template
+    let stamp_file = PathBuf::from(STAMP_PATH);
+    let new_stamp = to_files.remove(&stamp_file);
+    let mut project_files = dry_run::read_files(project_dir)?;
+    project_files.remove(&stamp_file);
+    let mut retheme = plan_retheme(&project_files, &from_files, &to_files);
+    if !retheme.updates.is_empty() || !retheme.conflicts.is_empty() {
+        retheme.updates.insert(stamp_file, new_stamp);
+    }
+
+    if dry_run {
+        let plan = dry_run::plan(project_dir, |copy| write_files(copy, &retheme.updates))?;
+        println!();
+        plan.print();
+        print_conflicts(project_dir, &retheme.conflicts, to_template);
+        return Ok(());
+    }
+
+    if retheme.updates.is_empty() && retheme.conflicts.is_empty() {
+        println!("{} The project already has every file as {} generates it, nothing to change", "Note:".blue(), to_template);
+        return Ok(());
+    }
+    // Told apart before writing, while the project still has the files it had
+    let changes: Vec<_> = retheme
+        .updates
+        .iter()
+        .map(|(path, content)| match (project_dir.join(path).exists(), content) {
+            (_, None) => ("delete".red(), path),
+            (true, Some(_)) => ("modify".yellow(), path),
+            (false, Some(_)) => ("create".green(), path),
+        })
+        .collect();
+    with_diff(project_dir, |dir| write_files(dir, &retheme.updates))?;
+    for (change, path) in changes {
+        println!("  {} {}", change, path.display());
+    }
+    resolve_conflicts(project_dir, &retheme.conflicts, to_template)?;
+    println!("{} Re-themed the project from {} to {}", "✅".green(), from_label.cyan(), to_template.cyan());
+    Ok(())
+}
+
+/// Name to generate the templates with: the package name, else the directory name
+fn project_name(project_dir: &Path) -> Result<String> {
+    let manifest = fs::read_to_string(project_dir.join("Cargo.toml"))?;
+    let package_name = manifest
+        .parse::<DocumentMut>()
+        .ok()
+        .and_then(|doc| doc.get("package")?.get("name")?.as_str().map(|n| n.to_string()));
+    match package_name {
+        Some(name) => Ok(name),
+        None => project_dir
+            .canonicalize()?
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("Can't tell the name of the project in {}", project_dir.display())),
+    }
+}
+
+/// Generate `template` (the stamped template when `None`) into `dir` the way the project
+/// was generated, returning its files
+///
+/// `ferrisup new` runs in a child process answering its prompts from the stamp, so the
+/// baseline has the project's choices. READMEs are left out: the user's README is theirs.
+fn generate_baseline(stamp: &DocumentMut, template: Option<&str>, name: &str, dir: &Path) -> Result<Files> {
+    let mut answers = stamp.clone();
+    if let Some(template) = template {
+        for key in TEMPLATE_CHOICES {
+            answers.remove(key);
+        }
+        answers.insert("template", toml_edit::value(template));
+    }
+    let answers_path = dir.with_extension("toml");
+    fs::write(&answers_path, answers.to_string())?;
+
+    let mut args: Vec<String> = vec!["--quiet".to_string()];
+    if offline::is_offline() {
+        args.push("--offline".to_string());
+    }
+    args.extend(["new", name, "--answers"].map(String::from));
+    args.push(answers_path.to_string_lossy().to_string());
+    args.push("--path".to_string());
+    args.push(dir.to_string_lossy().to_string());
+    args.extend(["--no-interactive", "--no-build-check", "--no-readme", "--vcs", "none"].map(String::from));
+    if let Some(license) = stamp.get("license").and_then(|l| l.as_str()) {
+        args.push("--license".to_string());
+        args.push(license.to_string());
+    }
+
+    let output = Command::new(std::env::current_exe()?)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run ferrisup new")?;
+    if !output.status.success() {
+        let log = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        let tail: Vec<&str> = log.lines().rev().take(10).collect();
+        return Err(anyhow!("{}", tail.into_iter().rev().collect::<Vec<_>>().join("\n")));
+    }
+    dry_run::read_files(dir)
+}
+
+/// Write each file to its new contents below `root`, deleting those without any
+fn write_files(root: &Path, files: &BTreeMap<PathBuf, Option<Vec<u8>>>) -> Result<()> {
+    for (path, content) in files {
+        let target = root.join(path);
+        match content {
+            Some(content) => {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&target, content).with_context(|| format!("Failed to write {}", target.display()))?;
+            }
+            None if target.exists() => fs::remove_file(&target)?,
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Print each conflict with a diff from the user's version to the `to_template` one
+fn print_conflicts(project_dir: &Path, conflicts: &BTreeMap<PathBuf, Option<Vec<u8>>>, to_template: &str) {
+    if conflicts.is_empty() {
+        return;
+    }
+    println!(
+        "\n{}",
+        format!("Conflicts: files you modified that {} changes, never overwritten without asking:", to_template).bold()
+    );
+    for (path, target) in conflicts {
+        println!("  {} {}", "conflict".red().bold(), path.display());
+        let current = fs::read(project_dir.join(path)).ok();
+        let text = |content: Option<&Vec<u8>>| content.map(|c| String::from_utf8(c.clone())).transpose();
+        if let (Ok(current), Ok(target)) = (text(current.as_ref()), text(target.as_ref())) {
+            diff::print_diff(&current.unwrap_or_default(), &target.unwrap_or_default(), &path.to_string_lossy());
+        }
+    }
+}
+
+/// Resolve the conflicts: overwrite a file only when the user confirms, otherwise leave it
+/// and write the `to_template` version next to it as `<file>.ferrisup-new`
+///
+/// Without a terminal to ask on (`--test-mode`, CI or piped stdin) nothing is overwritten.
+fn resolve_conflicts(project_dir: &Path, conflicts: &BTreeMap<PathBuf, Option<Vec<u8>>>, to_template: &str) -> Result<()> {
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    let interactive = !is_test_mode() && std::env::var_os("CI").is_none() && std::io::stdin().is_terminal();
+    print_conflicts(project_dir, conflicts, to_template);
+
+    let mut unresolved = Vec::new();
+    for (path, target) in conflicts {
+        let prompt = match (project_dir.join(path).exists(), target) {
+            (true, Some(_)) => format!("Overwrite {}, which you modified, with the {} version?", path.display(), to_template),
+            (true, None) => format!("Delete {}, which you modified and {} doesn't have?", path.display(), to_template),
+            (false, _) => format!("Restore {}, which you deleted, with the {} version?", path.display(), to_template),
+        };
+        if interactive && ui::confirm_action(&prompt, false)? {
+            write_files(project_dir, &BTreeMap::from([(path.clone(), target.clone())]))?;
+            continue;
+        }
+        if let Some(target) = target {
+            let mut theirs = project_dir.join(path).into_os_string();
+            theirs.push(format!(".{}", CONFLICT_SUFFIX));
+            fs::write(&theirs, target)?;
+        }
+        unresolved.push(path);
+    }
+
+    if !unresolved.is_empty() {
+        println!("\n{}", "Left as you modified them, merge these by hand:".yellow().bold());
+        for path in unresolved {
+            match conflicts[path] {
+                Some(_) => println!("  {} (the {} version is in {}.{})", path.display(), to_template, path.display(), CONFLICT_SUFFIX),
+                None => println!("  {} ({} doesn't have it)", path.display(), to_template),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> Files {
+        entries.iter().map(|(path, content)| (PathBuf::from(path), content.as_bytes().to_vec())).collect()
+    }
+
+    #[test]
+    fn test_plan_retheme() {
+        let from = files(&[("Cargo.toml", "v1"), ("src/main.rs", "counter"), ("src/counter.rs", "count"), ("style.css", "a")]);
+        let to = files(&[("Cargo.toml", "v1"), ("src/main.rs", "router"), ("src/routes.rs", "routes"), ("style.css", "b")]);
+        // The user rewrote the counter and already updated the styles
+        let project = files(&[
+            ("Cargo.toml", "v1"),
+            ("src/main.rs", "counter"),
+            ("src/counter.rs", "my count"),
+            ("style.css", "b"),
+            ("src/extra.rs", "mine"),
+        ]);
+
+        let retheme = plan_retheme(&project, &from, &to);
+        assert_eq!(
+            retheme.updates,
+            BTreeMap::from([
+                (PathBuf::from("src/main.rs"), Some(b"router".to_vec())),
+                (PathBuf::from("src/routes.rs"), Some(b"routes".to_vec())),
+            ])
+        );
+        assert_eq!(retheme.conflicts, BTreeMap::from([(PathBuf::from("src/counter.rs"), None)]));
+    }
+
+    #[test]
+    fn test_plan_retheme_flags_files_in_the_way() {
+        let from = files(&[("src/main.rs", "counter")]);
+        let to = files(&[("src/main.rs", "router"), ("src/routes.rs", "routes")]);
+        // A file of the user's where the new template puts one, and a deleted generated file
+        let project = files(&[("src/routes.rs", "my routes")]);
+
+        let retheme = plan_retheme(&project, &from, &to);
+        assert!(retheme.updates.is_empty(), "{:?}", retheme.updates);
+        assert_eq!(
+            retheme.conflicts.keys().collect::<Vec<_>>(),
+            vec![&PathBuf::from("src/main.rs"), &PathBuf::from("src/routes.rs")]
+        );
+    }
+}
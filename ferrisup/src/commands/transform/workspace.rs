@@ -4,8 +4,9 @@ use std::fs;
 use std::path::Path;
 use dialoguer::Confirm;
 use ferrisup_common::cargo;
-// Removed unused import: use toml_edit::DocumentMut;
+use toml_edit::DocumentMut;
 
+use crate::commands::offline;
 use crate::commands::test_mode::{is_test_mode, test_mode_or};
 use super::project_structure::{analyze_project_structure, detect_framework};
 use super::utils::{store_transformation_metadata, store_component_type_in_cargo, update_source_imports};
@@ -44,7 +45,7 @@ pub fn convert_to_workspace(project_dir: &Path) -> Result<()> {
     
     // If the component name indicates it's a shared component, check if the crate name is available on crates.io
     if component_name == "ferrisup_common" || component_name == "shared" || component_name.ends_with("-common") || component_name.ends_with("_common") {
-        if !is_test_mode() {
+        if !is_test_mode() && !offline::is_offline() {
             // Keep prompting until we get an available name
             let mut is_available = false;
             while !is_available {
@@ -200,3 +201,156 @@ pub fn convert_to_workspace(project_dir: &Path) -> Result<()> {
 
     Ok(())
 }
+
+/// Entries that stay at the workspace root when a crate is moved under `crates/`
+const WORKSPACE_ROOT_ENTRIES: &[&str] = &[
+    ".git",
+    ".gitignore",
+    ".github",
+    ".ferrisup",
+    ".cargo",
+    "Cargo.lock",
+    "rust-toolchain",
+    "rust-toolchain.toml",
+    "crates",
+];
+
+/// Manifest sections Cargo only honours in the workspace root manifest
+const WORKSPACE_ROOT_SECTIONS: &[&str] = &["profile", "patch", "replace"];
+
+/// Restructure a single-crate project into a workspace with the crate at `crates/<name>/`
+///
+/// Unlike `convert_to_workspace` this never prompts: the whole crate moves, its
+/// `[package]` section is kept as is and relative path dependencies are rewritten to
+/// still point at the same crates. `target/` is removed rather than moved, and
+/// `[profile]`/`[patch]` sections move to the new root manifest since Cargo ignores
+/// them in members. Returns the new location of the crate.
+pub fn to_workspace(project_dir: &Path) -> Result<std::path::PathBuf> {
+    let manifest_path = project_dir.join("Cargo.toml");
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", manifest_path.display(), e))?;
+    let mut manifest = content
+        .parse::<DocumentMut>()
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", manifest_path.display(), e))?;
+
+    if manifest.contains_key("workspace") {
+        return Err(anyhow::anyhow!(
+            "{} is already a workspace ([workspace] found in Cargo.toml)",
+            project_dir.display()
+        ));
+    }
+
+    let crate_name = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .ok_or_else(|| anyhow::anyhow!("{} has no [package] name", manifest_path.display()))?
+        .to_string();
+    let edition = manifest
+        .get("package")
+        .and_then(|p| p.get("edition"))
+        .and_then(|e| e.as_str())
+        .unwrap_or("2021")
+        .to_string();
+
+    let crate_dir = project_dir.join("crates").join(&crate_name);
+    if crate_dir.exists() {
+        return Err(anyhow::anyhow!("{} already exists", crate_dir.display()));
+    }
+
+    // Stale artifacts would be rebuilt under the workspace target anyway
+    let target_dir = project_dir.join("target");
+    if target_dir.exists() {
+        fs::remove_dir_all(&target_dir)
+            .map_err(|e| anyhow::anyhow!("Failed to remove {}: {}", target_dir.display(), e))?;
+    }
+
+    rewrite_path_dependencies(manifest.as_table_mut());
+
+    let mut root_manifest = DocumentMut::new();
+    let mut workspace = toml_edit::Table::new();
+    let mut members = toml_edit::Array::new();
+    members.push(format!("crates/{}", crate_name));
+    workspace.insert("members", toml_edit::value(members));
+    workspace.insert("resolver", toml_edit::value(if edition == "2024" { "3" } else { "2" }));
+    root_manifest.insert("workspace", toml_edit::Item::Table(workspace));
+    for section in WORKSPACE_ROOT_SECTIONS {
+        if let Some(item) = manifest.remove(section) {
+            root_manifest.insert(section, item);
+        }
+    }
+
+    create_directory(&crate_dir)?;
+    for entry in fs::read_dir(project_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "Cargo.toml" || WORKSPACE_ROOT_ENTRIES.contains(&name.as_str()) {
+            continue;
+        }
+        let destination = crate_dir.join(&name);
+        fs::rename(entry.path(), &destination)
+            .map_err(|e| anyhow::anyhow!("Failed to move {} to {}: {}", entry.path().display(), destination.display(), e))?;
+    }
+
+    fs::write(crate_dir.join("Cargo.toml"), manifest.to_string())?;
+    fs::write(&manifest_path, root_manifest.to_string())?;
+
+    println!(
+        "{} {} {}",
+        "Converted to a workspace, moved the crate to".green(),
+        format!("crates/{}", crate_name).cyan(),
+        "and removed target/".green()
+    );
+
+    Ok(crate_dir)
+}
+
+/// Point relative path dependencies of a crate moved two levels down at the same crates
+///
+/// Paths into the crate's own directory move along with it and are left alone; paths
+/// leaving it (`../other`) or into entries kept at the root get a `../../` prefix.
+fn rewrite_path_dependencies(manifest: &mut toml_edit::Table) {
+    fn rewrite_table(table: &mut dyn toml_edit::TableLike) {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(dependencies) = table.get_mut(section).and_then(|d| d.as_table_like_mut()) else {
+                continue;
+            };
+            for (_, dependency) in dependencies.iter_mut() {
+                let Some(dependency) = dependency.as_table_like_mut() else {
+                    continue;
+                };
+                let Some(path) = dependency.get("path").and_then(|p| p.as_str()) else {
+                    continue;
+                };
+                if let Some(rewritten) = rewrite_path(path) {
+                    dependency.insert("path", toml_edit::value(rewritten));
+                }
+            }
+        }
+    }
+
+    fn rewrite_path(path: &str) -> Option<String> {
+        let relative = Path::new(path);
+        if relative.is_absolute() {
+            return None;
+        }
+        let first = relative.components().find(|c| !matches!(c, std::path::Component::CurDir))?;
+        let leaves_crate = match first {
+            std::path::Component::ParentDir => true,
+            std::path::Component::Normal(name) => {
+                WORKSPACE_ROOT_ENTRIES.contains(&name.to_string_lossy().as_ref())
+            }
+            _ => false,
+        };
+        leaves_crate.then(|| format!("../../{}", path.trim_start_matches("./")))
+    }
+
+    rewrite_table(manifest);
+    if let Some(targets) = manifest.get_mut("target").and_then(|t| t.as_table_like_mut()) {
+        for (_, target) in targets.iter_mut() {
+            if let Some(target) = target.as_table_like_mut() {
+                rewrite_table(target);
+            }
+        }
+    }
+}
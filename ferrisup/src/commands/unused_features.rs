@@ -1,10 +1,21 @@
-use anyhow::Result;
-use std::path::PathBuf;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use colored::Colorize;
+use similar::TextDiff;
+use toml_edit::DocumentMut;
+use crate::commands::offline;
+
+/// Dependency tables that may enable features on a dependency
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
 
 /// Execute the unused-features command to find unused features in Cargo dependencies
-pub fn execute(path: Option<&str>) -> Result<()> {
+///
+/// With `apply`, the flagged features are removed from Cargo.toml (a backup is kept in
+/// `Cargo.toml.bak`). With `fail_on_unused`, the command returns an error if any unused
+/// features were found, so it can gate CI.
+pub fn execute(path: Option<&str>, apply: bool, fail_on_unused: bool) -> Result<()> {
     // Determine the target path
     let target_path = match path {
         Some(p) => PathBuf::from(p),
@@ -29,6 +40,7 @@ pub fn execute(path: Option<&str>) -> Result<()> {
     // Check if cargo-unused-features is installed
     if !is_cargo_unused_features_installed() {
         println!("{}", "cargo-unused-features is not installed.".yellow());
+        offline::ensure_online("install cargo-unused-features")?;
         println!("{}", "Installing cargo-unused-features...".yellow());
         
         // Install cargo-unused-features
@@ -66,15 +78,15 @@ pub fn execute(path: Option<&str>) -> Result<()> {
             
             // Parse and display the unused features from the log output
             let log_lines: Vec<&str> = stderr.lines().collect();
-            let mut current_dependency = "";
-            let mut unused_features = Vec::new();
+            let mut current_dependency = String::new();
+            let mut unused_features: Vec<(String, String)> = Vec::new();
             
             for line in log_lines {
                 if line.contains("==== Dependency") {
                     // Extract dependency name
                     if let Some(dep_name) = line.split("'")
                         .nth(1) {
-                        current_dependency = dep_name;
+                        current_dependency = dep_name.to_string();
                         println!("{}", format!("\nDependency: {}", current_dependency).yellow().bold());
                     }
                 } else if line.contains("Prune") && line.contains("feature flag from") {
@@ -82,35 +94,47 @@ pub fn execute(path: Option<&str>) -> Result<()> {
                     if let Some(feature_name) = line.split("'")
                         .nth(1) {
                         println!("  - {}", feature_name);
-                        unused_features.push((current_dependency, feature_name));
+                        unused_features.push((current_dependency.clone(), feature_name.to_string()));
                     }
                 }
             }
             
-            println!("\n{}", "Recommendations:".green());
-            println!("- Review the unused features and consider removing them from your Cargo.toml");
-            println!("- For each dependency with unused features, update it like this:");
-            
-            // Group by dependency
-            let mut deps = std::collections::HashMap::new();
-            for (dep, feature) in unused_features {
-                deps.entry(dep).or_insert_with(Vec::new).push(feature);
-            }
-            
-            // Print example for each dependency
-            for (dep, features) in deps {
-                println!("  {} = {{ version = \"x.y\", features = [] }} # Removed: {}", 
-                         dep.italic(), 
-                         features.join(", "));
-            }
-            
-            println!("- Run 'ferrisup unused-features' again after making changes to verify");
-            
             // Clean up any report file if it exists
             let report_path = target_path.join("report.json");
             if report_path.exists() {
                 let _ = std::fs::remove_file(&report_path);
             }
+            
+            if apply {
+                apply_feature_removals(&cargo_toml_path, &unused_features)?;
+            } else {
+                println!("\n{}", "Recommendations:".green());
+                println!("- Review the unused features and consider removing them from your Cargo.toml");
+                println!("- Or run 'ferrisup unused-features --apply' to remove them automatically");
+                println!("- For each dependency with unused features, update it like this:");
+                
+                // Group by dependency
+                let mut deps = std::collections::HashMap::new();
+                for (dep, feature) in &unused_features {
+                    deps.entry(dep.as_str()).or_insert_with(Vec::new).push(feature.as_str());
+                }
+                
+                // Print example for each dependency
+                for (dep, features) in deps {
+                    println!("  {} = {{ version = \"x.y\", features = [] }} # Removed: {}", 
+                             dep.italic(), 
+                             features.join(", "));
+                }
+                
+                println!("- Run 'ferrisup unused-features' again after making changes to verify");
+            }
+            
+            if fail_on_unused && !unused_features.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Found {} unused feature(s) in dependencies",
+                    unused_features.len()
+                ));
+            }
         } else {
             println!("{}", "✅ No unused features found!".green());
         }
@@ -133,3 +157,155 @@ fn is_cargo_unused_features_installed() -> bool {
         Err(_) => false,
     }
 }
+
+/// Remove the flagged features from Cargo.toml, printing a diff of the changes
+///
+/// The original manifest is copied to `Cargo.toml.bak` first and restored if writing fails.
+fn apply_feature_removals(cargo_toml_path: &Path, unused_features: &[(String, String)]) -> Result<()> {
+    let original = fs::read_to_string(cargo_toml_path)
+        .context(format!("Failed to read {}", cargo_toml_path.display()))?;
+    let mut doc = original
+        .parse::<DocumentMut>()
+        .context("Failed to parse Cargo.toml as TOML")?;
+    
+    let skipped = remove_unused_features(&mut doc, unused_features);
+    for (dep, feature) in &skipped {
+        println!("{} {}", 
+            "Keeping".yellow(), 
+            format!("{}/{} (required by a feature in [features])", dep, feature).yellow());
+    }
+    
+    let updated = doc.to_string();
+    if updated == original {
+        println!("{}", "No changes to apply to Cargo.toml".yellow());
+        return Ok(());
+    }
+    
+    // Back up the original manifest before touching it
+    let backup_path = cargo_toml_path.with_extension("toml.bak");
+    fs::copy(cargo_toml_path, &backup_path)
+        .context(format!("Failed to back up Cargo.toml to {}", backup_path.display()))?;
+    
+    if let Err(e) = fs::write(cargo_toml_path, &updated) {
+        fs::copy(&backup_path, cargo_toml_path)
+            .context("Failed to restore Cargo.toml from backup")?;
+        return Err(anyhow::anyhow!("Failed to write Cargo.toml (original restored): {}", e));
+    }
+    
+    let diff = TextDiff::from_lines(&original, &updated);
+    println!("\n{}", "Applied changes:".green().bold());
+    for line in diff
+        .unified_diff()
+        .header("Cargo.toml.bak", "Cargo.toml")
+        .to_string()
+        .lines()
+    {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            println!("{}", line.green());
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            println!("{}", line.red());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!("{} {}", "Backup saved to".blue(), backup_path.display());
+    
+    Ok(())
+}
+
+/// Remove unused features from the dependency entries in `doc`
+///
+/// Features that a `[features]` entry of this crate enables (e.g. `"dep/feature"`) are
+/// required by other features and are left alone; they are returned so they can be reported.
+fn remove_unused_features(doc: &mut DocumentMut, unused_features: &[(String, String)]) -> Vec<(String, String)> {
+    let required = required_dependency_features(doc);
+    let mut skipped = Vec::new();
+    
+    for (dep, feature) in unused_features {
+        if required.contains(&(dep.clone(), feature.clone())) {
+            skipped.push((dep.clone(), feature.clone()));
+            continue;
+        }
+        
+        for section in DEPENDENCY_SECTIONS {
+            let Some(entry) = doc
+                .get_mut(section)
+                .and_then(|t| t.as_table_like_mut())
+                .and_then(|t| t.get_mut(dep))
+            else {
+                continue;
+            };
+            
+            let now_empty = match entry.get_mut("features").and_then(|f| f.as_array_mut()) {
+                Some(features) => {
+                    features.retain(|f| f.as_str() != Some(feature.as_str()));
+                    features.is_empty()
+                }
+                None => false,
+            };
+            if now_empty {
+                if let Some(inline) = entry.as_inline_table_mut() {
+                    inline.remove("features");
+                    inline.fmt();
+                } else if let Some(table) = entry.as_table_like_mut() {
+                    table.remove("features");
+                }
+            }
+        }
+    }
+    
+    skipped
+}
+
+/// Collect the `dep/feature` pairs enabled by this crate's own `[features]` table
+fn required_dependency_features(doc: &DocumentMut) -> Vec<(String, String)> {
+    let mut required = Vec::new();
+    
+    if let Some(features) = doc.get("features").and_then(|f| f.as_table_like()) {
+        for (_, enables) in features.iter() {
+            let Some(enables) = enables.as_array() else {
+                continue;
+            };
+            for value in enables.iter().filter_map(|v| v.as_str()) {
+                if let Some((dep, feature)) = value.split_once('/') {
+                    required.push((dep.trim_end_matches('?').to_string(), feature.to_string()));
+                }
+            }
+        }
+    }
+    
+    required
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_remove_unused_features_keeps_required() -> Result<()> {
+        let mut doc = r#"[package]
+name = "fixture"
+
+[dependencies]
+tokio = { version = "1", features = ["rt", "macros", "fs"] }
+serde = { version = "1", features = ["rc"] }
+
+[features]
+files = ["tokio/fs"]
+"#
+        .parse::<DocumentMut>()?;
+        
+        let unused = vec![
+            ("tokio".to_string(), "macros".to_string()),
+            ("tokio".to_string(), "fs".to_string()),
+            ("serde".to_string(), "rc".to_string()),
+        ];
+        let skipped = remove_unused_features(&mut doc, &unused);
+        
+        assert_eq!(skipped, vec![("tokio".to_string(), "fs".to_string())]);
+        let updated = doc.to_string();
+        assert!(updated.contains(r#"tokio = { version = "1", features = ["rt", "fs"] }"#));
+        assert!(updated.contains(r#"serde = { version = "1" }"#));
+        Ok(())
+    }
+}
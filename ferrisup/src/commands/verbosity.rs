@@ -0,0 +1,135 @@
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+/// How much output the CLI should produce, set once from the global `--quiet`/`--verbose` flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only genuine errors are printed (to stderr)
+    Quiet,
+    /// Regular status output
+    #[default]
+    Normal,
+    /// Regular status output plus debug logging
+    Verbose,
+}
+
+impl Verbosity {
+    /// Build a verbosity from the CLI flags; `--quiet` wins if both are given
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+
+    pub fn is_verbose(self) -> bool {
+        self == Verbosity::Verbose
+    }
+
+    /// The `env_logger` level matching this verbosity
+    pub fn log_level(self) -> log::LevelFilter {
+        match self {
+            Verbosity::Quiet => log::LevelFilter::Error,
+            Verbosity::Normal => log::LevelFilter::Warn,
+            Verbosity::Verbose => log::LevelFilter::Debug,
+        }
+    }
+}
+
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+
+/// Record the verbosity chosen on the command line
+///
+/// Only the first call has an effect, later calls are ignored
+pub fn set_verbosity(verbosity: Verbosity) {
+    let _ = VERBOSITY.set(verbosity);
+}
+
+/// The verbosity for this run
+///
+/// Falls back to `Verbose` when the FERRISUP_VERBOSE environment variable is set,
+/// so the existing debug output keeps working without the flag
+pub fn verbosity() -> Verbosity {
+    *VERBOSITY.get_or_init(|| {
+        if std::env::var("FERRISUP_VERBOSE").is_ok() {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    })
+}
+
+/// Returns true if status output should be suppressed
+pub fn is_quiet() -> bool {
+    verbosity().is_quiet()
+}
+
+/// Returns true if debug output should be printed
+pub fn is_verbose() -> bool {
+    verbosity().is_verbose()
+}
+
+static STATUS_TO_STDERR: OnceLock<bool> = OnceLock::new();
+
+/// Record whether status lines go to stderr, so stdout only carries a result such as
+/// the `--output-format json` object
+///
+/// Only the first call has an effect, later calls are ignored
+pub fn set_status_to_stderr(to_stderr: bool) {
+    let _ = STATUS_TO_STDERR.set(to_stderr);
+}
+
+/// Returns true if status lines go to stderr rather than stdout
+pub fn status_to_stderr() -> bool {
+    *STATUS_TO_STDERR.get_or_init(|| false)
+}
+
+/// Where a tool FerrisUp runs writes its output: stdout, or stderr along with the status lines
+pub fn status_stdio() -> Stdio {
+    if status_to_stderr() {
+        Stdio::from(std::io::stderr())
+    } else {
+        Stdio::inherit()
+    }
+}
+
+/// Print a status line, like `println!`, on stdout or on stderr when
+/// [`set_status_to_stderr`] says so. Nothing is printed with `--quiet`
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::commands::verbosity::is_quiet() {
+            // --quiet only lets errors through
+        } else if $crate::commands::verbosity::status_to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(true, false), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(true, true), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(Verbosity::Quiet.log_level(), log::LevelFilter::Error);
+        assert_eq!(Verbosity::Verbose.log_level(), log::LevelFilter::Debug);
+    }
+}
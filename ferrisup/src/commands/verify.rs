@@ -0,0 +1,339 @@
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use toml_edit::DocumentMut;
+
+use crate::commands::offline;
+use crate::template_manager;
+
+/// How long generating a template may take before it's considered stuck on a prompt
+const GENERATE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Lines of diagnostics shown for each failing template
+const MAX_DIAGNOSTIC_LINES: usize = 40;
+
+/// Answers for the prompts `--set` can't skip, which pick a framework or provider.
+/// Every other prompt is answered with the default from the template's variables.
+const DEFAULT_ANSWERS: &[(&str, &[&str])] = &[
+    ("server", &["--framework", "axum"]),
+    ("client", &["--framework", "leptos"]),
+    ("serverless", &["--provider", "aws"]),
+    (
+        "data-science",
+        &[
+            "--framework",
+            "polars",
+            "--set",
+            "data_source=CSV files",
+            "--set",
+            "analysis_type=Exploratory data analysis",
+        ],
+    ),
+];
+
+/// How far a template got before failing
+#[derive(Debug)]
+pub enum Outcome {
+    Passed,
+    GenerateFailed(String),
+    CheckFailed(String),
+}
+
+/// Result of verifying one template
+#[derive(Debug)]
+pub struct Verification {
+    pub template: String,
+    /// The cargo command the generated project was checked with
+    pub command: String,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+/// Execute the verify command
+///
+/// Generates `template` (or every bundled template with `all`) with its default answers
+/// into a temporary directory and checks that the result compiles. Fails if any
+/// template doesn't, so it can be used as a regression check in CI.
+pub fn execute(template: Option<&str>, all: bool) -> Result<()> {
+    let templates = match (template, all) {
+        (_, true) => template_manager::get_all_templates()?,
+        (Some(template), false) => vec![template.to_string()],
+        (None, false) => return Err(anyhow!("Name a template to verify, or pass --all")),
+    };
+
+    let sandbox = tempfile::tempdir().context("Failed to create a sandbox directory")?;
+    let mut results = Vec::new();
+    for template in &templates {
+        println!("🔄 Verifying {}...", template.cyan());
+        let result = verify_template(template, sandbox.path())?;
+        match &result.outcome {
+            Outcome::Passed => println!("{} {} ({})", "✓".green(), template, result.command),
+            _ => println!("{} {}", "✗".red(), template),
+        }
+        results.push(result);
+    }
+
+    if all {
+        print_matrix(&results);
+    }
+    for result in &results {
+        let (stage, diagnostics) = match &result.outcome {
+            Outcome::Passed => continue,
+            Outcome::GenerateFailed(diagnostics) => ("generating", diagnostics),
+            Outcome::CheckFailed(diagnostics) => (result.command.as_str(), diagnostics),
+        };
+        println!("\n{} {} failed while {}:", "✗".red(), result.template.bold(), stage);
+        println!("{}", diagnostics);
+    }
+
+    let failed = results.iter().filter(|r| !matches!(r.outcome, Outcome::Passed)).count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} template(s) failed to verify", failed, results.len()));
+    }
+    println!("\n{}", format!("{} template(s) verified", results.len()).green());
+    Ok(())
+}
+
+/// Generate `template` below `sandbox` and check that the project compiles
+///
+/// Generation runs in a child process so a prompt that can't be answered fails the
+/// template after a timeout instead of hanging the whole run.
+pub fn verify_template(template: &str, sandbox: &Path) -> Result<Verification> {
+    let started = Instant::now();
+    let name = format!("verify-{}", template.replace(|c: char| !c.is_ascii_alphanumeric(), "-"));
+    let project_dir = sandbox.join(&name);
+    if project_dir.exists() {
+        fs::remove_dir_all(&project_dir)?;
+    }
+    fs::create_dir_all(&project_dir)?;
+
+    let finish = |command: String, outcome: Outcome| Verification {
+        template: template.to_string(),
+        command,
+        outcome,
+        duration: started.elapsed(),
+    };
+
+    if let Err(diagnostics) = generate(template, &name, &project_dir, sandbox)? {
+        return Ok(finish(String::new(), Outcome::GenerateFailed(diagnostics)));
+    }
+    if !project_dir.join("Cargo.toml").exists() {
+        return Ok(finish(
+            String::new(),
+            Outcome::GenerateFailed("The template did not generate a Cargo.toml".to_string()),
+        ));
+    }
+
+    let args = check_args(&project_dir);
+    let command = format!("cargo {}", args.join(" "));
+    let output = Command::new("cargo")
+        .args(&args)
+        .current_dir(&project_dir)
+        // Share one target directory so dependencies are only built once per run
+        .env("CARGO_TARGET_DIR", sandbox.join("target"))
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run cargo")?;
+
+    let outcome = if output.status.success() {
+        Outcome::Passed
+    } else {
+        Outcome::CheckFailed(diagnostics(&String::from_utf8_lossy(&output.stderr)))
+    };
+    Ok(finish(command, outcome))
+}
+
+/// Run `ferrisup new` for `template` into `project_dir`, returning its output on failure
+fn generate(template: &str, name: &str, project_dir: &Path, sandbox: &Path) -> Result<Result<(), String>> {
+    let mut args: Vec<String> = vec!["--quiet".to_string()];
+    if offline::is_offline() {
+        args.push("--offline".to_string());
+    }
+    args.extend(["new", name, "--component-type", template, "--into"].map(String::from));
+    args.push(project_dir.to_string_lossy().to_string());
+    args.push("--no-interactive".to_string());
+    args.extend(default_answers(template));
+
+    let log_path = sandbox.join(format!("{}.log", name));
+    let log = File::create(&log_path)?;
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(&args)
+        .current_dir(sandbox)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .spawn()
+        .context("Failed to run ferrisup new")?;
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if started.elapsed() > GENERATE_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+
+    let output = fs::read_to_string(&log_path).unwrap_or_default();
+    match status {
+        Some(status) if status.success() => Ok(Ok(())),
+        Some(_) => Ok(Err(diagnostics(&output))),
+        None => Ok(Err(format!(
+            "Timed out after {}s, probably waiting on a prompt without a default answer\n{}",
+            GENERATE_TIMEOUT.as_secs(),
+            tail(&output)
+        ))),
+    }
+}
+
+/// Arguments answering every prompt of `template` with its default
+fn default_answers(template: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    for variable in template_manager::template_variables(template) {
+        let default = match variable.default {
+            Some(serde_json::Value::String(s)) => s,
+            Some(other) => other.to_string(),
+            None => continue,
+        };
+        args.push("--set".to_string());
+        args.push(format!("{}={}", variable.name, default));
+    }
+
+    // Later `--set` values win, so the explicit answers go last
+    if let Some((_, answers)) = DEFAULT_ANSWERS.iter().find(|(name, _)| *name == template) {
+        args.extend(answers.iter().map(|a| a.to_string()));
+    }
+    args
+}
+
+/// Cargo arguments that check the project at `project_dir` for the platform it targets
+///
+/// wasm-bindgen libraries are built for `wasm32-unknown-unknown`, since host checks miss
+/// problems that only show up there. Everything else is checked with its tests and examples.
+fn check_args(project_dir: &Path) -> Vec<String> {
+    let manifest = fs::read_to_string(project_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok());
+
+    let mut args: Vec<&str> = match &manifest {
+        Some(doc) if is_wasm_library(doc) => vec!["build", "--target", "wasm32-unknown-unknown"],
+        _ => vec!["check", "--all-targets"],
+    };
+    if manifest.as_ref().is_some_and(|doc| doc.get("workspace").is_some()) {
+        args.push("--workspace");
+    }
+    if offline::is_offline() {
+        args.push("--offline");
+    }
+    args.into_iter().map(String::from).collect()
+}
+
+/// Whether the manifest describes a `cdylib` that depends on wasm-bindgen
+fn is_wasm_library(doc: &DocumentMut) -> bool {
+    let cdylib = doc
+        .get("lib")
+        .and_then(|lib| lib.get("crate-type"))
+        .and_then(|types| types.as_array())
+        .is_some_and(|types| types.iter().any(|t| t.as_str() == Some("cdylib")));
+    let wasm_bindgen = doc
+        .get("dependencies")
+        .is_some_and(|deps| deps.get("wasm-bindgen").is_some());
+    cdylib && wasm_bindgen
+}
+
+/// The interesting part of a failed command's output: everything from the first error on
+fn diagnostics(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    match lines.iter().position(|line| line.starts_with("error")) {
+        Some(first_error) => {
+            let shown = &lines[first_error..];
+            let mut text = shown.iter().take(MAX_DIAGNOSTIC_LINES).copied().collect::<Vec<_>>().join("\n");
+            if shown.len() > MAX_DIAGNOSTIC_LINES {
+                text.push_str(&format!("\n... {} more line(s)", shown.len() - MAX_DIAGNOSTIC_LINES));
+            }
+            text
+        }
+        None => tail(output),
+    }
+}
+
+/// The last lines of `output`
+fn tail(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    lines[lines.len().saturating_sub(MAX_DIAGNOSTIC_LINES)..].join("\n")
+}
+
+/// Print the pass/fail matrix of a `--all` run
+fn print_matrix(results: &[Verification]) {
+    let width = results.iter().map(|r| r.template.len()).max().unwrap_or(0).max("Template".len());
+    let mark = |passed: Option<bool>| match passed {
+        Some(true) => "✓".green().to_string(),
+        Some(false) => "✗".red().to_string(),
+        None => "-".dimmed().to_string(),
+    };
+
+    println!("\n{:<width$}  {:<8}  {:<5}  Time", "Template".bold(), "Generate".bold(), "Check".bold(), width = width);
+    for result in results {
+        let (generated, checked) = match result.outcome {
+            Outcome::Passed => (Some(true), Some(true)),
+            Outcome::GenerateFailed(_) => (Some(false), None),
+            Outcome::CheckFailed(_) => (Some(true), Some(false)),
+        };
+        // The marks carry color codes, so pad them by their visible width
+        println!(
+            "{:<width$}  {}{}  {}{}  {:.1}s",
+            result.template,
+            mark(generated),
+            " ".repeat(7),
+            mark(checked),
+            " ".repeat(4),
+            result.duration.as_secs_f64(),
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_start_at_first_error() {
+        let output = "   Compiling app v0.1.0\nwarning: unused variable\nerror[E0425]: cannot find value `MAX_SEQUENCE_LENGTH`\n --> src/main.rs:3:5\nerror: could not compile `app`\n";
+        let shown = diagnostics(output);
+        assert!(shown.starts_with("error[E0425]"), "{}", shown);
+        assert!(shown.ends_with("could not compile `app`"), "{}", shown);
+        assert_eq!(diagnostics("Timed out"), "Timed out");
+    }
+
+    #[test]
+    fn test_check_args_for_wasm_and_workspaces() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let manifest = temp_dir.path().join("Cargo.toml");
+
+        fs::write(&manifest, "[package]\nname = \"site\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\nwasm-bindgen = \"0.2\"\n")?;
+        assert_eq!(check_args(temp_dir.path())[..3], ["build", "--target", "wasm32-unknown-unknown"]);
+
+        fs::write(&manifest, "[workspace]\nmembers = [\"crates/app\"]\n")?;
+        assert_eq!(check_args(temp_dir.path())[..3], ["check", "--all-targets", "--workspace"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_answers_pick_a_framework() {
+        let answers = default_answers("server");
+        let framework = answers.iter().position(|a| a == "--framework").expect("framework answer");
+        assert_eq!(answers[framework + 1], "axum");
+
+        let answers = default_answers("embedded");
+        assert!(answers.windows(2).any(|pair| pair[0] == "--set" && pair[1] == "mcu_target=rp2040"));
+    }
+}
@@ -1,14 +1,38 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::{Confirm, Input, Select};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::fs;
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table, Value};
 use ferrisup_common::{fs::create_directory, cargo::*};
 
+use crate::core::diff::print_diff;
+use crate::status;
+use crate::template_manager::validate_edition;
+
+/// Dependency tables the `sync` action hoists from
+const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
 
 /// Execute the workspace command to manage Cargo workspaces
-pub fn execute(action: Option<&str>, path: Option<&str>) -> Result<()> {
-    println!("{}", "FerrisUp Workspace Manager".bold().green());
+///
+/// When `member` is given, the `add` and `remove` actions edit the `[workspace] members`
+/// list directly instead of running the interactive crate creation/removal flows.
+/// `format` selects the output of the `list` action (`tree` or `dot`). `edition`, `hoist`
+/// and `dry_run` are for the `sync` action.
+pub fn execute(
+    action: Option<&str>,
+    path: Option<&str>,
+    member: Option<&str>,
+    format: Option<&str>,
+    edition: Option<&str>,
+    hoist: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if format != Some("dot") {
+        status!("{}", "FerrisUp Workspace Manager".bold().green());
+    }
     
     // Get project path
     let project_dir = if let Some(p) = path {
@@ -35,7 +59,7 @@ pub fn execute(action: Option<&str>, path: Option<&str>) -> Result<()> {
     let action_str = if let Some(act) = action {
         act.to_string()
     } else {
-        let options = vec!["init", "add", "remove", "list", "optimize"];
+        let options = vec!["init", "add", "remove", "list", "optimize", "sync"];
         let selection = Select::new()
             .with_prompt("Select workspace action")
             .items(&options)
@@ -48,11 +72,18 @@ pub fn execute(action: Option<&str>, path: Option<&str>) -> Result<()> {
     // Execute the selected action
     match action_str.as_str() {
         "init" => init_workspace(&project_dir)?,
-        "add" => add_crate_to_workspace(&project_dir)?,
-        "remove" => remove_crate_from_workspace(&project_dir)?,
-        "list" => list_workspace_members(&project_dir)?,
+        "add" => match member {
+            Some(member) => add_member_to_workspace(&project_dir, member)?,
+            None => add_crate_to_workspace(&project_dir)?,
+        },
+        "remove" => match member {
+            Some(member) => remove_member_from_workspace(&project_dir, member)?,
+            None => remove_crate_from_workspace(&project_dir)?,
+        },
+        "list" => list_workspace_members(&project_dir, format)?,
         "optimize" => optimize_workspace(&project_dir)?,
-        _ => return Err(anyhow::anyhow!("Invalid action. Use 'init', 'add', 'remove', 'list', or 'optimize'")),
+        "sync" => sync_workspace(&project_dir, edition, hoist, dry_run)?,
+        _ => return Err(anyhow::anyhow!("Invalid action. Use 'init', 'add', 'remove', 'list', 'optimize', or 'sync'")),
     }
     
     Ok(())
@@ -71,7 +102,7 @@ fn init_workspace(project_dir: &Path) -> Result<()> {
     };
     
     if workspace_exists {
-        println!("{}", "Workspace already initialized!".yellow());
+        status!("{}", "Workspace already initialized!".yellow());
         return Ok(());
     }
     
@@ -87,7 +118,7 @@ fn init_workspace(project_dir: &Path) -> Result<()> {
         default_dirs
     } else {
         // Convert existing project to workspace
-        println!("\n{}", "Converting existing project to workspace".green());
+        status!("\n{}", "Converting existing project to workspace".green());
         
         let options = vec![
             "Use default workspace structure (client/*, server/*, ferrisup_common/*)",
@@ -163,7 +194,7 @@ log = "0.4"
                 let app_cargo = app_dir.join("Cargo.toml");
                 fs::write(&app_cargo, extract_package_section(&content))?;
                 
-                println!("{} {}", "Moved existing package to:".green(), app_dir.display());
+                status!("{} {}", "Moved existing package to:".green(), app_dir.display());
                 
                 // Add the new crate to workspace members
                 dirs.push(package_name);
@@ -215,15 +246,15 @@ log = "0.4"
     // Write the Cargo.toml file
     write_cargo_toml_content(project_dir, &cargo_content)?;
     
-    println!("{} {}", "Initialized workspace in:".green(), project_dir.display());
-    println!("{} {}", "Workspace members:".green(), dirs.join(", "));
+    status!("{} {}", "Initialized workspace in:".green(), project_dir.display());
+    status!("{} {}", "Workspace members:".green(), dirs.join(", "));
     
     // Create default directories if they don't exist
     for dir in &["client", "server", "ferrisup_common"] {
         let path = project_dir.join(dir);
         if !path.exists() {
             create_directory(&path)?;
-            println!("{} {}", "Created directory:".green(), path.display());
+            status!("{} {}", "Created directory:".green(), path.display());
         }
     }
     
@@ -313,7 +344,7 @@ edition = "2021"
     
     fs::write(crate_path.join("Cargo.toml"), crate_cargo_content)?;
     
-    println!("{} {}", "Created crate:".green(), crate_path.display());
+    status!("{} {}", "Created crate:".green(), crate_path.display());
     
     // Update workspace members
     update_workspace_members(project_dir)?;
@@ -333,7 +364,7 @@ fn remove_crate_from_workspace(project_dir: &Path) -> Result<()> {
     let members = list_workspace_crates(project_dir)?;
     
     if members.is_empty() {
-        println!("{}", "No workspace members found".yellow());
+        status!("{}", "No workspace members found".yellow());
         return Ok(());
     }
     
@@ -358,57 +389,387 @@ fn remove_crate_from_workspace(project_dir: &Path) -> Result<()> {
         fs::remove_dir_all(&full_path)
             .context(format!("Failed to remove {}", full_path.display()))?;
         
-        println!("{} {}", "Deleted crate files:".green(), crate_path);
+        status!("{} {}", "Deleted crate files:".green(), crate_path);
     }
     
     // Update workspace members
     update_workspace_members(project_dir)?;
     
-    println!("{} {}", "Removed crate from workspace:".green(), crate_path);
+    status!("{} {}", "Removed crate from workspace:".green(), crate_path);
     
     Ok(())
 }
 
-/// List members of an existing workspace
-fn list_workspace_members(project_dir: &Path) -> Result<()> {
-    // Verify it's a workspace
+/// Add an existing crate directory to the `[workspace] members` list
+///
+/// The root Cargo.toml is edited with `toml_edit` so formatting and comments are preserved.
+pub fn add_member_to_workspace(project_dir: &Path, member: &str) -> Result<()> {
+    let member = normalize_member_path(member);
+
+    // Make sure the member is a real crate before touching the workspace manifest
+    let member_manifest = project_dir.join(&member).join("Cargo.toml");
+    if !member_manifest.exists() {
+        return Err(anyhow::anyhow!(
+            "No Cargo.toml found in {}",
+            project_dir.join(&member).display()
+        ));
+    }
+    fs::read_to_string(&member_manifest)?
+        .parse::<DocumentMut>()
+        .context(format!("Failed to parse {}", member_manifest.display()))?;
+
+    let mut doc = read_workspace_document(project_dir)?;
+    let members = workspace_members_mut(&mut doc)?;
+
+    if members.iter().any(|m| m.as_str() == Some(member.as_str())) {
+        status!("{} {}", "Member is already part of the workspace:".yellow(), member);
+    } else {
+        let trailing = members.trailing().as_str().unwrap_or("").to_string();
+        let indent = members
+            .iter()
+            .last()
+            .and_then(|last| last.decor().prefix().and_then(|p| p.as_str()))
+            .and_then(|prefix| prefix.rsplit_once('\n').map(|(_, indent)| indent.to_string()));
+
+        match (trailing.rsplit_once('\n'), indent) {
+            // Multi-line array: put the new entry on its own line, keeping any comment
+            // that trails the previous last entry attached to it
+            (Some((comment, _)), Some(indent)) => {
+                let mut value = Value::from(member.as_str());
+                value.decor_mut().set_prefix(format!("{}\n{}", comment, indent));
+                members.push_formatted(value);
+                members.set_trailing("\n");
+                members.set_trailing_comma(true);
+            }
+            _ => members.push(member.as_str()),
+        }
+
+        write_cargo_toml_content(project_dir, &doc.to_string())?;
+        status!("{} {}", "Added member to workspace:".green(), member);
+    }
+
+    print_members(&workspace_members(&doc));
+    Ok(())
+}
+
+/// Remove an entry from the `[workspace] members` list, leaving the directory on disk
+pub fn remove_member_from_workspace(project_dir: &Path, member: &str) -> Result<()> {
+    let member = normalize_member_path(member);
+
+    let mut doc = read_workspace_document(project_dir)?;
+    let members = workspace_members_mut(&mut doc)?;
+
+    let position = members.iter().position(|m| m.as_str() == Some(member.as_str()));
+
+    match position {
+        Some(index) => {
+            let removed = members.remove(index);
+
+            // In a multi-line array, the comment trailing an entry is stored in the prefix of
+            // the entry after it. Hand the previous entry's comment over to whatever now
+            // follows it, dropping the removed entry's own comment.
+            let comment = removed
+                .decor()
+                .prefix()
+                .and_then(|p| p.as_str())
+                .and_then(|p| p.rsplit_once('\n'))
+                .map(|(comment, _)| comment.to_string())
+                .unwrap_or_default();
+            match members.get_mut(index) {
+                Some(next) => {
+                    let prefix = next.decor().prefix().and_then(|p| p.as_str()).unwrap_or("").to_string();
+                    if let Some((_, indent)) = prefix.rsplit_once('\n') {
+                        next.decor_mut().set_prefix(format!("{}\n{}", comment, indent));
+                    } else if index == 0 {
+                        next.decor_mut().set_prefix("");
+                    }
+                }
+                None => {
+                    // The array trailing held the removed entry's own comment
+                    let trailing = members.trailing().as_str().unwrap_or("").to_string();
+                    let newline = if trailing.contains('\n') { "\n" } else { "" };
+                    members.set_trailing(format!("{}{}", comment, newline));
+                }
+            }
+
+            write_cargo_toml_content(project_dir, &doc.to_string())?;
+            status!("{} {}", "Removed member from workspace:".green(), member);
+        }
+        None => {
+            status!(
+                "{} {}",
+                "Warning: not a workspace member, nothing to remove:".yellow(),
+                member
+            );
+        }
+    }
+
+    print_members(&workspace_members(&doc));
+    Ok(())
+}
+
+/// Read the root Cargo.toml as an editable document, ensuring it declares a workspace
+fn read_workspace_document(project_dir: &Path) -> Result<DocumentMut> {
     let cargo_content = read_cargo_toml(project_dir)?;
-    if !cargo_content.contains("[workspace]") {
+    let doc = cargo_content
+        .parse::<DocumentMut>()
+        .context("Failed to parse Cargo.toml as TOML")?;
+
+    if doc.get("workspace").is_none() {
         return Err(anyhow::anyhow!("Not a Cargo workspace (no [workspace] section in Cargo.toml)"));
     }
-    
-    // Extract workspace members
-    let members = extract_workspace_members(&cargo_content);
-    
-    println!("\n{}", "Workspace Members:".bold());
-    
+
+    Ok(doc)
+}
+
+/// Get the `[workspace] members` array, creating it if the workspace has none yet
+fn workspace_members_mut(doc: &mut DocumentMut) -> Result<&mut Array> {
+    let workspace = doc["workspace"]
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("[workspace] in Cargo.toml is not a table"))?;
+
+    if workspace.get("members").is_none() {
+        workspace.insert("members", toml_edit::value(Array::new()));
+    }
+
+    workspace
+        .get_mut("members")
+        .and_then(|m| m.as_array_mut())
+        .ok_or_else(|| anyhow::anyhow!("[workspace] members in Cargo.toml is not an array"))
+}
+
+/// Get the `[workspace] members` entries as strings
+fn workspace_members(doc: &DocumentMut) -> Vec<String> {
+    doc.get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Normalize a member path the way it is written in Cargo.toml (forward slashes, no `./`)
+fn normalize_member_path(member: &str) -> String {
+    let member = member.replace('\\', "/");
+    let member = member.trim_start_matches("./").trim_end_matches('/');
+    member.to_string()
+}
+
+fn print_members(members: &[String]) {
+    status!("\n{}", "Workspace Members:".bold());
+
     if members.is_empty() {
-        println!("  No members found");
+        status!("  No members found");
     } else {
         for (i, member) in members.iter().enumerate() {
-            println!("  {}. {}", i + 1, member);
+            status!("  {}. {}", i + 1, member);
         }
     }
-    
-    // List actual crates found (resolved)
-    let crates = list_workspace_crates(project_dir)?;
-    
-    println!("\n{}", "Found Crates:".bold());
-    
-    if crates.is_empty() {
-        println!("  No crates found");
-    } else {
-        for (i, crate_path) in crates.iter().enumerate() {
-            println!("  {}. {}", i + 1, crate_path);
-        }
+}
+
+/// A workspace crate together with the workspace crates it depends on via path dependencies
+struct WorkspaceCrate {
+    name: String,
+    path: String,
+    dependencies: Vec<String>,
+}
+
+/// List members of an existing workspace and the path dependencies between them
+///
+/// With `format` set to `dot`, the dependency graph is printed as Graphviz DOT instead.
+/// Cyclic path dependencies are reported on stderr in both formats.
+fn list_workspace_members(project_dir: &Path, format: Option<&str>) -> Result<()> {
+    if !matches!(format, Some("tree" | "dot") | None) {
+        return Err(anyhow::anyhow!("Invalid format '{}'. Use 'tree' or 'dot'", format.unwrap_or_default()));
     }
-    
+
+    // Verify it's a workspace
+    let doc = read_workspace_document(project_dir)?;
+    let graph = load_workspace_graph(project_dir)?;
+
+    for cycle in find_dependency_cycles(&graph) {
+        eprintln!(
+            "{} {}",
+            "Warning: cyclic path dependency detected:".yellow(),
+            cycle.join(" -> ")
+        );
+    }
+
+    if format == Some("dot") {
+        print!("{}", workspace_graph_to_dot(&graph));
+        return Ok(());
+    }
+
+    print_members(&workspace_members(&doc));
+
+    status!("\n{}", "Dependency Graph:".bold());
+
+    if graph.is_empty() {
+        status!("  No crates found");
+        return Ok(());
+    }
+
+    for krate in &graph {
+        status!("  {} {}", krate.name.cyan(), format!("({})", krate.path).dimmed());
+        let mut stack = vec![krate.name.clone()];
+        print_dependency_tree(&graph, krate, "  ", &mut stack);
+    }
+
     Ok(())
 }
 
+/// Load every workspace member and resolve its path dependencies to other members
+fn load_workspace_graph(project_dir: &Path) -> Result<Vec<WorkspaceCrate>> {
+    let crate_paths = list_workspace_crates(project_dir)?;
+
+    let mut manifests = Vec::new();
+    let mut names_by_dir = HashMap::new();
+    for crate_path in crate_paths {
+        let crate_dir = project_dir.join(&crate_path);
+        let manifest_path = crate_dir.join("Cargo.toml");
+        let doc = fs::read_to_string(&manifest_path)?
+            .parse::<DocumentMut>()
+            .context(format!("Failed to parse {}", manifest_path.display()))?;
+
+        let name = doc
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| crate_path.clone());
+
+        if let Ok(canonical) = crate_dir.canonicalize() {
+            names_by_dir.insert(canonical, name.clone());
+        }
+        manifests.push((name, crate_path, crate_dir, doc));
+    }
+
+    let mut graph = Vec::new();
+    for (name, path, crate_dir, doc) in manifests {
+        let mut dependencies = Vec::new();
+
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(table) = doc.get(section).and_then(|t| t.as_table_like()) else {
+                continue;
+            };
+
+            for (_, item) in table.iter() {
+                let Some(dep_path) = item.get("path").and_then(|p| p.as_str()) else {
+                    continue;
+                };
+                let dep_name = crate_dir
+                    .join(dep_path)
+                    .canonicalize()
+                    .ok()
+                    .and_then(|dir| names_by_dir.get(&dir));
+                if let Some(dep_name) = dep_name {
+                    if !dependencies.contains(dep_name) {
+                        dependencies.push(dep_name.clone());
+                    }
+                }
+            }
+        }
+
+        graph.push(WorkspaceCrate { name, path, dependencies });
+    }
+
+    Ok(graph)
+}
+
+/// Print the workspace dependencies of `krate` as a tree, stopping at cycles
+fn print_dependency_tree(
+    graph: &[WorkspaceCrate],
+    krate: &WorkspaceCrate,
+    indent: &str,
+    stack: &mut Vec<String>,
+) {
+    for (i, dep_name) in krate.dependencies.iter().enumerate() {
+        let is_last = i == krate.dependencies.len() - 1;
+        let branch = if is_last { "└── " } else { "├── " };
+
+        if stack.contains(dep_name) {
+            status!("{}{}{} {}", indent, branch, dep_name, "(cycle)".yellow());
+            continue;
+        }
+
+        status!("{}{}{}", indent, branch, dep_name);
+
+        if let Some(dep) = graph.iter().find(|c| &c.name == dep_name) {
+            let child_indent = format!("{}{}", indent, if is_last { "    " } else { "│   " });
+            stack.push(dep_name.clone());
+            print_dependency_tree(graph, dep, &child_indent, stack);
+            stack.pop();
+        }
+    }
+}
+
+/// The cyclic path dependencies of the workspace, each starting and ending with the same crate
+fn find_dependency_cycles(graph: &[WorkspaceCrate]) -> Vec<Vec<String>> {
+    fn visit(
+        graph: &[WorkspaceCrate],
+        krate: &WorkspaceCrate,
+        stack: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        for dep_name in &krate.dependencies {
+            if let Some(start) = stack.iter().position(|name| name == dep_name) {
+                let mut cycle = stack[start..].to_vec();
+                cycle.push(dep_name.clone());
+                if !cycles.iter().any(|c| is_same_cycle(c, &cycle)) {
+                    cycles.push(cycle);
+                }
+                continue;
+            }
+
+            if let Some(dep) = graph.iter().find(|c| &c.name == dep_name) {
+                stack.push(dep_name.clone());
+                visit(graph, dep, stack, cycles);
+                stack.pop();
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    for krate in graph {
+        visit(graph, krate, &mut vec![krate.name.clone()], &mut cycles);
+    }
+    cycles
+}
+
+/// Two cycles are the same if they contain the same edges, whichever crate they start from
+fn is_same_cycle(a: &[String], b: &[String]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let a = &a[..a.len() - 1];
+    let b = &b[..b.len() - 1];
+    (0..a.len()).any(|offset| a.iter().cycle().skip(offset).take(a.len()).eq(b.iter()))
+}
+
+/// Render the workspace dependency graph in Graphviz DOT format
+fn workspace_graph_to_dot(graph: &[WorkspaceCrate]) -> String {
+    let mut dot = String::from("digraph workspace {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for krate in graph {
+        dot.push_str(&format!("    \"{}\";\n", krate.name));
+    }
+    for krate in graph {
+        for dep in &krate.dependencies {
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n", krate.name, dep));
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
 /// Optimize a workspace by identifying and fixing common issues
 fn optimize_workspace(project_dir: &Path) -> Result<()> {
-    println!("{}", "Optimizing workspace...".green());
+    status!("{}", "Optimizing workspace...".green());
     
     // Verify it's a workspace
     let cargo_content = read_cargo_toml(project_dir)?;
@@ -453,16 +814,366 @@ fn optimize_workspace(project_dir: &Path) -> Result<()> {
     }
     
     // Report improvements
-    println!("\n{}", "Workspace Optimization Results:".bold());
+    status!("\n{}", "Workspace Optimization Results:".bold());
     for improvement in improvements {
-        println!("  {}", improvement);
+        status!("  {}", improvement);
     }
     
-    println!("\n{}", "Workspace optimized successfully!".green());
+    status!("\n{}", "Workspace optimized successfully!".green());
     
     Ok(())
 }
 
+/// Changes the `sync` action makes to a workspace
+#[derive(Debug, Default)]
+struct SyncPlan {
+    edition: String,
+    resolver: String,
+    /// Manifests that change, as (path relative to the workspace, old content, new content)
+    changes: Vec<(String, String, String)>,
+    /// Dependencies moved into `[workspace.dependencies]`
+    hoisted: Vec<String>,
+    /// What couldn't be aligned automatically and was left as it is
+    conflicts: Vec<String>,
+}
+
+/// A workspace member's manifest, as read before `sync` changes it
+struct MemberManifest {
+    path: String,
+    content: String,
+    doc: DocumentMut,
+}
+
+/// Give every member the same edition and the workspace a resolver that matches it,
+/// optionally hoisting the dependencies members share into `[workspace.dependencies]`
+///
+/// The diff of each manifest is printed; with `dry_run` nothing is written.
+fn sync_workspace(project_dir: &Path, edition: Option<&str>, hoist: bool, dry_run: bool) -> Result<()> {
+    let plan = plan_sync(project_dir, edition, hoist)?;
+    status!(
+        "{} edition {} and resolver {}",
+        "Syncing workspace members to".green(),
+        plan.edition,
+        plan.resolver
+    );
+
+    for (path, old, new) in &plan.changes {
+        print_diff(old, new, path);
+    }
+    if !dry_run {
+        for (path, _, new) in &plan.changes {
+            fs::write(project_dir.join(path), new).context(format!("Failed to write {}", path))?;
+        }
+    }
+
+    for name in &plan.hoisted {
+        status!("  {} {}", "Hoisted into [workspace.dependencies]:".green(), name);
+    }
+    for conflict in &plan.conflicts {
+        status!("{} {}", "Not synced:".yellow(), conflict);
+    }
+
+    if plan.changes.is_empty() {
+        status!("\n{}", "Workspace is already in sync".green());
+    } else if dry_run {
+        status!(
+            "\n{} {} manifest(s) would change, nothing was written",
+            "Dry run:".yellow(),
+            plan.changes.len()
+        );
+    } else {
+        status!("\n{} {} manifest(s)", "Synced".green(), plan.changes.len());
+    }
+    Ok(())
+}
+
+/// Work out what `sync` changes without writing anything
+///
+/// Without an explicit `edition` the workspace's `[workspace.package]` edition is used,
+/// or else the one most members have. Members are only moved to an older edition when
+/// it's given explicitly, since their code may need the newer one.
+fn plan_sync(project_dir: &Path, edition: Option<&str>, hoist: bool) -> Result<SyncPlan> {
+    let root_content = read_cargo_toml(project_dir)?;
+    let mut root = read_workspace_document(project_dir)?;
+    let mut members = Vec::new();
+    for path in list_workspace_crates(project_dir)? {
+        let path = normalize_member_path(&path);
+        let manifest = project_dir.join(&path).join("Cargo.toml");
+        let content = fs::read_to_string(&manifest)?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .context(format!("Failed to parse {}", manifest.display()))?;
+        members.push(MemberManifest { path, content, doc });
+    }
+
+    let explicit = edition.is_some();
+    let edition = match edition {
+        Some(edition) => {
+            validate_edition(edition)?;
+            edition.to_string()
+        }
+        None => common_edition(&root, &members),
+    };
+    // Edition 2024 brings its own resolver, like `new --edition 2024`
+    let resolver = if edition == "2024" { "3" } else { "2" };
+    let mut plan = SyncPlan {
+        edition: edition.clone(),
+        resolver: resolver.to_string(),
+        ..SyncPlan::default()
+    };
+
+    let mut inherited = false;
+    for member in members.iter_mut() {
+        inherited |= sync_package_edition(&mut member.doc, &member.path, &edition, explicit, &mut plan.conflicts);
+    }
+    inherited |= sync_package_edition(&mut root, ".", &edition, explicit, &mut plan.conflicts);
+
+    let workspace = root["workspace"]
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("[workspace] in Cargo.toml is not a table"))?;
+    // A newer resolver than the edition needs is kept
+    if workspace.get("resolver").and_then(Item::as_str).is_none_or(|current| current < resolver) {
+        workspace.insert("resolver", value(resolver));
+    }
+    let has_package_edition = workspace
+        .get("package")
+        .and_then(|p| p.get("edition"))
+        .is_some();
+    if inherited || has_package_edition {
+        if workspace.get("package").is_none() {
+            workspace.insert("package", Item::Table(Table::new()));
+        }
+        if let Some(package) = workspace.get_mut("package").and_then(|p| p.as_table_like_mut()) {
+            package.insert("edition", value(edition.as_str()));
+        }
+    }
+
+    if hoist {
+        hoist_dependencies(&mut root, &mut members, &mut plan)?;
+    }
+
+    let updated = root.to_string();
+    if updated != root_content {
+        plan.changes.push(("Cargo.toml".to_string(), root_content, updated));
+    }
+    for member in members {
+        let updated = member.doc.to_string();
+        if updated != member.content {
+            plan.changes.push((format!("{}/Cargo.toml", member.path), member.content, updated));
+        }
+    }
+    Ok(plan)
+}
+
+/// The `[workspace.package]` edition, or else the edition most members have (the newer
+/// one on a tie), 2021 when no member sets one
+fn common_edition(root: &DocumentMut, members: &[MemberManifest]) -> String {
+    let workspace_edition = root
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("edition"))
+        .and_then(|e| e.as_str());
+    if let Some(edition) = workspace_edition {
+        return edition.to_string();
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for doc in std::iter::once(root).chain(members.iter().map(|m| &m.doc)) {
+        if let Some(edition) = doc.get("package").and_then(|p| p.get("edition")).and_then(|e| e.as_str()) {
+            *counts.entry(edition).or_default() += 1;
+        }
+    }
+    // Editions iterate oldest first and max_by_key keeps the last maximum
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(edition, _)| edition.to_string())
+        .unwrap_or_else(|| "2021".to_string())
+}
+
+/// Set the `[package]` edition of a manifest, returning whether it inherits the workspace's
+///
+/// A `resolver` in `[package]` is removed, cargo ignores it in workspace members.
+fn sync_package_edition(
+    doc: &mut DocumentMut,
+    path: &str,
+    edition: &str,
+    explicit: bool,
+    conflicts: &mut Vec<String>,
+) -> bool {
+    let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_like_mut()) else {
+        return false;
+    };
+    package.remove("resolver");
+    match package.get("edition") {
+        // `edition.workspace = true` is a table, a plain string is the crate's own edition
+        Some(current) if current.as_str().is_none() => return true,
+        Some(current) if !explicit && current.as_str().is_some_and(|current| current > edition) => {
+            conflicts.push(format!(
+                "{} uses edition {}, newer than {}; pass --edition to change it",
+                path,
+                current.as_str().unwrap_or_default(),
+                edition
+            ));
+        }
+        _ => {
+            package.insert("edition", value(edition));
+        }
+    }
+    false
+}
+
+/// A member's dependency on a registry crate, as written in its manifest
+struct DependencyUse {
+    member: usize,
+    section: &'static str,
+    version: String,
+    default_features: bool,
+}
+
+/// Move dependencies that several members pin to the same version into
+/// `[workspace.dependencies]`, pointing the members at them with `workspace = true`
+///
+/// Members keep their own `features` and `optional`. Dependencies already in
+/// `[workspace.dependencies]` are used by every member that pins the same version. Members
+/// pinning other versions, or disagreeing on default features, are left as they are and
+/// reported; path and git dependencies are never hoisted.
+fn hoist_dependencies(root: &mut DocumentMut, members: &mut [MemberManifest], plan: &mut SyncPlan) -> Result<()> {
+    let mut uses: BTreeMap<String, Vec<DependencyUse>> = BTreeMap::new();
+    for (index, member) in members.iter().enumerate() {
+        for section in DEPENDENCY_SECTIONS {
+            let Some(table) = member.doc.get(section).and_then(|t| t.as_table_like()) else {
+                continue;
+            };
+            for (name, item) in table.iter() {
+                let (version, default_features) = match item.as_str() {
+                    Some(version) => (version.to_string(), true),
+                    None => {
+                        let Some(spec) = item.as_table_like() else { continue };
+                        let local = ["workspace", "path", "git", "package", "registry"];
+                        if local.iter().any(|key| spec.contains_key(key)) {
+                            continue;
+                        }
+                        let Some(version) = spec.get("version").and_then(|v| v.as_str()) else {
+                            continue;
+                        };
+                        let default_features = spec
+                            .get("default-features")
+                            .or_else(|| spec.get("default_features"))
+                            .and_then(|d| d.as_bool())
+                            .unwrap_or(true);
+                        (version.to_string(), default_features)
+                    }
+                };
+                uses.entry(name.to_string()).or_default().push(DependencyUse {
+                    member: index,
+                    section,
+                    version,
+                    default_features,
+                });
+            }
+        }
+    }
+
+    let workspace = root["workspace"]
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("[workspace] in Cargo.toml is not a table"))?;
+    let created = workspace.get("dependencies").is_none();
+    if created {
+        workspace.insert("dependencies", Item::Table(Table::new()));
+    }
+    let hoisted = workspace
+        .get_mut("dependencies")
+        .and_then(|d| d.as_table_like_mut())
+        .ok_or_else(|| anyhow::anyhow!("[workspace.dependencies] in Cargo.toml is not a table"))?;
+
+    for (name, uses) in uses {
+        let existing = hoisted.get(&name).map(|item| {
+            let version = item.as_str().or_else(|| item.get("version").and_then(|v| v.as_str()));
+            let default_features = item.get("default-features").and_then(|d| d.as_bool()).unwrap_or(true);
+            (version.map(|v| v.to_string()), default_features)
+        });
+
+        let (version, default_features) = match existing {
+            // Path and git dependencies of the workspace aren't ours to compare
+            Some((None, _)) => continue,
+            Some((Some(version), default_features)) => (version, default_features),
+            None => {
+                let mut used_by: Vec<usize> = uses.iter().map(|u| u.member).collect();
+                used_by.dedup();
+                if used_by.len() < 2 {
+                    continue;
+                }
+                let versions: Vec<&str> = uses.iter().map(|u| u.version.as_str()).collect();
+                if versions.iter().any(|v| *v != versions[0]) {
+                    let pins: Vec<String> = uses
+                        .iter()
+                        .map(|u| format!("{}: {}", members[u.member].path, u.version))
+                        .collect();
+                    plan.conflicts.push(format!(
+                        "{} is pinned to different versions ({}), left in the members",
+                        name,
+                        pins.join(", ")
+                    ));
+                    continue;
+                }
+                if uses.iter().any(|u| u.default_features != uses[0].default_features) {
+                    plan.conflicts.push(format!(
+                        "{} has default features turned off in some members only, left in the members",
+                        name
+                    ));
+                    continue;
+                }
+
+                let entry = if uses[0].default_features {
+                    value(versions[0])
+                } else {
+                    let mut spec = InlineTable::new();
+                    spec.insert("version", versions[0].into());
+                    spec.insert("default-features", false.into());
+                    value(spec)
+                };
+                hoisted.insert(&name, entry);
+                plan.hoisted.push(format!("{} = \"{}\"", name, versions[0]));
+                (versions[0].to_string(), uses[0].default_features)
+            }
+        };
+
+        for dependency in &uses {
+            let member = &mut members[dependency.member];
+            if dependency.version != version {
+                plan.conflicts.push(format!(
+                    "{} pins {} {} while [workspace.dependencies] has {}, left as it is",
+                    member.path, name, dependency.version, version
+                ));
+                continue;
+            }
+            if dependency.default_features != default_features {
+                plan.conflicts.push(format!(
+                    "{} and [workspace.dependencies] disagree on the default features of {}, left as it is",
+                    member.path, name
+                ));
+                continue;
+            }
+            let item = &mut member.doc[dependency.section][name.as_str()];
+            let mut spec = InlineTable::new();
+            spec.insert("workspace", true.into());
+            for key in ["features", "optional"] {
+                if let Some(kept) = item.get(key).and_then(|v| v.as_value()) {
+                    spec.insert(key, kept.clone());
+                }
+            }
+            *item = value(spec);
+        }
+    }
+
+    // Don't leave the table behind when nothing was hoisted into it
+    if created && hoisted.is_empty() {
+        workspace.remove("dependencies");
+    }
+    Ok(())
+}
+
 /// Helper function to discover crates in a project directory
 fn discover_crates(project_dir: &Path) -> Result<Vec<String>> {
     let mut crates = Vec::new();
@@ -510,8 +1221,8 @@ fn list_workspace_crates(project_dir: &Path) -> Result<Vec<String>> {
     let mut crates = Vec::new();
     
     // Extract workspace members
-    let cargo_content = read_cargo_toml(project_dir)?;
-    let members = extract_workspace_members(&cargo_content);
+    let doc = read_workspace_document(project_dir)?;
+    let members = workspace_members(&doc);
     
     // Resolve glob patterns and check if each member exists
     for member in members {
@@ -523,7 +1234,10 @@ fn list_workspace_crates(project_dir: &Path) -> Result<Vec<String>> {
             let prefix_path = project_dir.join(prefix);
             if prefix_path.exists() && prefix_path.is_dir() {
                 if let Ok(entries) = fs::read_dir(&prefix_path) {
-                    for entry in entries.filter_map(|e| e.ok()) {
+                    // Sort so glob members are listed in a stable order
+                    let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+                    entries.sort_by_key(|entry| entry.path());
+                    for entry in entries {
                         if entry.path().is_dir() && entry.path().join("Cargo.toml").exists() {
                             if let Ok(rel_path) = entry.path().strip_prefix(project_dir) {
                                 crates.push(rel_path.to_string_lossy().to_string());
@@ -544,39 +1258,6 @@ fn list_workspace_crates(project_dir: &Path) -> Result<Vec<String>> {
     Ok(crates)
 }
 
-/// Helper function to extract workspace members from Cargo.toml content
-fn extract_workspace_members(cargo_content: &str) -> Vec<String> {
-    let mut members = Vec::new();
-    
-    // Basic parsing of members array
-    if let Some(workspace_section) = cargo_content.split("[workspace]").nth(1) {
-        if let Some(members_section) = workspace_section.split("members").nth(1) {
-            if let Some(members_list) = members_section.split('[').nth(1) {
-                if let Some(members_list) = members_list.split(']').next() {
-                    for line in members_list.lines() {
-                        let line = line.trim();
-                        if line.starts_with('"') && line.contains('"') {
-                            let member = line
-                                .trim_start_matches('"')
-                                .split('"')
-                                .next()
-                                .unwrap_or("")
-                                .trim()
-                                .trim_end_matches(',');
-                            
-                            if !member.is_empty() {
-                                members.push(member.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    members
-}
-
 /// Helper function to extract package name from Cargo.toml content
 fn extract_package_name(cargo_content: &str) -> Option<String> {
     if let Some(package_section) = cargo_content.split("[package]").nth(1) {
@@ -610,3 +1291,199 @@ fn extract_package_section(cargo_content: &str) -> String {
     }
     String::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn create_member(root: &Path, member: &str) -> Result<()> {
+        let member_dir = root.join(member);
+        fs::create_dir_all(member_dir.join("src"))?;
+        fs::write(
+            member_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n", member.replace('/', "-")),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_remove_member_preserves_comments() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "# Root workspace\n[workspace]\nmembers = [\n    \"crates/app\", # the binary\n]\nresolver = \"2\"\n",
+        )?;
+        create_member(root, "crates/app")?;
+        create_member(root, "crates/api")?;
+
+        add_member_to_workspace(root, "./crates/api/")?;
+        let content = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(content.contains("# Root workspace"));
+        assert!(content.contains("# the binary"));
+        let doc = content.parse::<DocumentMut>()?;
+        assert_eq!(workspace_members(&doc), vec!["crates/app", "crates/api"]);
+
+        // Adding the same member twice is a no-op
+        add_member_to_workspace(root, "crates/api")?;
+        let doc = fs::read_to_string(root.join("Cargo.toml"))?.parse::<DocumentMut>()?;
+        assert_eq!(workspace_members(&doc).len(), 2);
+
+        remove_member_from_workspace(root, "crates/api")?;
+        let content = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(content.contains("# the binary"));
+        let doc = content.parse::<DocumentMut>()?;
+        assert_eq!(workspace_members(&doc), vec!["crates/app"]);
+        // The member directory is left on disk
+        assert!(root.join("crates/api/Cargo.toml").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_member_requires_cargo_toml() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n")?;
+        fs::create_dir_all(root.join("not-a-crate"))?;
+
+        assert!(add_member_to_workspace(root, "not-a-crate").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspace_graph_detects_path_dependencies() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n")?;
+        create_member(root, "crates/app")?;
+        create_member(root, "crates/core")?;
+        fs::write(
+            root.join("crates/app/Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\ncore = { path = \"../core\" }\nserde = \"1\"\n",
+        )?;
+
+        let graph = load_workspace_graph(root)?;
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph[0].name, "app");
+        assert_eq!(graph[0].dependencies, vec!["crates-core"]);
+        assert!(graph[1].dependencies.is_empty());
+
+        let dot = workspace_graph_to_dot(&graph);
+        assert!(dot.starts_with("digraph workspace {"));
+        assert!(dot.contains("\"app\" -> \"crates-core\";"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_dependency_cycles() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n")?;
+        for (name, dep) in [("app", "core"), ("core", "app")] {
+            create_member(root, &format!("crates/{}", name))?;
+            fs::write(
+                root.join(format!("crates/{}/Cargo.toml", name)),
+                format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{} = {{ path = \"../{}\" }}\n", name, dep, dep),
+            )?;
+        }
+
+        // The cycle is found once, whichever crate it is reached from
+        let graph = load_workspace_graph(root)?;
+        assert_eq!(find_dependency_cycles(&graph), vec![vec!["app", "core", "app"]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_same_cycle_ignores_starting_crate() {
+        let cycle = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(is_same_cycle(&cycle(&["a", "b", "a"]), &cycle(&["b", "a", "b"])));
+        assert!(!is_same_cycle(&cycle(&["a", "b", "a"]), &cycle(&["a", "c", "a"])));
+    }
+
+    #[test]
+    fn test_sync_aligns_editions_and_hoists_shared_dependencies() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n")?;
+        create_member(root, "crates/app")?;
+        create_member(root, "crates/core")?;
+        fs::write(
+            root.join("crates/app/Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2018\"\n\n[dependencies]\n\
+             serde = { version = \"1.0\", features = [\"derive\"] }\nanyhow = \"1.0\"\ncore = { path = \"../core\" }\n",
+        )?;
+        fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n\
+             serde = \"1.0\"\nanyhow = \"1.0.80\"\n",
+        )?;
+        let before = fs::read_to_string(root.join("crates/app/Cargo.toml"))?;
+
+        // A dry run changes nothing on disk
+        sync_workspace(root, None, true, true)?;
+        assert_eq!(fs::read_to_string(root.join("crates/app/Cargo.toml"))?, before);
+
+        let plan = plan_sync(root, None, true)?;
+        assert_eq!(plan.edition, "2021");
+        assert_eq!(plan.hoisted, vec!["serde = \"1.0\""]);
+        assert_eq!(plan.conflicts.len(), 1, "{:?}", plan.conflicts);
+        assert!(plan.conflicts[0].starts_with("anyhow is pinned to different versions"), "{:?}", plan.conflicts);
+
+        sync_workspace(root, None, true, false)?;
+        let workspace = fs::read_to_string(root.join("Cargo.toml"))?.parse::<DocumentMut>()?;
+        assert_eq!(workspace["workspace"]["resolver"].as_str(), Some("2"));
+        assert_eq!(workspace["workspace"]["dependencies"]["serde"].as_str(), Some("1.0"));
+        let app = fs::read_to_string(root.join("crates/app/Cargo.toml"))?.parse::<DocumentMut>()?;
+        assert_eq!(app["package"]["edition"].as_str(), Some("2021"));
+        assert_eq!(app["dependencies"]["serde"]["workspace"].as_bool(), Some(true));
+        assert_eq!(app["dependencies"]["serde"]["features"].as_array().map(|f| f.len()), Some(1));
+        // Differing pins and path dependencies stay in the members
+        assert_eq!(app["dependencies"]["anyhow"].as_str(), Some("1.0"));
+        assert_eq!(app["dependencies"]["core"]["path"].as_str(), Some("../core"));
+
+        // Syncing again has nothing left to change
+        assert!(plan_sync(root, None, true)?.changes.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_only_lowers_editions_when_asked() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n")?;
+        create_member(root, "crates/a")?;
+        create_member(root, "crates/b")?;
+        create_member(root, "crates/next")?;
+        fs::write(
+            root.join("crates/next/Cargo.toml"),
+            "[package]\nname = \"next\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        )?;
+
+        let plan = plan_sync(root, None, false)?;
+        assert_eq!(plan.edition, "2021");
+        assert!(plan.changes.is_empty());
+        assert!(plan.conflicts[0].contains("crates/next uses edition 2024, newer than 2021"), "{:?}", plan.conflicts);
+
+        // 2024 everywhere also moves the workspace to the matching resolver
+        let plan = plan_sync(root, Some("2024"), false)?;
+        assert!(plan.conflicts.is_empty());
+        assert_eq!(plan.changes.len(), 3);
+        assert!(plan.changes[0].2.contains("resolver = \"3\""));
+        assert!(plan_sync(root, Some("2027"), false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_missing_member_is_noop() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        let manifest = "[workspace]\nmembers = [\"crates/app\"]\n";
+        fs::write(root.join("Cargo.toml"), manifest)?;
+
+        remove_member_from_workspace(root, "crates/missing")?;
+        assert_eq!(fs::read_to_string(root.join("Cargo.toml"))?, manifest);
+        Ok(())
+    }
+}
@@ -6,7 +6,11 @@ use std::path::{Path, PathBuf};
 use super::error::{Error, Result};
 
 /// Configuration for FerrisUp
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Unknown fields are rejected so that a misspelled key in a hand-edited config is
+/// reported instead of silently falling back to the default.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     /// Path to templates directory
     pub templates_dir: PathBuf,
@@ -15,7 +19,8 @@ pub struct Config {
 }
 
 /// User preferences for FerrisUp
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Preferences {
     /// Whether to initialize git repositories for new projects
     #[serde(default = "default_git")]
@@ -0,0 +1,85 @@
+// Looking up crates on crates.io, for `new --check-name` and `dependency add`
+use anyhow::{anyhow, Context, Result};
+use std::process::Command;
+
+use crate::core::retry::{retry, DEFAULT_ATTEMPTS, DEFAULT_BACKOFF};
+
+/// A crate published on crates.io
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedCrate {
+    pub name: String,
+    /// Its newest version
+    pub version: String,
+}
+
+impl PublishedCrate {
+    /// The crate's page on crates.io
+    pub fn url(&self) -> String {
+        format!("https://crates.io/crates/{}", self.name)
+    }
+}
+
+/// Look `name` up on crates.io with `cargo search`, `None` when no crate has that name
+///
+/// crates.io doesn't tell `-` and `_` apart, so `my_app` finds a published `my-app`.
+/// A failed search is retried, and fails when it can't be run at all, such as without
+/// network access; callers check offline mode before asking.
+pub fn find_crate(name: &str) -> Result<Option<PublishedCrate>> {
+    let output = retry(DEFAULT_ATTEMPTS, DEFAULT_BACKOFF, || {
+        let output = Command::new("cargo")
+            .args(["search", "--limit", "10", name])
+            .output()
+            .context("Failed to execute cargo search command")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "cargo search failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(output)
+    })?;
+    Ok(parse_search_output(&String::from_utf8_lossy(&output.stdout), name))
+}
+
+/// The result of `cargo search` output naming `name`, ignoring `-`/`_` and case
+///
+/// Result lines look like `serde = "1.0.219"    # A generic serialization framework`.
+fn parse_search_output(output: &str, name: &str) -> Option<PublishedCrate> {
+    let wanted = normalize(name);
+    output.lines().find_map(|line| {
+        let (found, rest) = line.split_once(" = ")?;
+        if normalize(found.trim()) != wanted {
+            return None;
+        }
+        let version = rest.split('"').nth(1)?;
+        Some(PublishedCrate {
+            name: found.trim().to_string(),
+            version: version.to_string(),
+        })
+    })
+}
+
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_output() {
+        let output = "serde_json = \"1.0.140\"    # A JSON serialization file format\n\
+                      serde-value = \"0.7.0\"     # Serialization value trees\n\
+                      serde = \"1.0.219\"         # A generic serialization/deserialization framework\n\
+                      ... and 52741 crates more (use --limit N to see more)\n";
+
+        let serde = parse_search_output(output, "serde").unwrap();
+        assert_eq!(serde.version, "1.0.219");
+        assert_eq!(serde.url(), "https://crates.io/crates/serde");
+        // crates.io treats `-` and `_` as the same name
+        assert_eq!(parse_search_output(output, "serde_value").unwrap().name, "serde-value");
+        assert_eq!(parse_search_output(output, "serde-derive"), None);
+        assert_eq!(parse_search_output("", "serde"), None);
+    }
+}
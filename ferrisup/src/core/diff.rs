@@ -0,0 +1,106 @@
+// Unified diffs of the files commands rewrite, shared with `transform --dry-run`
+use colored::Colorize;
+use similar::TextDiff;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::commands::verbosity::is_quiet;
+
+/// Unified diff from `old` to `new` under the given headers, empty when nothing changed
+pub fn unified_diff(old: &str, new: &str, old_header: &str, new_header: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(old_header, new_header)
+        .to_string()
+}
+
+/// Color the added lines of a unified diff green and the removed ones red
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| {
+            if line.starts_with('+') && !line.starts_with("+++") {
+                line.green().to_string()
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                line.red().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Print a unified diff, colored on a terminal and as plain text otherwise
+pub fn print_unified(diff: &str) {
+    if std::io::stdout().is_terminal() {
+        print!("{}", colorize(diff));
+    } else {
+        print!("{}", diff);
+    }
+}
+
+/// Print what changed from `old` to `new` in the file at `path`
+///
+/// Nothing is printed with `--quiet` or when the contents are the same.
+pub fn print_diff(old: &str, new: &str, path: &str) {
+    if is_quiet() {
+        return;
+    }
+    let path = path.replace('\\', "/");
+    let diff = unified_diff(old, new, &format!("a/{}", path), &format!("b/{}", path));
+    if !diff.is_empty() {
+        print_unified(&diff);
+    }
+}
+
+/// Write `content` to `path`, printing the diff against the file it replaces
+pub fn write_with_diff(path: &Path, content: &str) -> std::io::Result<()> {
+    if let Ok(old) = fs::read_to_string(path) {
+        print_diff(&old, content, &display_path(path));
+    }
+    fs::write(path, content)
+}
+
+/// `path` relative to the current directory when it's below it
+fn display_path(path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf));
+    relative.unwrap_or_else(|| path.to_path_buf()).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_detects_added_and_removed_lines() {
+        let old = "[dependencies]\nserde = \"1.0\"\nrand = \"0.8\"\n";
+        let new = "[dependencies]\nserde = \"1.0\"\ntokio = \"1\"\n";
+        let diff = unified_diff(old, new, "a/Cargo.toml", "b/Cargo.toml");
+
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines[0], "--- a/Cargo.toml");
+        assert_eq!(lines[1], "+++ b/Cargo.toml");
+        assert!(lines.contains(&"-rand = \"0.8\""), "{}", diff);
+        assert!(lines.contains(&"+tokio = \"1\""), "{}", diff);
+        assert!(lines.contains(&" serde = \"1.0\""), "{}", diff);
+        assert_eq!(diff.lines().filter(|l| l.starts_with('+') && !l.starts_with("+++")).count(), 1);
+        assert_eq!(diff.lines().filter(|l| l.starts_with('-') && !l.starts_with("---")).count(), 1);
+
+        assert!(unified_diff(old, old, "a/Cargo.toml", "b/Cargo.toml").is_empty());
+
+        colored::control::set_override(true);
+        let colored = colorize(&diff);
+        let removed = "-rand = \"0.8\"".red().to_string();
+        let added = "+tokio = \"1\"".green().to_string();
+        colored::control::unset_override();
+        assert!(colored.contains(&removed), "{}", colored);
+        assert!(colored.contains(&added), "{}", colored);
+        assert!(colored.contains("--- a/Cargo.toml\n"));
+    }
+}
@@ -1,6 +1,7 @@
 // Error types for FerrisUp
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
 
 /// Custom Result type for FerrisUp operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -16,6 +17,21 @@ pub enum Error {
     Handler(String),
     /// Config error
     Config(String),
+    /// A template variable failed validation
+    Validation { variable: String, reason: String },
+    /// No bundled or user template has this name
+    TemplateNotFound(String),
+    /// The project name can't be used as a crate name
+    InvalidProjectName { name: String, reason: String },
+    /// The target directory already has files the template would overwrite
+    TargetExists(PathBuf),
+    /// A command run after generation failed, with its exit code if it exited normally;
+    /// the generated project is kept
+    HookFailed { name: String, code: Option<i32> },
+    /// The installed Rust toolchain is older than the template's `rust_version`
+    RustTooOld { template: String, required: String, found: String },
+    /// `--template-version` asked for a version of the template this FerrisUp doesn't have
+    TemplateVersionMismatch { template: String, requested: String, available: Option<String> },
     /// Other error
     Other(String),
     /// Anyhow error (for compatibility)
@@ -29,13 +45,51 @@ impl fmt::Display for Error {
             Error::Template(msg) => write!(f, "Template error: {}", msg),
             Error::Handler(msg) => write!(f, "Project handler error: {}", msg),
             Error::Config(msg) => write!(f, "Config error: {}", msg),
+            Error::Validation { variable, reason } => {
+                write!(f, "Invalid value for `{}`: {}", variable, reason)
+            }
+            Error::TemplateNotFound(name) => write!(f, "Template '{}' not found", name),
+            Error::InvalidProjectName { name, reason } => {
+                write!(f, "Invalid project name \"{}\": {}", name, reason)
+            }
+            Error::TargetExists(path) => write!(
+                f,
+                "{} already contains files generated by this template. Pass --force to overwrite them, or --overwrite-policy skip to keep them",
+                path.display()
+            ),
+            Error::HookFailed { name, code: Some(code) } => write!(f, "`{}` failed with exit code {}", name, code),
+            Error::HookFailed { name, code: None } => write!(f, "`{}` was terminated by a signal", name),
+            Error::RustTooOld { template, required, found } => write!(
+                f,
+                "Template '{}' requires Rust >= {}, found {}. Update the toolchain with `rustup update stable`",
+                template, required, found
+            ),
+            Error::TemplateVersionMismatch { template, requested, available: Some(available) } => write!(
+                f,
+                "Template '{}' is at version {}, not the requested {}. Install the FerrisUp release that bundles it to generate that version",
+                template, available, requested
+            ),
+            Error::TemplateVersionMismatch { template, requested, available: None } => write!(
+                f,
+                "Template '{}' doesn't declare a `version`, so it can't be pinned to {}",
+                template, requested
+            ),
             Error::Other(msg) => write!(f, "{}", msg),
             Error::Anyhow(err) => write!(f, "{}", err),
         }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            // The wrapped error's own message is displayed, so its causes come next
+            Error::Anyhow(err) => err.source(),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
@@ -44,8 +98,12 @@ impl From<io::Error> for Error {
 }
 
 impl From<anyhow::Error> for Error {
+    /// Errors raised as one of our variants and passed on through `anyhow` get that variant back
     fn from(err: anyhow::Error) -> Self {
-        Error::Anyhow(err)
+        match err.downcast::<Error>() {
+            Ok(err) => err,
+            Err(err) => Error::Anyhow(err),
+        }
     }
 }
 
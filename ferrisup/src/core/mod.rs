@@ -1,6 +1,10 @@
 // Core workspace entry point
 pub mod config;
+pub mod crates_io;
+pub mod diff;
 pub mod error;
+pub mod retry;
+pub mod rust_version;
 
 // Re-exports of core components
 pub use config::Config;
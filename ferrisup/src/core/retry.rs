@@ -0,0 +1,66 @@
+// Retrying network operations that can fail transiently, such as clones and crates.io lookups
+use std::fmt::Display;
+use std::thread;
+use std::time::Duration;
+
+use crate::commands::offline;
+
+/// Attempts made by network operations unless they have a reason to differ
+pub const DEFAULT_ATTEMPTS: u32 = 3;
+
+/// Wait before the second attempt, doubled before each one after it
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Run `op` up to `times` times until it succeeds, waiting `backoff`, then twice as long,
+/// and so on between attempts
+///
+/// Returns the first success or the last error. In offline mode `op` runs once: it can
+/// only fail for reasons a retry won't fix, so the error comes back right away.
+pub fn retry<T, E: Display>(times: u32, backoff: Duration, mut op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let times = if offline::is_offline() { 1 } else { times.max(1) };
+    let mut wait = backoff;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < times => {
+                log::warn!("Attempt {} of {} failed, retrying in {:?}: {}", attempt, times, wait, e);
+                thread::sleep(wait);
+                wait = wait.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_until_success() {
+        let mut attempts = 0;
+        let result = retry(DEFAULT_ATTEMPTS, Duration::ZERO, || {
+            attempts += 1;
+            if attempts < 3 {
+                Err(format!("attempt {} failed", attempts))
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(result, Ok(3));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up() {
+        let mut attempts = 0;
+        let result: Result<(), String> = retry(2, Duration::ZERO, || {
+            attempts += 1;
+            Err(format!("attempt {} failed", attempts))
+        });
+        assert_eq!(result, Err("attempt 2 failed".to_string()));
+        assert_eq!(attempts, 2);
+    }
+}
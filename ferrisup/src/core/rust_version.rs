@@ -0,0 +1,72 @@
+// Rust toolchain versions, for the `rust_version` templates require
+use semver::Version;
+use std::process::Command;
+use std::sync::OnceLock;
+
+/// Parse the output of `rustc --version`, e.g. `rustc 1.80.0-nightly (ada5e2c7b 2024-05-31)`
+///
+/// Nightly and beta suffixes are dropped, so a 1.80 nightly counts as 1.80.0 the way cargo's
+/// own `rust-version` check treats it.
+pub fn parse_rustc_version(output: &str) -> Option<Version> {
+    let version = output.split_whitespace().nth(1)?;
+    let release = version.split(['-', '+']).next()?;
+    parse_rust_version(release)
+}
+
+/// Parse a required Rust version such as `1.75` or `1.75.0`
+pub fn parse_rust_version(version: &str) -> Option<Version> {
+    let parts: Vec<u64> = version
+        .trim()
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [major, minor] => Some(Version::new(major, minor, 0)),
+        [major, minor, patch] => Some(Version::new(major, minor, patch)),
+        _ => None,
+    }
+}
+
+/// The version of the `rustc` on the PATH, `None` if it isn't installed or can't be parsed
+pub fn rustc_version() -> Option<Version> {
+    static RUSTC_VERSION: OnceLock<Option<Version>> = OnceLock::new();
+    RUSTC_VERSION
+        .get_or_init(|| {
+            let output = Command::new("rustc").arg("--version").output().ok()?;
+            parse_rustc_version(&String::from_utf8_lossy(&output.stdout))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rustc_version() {
+        assert_eq!(
+            parse_rustc_version("rustc 1.79.0 (129f3b996 2024-06-10)\n"),
+            Some(Version::new(1, 79, 0))
+        );
+        assert_eq!(
+            parse_rustc_version("rustc 1.80.0-nightly (ada5e2c7b 2024-05-31)"),
+            Some(Version::new(1, 80, 0))
+        );
+        assert_eq!(
+            parse_rustc_version("rustc 1.80.0-beta.3 (105fc5de5 2024-06-25)"),
+            Some(Version::new(1, 80, 0))
+        );
+        assert_eq!(parse_rustc_version("rustc 1.72.1"), Some(Version::new(1, 72, 1)));
+        assert_eq!(parse_rustc_version("command not found"), None);
+        assert_eq!(parse_rustc_version(""), None);
+    }
+
+    #[test]
+    fn test_parse_rust_version() {
+        assert_eq!(parse_rust_version("1.75"), Some(Version::new(1, 75, 0)));
+        assert_eq!(parse_rust_version("1.75.2"), Some(Version::new(1, 75, 2)));
+        assert_eq!(parse_rust_version("1"), None);
+        assert_eq!(parse_rust_version("1.x"), None);
+        assert_eq!(parse_rust_version("1.75.0.1"), None);
+    }
+}
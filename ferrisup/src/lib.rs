@@ -30,4 +30,5 @@ pub mod commands;
 
 // Re-exports of frequently used components
 pub use core::{Config, Result, Error};
-pub use project::{find_handler, get_handlers, ProjectHandler};
+pub use commands::new::{generate, GenerateOptions, GeneratedProject};
+pub use project::{detect_handler, find_handler, get_handlers, handler_for_project, ProjectHandler};
@@ -1,9 +1,13 @@
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
+use std::path::PathBuf;
 
 // Use the library modules instead of local definitions
 use ferrisup::commands;
+use ferrisup::commands::{log_file, offline};
+use ferrisup::commands::output_format::{self, OutputFormat};
+use ferrisup::commands::verbosity::{self, Verbosity};
 
 #[derive(Parser)]
 #[command(
@@ -14,15 +18,64 @@ use ferrisup::commands;
     long_about = "FerrisUp is a powerful CLI tool for bootstrapping Rust projects with various templates ranging from minimal binaries to full-stack applications with AI, edge computing, and embedded systems support."
 )]
 struct Cli {
+    /// Only print errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Print debug output and logging
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Never access the network; also enabled by FERRISUP_OFFLINE=1
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Also write a JSON-lines log of the templates rendered and variables resolved to
+    /// this file, to attach to bug reports
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Print results as text or as JSON for tools wrapping the CLI. In JSON mode `new`
+    /// prints the generated project as one object and errors as {"error": ...}
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
     #[command(subcommand)]
     command: Option<commands::Commands>,
 }
 
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let verbosity = Verbosity::from_flags(cli.quiet, cli.verbose);
+    verbosity::set_verbosity(verbosity);
+    offline::set_offline(cli.offline);
+    let json_output = cli.output_format == OutputFormat::Json;
+    verbosity::set_status_to_stderr(json_output);
+    let quiet = verbosity.is_quiet() || json_output;
+
+    // Print a status line unless --quiet was given
+    let status = |message: String| {
+        if !quiet {
+            println!("{}", message);
+        }
+    };
+
+    // Machine-readable output must not be mixed with the banner
+    let machine_output = json_output || match &cli.command {
+        Some(commands::Commands::Info { json, .. }) => *json,
+        Some(commands::Commands::Component { json, .. }) => *json,
+        #[cfg(not(feature = "workspace_test"))]
+        Some(commands::Commands::Preview { json, .. }) => *json,
+        Some(commands::Commands::Workspace { format, .. }) => format.as_deref() == Some("dot"),
+        _ => false,
+    };
+
     // High-quality ASCII art of Ferris (Rust mascot)
     // Convert img.png to ASCII art using image-to-ascii library
 
-    println!("{}", r#"                                                 
+    if !machine_output && !quiet {
+        println!("{}", r#"                                                 
                  ######                           
               ##########                          
             #########  ##                         
@@ -52,73 +105,138 @@ fn main() -> Result<()> {
         ═══════════════════════════════
 
 "#.bright_green().bold());
+    }
 
-    env_logger::init();
-    let cli = Cli::parse();
+    log_file::init(verbosity.log_level(), cli.log_file.as_deref())?;
 
     // Match the CLI command and execute
-    match cli.command {
-        Some(commands::Commands::New { name, component_type, framework, provider, application_type, project_type, git, build, no_interactive }) => {
+    let result = match cli.command {
+        Some(commands::Commands::New { name, component_type, framework, provider, application_type, project_type: _, set, answers, edition, features, dependencies_from, registry, registry_url, target_dir, profile, no_readme, vcs, git, commit_message, git_remote, git_branch, into, path, force, overwrite_policy, build, no_build_check, sample_data, ci, license, minimal, template_version, author, check_name, no_interactive }) => {
             match &name {
-                Some(n) => println!(
+                Some(n) => status(format!(
                     "{} {} {} {}",
                     "Creating".green().bold(),
                     "new".green().bold(),
                     n.cyan().bold(),
                     "project".green().bold()
-                ),
-                None => println!(
+                )),
+                None => status(format!(
                     "{} {} {}",
                     "Creating".green().bold(),
                     "new".green().bold(),
                     "project".green().bold()
-                )
+                ))
             }
             
-            // Convert ComponentType to &str safely
-            let component_type_str = component_type.as_ref().map(|ct| ct.to_string());
-            let component_type_ref = component_type_str.as_deref();
+            // Options given on the command line win over the answers file
+            let answers = match &answers {
+                Some(path) => ferrisup::template_manager::load_answers(path)?,
+                None => Default::default(),
+            };
+            let component_type = component_type.or(answers.template);
+            let framework = framework.or(answers.framework);
+            let provider = provider.or(answers.provider);
+            let application_type = application_type.or(answers.application_type);
+            let target_dir = target_dir.or(answers.target_dir);
+            let profile = profile.or(answers.profile);
+
+            // Later --set values for the same key win
+            let mut variables = answers.variables;
+            variables.extend(set.into_iter().map(|(key, value)| (key, serde_json::Value::String(value))));
             
-            commands::new::execute(
-                name.as_deref(), 
-                component_type_ref, 
-                framework.as_deref(), 
-                provider.as_deref(), 
-                application_type.as_deref(), 
-                git, 
-                build, 
-                no_interactive, 
-                project_type.as_deref()
-            )
+            // --git predates --vcs and still works, with a nudge towards the new flag
+            if git {
+                status(format!("{} --git is deprecated, use --vcs git", "Note:".blue()));
+            }
+            let vcs = vcs.or(git.then(|| "git".to_string()));
+
+            // Without --build or --no-build-check the template decides
+            let build = if build {
+                Some(true)
+            } else if no_build_check {
+                Some(false)
+            } else {
+                None
+            };
+
+            let options = ferrisup::GenerateOptions {
+                variables,
+                framework,
+                provider,
+                application_type,
+                edition,
+                features,
+                dependencies_from,
+                registry,
+                registry_url,
+                build_target_dir: target_dir,
+                profile,
+                readme: !no_readme,
+                vcs: vcs.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+                commit_message,
+                git_remote,
+                git_branch,
+                force,
+                overwrite_policy: overwrite_policy.as_deref().map(str::parse).transpose()?,
+                build,
+                sample_data,
+                ci: (ci != "none").then_some(ci),
+                license: Some(license),
+                minimal,
+                template_version,
+                author,
+                check_name,
+                interactive: !no_interactive,
+                // The name and template are filled in once they're known
+                ..ferrisup::GenerateOptions::new(String::new(), String::new())
+            };
+            commands::new::execute(commands::new::NewArgs {
+                name,
+                component_type,
+                into,
+                path,
+                output_format: cli.output_format,
+                options,
+            })
         }
-        Some(commands::Commands::Transform { project, template }) => {
+        Some(commands::Commands::Transform { project, template, name, from, to, dry_run }) => {
             match &project {
-                Some(p) => println!(
+                Some(p) => status(format!(
                     "{} {}",
                     "Transforming".yellow().bold(),
                     p.cyan().bold()
-                ),
-                None => println!(
-                    "{}",
-                    "Starting interactive project transformation".yellow().bold()
+                )),
+                None => status(
+                    "Starting interactive project transformation".yellow().bold().to_string()
                 )
             }
-            commands::transform::execute(project.as_deref(), template.as_deref())
+            commands::transform::execute(
+                project.as_deref(),
+                template.as_deref(),
+                name.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                dry_run,
+            )
         }
-        Some(commands::Commands::List) => {
-            println!("{}", "Listing available component types".blue().bold());
-            commands::list::execute()
+        Some(commands::Commands::List { outdated }) => {
+            status("Listing available component types".blue().bold().to_string());
+            commands::list::execute(outdated)
         }
         #[cfg(not(feature = "workspace_test"))]
-        Some(commands::Commands::Preview { component_type, framework, provider, application_type }) => {
-            println!("{}", "Previewing component type".green().bold());
-            // Convert ComponentType to &str for the preview command
-            let component_type_str = component_type.map(|ct| ct.to_string());
-            commands::preview::execute(component_type_str.as_deref(), framework.as_deref(), provider.as_deref(), application_type.as_deref())
+        Some(commands::Commands::Preview { component_type, framework, provider, application_type, tree, list_variables, json }) => {
+            if list_variables {
+                commands::preview::list_variables(component_type.as_deref(), json)
+            } else {
+                status("Previewing component type".green().bold().to_string());
+                commands::preview::execute(component_type.as_deref(), framework.as_deref(), provider.as_deref(), application_type.as_deref(), tree)
+            }
         }
         #[cfg(not(feature = "workspace_test"))]
-        Some(commands::Commands::Component { action, component_type, project }) => {
-            println!("{}", "Managing components".green().bold());
+        Some(commands::Commands::Component { action, component_type, project, force, dry_run, json }) => {
+            if !json {
+                status("Managing components".green().bold().to_string());
+            }
             
             // Convert ComponentType to &str safely
             let component_type_str = component_type.as_ref().map(|ct| ct.to_string());
@@ -127,33 +245,86 @@ fn main() -> Result<()> {
             commands::component::execute(
                 action.as_deref(), 
                 component_type_ref, 
-                project.as_deref()
+                project.as_deref(),
+                force,
+                dry_run,
+                json || json_output,
             )
         }
         #[cfg(not(feature = "workspace_test"))]
-        Some(commands::Commands::Config { export, import, path }) => {
-            println!("{}", "Managing configuration".green().bold());
-            commands::config::execute(export, import.as_deref(), path.as_deref())
+        Some(commands::Commands::Config { export, import, path, format, validate_only }) => {
+            status("Managing configuration".green().bold().to_string());
+            commands::config::execute(export, import.as_deref(), path.as_deref(), format, validate_only)
         }
-        Some(commands::Commands::Workspace { action, path }) => {
-            println!("{}", "Managing Cargo workspace".green().bold());
-            commands::workspace::execute(action.as_deref(), path.as_deref())
+        Some(commands::Commands::Workspace { action, path, member, format, edition, hoist, dry_run }) => {
+            if format.as_deref() != Some("dot") {
+                status("Managing Cargo workspace".green().bold().to_string());
+            }
+            commands::workspace::execute(
+                action.as_deref(),
+                path.as_deref(),
+                member.as_deref(),
+                format.as_deref(),
+                edition.as_deref(),
+                hoist,
+                dry_run,
+            )
         }
         #[cfg(not(feature = "workspace_test"))]
         Some(commands::Commands::Dependency(args)) => {
-            println!("{}", "Managing dependencies".green().bold());
+            status("Managing dependencies".green().bold().to_string());
             commands::dependency::execute(args)
         }
         #[cfg(not(feature = "workspace_test"))]
-        Some(commands::Commands::UnusedFeatures { path }) => {
-            println!("{}", "Finding unused features in dependencies".green().bold());
-            commands::unused_features::execute(path.as_deref())
+        Some(commands::Commands::UnusedFeatures { path, apply, fail_on_unused }) => {
+            status("Finding unused features in dependencies".green().bold().to_string());
+            commands::unused_features::execute(path.as_deref(), apply, fail_on_unused)
+        }
+        Some(commands::Commands::Clean { path, include, dry_run }) => {
+            status("Cleaning build artifacts".green().bold().to_string());
+            commands::clean::execute(path.as_deref(), &include, dry_run)
+        }
+        Some(commands::Commands::Scale { path }) => {
+            status("Scaling project".green().bold().to_string());
+            commands::scale::execute(path.as_deref())
+        }
+        Some(commands::Commands::Info { path, json }) => {
+            if !json {
+                status("Inspecting project".green().bold().to_string());
+            }
+            commands::info::execute(path.as_deref(), json || json_output)
+        }
+        Some(commands::Commands::Doctor { fix, yes, no_interactive }) => {
+            status("Checking FerrisUp setup".green().bold().to_string());
+            commands::doctor::execute(fix, yes, no_interactive)
+        }
+        Some(commands::Commands::Migrate { path }) => {
+            status("Migrating project stamp".green().bold().to_string());
+            commands::migrate::execute(path.as_deref())
+        }
+        Some(commands::Commands::Verify { template, all }) => {
+            status("Verifying templates".green().bold().to_string());
+            commands::verify::execute(template.as_deref(), all)
+        }
+        Some(commands::Commands::Template { action, name }) => {
+            let verb = if action == "new" { "Creating template" } else { "Validating template" };
+            status(format!("{} {}", verb.green().bold(), name.cyan().bold()));
+            commands::template::execute(&action, &name)
         }
         None => {
-            println!("{}", "No command specified, using interactive mode".yellow());
+            status("No command specified, using interactive mode".yellow().to_string());
             // Just show help for now
             Cli::parse_from(["ferrisup", "--help"]);
             Ok(())
         }
+    };
+
+    // Tools reading JSON get the error in the same form, still with a failing exit code
+    match result {
+        Err(e) if json_output => {
+            output_format::print_json_error(&e);
+            std::process::exit(1);
+        }
+        result => result,
     }
 }
@@ -3,6 +3,8 @@ use anyhow::{Result, anyhow};
 use std::path::Path;
 use serde_json::Value;
 use std::process::Command;
+use crate::commands::offline;
+use crate::project::handlers::detect::Detection;
 use crate::project::handlers::traits::ProjectHandler;
 
 /// Handler for CLI-based project generation
@@ -34,6 +36,14 @@ pub struct CliProjectHandler {
     
     /// Command to check if the CLI tool is installed
     version_check_command: Option<String>,
+    /// Signals used to recognise existing projects of this kind
+    detection: Detection,
+    
+    /// Command that builds a generated project
+    build_command: Vec<String>,
+    
+    /// Command that runs a generated project, if it can be run
+    run_command: Option<Vec<String>>,
 }
 
 impl CliProjectHandler {
@@ -57,9 +67,25 @@ impl CliProjectHandler {
             next_steps_fn,
             installation_command,
             version_check_command,
+            detection: Detection::default(),
+            build_command: vec!["cargo".to_string(), "build".to_string()],
+            run_command: Some(vec!["cargo".to_string(), "run".to_string()]),
         }
     }
     
+    /// Set the signals used to recognise existing projects created by this handler
+    pub fn with_detection(mut self, detection: Detection) -> Self {
+        self.detection = detection;
+        self
+    }
+    
+    /// Set the suggested build and run commands, replacing `cargo build` and `cargo run`
+    pub fn with_commands(mut self, build: &[&str], run: Option<&[&str]>) -> Self {
+        self.build_command = build.iter().map(|s| s.to_string()).collect();
+        self.run_command = run.map(|run| run.iter().map(|s| s.to_string()).collect());
+        self
+    }
+    
     /// Check if the CLI tool is installed
     fn is_installed(&self) -> Result<bool> {
         if let Some(check_cmd) = &self.version_check_command {
@@ -88,6 +114,7 @@ impl CliProjectHandler {
     /// Install the CLI tool if needed
     fn install_if_needed(&self) -> Result<bool> {
         if !self.is_installed()? {
+            offline::ensure_online(&format!("install {}", self.name))?;
             println!("⚠️ {} not found. Installing...", self.name);
             
             if let Some(install_cmd) = &self.installation_command {
@@ -132,6 +159,18 @@ impl ProjectHandler for CliProjectHandler {
         self.templates.contains(&template_name.to_string())
     }
     
+    fn detect_confidence(&self, project_dir: &Path) -> f32 {
+        self.detection.score(project_dir)
+    }
+    
+    fn build_command(&self) -> Vec<String> {
+        self.build_command.clone()
+    }
+    
+    fn run_command(&self) -> Option<Vec<String>> {
+        self.run_command.clone()
+    }
+    
     fn initialize_project(&self, project_name: &str, target_dir: &Path, variables: &Value) -> crate::core::Result<()> {
         // Install CLI tool if needed
         self.install_if_needed().map_err(|e| e.to_string())?;
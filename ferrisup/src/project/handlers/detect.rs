@@ -0,0 +1,138 @@
+// Scoring of how closely an existing project matches a handler
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Weight of a framework crate appearing among the dependencies
+const CRATE_WEIGHT: f32 = 0.6;
+
+/// Weight of a characteristic file such as `Trunk.toml` or `wrangler.toml`
+const FILE_WEIGHT: f32 = 0.3;
+
+/// Weight of a characteristic directory layout
+const DIR_WEIGHT: f32 = 0.1;
+
+/// Signals that identify a project created by a handler
+///
+/// Each kind of signal contributes its weight once when any of its entries matches,
+/// so a project scores at most 1.0 and pulling in several crates of the same handler
+/// doesn't outweigh a characteristic file of another.
+#[derive(Debug, Clone, Default)]
+pub struct Detection {
+    /// Crates that only projects of this kind depend on
+    crates: Vec<String>,
+
+    /// Files at the project root, relative paths
+    files: Vec<String>,
+
+    /// Directories at the project root, relative paths
+    dirs: Vec<String>,
+}
+
+impl Detection {
+    /// Create a set of detection signals
+    pub fn new(crates: &[&str], files: &[&str], dirs: &[&str]) -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            crates: owned(crates),
+            files: owned(files),
+            dirs: owned(dirs),
+        }
+    }
+
+    /// Confidence between 0.0 and 1.0 that the project in `project_dir` matches these signals
+    ///
+    /// Only reads the project, so scoring the same directory twice gives the same result.
+    pub fn score(&self, project_dir: &Path) -> f32 {
+        let dependencies = manifest_dependencies(project_dir);
+
+        let mut score = 0.0;
+        if self.crates.iter().any(|c| dependencies.contains(c)) {
+            score += CRATE_WEIGHT;
+        }
+        if self.files.iter().any(|f| project_dir.join(f).is_file()) {
+            score += FILE_WEIGHT;
+        }
+        if self.dirs.iter().any(|d| project_dir.join(d).is_dir()) {
+            score += DIR_WEIGHT;
+        }
+        score
+    }
+}
+
+/// Names of every crate the project's Cargo.toml depends on, including dev, build,
+/// target-specific and workspace dependencies, resolving `package = "..."` renames
+fn manifest_dependencies(project_dir: &Path) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let Ok(content) = fs::read_to_string(project_dir.join("Cargo.toml")) else {
+        return names;
+    };
+    let Ok(doc) = content.parse::<DocumentMut>() else {
+        return names;
+    };
+
+    let mut tables: Vec<&Item> = Vec::new();
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        tables.extend(doc.get(section));
+    }
+    if let Some(targets) = doc.get("target").and_then(|t| t.as_table_like()) {
+        for (_, target) in targets.iter() {
+            for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                tables.extend(target.get(section));
+            }
+        }
+    }
+    tables.extend(doc.get("workspace").and_then(|w| w.get("dependencies")));
+
+    for table in tables.into_iter().filter_map(|t| t.as_table_like()) {
+        for (key, value) in table.iter() {
+            let name = value
+                .get("package")
+                .and_then(|p| p.as_str())
+                .unwrap_or(key);
+            names.insert(name.to_string());
+        }
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_score_weights_each_kind_of_signal_once() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nleptos = \"0.7\"\nleptos_router = \"0.7\"\n\n[target.'cfg(unix)'.dependencies]\nweb = { package = \"web-sys\", version = \"0.3\" }\n",
+        )
+        .unwrap();
+
+        let leptos = Detection::new(&["leptos", "leptos_router"], &["Trunk.toml"], &["style"]);
+        assert!((leptos.score(root) - CRATE_WEIGHT).abs() < f32::EPSILON);
+
+        fs::write(root.join("Trunk.toml"), "").unwrap();
+        fs::create_dir(root.join("style")).unwrap();
+        assert!((leptos.score(root) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(leptos.score(root), leptos.score(root));
+
+        // Renamed and target-specific dependencies count under their real name
+        assert!(Detection::new(&["web-sys"], &[], &[]).score(root) > 0.0);
+        assert_eq!(Detection::new(&["web"], &[], &[]).score(root), 0.0);
+    }
+
+    #[test]
+    fn test_score_without_manifest() {
+        let temp_dir = tempdir().unwrap();
+        let detection = Detection::new(&["worker"], &["wrangler.toml"], &[]);
+        assert_eq!(detection.score(temp_dir.path()), 0.0);
+
+        fs::write(temp_dir.path().join("wrangler.toml"), "").unwrap();
+        assert!((detection.score(temp_dir.path()) - FILE_WEIGHT).abs() < f32::EPSILON);
+    }
+}
@@ -1,13 +1,16 @@
 // Project handlers module - central registry for all project handlers
 mod traits;
 mod cli;
+mod detect;
 mod template;
 
 // Removed unused imports
 use serde_json::Value;
+use std::path::Path;
 
 pub use traits::ProjectHandler;
 pub use cli::CliProjectHandler;
+pub use detect::Detection;
 pub use template::TemplateProjectHandler;
 
 // Get all registered project handlers
@@ -49,7 +52,11 @@ pub fn get_handlers() -> Vec<Box<dyn ProjectHandler>> {
         },
         Some("cargo install cargo-embassy".to_string()),
         Some("cargo embassy --version".to_string()),
-    )));
+    ).with_detection(Detection::new(
+        &["embassy-executor", "embassy-time", "embassy-embedded-hal"],
+        &["memory.x", "Embed.toml"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], Some(&["cargo", "run", "--release"]))));
     
     // Dioxus CLI Handler
     handlers.push(Box::new(CliProjectHandler::new(
@@ -96,7 +103,8 @@ pub fn get_handlers() -> Vec<Box<dyn ProjectHandler>> {
         },
         Some("cargo install dioxus-cli".to_string()),
         Some("dioxus --version".to_string()),
-    )));
+    ).with_detection(Detection::new(&["dioxus"], &["Dioxus.toml"], &["assets"]))
+        .with_commands(&["dx", "build"], Some(&["dx", "serve"]))));
     
     // Tauri CLI Handler
     handlers.push(Box::new(CliProjectHandler::new(
@@ -117,7 +125,11 @@ pub fn get_handlers() -> Vec<Box<dyn ProjectHandler>> {
         },
         Some("cargo install tauri-cli".to_string()),
         Some("cargo tauri --version".to_string()),
-    )));
+    ).with_detection(Detection::new(
+        &["tauri", "tauri-build"],
+        &["tauri.conf.json", "src-tauri/tauri.conf.json"],
+        &["src-tauri"],
+    )).with_commands(&["cargo", "tauri", "build"], Some(&["cargo", "tauri", "dev"]))));
     
     // Add Template Handlers
     
@@ -126,62 +138,194 @@ pub fn get_handlers() -> Vec<Box<dyn ProjectHandler>> {
         "Full Stack",
         "Complete application with client, server, and ferrisup_common libraries",
         vec!["full-stack".to_string()]
-    )));
+    ).with_detection(Detection::new(&[], &[], &["client", "server", "shared"]))
+        .with_commands(&["cargo", "build", "--workspace"], None)));
+    
+    // Minimal Workspace Template (the app crate is the default member, so `cargo run` works)
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Workspace",
+        "Cargo workspace with app, core and shared crates",
+        vec!["minimal-workspace".to_string()]
+    ).with_detection(Detection::new(&[], &[], &["crates"]))
+        .with_commands(&["cargo", "build", "--workspace"], Some(&["cargo", "run"]))));
+    
+    // Leptos Templates
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Leptos",
+        "Reactive web applications with Leptos",
+        vec!["client".to_string(), "leptos".to_string(), "counter".to_string(), "router".to_string(), "todo".to_string()]
+    ).with_detection(Detection::new(&["leptos", "leptos_router"], &["Trunk.toml"], &["style"]))
+        .with_commands(&["trunk", "build"], Some(&["trunk", "serve"]))));
     
     // Server Templates
     handlers.push(Box::new(TemplateProjectHandler::new(
         "Server",
         "Web server with API endpoints",
         vec!["server".to_string(), "axum".to_string(), "actix".to_string(), "poem".to_string()]
-    )));
+    ).with_detection(Detection::new(&["axum", "actix-web", "poem"], &[], &[]))));
+    
+    // Polars CLI Template
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Polars CLI",
+        "Command-line data analysis with Polars",
+        vec!["data-science/polars-cli".to_string(), "polars-cli".to_string()]
+    ).with_detection(Detection::new(&["polars"], &[], &["data"]))
+        .with_commands(
+            &["cargo", "build", "--release"],
+            Some(&["cargo", "run", "--", "analyze", "-f", "data/example_data.csv"]),
+        )));
     
     // Data Science Templates
     handlers.push(Box::new(TemplateProjectHandler::new(
         "Data Science",
         "Data science and machine learning projects",
         vec!["data-science".to_string(), "burn".to_string(), "linfa".to_string()]
-    )));
+    ).with_detection(Detection::new(&["burn", "linfa", "polars", "ndarray"], &[], &["data", "notebooks"]))
+        .with_commands(&["cargo", "build", "--release"], Some(&["cargo", "run", "--release"]))));
     
     // Edge Templates
     handlers.push(Box::new(TemplateProjectHandler::new(
         "Edge",
         "WebAssembly-based applications for edge computing",
         vec!["edge".to_string(), "edge-app".to_string()]
-    )));
+    ).with_detection(Detection::new(
+        &["worker", "fastly", "spin-sdk"],
+        &["wrangler.toml", "fastly.toml", "spin.toml"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], None)));
     
     // Standard Embedded Templates
     handlers.push(Box::new(TemplateProjectHandler::new(
         "Embedded",
         "Embedded systems firmware for microcontrollers",
         vec!["embedded".to_string()]
-    )));
+    ).with_detection(Detection::new(
+        &["cortex-m", "cortex-m-rt", "rp2040-hal", "esp-hal", "arduino-hal"],
+        &["memory.x", "Embed.toml"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], Some(&["cargo", "run", "--release"]))));
     
-    // Serverless Templates
+    // Serverless Templates, one per cloud_provider since each has its own tooling.
+    // AWS Lambda, the default provider, comes first
     handlers.push(Box::new(TemplateProjectHandler::new(
-        "Serverless",
+        "Serverless (AWS Lambda)",
         "Serverless functions for cloud deployment",
         vec!["serverless".to_string()]
-    )));
+    ).with_variable("cloud_provider", "aws").with_detection(Detection::new(
+        &["lambda_runtime", "lambda_http"],
+        &["template.yml", "template.yaml", "serverless.yml"],
+        &[],
+    )).with_commands(&["cargo", "lambda", "build", "--release"], Some(&["cargo", "lambda", "watch"]))));
+    
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Serverless (Google Cloud Functions)",
+        "Serverless functions for cloud deployment",
+        vec!["serverless".to_string()]
+    ).with_variable("cloud_provider", "gcp").with_detection(Detection::new(
+        &[],
+        &["cloudbuild.yaml"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], None)));
+    
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Serverless (Azure Functions)",
+        "Serverless functions for cloud deployment",
+        vec!["serverless".to_string()]
+    ).with_variable("cloud_provider", "azure").with_detection(Detection::new(
+        &[],
+        &["host.json", "function.json"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], Some(&["func", "start"]))));
+    
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Serverless (Vercel)",
+        "Serverless functions for cloud deployment",
+        vec!["serverless".to_string()]
+    ).with_variable("cloud_provider", "vercel").with_detection(Detection::new(
+        &["vercel_runtime"],
+        &["vercel.json"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], Some(&["vercel", "dev"]))));
+    
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Serverless (Netlify)",
+        "Serverless functions for cloud deployment",
+        vec!["serverless".to_string()]
+    ).with_variable("cloud_provider", "netlify").with_detection(Detection::new(
+        &["netlify_lambda_http"],
+        &["netlify.toml"],
+        &[],
+    )).with_commands(&["cargo", "build", "--release"], Some(&["netlify", "dev"]))));
     
     // Generic Template Handler (fallback)
     handlers.push(Box::new(TemplateProjectHandler::new(
         "Generic",
         "Generic template handler for all other templates",
-        vec!["minimal".to_string(), "library".to_string(), "iot-device".to_string(), "ml-pipeline".to_string()]
+        vec!["minimal".to_string(), "iot-device".to_string(), "ml-pipeline".to_string()]
     )));
     
+    // Libraries build but have nothing to run
+    handlers.push(Box::new(TemplateProjectHandler::new(
+        "Generic",
+        "Generic template handler for all other templates",
+        vec!["library".to_string()]
+    ).with_commands(&["cargo", "build"], None)));
+    
     handlers
 }
 
-// Find a handler for the given template name
-pub fn find_handler(template_name: &str, variables: &Value) -> Option<Box<dyn ProjectHandler>> {
-    for handler in get_handlers() {
-        if handler.can_handle(template_name, variables) {
-            return Some(handler);
+// Find the handler for the given template name that best matches the project in `project_dir`
+//
+// Of the handlers that can handle the template, the one whose `detect_confidence` scores
+// the project highest wins, ties going to the handler registered first. One is returned
+// even when nothing in the project matches, as the template name already picked it.
+pub fn find_handler(template_name: &str, variables: &Value, project_dir: &Path) -> Option<Box<dyn ProjectHandler>> {
+    let candidates = get_handlers()
+        .into_iter()
+        .filter(|handler| handler.can_handle(template_name, variables));
+    highest_scoring(candidates, project_dir).map(|(_, handler)| handler)
+}
+
+// Find the handler for an existing project in `project_dir`
+//
+// Uses the best handler registered for `template` when the template is known and has one,
+// otherwise scores the project contents with `detect_handler`.
+pub fn handler_for_project(template: Option<&str>, variables: &Value, project_dir: &Path) -> Option<Box<dyn ProjectHandler>> {
+    template
+        .and_then(|template| find_handler(template, variables, project_dir))
+        .or_else(|| detect_handler(project_dir))
+}
+
+// Find the handler that most likely created the existing project in `project_dir`
+//
+// Every handler scores the project with `detect_confidence` and the highest score wins.
+// Ties go to the handler registered first, so the result doesn't depend on anything
+// but the project contents. Returns `None` when no handler sees any matching signal.
+pub fn detect_handler(project_dir: &Path) -> Option<Box<dyn ProjectHandler>> {
+    highest_scoring(get_handlers(), project_dir)
+        .filter(|(score, _)| *score > 0.0)
+        .map(|(_, handler)| handler)
+}
+
+// The handler scoring `project_dir` highest with its score, the first one on ties
+fn highest_scoring(
+    handlers: impl IntoIterator<Item = Box<dyn ProjectHandler>>,
+    project_dir: &Path,
+) -> Option<(f32, Box<dyn ProjectHandler>)> {
+    let mut best: Option<(f32, Box<dyn ProjectHandler>)> = None;
+
+    for handler in handlers {
+        let score = handler.detect_confidence(project_dir);
+        let is_better = match &best {
+            Some((best_score, _)) => score > *best_score,
+            None => true,
+        };
+        if is_better {
+            best = Some((score, handler));
         }
     }
-    
-    None
+
+    best
 }
 
 // Use the ferrisup_common copy_directory function for directory operations
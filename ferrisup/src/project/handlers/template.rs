@@ -2,6 +2,7 @@
 use std::path::Path;
 use serde_json::Value;
 use handlebars::Handlebars;
+use crate::project::handlers::detect::Detection;
 use crate::project::handlers::traits::ProjectHandler;
 use crate::core::Result;
 
@@ -19,6 +20,17 @@ pub struct TemplateProjectHandler {
     
     /// Templates that this handler can handle
     templates: Vec<String>,
+    /// Signals used to recognise existing projects of this kind
+    detection: Detection,
+    
+    /// Command that builds a generated project
+    build_command: Vec<String>,
+    
+    /// Command that runs a generated project, if it can be run
+    run_command: Option<Vec<String>>,
+    
+    /// Template variable and the value it must have for this handler to apply
+    variable: Option<(String, String)>,
 }
 
 impl TemplateProjectHandler {
@@ -28,8 +40,35 @@ impl TemplateProjectHandler {
             name: name.to_string(),
             description: description.to_string(),
             templates,
+            detection: Detection::default(),
+            build_command: vec!["cargo".to_string(), "build".to_string()],
+            run_command: Some(vec!["cargo".to_string(), "run".to_string()]),
+            variable: None,
         }
     }
+    
+    /// Set the signals used to recognise existing projects created by this handler
+    pub fn with_detection(mut self, detection: Detection) -> Self {
+        self.detection = detection;
+        self
+    }
+    
+    /// Set the suggested build and run commands, replacing `cargo build` and `cargo run`
+    pub fn with_commands(mut self, build: &[&str], run: Option<&[&str]>) -> Self {
+        self.build_command = build.iter().map(|s| s.to_string()).collect();
+        self.run_command = run.map(|run| run.iter().map(|s| s.to_string()).collect());
+        self
+    }
+    
+    /// Only handle the templates when the variable `name` is `value`, for templates whose
+    /// options change the tooling (the serverless `cloud_provider`)
+    ///
+    /// A variable that isn't set doesn't rule the handler out, so a project known only by
+    /// its template name is still matched by its contents.
+    pub fn with_variable(mut self, name: &str, value: &str) -> Self {
+        self.variable = Some((name.to_string(), value.to_string()));
+        self
+    }
 }
 
 impl ProjectHandler for TemplateProjectHandler {
@@ -41,8 +80,24 @@ impl ProjectHandler for TemplateProjectHandler {
         &self.description
     }
     
-    fn can_handle(&self, template_name: &str, _variables: &Value) -> bool {
-        self.templates.contains(&template_name.to_string())
+    fn can_handle(&self, template_name: &str, variables: &Value) -> bool {
+        let variable_matches = match &self.variable {
+            Some((name, value)) => !matches!(variables.get(name).and_then(|v| v.as_str()), Some(v) if v != value),
+            None => true,
+        };
+        self.templates.contains(&template_name.to_string()) && variable_matches
+    }
+    
+    fn detect_confidence(&self, project_dir: &Path) -> f32 {
+        self.detection.score(project_dir)
+    }
+    
+    fn build_command(&self) -> Vec<String> {
+        self.build_command.clone()
+    }
+    
+    fn run_command(&self) -> Option<Vec<String>> {
+        self.run_command.clone()
     }
     
     fn initialize_project(&self, project_name: &str, target_dir: &Path, variables: &Value) -> Result<()> {
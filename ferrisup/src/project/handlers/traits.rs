@@ -20,6 +20,13 @@ pub trait ProjectHandler {
     /// and variables. It's used by the registry to find the appropriate handler.
     fn can_handle(&self, template_name: &str, variables: &Value) -> bool;
     
+    /// Score how likely it is that the existing project in `project_dir` was created by this handler
+    /// 
+    /// Returns a confidence between 0.0 (no matching signals) and 1.0, based on the framework
+    /// crates the project depends on, characteristic files and its directory layout. It only
+    /// reads the project, so repeated calls give the same score.
+    fn detect_confidence(&self, project_dir: &Path) -> f32;
+    
     /// Initialize a project using this handler
     /// 
     /// This is where the actual project generation happens. For CLI handlers,
@@ -32,4 +39,13 @@ pub trait ProjectHandler {
     /// This provides guidance to users after project creation. The steps may
     /// vary based on the project type, selected options, etc.
     fn get_next_steps(&self, project_name: &str, variables: &Value) -> Vec<String>;
+    
+    /// Get the command that builds a project created with this handler
+    /// 
+    /// Returned as program and arguments, e.g. `["trunk", "build"]`, so callers can
+    /// either run it or join it for display.
+    fn build_command(&self) -> Vec<String>;
+    
+    /// Get the command that runs a project created with this handler, if it can be run
+    fn run_command(&self) -> Option<Vec<String>>;
 }
@@ -3,5 +3,5 @@ pub mod handlers;
 pub mod templates;
 
 // Re-export key components
-pub use handlers::{find_handler, get_handlers, ProjectHandler};
+pub use handlers::{detect_handler, find_handler, get_handlers, handler_for_project, ProjectHandler};
 pub use templates::{list_templates, get_template_config};
@@ -18,12 +18,17 @@ use regex::Regex;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use ferrisup_common::to_pascal_case;
+use crate::template_manager::{find_user_template, merge_user_templates, read_user_manifest, USER_MANIFEST};
 
 lazy_static! {
     static ref CURRENT_VARIABLES: Arc<RwLock<Map<String, Value>>> = Arc::new(RwLock::new(Map::new()));
 }
 
 pub fn get_template(name: &str) -> Result<String> {
+    if find_user_template(name).is_some() {
+        return Ok(name.to_string());
+    }
+    
     let templates = get_all_templates()?;
     
     if templates.contains(&name.to_string()) {
@@ -55,6 +60,7 @@ pub fn get_all_templates() -> Result<Vec<String>> {
         "serverless".to_string(),
         "data-science".to_string(),
         "edge".to_string(),
+        "minimal-workspace".to_string(),
     ];
     
     // Check for custom templates in the templates directory
@@ -88,7 +94,7 @@ pub fn get_all_templates() -> Result<Vec<String>> {
 /// Format: Vec<(name, description)>
 pub fn list_templates() -> Result<Vec<(String, String)>> {
     // Define core templates with descriptions
-    // IMPORTANT: Only include the 8 core templates that are actually available in the new command
+    // IMPORTANT: Only include the core templates that are actually available in the new command
     let templates = vec![
         ("minimal".to_string(), "Simple binary with a single main.rs file".to_string()),
         ("library".to_string(), "Rust library crate with a lib.rs file".to_string()),
@@ -98,12 +104,13 @@ pub fn list_templates() -> Result<Vec<(String, String)>> {
         ("serverless".to_string(), "Serverless function (AWS Lambda, Cloudflare Workers, etc.)".to_string()),
         ("data-science".to_string(), "Data science and machine learning projects".to_string()),
         ("edge".to_string(), "Edge computing applications (Cloudflare, Vercel, Fastly, AWS, etc.)".to_string()),
+        ("minimal-workspace".to_string(), "Cargo workspace with an app binary, a core library and a shared crate".to_string()),
     ];
     
-    // Return only the core templates without discovering additional ones
-    // This ensures the list matches exactly what's shown in the new command
+    // Return only the core templates without discovering additional ones, plus the
+    // user's own templates, so the list matches exactly what's shown in the new command
     
-    Ok(templates)
+    Ok(merge_user_templates(templates))
 }
 
 /// Get data science templates with descriptions
@@ -115,6 +122,9 @@ pub fn list_data_science_templates() -> Result<Vec<(String, String)>> {
         // Machine Learning
         ("data-science/linfa-examples".to_string(), "Machine Learning: Working examples with Linfa 0.7.1 (classification, regression, clustering)".to_string()),
         
+        // Deep Learning with Candle
+        ("data-science/candle-mnist".to_string(), "Candle - MNIST Digit Recognition: Train a small neural network with Candle".to_string()),
+        
         // Deep Learning with Burn Framework - Image Processing
         ("data-science/burn-image-recognition".to_string(), "Burn - Image Recognition: Identify handwritten numbers with MNIST dataset".to_string()),
         ("data-science/burn-image-classifier".to_string(), "Burn - Image Classifier: Customizable CNN for multi-class image classification".to_string()),
@@ -647,6 +657,10 @@ fn process_dependencies(dependencies: &Value, _target_dir: &Path, section: &str)
 
 #[allow(dead_code)]
 fn get_template_dir(template_name: &str) -> Result<PathBuf> {
+    if let Some(user_path) = find_user_template(template_name) {
+        return Ok(user_path);
+    }
+    
     let templates_dir = format!("{}/templates", env!("CARGO_MANIFEST_DIR"));
     
     // Check if it's a direct template
@@ -700,6 +714,10 @@ fn get_template_dir(template_name: &str) -> Result<PathBuf> {
 pub fn get_template_config(template_name: &str) -> Result<Value> {
     let template_dir = get_template_dir(template_name)?;
     
+    if template_dir.join(USER_MANIFEST).exists() {
+        return read_user_manifest(&template_dir);
+    }
+    
     // Read the template configuration
     let template_config_path = template_dir.join("template.json");
     let template_config_str = fs::read_to_string(&template_config_path)?;
@@ -729,6 +747,10 @@ fn replace_variables(content: &str, variables: &Value) -> String {
 
 /// Find the directory containing a template
 pub fn find_template_directory(template_name: &str) -> Result<PathBuf> {
+    if let Some(user_path) = find_user_template(template_name) {
+        return Ok(user_path);
+    }
+    
     let templates_dir = format!("{}/templates", env!("CARGO_MANIFEST_DIR"));
     
     // Check if it's a direct template
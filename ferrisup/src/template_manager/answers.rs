@@ -0,0 +1,119 @@
+// Prompt answers loaded from a file (`new --answers`)
+use anyhow::{anyhow, Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+/// Keys of a `.ferrisup/origin.toml` stamp that pick the template rather than answer a prompt,
+/// or record another choice made on the command line
+const STAMP_KEYS: &[&str] = &["template", "component_type", "framework", "provider", "application_type", "target_dir", "profile"];
+
+/// Keys of a stamp that describe the generated project and aren't template variables
+const STAMP_METADATA: &[&str] = &["stamp_version", "license", "template_version", "template_commit"];
+
+/// Answers read from an answers file or an origin stamp
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    /// Template to generate, from a stamp's `template` (or `component_type`) key
+    pub template: Option<String>,
+    pub framework: Option<String>,
+    pub provider: Option<String>,
+    pub application_type: Option<String>,
+    /// The project's `build.target-dir`, from a stamp's `target_dir` key
+    pub target_dir: Option<String>,
+    /// The project's build profile, from a stamp's `profile` key
+    pub profile: Option<String>,
+    /// Template variables, answering their prompts like `--set`
+    pub variables: Map<String, Value>,
+}
+
+/// Load the answers in `path`, a TOML, YAML or JSON file depending on its extension
+///
+/// Top-level keys answer the template's variables, as do the keys of a `[variables]` table.
+/// The keys of a `.ferrisup/origin.toml` stamp (`template`, `framework`, ...) are read as
+/// the choices they record, so a stamp recreates the configuration of the project it came from.
+pub fn load_answers(path: &Path) -> Result<Answers> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+    let parsed: Value = match extension {
+        "yaml" | "yml" => serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as YAML", path.display()))?,
+        "json" => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as JSON", path.display()))?,
+        _ => {
+            let doc: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {} as TOML", path.display()))?;
+            serde_json::to_value(doc)?
+        }
+    };
+    let Value::Object(entries) = parsed else {
+        return Err(anyhow!("{} must map variable names to answers", path.display()));
+    };
+
+    let mut answers = Answers::default();
+    for (key, value) in entries {
+        match (key.as_str(), value) {
+            ("variables", Value::Object(variables)) => answers.variables.extend(variables),
+            (stamp_key, Value::String(choice)) if STAMP_KEYS.contains(&stamp_key) => {
+                let field = match stamp_key {
+                    // The resolved template (client/leptos/counter) is more precise than its type
+                    "template" => &mut answers.template,
+                    "component_type" if answers.template.is_some() => continue,
+                    "component_type" => &mut answers.template,
+                    "framework" => &mut answers.framework,
+                    "provider" => &mut answers.provider,
+                    "target_dir" => &mut answers.target_dir,
+                    "profile" => &mut answers.profile,
+                    _ => &mut answers.application_type,
+                };
+                *field = Some(choice);
+            }
+            (metadata, _) if STAMP_METADATA.contains(&metadata) => {}
+            (_, value) => {
+                answers.variables.insert(key, value);
+            }
+        }
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_answers_formats() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let toml_path = temp_dir.path().join("answers.toml");
+        fs::write(&toml_path, "data_source = \"CSV files\"\nvisualization = \"yes\"\n")?;
+        let yaml_path = temp_dir.path().join("answers.yaml");
+        fs::write(&yaml_path, "data_source: CSV files\nvisualization: \"yes\"\n")?;
+
+        let from_toml = load_answers(&toml_path)?;
+        assert_eq!(from_toml.variables.get("data_source"), Some(&json!("CSV files")));
+        assert_eq!(from_toml.template, None);
+        assert_eq!(load_answers(&yaml_path)?, from_toml);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_answers_from_origin_stamp() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let stamp = temp_dir.path().join("origin.toml");
+        fs::write(
+            &stamp,
+            "stamp_version = 2\ncomponent_type = \"client\"\ntemplate = \"client/leptos/counter\"\nframework = \"leptos\"\n\
+             template_version = \"0.1.0\"\ntarget_dir = \"../target\"\n\n[variables]\nstyling = \"tailwind\"\n",
+        )?;
+
+        let answers = load_answers(&stamp)?;
+        assert_eq!(answers.template.as_deref(), Some("client/leptos/counter"));
+        assert_eq!(answers.framework.as_deref(), Some("leptos"));
+        assert_eq!(answers.target_dir.as_deref(), Some("../target"));
+        assert_eq!(answers.variables, json!({ "styling": "tailwind" }).as_object().unwrap().clone());
+        Ok(())
+    }
+}
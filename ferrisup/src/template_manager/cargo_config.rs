@@ -0,0 +1,151 @@
+// The `.cargo/config.toml` of generated projects (`new --target-dir`, `new --registry-url`)
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use toml_edit::{value, DocumentMut, Item, Table};
+
+/// Edit the `.cargo/config.toml` of the project in `project_dir`, creating it if needed
+///
+/// What a template already configures there is kept.
+pub(super) fn edit_cargo_config(project_dir: &Path, edit: impl FnOnce(&mut DocumentMut) -> Result<()>) -> Result<()> {
+    let config_path = project_dir.join(".cargo").join("config.toml");
+    let mut config = match fs::read_to_string(&config_path) {
+        Ok(content) => content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?,
+        Err(_) => DocumentMut::new(),
+    };
+    edit(&mut config).with_context(|| format!("Failed to update {}", config_path.display()))?;
+    fs::create_dir_all(project_dir.join(".cargo"))?;
+    fs::write(&config_path, config.to_string())?;
+    Ok(())
+}
+
+/// The table `key` of `config`, added when it's missing
+pub(super) fn config_table<'a>(config: &'a mut DocumentMut, key: &str, implicit: bool) -> Result<&'a mut Table> {
+    if config.get(key).is_none() {
+        let mut table = Table::new();
+        table.set_implicit(implicit);
+        config.insert(key, Item::Table(table));
+    }
+    config[key].as_table_mut().ok_or_else(|| anyhow!("[{}] is not a table", key))
+}
+
+/// The `build.target-dir` to write for `--target-dir`, relative to the project in `project_dir`
+///
+/// A relative `target_dir` is already relative to the project, which is how cargo reads it,
+/// so `../target` shares the target directory of a monorepo root. An absolute one is made
+/// relative to the project when it can be, so the setting survives moving the monorepo.
+/// The project directory itself and existing files are refused.
+pub fn project_target_dir(project_dir: &Path, target_dir: &str) -> Result<String> {
+    if target_dir.trim().is_empty() {
+        return Err(anyhow!("--target-dir needs a path"));
+    }
+    let project_dir = normalize(&std::path::absolute(project_dir)?);
+    let target = Path::new(target_dir);
+    let relative = if target.is_absolute() {
+        let target = normalize(target);
+        relative_to(&target, &project_dir).unwrap_or(target)
+    } else {
+        normalize(target)
+    };
+
+    if relative.as_os_str().is_empty() {
+        return Err(anyhow!("--target-dir {} is the project directory itself, pick a directory for the build output", target_dir));
+    }
+    let resolved = normalize(&project_dir.join(&relative));
+    if resolved.is_file() {
+        return Err(anyhow!("--target-dir {} is a file, not a directory", resolved.display()));
+    }
+
+    if relative.is_absolute() {
+        return Ok(relative.to_string_lossy().into_owned());
+    }
+    // Forward slashes work for cargo on every platform
+    Ok(relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Write `target_dir` as `[build] target-dir` to the project's `.cargo/config.toml`
+pub fn set_project_target_dir(project_dir: &Path, target_dir: &str) -> Result<()> {
+    edit_cargo_config(project_dir, |config| {
+        config_table(config, "build", false)?.insert("target-dir", value(target_dir));
+        Ok(())
+    })
+}
+
+/// `path` without `.` components and with `dir/..` pairs folded away, leading `..` kept
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `path` relative to `base`, both absolute and normalized; `None` on different roots
+fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    if path_components.peek() != base_components.peek() {
+        return None;
+    }
+    while path_components.peek().is_some() && path_components.peek() == base_components.peek() {
+        path_components.next();
+        base_components.next();
+    }
+    let mut relative: PathBuf = base_components.map(|_| Component::ParentDir).collect();
+    relative.extend(path_components);
+    Some(relative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_project_target_dir() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let monorepo = temp_dir.path();
+        let project = monorepo.join("services").join("api");
+
+        assert_eq!(project_target_dir(&project, "../../target")?, "../../target");
+        assert_eq!(project_target_dir(&project, "./build/../out")?, "out");
+        let absolute = monorepo.join("target");
+        assert_eq!(project_target_dir(&project, &absolute.to_string_lossy())?, "../../target");
+
+        assert!(project_target_dir(&project, "").is_err());
+        assert!(project_target_dir(&project, "out/..").is_err());
+        fs::write(monorepo.join("notes"), "")?;
+        assert!(project_target_dir(&project, "../../notes").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_project_target_dir() -> Result<()> {
+        let temp_dir = tempdir()?;
+        fs::create_dir_all(temp_dir.path().join(".cargo"))?;
+        fs::write(temp_dir.path().join(".cargo/config.toml"), "[build]\ntarget = \"wasm32-unknown-unknown\"\n")?;
+
+        set_project_target_dir(temp_dir.path(), "../target")?;
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(".cargo/config.toml"))?,
+            "[build]\ntarget = \"wasm32-unknown-unknown\"\ntarget-dir = \"../target\"\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+// Alternate crate registries for generated projects (`new --registry`)
+use anyhow::{anyhow, Context, Result};
+use ferrisup_common::cargo::{registry_configured, set_dependency_registry};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, DocumentMut};
+use walkdir::WalkDir;
+
+use super::cargo_config::{config_table, edit_cargo_config};
+use super::edition::SKIPPED_DIRS;
+
+/// Fail unless `name` can be used as a registry name, and cargo can find the registry
+///
+/// A registry cargo isn't configured for yet needs its index `url`, which then ends up in
+/// the project's `.cargo/config.toml`.
+pub fn validate_registry(name: &str, url: Option<&str>) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(anyhow!(
+            "Invalid registry name '{}', use only letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    match url {
+        Some(url) if !url.contains("://") => Err(anyhow!(
+            "Invalid registry index URL '{}', expected something like sparse+https://{}.example.com/index/",
+            url,
+            name
+        )),
+        Some(_) => Ok(()),
+        None if registry_configured(name, &std::env::current_dir()?) => Ok(()),
+        None => Err(anyhow!(
+            "Registry '{}' isn't in your cargo configuration. Pass its index with --registry-url so the project can use it",
+            name
+        )),
+    }
+}
+
+/// Point the dependencies of every manifest in `project_dir` at the registry `name`
+///
+/// With a `url`, the registry is also declared in the project's `.cargo/config.toml`,
+/// keeping what a template already configures there. Returns the manifests that changed.
+pub fn set_project_registry(project_dir: &Path, name: &str, url: Option<&str>) -> Result<Vec<PathBuf>> {
+    let manifests = WalkDir::new(project_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "Cargo.toml")
+        .map(|e| e.into_path());
+
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        let content = fs::read_to_string(&manifest)?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+        if !set_dependency_registry(&mut doc, name).is_empty() {
+            fs::write(&manifest, doc.to_string())?;
+            changed.push(manifest);
+        }
+    }
+
+    if let Some(url) = url {
+        edit_cargo_config(project_dir, |config| {
+            let mut registry = toml_edit::Table::new();
+            registry.insert("index", value(url));
+            config_table(config, "registries", true)?.insert(name, toml_edit::Item::Table(registry));
+            Ok(())
+        })?;
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_project_registry() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1.0\"\n")?;
+        fs::create_dir_all(root.join(".cargo"))?;
+        fs::write(root.join(".cargo/config.toml"), "[build]\ntarget = \"thumbv7em-none-eabihf\"\n")?;
+
+        let changed = set_project_registry(root, "internal", Some("sparse+https://crates.example.com/index/"))?;
+        assert_eq!(changed, vec![root.join("Cargo.toml")]);
+        let manifest = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(manifest.contains(r#"serde = { version = "1.0", registry = "internal" }"#), "{}", manifest);
+        let config = fs::read_to_string(root.join(".cargo/config.toml"))?;
+        assert_eq!(
+            config,
+            "[build]\ntarget = \"thumbv7em-none-eabihf\"\n\n[registries.internal]\nindex = \"sparse+https://crates.example.com/index/\"\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_registry() {
+        assert!(validate_registry("internal", Some("sparse+https://crates.example.com/index/")).is_ok());
+        assert!(validate_registry("in ternal", Some("https://crates.example.com/")).is_err());
+        assert!(validate_registry("internal", Some("crates.example.com")).is_err());
+    }
+}
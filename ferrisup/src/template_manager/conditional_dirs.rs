@@ -0,0 +1,63 @@
+// Template directories generated only under a condition, such as `server__if_ssr/`
+use anyhow::{anyhow, Result};
+use ferrisup_common::fs::render_template_str;
+use handlebars::Context;
+use serde_json::Value;
+
+/// Separates a directory's output name from the variable it depends on
+const CONDITION_MARKER: &str = "__if_";
+
+/// Split a conditional directory name such as `server__if_ssr` into the name it's
+/// generated as and the variable deciding whether it is, `("server", "ssr")`
+///
+/// `None` for directories without the marker, which are always generated.
+pub fn split_conditional_dir(dir_name: &str) -> Option<(&str, &str)> {
+    let (name, variable) = dir_name.split_once(CONDITION_MARKER)?;
+    let is_variable = !variable.is_empty() && variable.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (!name.is_empty() && is_variable).then_some((name, variable))
+}
+
+/// Whether the directory conditional on `variable` is generated, which is when
+/// `{{#if variable}}` would render its block with `template_vars`
+///
+/// An undefined variable, `false`, an empty string or an empty list leave it out. So do
+/// the strings `no` and `false`: yes/no options and `--set ssr=false` answer with those,
+/// which `{{#if}}` alone would take as true.
+pub fn conditional_dir_included(variable: &str, template_vars: &Value) -> Result<bool> {
+    if let Some(Value::String(answer)) = template_vars.get(variable) {
+        if matches!(answer.trim().to_lowercase().as_str(), "no" | "false") {
+            return Ok(false);
+        }
+    }
+    let predicate = format!("{{{{#if {}}}}}true{{{{/if}}}}", variable);
+    let rendered = render_template_str(&predicate, &Context::wraps(template_vars)?)
+        .map_err(|e| anyhow!("Failed to evaluate directory condition '{}': {}", variable, e))?;
+    Ok(rendered == "true")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_split_conditional_dir() {
+        assert_eq!(split_conditional_dir("server__if_ssr"), Some(("server", "ssr")));
+        assert_eq!(split_conditional_dir("mcu__if_use_embassy"), Some(("mcu", "use_embassy")));
+        assert_eq!(split_conditional_dir("server"), None);
+        assert_eq!(split_conditional_dir("__if_ssr"), None);
+        assert_eq!(split_conditional_dir("server__if_"), None);
+    }
+
+    #[test]
+    fn test_conditional_dir_included() -> Result<()> {
+        let vars = json!({ "ssr": true, "hydrate": false, "name": "", "charts": "yes", "tables": "no" });
+        assert!(conditional_dir_included("ssr", &vars)?);
+        assert!(!conditional_dir_included("hydrate", &vars)?);
+        assert!(!conditional_dir_included("name", &vars)?);
+        assert!(!conditional_dir_included("undefined", &vars)?);
+        assert!(conditional_dir_included("charts", &vars)?);
+        assert!(!conditional_dir_included("tables", &vars)?);
+        Ok(())
+    }
+}
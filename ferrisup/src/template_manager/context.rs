@@ -0,0 +1,118 @@
+// Settings and results of one generation, passed to `apply_template` and the helpers it calls
+use serde_json::{Map, Value};
+use std::path::Path;
+
+use crate::commands::progress::Progress;
+
+/// What `{{authors}}` renders to when the author isn't known
+const AUTHORS_PLACEHOLDER: &str = "Your Name <your.email@example.com>";
+
+/// The settings one project is generated with, and what applying its templates found out
+///
+/// Each generation has its own context, so projects can be generated one after another,
+/// or from several threads at once, without one generation's options leaking into the next.
+#[derive(Debug, Default)]
+pub struct GenerationContext {
+    /// Variables given with `--set`, which take precedence over prompts and defaults
+    pub variable_overrides: Map<String, Value>,
+    /// The author `{{author}}` renders to, such as `Ferris <ferris@example.com>`; empty when unknown
+    pub author: String,
+    /// Edition requested with `--edition`, checked against every template that gets applied
+    pub edition: Option<String>,
+    /// Whether `--minimal` was given, honored by the templates that support it
+    pub minimal: bool,
+    /// Whether the network must not be used, e.g. to clone remote templates
+    pub offline: bool,
+    /// Whether choices the options don't answer are prompted for rather than defaulted
+    pub interactive: bool,
+    /// The progress line reporting each file written, if one is shown
+    pub progress: Option<Progress>,
+    /// The variables the last applied template was rendered with
+    pub variables: Map<String, Value>,
+    /// The last template applied, after following redirects, such as the
+    /// `client/leptos/counter` a Leptos client was generated from
+    pub applied_template: Option<String>,
+    /// Commit of the git repository a remote template was copied from, `None` for local templates
+    pub template_commit: Option<String>,
+    /// Where the project is generated, as the next steps `cd` into it, such as `out/api`
+    /// for `--path out/api`; `None` when that's the directory named after the project
+    pub project_dir: Option<String>,
+}
+
+impl GenerationContext {
+    /// What `{{authors}}` renders to: the author, or a placeholder to fill in
+    pub fn authors(&self) -> String {
+        if self.author.is_empty() {
+            AUTHORS_PLACEHOLDER.to_string()
+        } else {
+            self.author.clone()
+        }
+    }
+
+    /// Merge the `--set` overrides into `variables`, overriding any value already present
+    pub fn apply_variable_overrides(&self, variables: Option<Value>) -> Option<Value> {
+        if self.variable_overrides.is_empty() {
+            return variables;
+        }
+
+        let mut merged = variables
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+        merged.extend(self.variable_overrides.clone());
+        Some(Value::Object(merged))
+    }
+
+    /// Keep `variables` as the ones the last template was rendered with, for generators
+    /// that write a project without `apply_template`
+    pub fn record_variables(&mut self, variables: &Value) {
+        if let Some(vars) = variables.as_object() {
+            self.variables = vars.clone();
+        }
+    }
+
+    /// The resolved variable `name` of the last template
+    pub fn variable(&self, name: &str) -> Option<&Value> {
+        self.variables.get(name)
+    }
+
+    /// The directory the next steps `cd` into: the project directory, or `project_name`
+    pub fn cd_dir<'a>(&'a self, project_name: &'a str) -> &'a str {
+        self.project_dir.as_deref().unwrap_or(project_name)
+    }
+
+    /// `step` with a `cd {{project_name}}` pointing at the project directory instead
+    pub fn resolve_cd(&self, step: &str, project_name: &str) -> String {
+        step.replace("cd {{project_name}}", &format!("cd {}", self.cd_dir(project_name)))
+    }
+
+    /// Report a generated file to the progress line, if one is shown
+    pub fn file_written(&self, path: &Path) {
+        if let Some(progress) = &self.progress {
+            progress.file_written(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_variable_overrides() {
+        let mut ctx = GenerationContext::default();
+        assert_eq!(ctx.apply_variable_overrides(None), None);
+
+        ctx.variable_overrides.insert("port".to_string(), json!(8080));
+        let merged = ctx.apply_variable_overrides(Some(json!({ "port": 3000, "db": "postgres" })));
+        assert_eq!(merged, Some(json!({ "port": 8080, "db": "postgres" })));
+    }
+
+    #[test]
+    fn test_authors() {
+        let mut ctx = GenerationContext::default();
+        assert_eq!(ctx.authors(), AUTHORS_PLACEHOLDER);
+        ctx.author = "Ferris <ferris@example.com>".to_string();
+        assert_eq!(ctx.authors(), "Ferris <ferris@example.com>");
+    }
+}
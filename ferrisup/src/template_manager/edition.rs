@@ -0,0 +1,167 @@
+// Rust edition selection for generated projects (`new --edition`)
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, DocumentMut, Item};
+use walkdir::WalkDir;
+
+use super::get_template_config;
+
+/// Editions `--edition` accepts
+pub const EDITIONS: &[&str] = &["2018", "2021", "2024"];
+
+/// Directories never searched for generated manifests
+pub(super) const SKIPPED_DIRS: &[&str] = &["target", "node_modules", ".git"];
+
+/// Fail unless `edition` is one of the known Rust editions
+pub fn validate_edition(edition: &str) -> Result<()> {
+    if EDITIONS.contains(&edition) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Unknown Rust edition '{}'. Valid editions are: {}",
+            edition,
+            EDITIONS.join(", ")
+        ))
+    }
+}
+
+/// Fail if `template_name` can't be generated with the `edition` requested with `--edition`
+///
+/// Templates whose code only compiles on some editions list them under `editions` in
+/// their `template.json`, with the reason in `edition_note`. Templates without the list
+/// support every edition, and so does every template when no edition was requested.
+pub fn check_requested_edition(template_name: &str, edition: Option<&str>) -> Result<()> {
+    let Some(edition) = edition else {
+        return Ok(());
+    };
+    let Ok(config) = get_template_config(template_name) else {
+        return Ok(());
+    };
+    let Some(supported) = config.get("editions").and_then(|e| e.as_array()) else {
+        return Ok(());
+    };
+    let supported: Vec<&str> = supported.iter().filter_map(|e| e.as_str()).collect();
+    if supported.contains(&edition) {
+        return Ok(());
+    }
+
+    let note = config
+        .get("edition_note")
+        .and_then(|n| n.as_str())
+        .map(|n| format!(": {}", n))
+        .unwrap_or_default();
+    Err(anyhow!(
+        "Template '{}' does not support Rust edition {} (supported: {}){}",
+        template_name,
+        edition,
+        supported.join(", "),
+        note
+    ))
+}
+
+/// Set `edition` in every `Cargo.toml` below `project_dir`, returning the manifests changed
+///
+/// Crates that inherit their edition from the workspace are left alone, the workspace's
+/// `[workspace.package]` edition is set instead. A workspace moving to 2024 also gets the
+/// matching resolver.
+pub fn set_project_edition(project_dir: &Path, edition: &str) -> Result<Vec<PathBuf>> {
+    validate_edition(edition)?;
+
+    let manifests = WalkDir::new(project_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "Cargo.toml")
+        .map(|e| e.into_path());
+
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        let content = fs::read_to_string(&manifest)?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+
+        if let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_like_mut()) {
+            // `edition.workspace = true` is a table, a plain string is the crate's own edition
+            let inherited = package.get("edition").is_some_and(|e| e.as_str().is_none());
+            if !inherited {
+                package.insert("edition", value(edition));
+            }
+        }
+        if let Some(workspace) = doc.get_mut("workspace").and_then(|w| w.as_table_like_mut()) {
+            if let Some(package) = workspace.get_mut("package").and_then(|p| p.as_table_like_mut()) {
+                if package.contains_key("edition") {
+                    package.insert("edition", value(edition));
+                }
+            }
+            if edition == "2024" && workspace.get("resolver").and_then(Item::as_str).is_some() {
+                workspace.insert("resolver", value("3"));
+            }
+        }
+
+        let updated = doc.to_string();
+        if updated != content {
+            fs::write(&manifest, updated)?;
+            changed.push(manifest);
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_project_edition_in_workspace() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n\n[workspace.package]\nedition = \"2021\"\n",
+        )?;
+        for (name, package) in [
+            ("app", "edition.workspace = true"),
+            ("core", "edition = \"2021\""),
+            ("old", "version = \"0.1.0\""),
+        ] {
+            let dir = root.join("crates").join(name);
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join("Cargo.toml"), format!("[package]\nname = \"{}\"\n{}\n", name, package))?;
+        }
+        fs::create_dir_all(root.join("target/package"))?;
+        fs::write(root.join("target/package/Cargo.toml"), "[package]\nname = \"vendored\"\nedition = \"2021\"\n")?;
+
+        let changed = set_project_edition(root, "2024")?;
+        assert_eq!(changed.len(), 3);
+
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert!(read("Cargo.toml").contains("resolver = \"3\""));
+        assert!(read("Cargo.toml").contains("edition = \"2024\""));
+        assert!(read("crates/app/Cargo.toml").contains("edition.workspace = true"));
+        assert!(read("crates/core/Cargo.toml").contains("edition = \"2024\""));
+        // A manifest without an edition would silently fall back to 2015
+        assert!(read("crates/old/Cargo.toml").contains("edition = \"2024\""));
+        assert!(read("target/package/Cargo.toml").contains("edition = \"2021\""));
+
+        assert!(set_project_edition(root, "2027").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_requested_edition() {
+        let err = check_requested_edition("data-science/polars-cli", Some("2024")).unwrap_err().to_string();
+        assert!(err.contains("does not support Rust edition 2024 (supported: 2018, 2021)"), "{}", err);
+        assert!(err.contains("`gen` is a reserved keyword"), "{}", err);
+        assert!(check_requested_edition("library", Some("2024")).is_ok());
+
+        assert!(check_requested_edition("data-science/polars-cli", Some("2021")).is_ok());
+        assert!(check_requested_edition("data-science/polars-cli", None).is_ok());
+    }
+}
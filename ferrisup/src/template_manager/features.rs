@@ -0,0 +1,104 @@
+// Cargo features enabled in generated projects (`new --features`)
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use toml_edit::{Array, DocumentMut, Item};
+
+use super::declared_variables;
+
+/// Add `features` to the `[features] default` list of the project generated in `project_dir`
+///
+/// Only features the generated `Cargo.toml` declares are accepted. A name that is a template
+/// variable rather than a cargo feature is pointed at `--set` instead.
+pub fn enable_features(project_dir: &Path, template_name: &str, features: &[String]) -> Result<()> {
+    if features.is_empty() {
+        return Ok(());
+    }
+
+    let manifest_path = project_dir.join("Cargo.toml");
+    let content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    let declared = declared_features(&doc);
+    for feature in features {
+        if declared.contains(feature) {
+            continue;
+        }
+        let valid = if declared.is_empty() {
+            format!("the {} template does not declare any cargo features", template_name)
+        } else {
+            format!("valid features are: {}", declared.join(", "))
+        };
+        let hint = if declared_variables(template_name).contains(feature) {
+            format!(". `{}` is a template variable, set it with --set {}=...", feature, feature)
+        } else {
+            String::new()
+        };
+        return Err(anyhow!("Unknown feature '{}': {}{}", feature, valid, hint));
+    }
+
+    let table = doc["features"]
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow!("[features] in {} is not a table", manifest_path.display()))?;
+    let mut default = table
+        .get("default")
+        .and_then(Item::as_array)
+        .cloned()
+        .unwrap_or_else(Array::new);
+    for feature in features {
+        if !default.iter().any(|f| f.as_str() == Some(feature.as_str())) {
+            default.push(feature.as_str());
+        }
+    }
+    table.insert("default", toml_edit::value(default));
+
+    fs::write(&manifest_path, doc.to_string())?;
+    Ok(())
+}
+
+/// The features a manifest declares, other than `default`
+fn declared_features(doc: &DocumentMut) -> Vec<String> {
+    doc.get("features")
+        .and_then(|f| f.as_table_like())
+        .map(|table| {
+            table
+                .iter()
+                .map(|(name, _)| name.to_string())
+                .filter(|name| name != "default")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_enable_features() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let manifest = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"report\"\n\n[features]\ndefault = [\"csv\"]\ncsv = []\nxlsx = [\"dep:rust_xlsxwriter\"]\n",
+        )?;
+
+        enable_features(temp_dir.path(), "data-science/polars-cli", &["xlsx".to_string(), "csv".to_string()])?;
+        assert!(fs::read_to_string(&manifest)?.contains("default = [\"csv\", \"xlsx\"]"));
+
+        let err = enable_features(temp_dir.path(), "data-science/polars-cli", &["visualization".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown feature 'visualization': valid features are: csv, xlsx"), "{}", err);
+        assert!(err.contains("--set visualization="), "{}", err);
+
+        fs::write(&manifest, "[package]\nname = \"app\"\n")?;
+        let err = enable_features(temp_dir.path(), "minimal", &["xlsx".to_string()]).unwrap_err().to_string();
+        assert!(err.contains("does not declare any cargo features"), "{}", err);
+        Ok(())
+    }
+}
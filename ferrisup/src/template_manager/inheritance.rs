@@ -0,0 +1,84 @@
+// Resolution of `extends` chains between template manifests
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Base templates a template inherits from, outermost base first
+///
+/// Follows the `extends` key of each manifest in turn, so for `app` extending
+/// `base/rust-bin` extending `base/rust` this returns `["base/rust", "base/rust-bin"]`.
+/// Files are rendered in that order with the extending template last, so the most
+/// specific template wins when several provide the same file.
+pub fn resolve_base_templates(
+    template_name: &str,
+    load_config: impl Fn(&str) -> Result<Value>,
+) -> Result<Vec<String>> {
+    let mut chain = vec![template_name.to_string()];
+    let mut config = load_config(template_name)?;
+
+    while let Some(base) = config.get("extends").and_then(|e| e.as_str()) {
+        let base = base.to_string();
+        if chain.contains(&base) {
+            chain.push(base);
+            return Err(anyhow!("Template inheritance cycle: {}", chain.join(" extends ")));
+        }
+
+        config = load_config(&base).map_err(|e| {
+            anyhow!(
+                "Template '{}' extends '{}', which could not be loaded: {}",
+                chain.last().unwrap(),
+                base,
+                e
+            )
+        })?;
+        chain.push(base);
+    }
+
+    Ok(chain.into_iter().skip(1).rev().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn loader(manifests: Vec<(&'static str, Value)>) -> impl Fn(&str) -> Result<Value> {
+        move |name| {
+            manifests
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, config)| config.clone())
+                .ok_or_else(|| anyhow!("Template '{}' not found", name))
+        }
+    }
+
+    #[test]
+    fn test_resolves_chain_outermost_first() {
+        let load = loader(vec![
+            ("app", json!({ "extends": "base/rust-bin" })),
+            ("base/rust-bin", json!({ "extends": "base/rust" })),
+            ("base/rust", json!({})),
+        ]);
+        assert_eq!(resolve_base_templates("app", &load).unwrap(), vec!["base/rust", "base/rust-bin"]);
+        assert!(resolve_base_templates("base/rust", &load).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_detects_cycles() {
+        let load = loader(vec![
+            ("a", json!({ "extends": "b" })),
+            ("b", json!({ "extends": "a" })),
+        ]);
+        let err = resolve_base_templates("a", &load).unwrap_err();
+        assert_eq!(err.to_string(), "Template inheritance cycle: a extends b extends a");
+
+        let load = loader(vec![("self", json!({ "extends": "self" }))]);
+        assert!(resolve_base_templates("self", &load).is_err());
+    }
+
+    #[test]
+    fn test_missing_base_is_reported() {
+        let load = loader(vec![("app", json!({ "extends": "base/missing" }))]);
+        let err = resolve_base_templates("app", &load).unwrap_err();
+        assert!(err.to_string().contains("extends 'base/missing'"));
+    }
+}
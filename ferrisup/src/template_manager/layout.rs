@@ -0,0 +1,397 @@
+// The file layout a template generates, worked out from its manifest without rendering it
+use anyhow::Result;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Variant sections of a manifest, in the order `apply_template` applies them, with the
+/// variant it uses when none is selected
+const VARIANT_SECTIONS: &[(&str, &str)] = &[("styling", "plain-css"), ("rendering", "csr")];
+
+/// Project name substituted into templated paths
+const PLACEHOLDER_PROJECT_NAME: &str = "example_project";
+
+/// A file in a template's layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutEntry {
+    /// Path relative to the project root, using `/` separators
+    pub path: String,
+    /// Option values that make the template generate this file, by variable name.
+    /// Empty for files that are always generated.
+    pub conditions: BTreeMap<String, Vec<String>>,
+}
+
+/// The files `template_name` generates for the option values in `selections`
+///
+/// Options that are not selected keep every branch, with the files annotated with the
+/// value that triggers them (`cloud_provider=aws`). Redirects that are not selected follow
+/// the option's default. Conditional files, variants (rendering, styling) and base templates
+/// are taken from the manifests; templates that list no files contribute their directory.
+pub fn template_layout(template_name: &str, selections: &Map<String, Value>) -> Result<Vec<LayoutEntry>> {
+    let mut layout = Layout::default();
+    collect(template_name, selections, &[], &mut layout)?;
+
+    Ok(layout
+        .files
+        .into_iter()
+        .map(|(path, conditions)| LayoutEntry {
+            path,
+            conditions: conditions.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Files by path; `None` marks a file generated regardless of the options
+#[derive(Default)]
+struct Layout {
+    files: BTreeMap<String, Option<BTreeMap<String, Vec<String>>>>,
+}
+
+impl Layout {
+    fn add(&mut self, path: &str, conditions: &[(String, String)]) {
+        let path = path.replace("{{project_name}}", PLACEHOLDER_PROJECT_NAME);
+        if conditions.is_empty() {
+            self.files.insert(path, None);
+            return;
+        }
+
+        let entry = self.files.entry(path).or_insert_with(|| Some(BTreeMap::new()));
+        if let Some(existing) = entry {
+            for (variable, value) in conditions {
+                let values = existing.entry(variable.clone()).or_default();
+                if !values.contains(value) {
+                    values.push(value.clone());
+                }
+            }
+        }
+    }
+
+    /// Add every file below `dir`, skipping the manifest and the `skip` directories
+    fn add_dir(&mut self, dir: &Path, skip: &[&str], conditions: &[(String, String)]) {
+        let walker = WalkDir::new(dir).into_iter().filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            !(entry.file_type().is_dir()
+                && (matches!(name.as_ref(), ".git" | ".github" | "target" | "node_modules")
+                    || skip.iter().any(|s| relative == Path::new(s))))
+        });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() || matches!(entry.file_name().to_str(), Some("template.json" | super::registry::USER_MANIFEST)) {
+                continue;
+            }
+            if let Ok(relative) = entry.path().strip_prefix(dir) {
+                // Conditional directories are listed under their output name, annotated with their variable
+                let mut conditions = conditions.to_vec();
+                let components: Vec<String> = relative
+                    .components()
+                    .map(|component| {
+                        let name = component.as_os_str().to_string_lossy();
+                        match super::split_conditional_dir(&name) {
+                            Some((name, variable)) => {
+                                conditions.push((variable.to_string(), "true".to_string()));
+                                name.to_string()
+                            }
+                            None => name.into_owned(),
+                        }
+                    })
+                    .collect();
+                let path = components.join("/");
+                self.add(path.trim_end_matches(".template"), &conditions);
+            }
+        }
+    }
+}
+
+fn collect(
+    template_name: &str,
+    selections: &Map<String, Value>,
+    conditions: &[(String, String)],
+    layout: &mut Layout,
+) -> Result<()> {
+    let config = super::get_template_config(template_name)?;
+    let template_dir = super::get_template_dir(template_name)?;
+
+    // A redirect replaces the template with the one for the selected value
+    let mut manifest = config.clone();
+    let mut conditions = conditions.to_vec();
+    if let Some(mut redirect) = config.get("redirect").cloned() {
+        while let Some(branches) = redirect.as_object().filter(|b| !b.contains_key("files")) {
+            let Some(value) = select_branch(&config, branches, selections, &conditions) else {
+                break;
+            };
+            if let Some(variable) = option_for_value(&config, &value, &conditions) {
+                conditions.push((variable, value.clone()));
+            }
+            redirect = branches[&value].clone();
+        }
+
+        match redirect {
+            Value::String(target) if !target.is_empty() => {
+                return collect(&target, selections, &conditions, layout);
+            }
+            Value::Object(_) => manifest = redirect,
+            _ => {}
+        }
+    }
+
+    for base in super::resolve_base_templates(template_name, super::get_template_config)? {
+        layout.add_dir(&super::get_template_dir(&base)?, &[], &conditions);
+    }
+
+    let variant_dirs: Vec<&str> = VARIANT_SECTIONS
+        .iter()
+        .filter_map(|(section, _)| config.get(*section).and_then(|v| v.as_object()))
+        .flat_map(|variants| variants.keys().map(|k| k.as_str()))
+        .collect();
+
+    let files = manifest.get("files").and_then(|f| f.as_array());
+    let conditional_files = manifest.get("conditional_files").and_then(|f| f.as_array());
+    let lists_files = files.is_some_and(|f| !f.is_empty()) || conditional_files.is_some_and(|f| !f.is_empty());
+
+    if !lists_files {
+        layout.add_dir(&template_dir, &variant_dirs, &conditions);
+    }
+
+    for file in files.into_iter().flatten() {
+        let Some(target) = file.get("target").and_then(|t| t.as_str()) else {
+            continue;
+        };
+        match file.get("condition").and_then(|c| c.as_str()).and_then(parse_condition) {
+            Some(condition) => add_if_selected(layout, target, &conditions, condition, selections),
+            None => layout.add(target, &conditions),
+        }
+    }
+
+    for group in conditional_files.into_iter().flatten() {
+        let Some(condition) = group.get("when").and_then(|w| w.as_str()).and_then(parse_condition) else {
+            continue;
+        };
+        for file in group.get("files").and_then(|f| f.as_array()).into_iter().flatten() {
+            if let Some(target) = file.get("target").and_then(|t| t.as_str()) {
+                add_if_selected(layout, target, &conditions, condition.clone(), selections);
+            }
+        }
+    }
+
+    for &(section, default) in VARIANT_SECTIONS {
+        if let Some(variants) = config.get(section).and_then(|v| v.as_object()) {
+            let selected = selections.get(section).and_then(|r| r.as_str());
+            add_variants(layout, &template_dir, (section, default), variants, selected, &conditions);
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply the renames, removals and extra files of the variants in one of the template's
+/// variant sections, given as the section name and its default variant
+fn add_variants(
+    layout: &mut Layout,
+    template_dir: &Path,
+    (section, default): (&str, &str),
+    variants: &Map<String, Value>,
+    selected: Option<&str>,
+    conditions: &[(String, String)],
+) {
+    let with_variant = |mode: &str| {
+        let mut conditions = conditions.to_vec();
+        conditions.push((section.to_string(), mode.to_string()));
+        conditions
+    };
+
+    for (mode, variant) in variants {
+        if selected.is_some_and(|s| s != mode) {
+            continue;
+        }
+
+        let renames = variant.get("rename").and_then(|r| r.as_array()).into_iter().flatten();
+        let removals = variant.get("remove").and_then(|r| r.as_array()).into_iter().flatten();
+        let moved: Vec<(&str, &str)> = renames
+            .filter_map(|r| Some((r.get("source")?.as_str()?, r.get("target")?.as_str()?)))
+            .collect();
+        let removed: Vec<&str> = removals.filter_map(|r| r.as_str()).collect();
+
+        for path in moved.iter().map(|(source, _)| *source).chain(removed.iter().copied()) {
+            if !layout.files.contains_key(path) {
+                continue;
+            }
+            layout.files.remove(path);
+            if selected.is_none() {
+                layout.add(path, &with_variant(default));
+            }
+        }
+        for (_, target) in &moved {
+            layout.add(target, &with_variant(mode));
+        }
+
+        let variant_dir = template_dir.join(mode);
+        if variant_dir.is_dir() {
+            let mut variant_layout = Layout::default();
+            variant_layout.add_dir(&variant_dir, &[], &[]);
+            for path in variant_layout.files.into_keys() {
+                if !layout.files.contains_key(&path) {
+                    layout.add(&path, &with_variant(mode));
+                }
+            }
+        }
+    }
+}
+
+/// Add `target` when `condition` holds for the selections, or when its variable is unselected
+fn add_if_selected(
+    layout: &mut Layout,
+    target: &str,
+    conditions: &[(String, String)],
+    condition: (String, String),
+    selections: &Map<String, Value>,
+) {
+    let (variable, value) = &condition;
+    match selections.get(variable).and_then(|v| v.as_str()) {
+        Some(selected) if selected != value => {}
+        _ => {
+            let mut conditions = conditions.to_vec();
+            conditions.push(condition);
+            layout.add(target, &conditions);
+        }
+    }
+}
+
+/// Parse a manifest condition such as `cloud_provider == "aws"`
+fn parse_condition(expr: &str) -> Option<(String, String)> {
+    let (variable, value) = expr.split_once("==")?;
+    let value = value.trim().trim_matches('\'').trim_matches('"');
+    Some((variable.trim().to_string(), value.to_string()))
+}
+
+/// The redirect branch to follow: a selected value, else the option's default, else the first
+fn select_branch(
+    config: &Value,
+    branches: &Map<String, Value>,
+    selections: &Map<String, Value>,
+    conditions: &[(String, String)],
+) -> Option<String> {
+    let selected = selections
+        .values()
+        .filter_map(|v| v.as_str())
+        .find(|value| branches.contains_key(*value));
+    if let Some(value) = selected {
+        return Some(value.to_string());
+    }
+
+    let default = branches.keys().find_map(|key| {
+        let variable = option_for_value(config, key, conditions)?;
+        let default = option_default(config, &variable)?;
+        branches.contains_key(&default).then_some(default)
+    });
+    default.or_else(|| branches.keys().next().cloned())
+}
+
+/// The option declaring `value` among its choices, in either manifest option format
+///
+/// Options that only apply under a `condition` (such as the provider for one edge type)
+/// are only considered when that condition is among `conditions`.
+fn option_for_value(config: &Value, value: &str, conditions: &[(String, String)]) -> Option<String> {
+    let applies = |option: &Value| match option.get("condition").and_then(|c| c.as_str()) {
+        Some(condition) => parse_condition(condition).is_some_and(|c| conditions.contains(&c)),
+        None => true,
+    };
+    let has_value = |option: &Value| {
+        let choices = option.get("options").or_else(|| option.get("values"));
+        choices.and_then(|c| c.as_array()).is_some_and(|choices| {
+            choices
+                .iter()
+                .any(|c| c.as_str().or_else(|| c.get("value").and_then(|v| v.as_str())) == Some(value))
+        })
+    };
+
+    match config.get("options")? {
+        Value::Array(options) => options
+            .iter()
+            .find(|o| applies(o) && has_value(o))
+            .and_then(|o| o.get("name").and_then(|n| n.as_str()))
+            .map(|n| n.to_string()),
+        Value::Object(options) => options
+            .iter()
+            .find(|(_, o)| applies(o) && has_value(o))
+            .map(|(name, _)| name.clone()),
+        _ => None,
+    }
+}
+
+fn option_default(config: &Value, variable: &str) -> Option<String> {
+    let option = match config.get("options")? {
+        Value::Array(options) => options
+            .iter()
+            .find(|o| o.get("name").and_then(|n| n.as_str()) == Some(variable))?,
+        Value::Object(options) => options.get(variable)?,
+        _ => return None,
+    };
+    option.get("default").and_then(|d| d.as_str()).map(|d| d.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn find<'a>(layout: &'a [LayoutEntry], path: &str) -> Option<&'a LayoutEntry> {
+        layout.iter().find(|e| e.path == path)
+    }
+
+    #[test]
+    fn test_conditional_files_are_annotated() -> Result<()> {
+        let layout = template_layout("serverless", &Map::new())?;
+        let main_rs = find(&layout, "src/main.rs").expect("src/main.rs should be listed");
+        assert!(main_rs.conditions["cloud_provider"].contains(&"aws".to_string()));
+        assert!(main_rs.conditions["cloud_provider"].contains(&"gcp".to_string()));
+        assert_eq!(find(&layout, "template.yml").unwrap().conditions["cloud_provider"], vec!["aws"]);
+
+        // Selecting a provider drops the other providers' files
+        let selections = json!({ "cloud_provider": "gcp" }).as_object().unwrap().clone();
+        let layout = template_layout("serverless", &selections)?;
+        assert!(find(&layout, "template.yml").is_none());
+        assert!(find(&layout, "Dockerfile").is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rendering_variants_are_annotated() -> Result<()> {
+        let layout = template_layout("client/leptos/counter", &Map::new())?;
+        assert!(find(&layout, "Cargo.toml").unwrap().conditions.is_empty());
+        assert_eq!(find(&layout, "index.html").unwrap().conditions["rendering"], vec!["csr"]);
+        assert_eq!(find(&layout, "src/app.rs").unwrap().conditions["rendering"], vec!["ssr"]);
+        assert!(find(&layout, "ssr/Cargo.toml").is_none());
+        assert_eq!(find(&layout, "style.css").unwrap().conditions["styling"], vec!["plain-css"]);
+        assert_eq!(find(&layout, "input.css").unwrap().conditions["styling"], vec!["tailwind"]);
+        assert!(find(&layout, "tailwind/input.css").is_none());
+
+        let selections = json!({ "rendering": "ssr" }).as_object().unwrap().clone();
+        let layout = template_layout("client/leptos/counter", &selections)?;
+        assert!(find(&layout, "index.html").is_none());
+        assert!(find(&layout, "src/app.rs").is_some());
+        // The variant's own files are overlaid on the renamed ones
+        assert!(find(&layout, "src/lib.rs").is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_redirects_follow_the_selection() -> Result<()> {
+        let selections = json!({ "application_type": "api-function", "provider": "fastly" })
+            .as_object()
+            .unwrap()
+            .clone();
+        let layout = template_layout("edge", &selections)?;
+        let fastly_toml = find(&layout, "fastly.toml").expect("fastly.toml should be listed");
+        assert_eq!(fastly_toml.conditions["edge_type"], vec!["api-function"]);
+        assert_eq!(fastly_toml.conditions["api_function_provider"], vec!["fastly"]);
+
+        // Unselected redirects follow the defaults
+        let layout = template_layout("edge", &Map::new())?;
+        let lib_rs = find(&layout, "src/lib.rs").expect("src/lib.rs should be listed");
+        assert_eq!(lib_rs.conditions["edge_type"], vec!["static-site"]);
+        assert_eq!(lib_rs.conditions["static_site_provider"], vec!["cloudflare"]);
+        Ok(())
+    }
+}
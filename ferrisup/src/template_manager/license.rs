@@ -0,0 +1,157 @@
+// License selection for generated projects (`new --license`)
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, DocumentMut};
+use walkdir::WalkDir;
+
+/// SPDX expressions `--license` accepts
+pub const LICENSES: &[&str] = &["MIT", "Apache-2.0", "BSD-3-Clause", "MPL-2.0", "MIT OR Apache-2.0"];
+
+/// Directories never searched for generated manifests
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", ".git"];
+
+const MIT: &str = include_str!("licenses/MIT.txt");
+const APACHE: &str = include_str!("licenses/Apache-2.0.txt");
+const BSD_3_CLAUSE: &str = include_str!("licenses/BSD-3-Clause.txt");
+const MPL: &str = include_str!("licenses/MPL-2.0.txt");
+
+/// The supported SPDX expression `license` names, ignoring case
+///
+/// The dual license is also accepted as `Apache-2.0 OR MIT` and the older `MIT/Apache-2.0`.
+pub fn validate_license(license: &str) -> Result<&'static str> {
+    let normalized = license.trim().to_ascii_uppercase().replace('/', " OR ");
+    let dual = ["MIT OR APACHE-2.0", "APACHE-2.0 OR MIT"];
+    if dual.contains(&normalized.as_str()) {
+        return Ok("MIT OR Apache-2.0");
+    }
+    LICENSES
+        .iter()
+        .find(|id| id.to_ascii_uppercase() == normalized)
+        .copied()
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown license '{}'. Supported SPDX identifiers are: {}",
+                license,
+                LICENSES.join(", ")
+            )
+        })
+}
+
+/// The files `license` is written to, as (file name, text)
+///
+/// The dual license gets the usual `LICENSE-MIT` and `LICENSE-APACHE` pair.
+fn license_files(license: &str) -> Vec<(&'static str, &'static str)> {
+    match license {
+        "MIT" => vec![("LICENSE", MIT)],
+        "Apache-2.0" => vec![("LICENSE", APACHE)],
+        "BSD-3-Clause" => vec![("LICENSE", BSD_3_CLAUSE)],
+        "MPL-2.0" => vec![("LICENSE", MPL)],
+        _ => vec![("LICENSE-MIT", MIT), ("LICENSE-APACHE", APACHE)],
+    }
+}
+
+/// Write the text of `license` to `project_dir`, returning the files written
+///
+/// `{{author}}` and `{{year}}` in the copyright lines are filled in with the holder
+/// and year given.
+pub fn write_license(project_dir: &Path, license: &str, author: &str, year: i32) -> Result<Vec<PathBuf>> {
+    let license = validate_license(license)?;
+    let mut written = Vec::new();
+    for (file_name, text) in license_files(license) {
+        let path = project_dir.join(file_name);
+        let text = text.replace("{{author}}", author).replace("{{year}}", &year.to_string());
+        fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Set `license` in every `Cargo.toml` below `project_dir`, returning the manifests changed
+///
+/// Crates that inherit their license from the workspace are left alone, the workspace's
+/// `[workspace.package]` gets it instead.
+pub fn set_project_license(project_dir: &Path, license: &str) -> Result<Vec<PathBuf>> {
+    let license = validate_license(license)?;
+
+    let manifests = WalkDir::new(project_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "Cargo.toml")
+        .map(|e| e.into_path());
+
+    let mut changed = Vec::new();
+    for manifest in manifests {
+        let content = fs::read_to_string(&manifest)?;
+        let mut doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+
+        if let Some(package) = doc.get_mut("package").and_then(|p| p.as_table_like_mut()) {
+            // `license.workspace = true` is a table, a plain string is the crate's own license
+            let inherited = package.get("license").is_some_and(|l| l.as_str().is_none());
+            if !inherited {
+                package.insert("license", value(license));
+            }
+        }
+        if let Some(package) = doc
+            .get_mut("workspace")
+            .and_then(|w| w.get_mut("package"))
+            .and_then(|p| p.as_table_like_mut())
+        {
+            package.insert("license", value(license));
+        }
+
+        let updated = doc.to_string();
+        if updated != content {
+            fs::write(&manifest, updated)?;
+            changed.push(manifest);
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_license() {
+        assert_eq!(validate_license("mit").unwrap(), "MIT");
+        assert_eq!(validate_license("Apache-2.0 OR MIT").unwrap(), "MIT OR Apache-2.0");
+        assert_eq!(validate_license("MIT/Apache-2.0").unwrap(), "MIT OR Apache-2.0");
+        let error = validate_license("GPL-3.0").unwrap_err().to_string();
+        assert!(error.contains("Unknown license 'GPL-3.0'"), "{}", error);
+        assert!(error.contains("BSD-3-Clause"), "{}", error);
+    }
+
+    #[test]
+    fn test_write_and_set_license() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+        )?;
+
+        let written = write_license(root, "MIT", "Ferris Crab", 2025)?;
+        assert_eq!(written, vec![root.join("LICENSE")]);
+        let text = fs::read_to_string(root.join("LICENSE"))?;
+        assert!(text.starts_with("MIT License\n\nCopyright (c) 2025 Ferris Crab\n"), "{}", text);
+
+        set_project_license(root, "BSD-3-Clause")?;
+        let manifest = fs::read_to_string(root.join("Cargo.toml"))?;
+        assert!(manifest.contains("license = \"BSD-3-Clause\""), "{}", manifest);
+
+        let written = write_license(root, "MIT OR Apache-2.0", "Ferris Crab", 2025)?;
+        assert_eq!(written, vec![root.join("LICENSE-MIT"), root.join("LICENSE-APACHE")]);
+        assert!(fs::read_to_string(root.join("LICENSE-APACHE"))?.contains("Version 2.0, January 2004"));
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+// Lean generation without tutorial comments and example code (`new --minimal`)
+use anyhow::{anyhow, Result};
+
+use super::get_template_config;
+
+/// Start of a block only generated with `--minimal`
+const MINIMAL_START: &str = "{{#minimal}}";
+/// Start of a block left out with `--minimal`, such as tutorial comments and demo code
+const FULL_START: &str = "{{^minimal}}";
+const BLOCK_END: &str = "{{/minimal}}";
+
+/// Whether `template_name` declares `supports_minimal = true` in its manifest
+pub fn supports_minimal(template_name: &str) -> bool {
+    get_template_config(template_name)
+        .ok()
+        .and_then(|config| config.get("supports_minimal")?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Keep the `{{#minimal}}` blocks of `content` and drop its `{{^minimal}}` blocks when
+/// `minimal`, the other way round otherwise
+///
+/// Markers go on lines of their own, which are removed along with them; blocks end with
+/// `{{/minimal}}` and can't be nested.
+pub fn strip_minimal_blocks(content: &str, minimal: bool) -> Result<String> {
+    if !content.contains("minimal}}") {
+        return Ok(content.to_string());
+    }
+
+    let mut result = String::with_capacity(content.len());
+    // Whether the lines of the open block are kept, `None` outside a block
+    let mut block: Option<bool> = None;
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let marker = line.trim();
+        match (marker, block) {
+            (MINIMAL_START, None) => block = Some(minimal),
+            (FULL_START, None) => block = Some(!minimal),
+            (BLOCK_END, Some(_)) => block = None,
+            (MINIMAL_START | FULL_START, Some(_)) => {
+                return Err(anyhow!("line {}: {} and {} blocks can't be nested", index + 1, MINIMAL_START, FULL_START))
+            }
+            (BLOCK_END, None) => return Err(anyhow!("line {}: {} without an open block", index + 1, BLOCK_END)),
+            _ if [MINIMAL_START, FULL_START, BLOCK_END].iter().any(|m| line.contains(m)) => {
+                return Err(anyhow!("line {}: minimal markers must be on a line of their own", index + 1))
+            }
+            (_, Some(false)) => {}
+            _ => result.push_str(line),
+        }
+    }
+    if block.is_some() {
+        return Err(anyhow!("a {} or {} block is missing its {}", MINIMAL_START, FULL_START, BLOCK_END));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_minimal_blocks() -> Result<()> {
+        let content = "//! Crate docs\n{{^minimal}}\n// Try changing the greeting!\nfn demo() {}\n{{/minimal}}\n  {{#minimal}}\nfn lean() {}\n  {{/minimal}}\nfn main() {}\n";
+        assert_eq!(strip_minimal_blocks(content, true)?, "//! Crate docs\nfn lean() {}\nfn main() {}\n");
+        assert_eq!(
+            strip_minimal_blocks(content, false)?,
+            "//! Crate docs\n// Try changing the greeting!\nfn demo() {}\nfn main() {}\n"
+        );
+        assert_eq!(strip_minimal_blocks("fn main() {}", true)?, "fn main() {}");
+
+        assert!(strip_minimal_blocks("{{^minimal}}\nfn demo() {}\n", true).is_err());
+        assert!(strip_minimal_blocks("{{^minimal}}\n{{#minimal}}\n{{/minimal}}\n", true).is_err());
+        assert!(strip_minimal_blocks("let x = 1; {{^minimal}}\n{{/minimal}}\n", true).is_err());
+        Ok(())
+    }
+}
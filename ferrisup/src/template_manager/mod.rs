@@ -5,22 +5,65 @@ use std::fs;
 use std::io::{self, Write, BufRead};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::{Arc, RwLock};
 use serde_json::{Value, json, Map};
 use handlebars::{Handlebars, Helper, Context, RenderContext, Output, RenderError};
 use colored::Colorize;
 use dialoguer::Select;
-use lazy_static::lazy_static;
 use walkdir::WalkDir;
 use regex::Regex;
 // Cross-platform file permission handling
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use ferrisup_common::to_pascal_case;
-
-lazy_static! {
-    static ref CURRENT_VARIABLES: Arc<RwLock<Map<String, Value>>> = Arc::new(RwLock::new(Map::new()));
-}
+use ferrisup_common::fs::{render_template_str, write_atomic};
+use crate::commands::{log_file, offline};
+use crate::commands::verbosity::{is_verbose, status_stdio};
+use crate::status;
+use crate::core::retry::{retry, DEFAULT_ATTEMPTS, DEFAULT_BACKOFF};
+
+mod answers;
+mod cargo_config;
+mod cargo_registry;
+mod conditional_dirs;
+mod context;
+mod edition;
+mod features;
+mod inheritance;
+mod layout;
+mod license;
+mod minimal;
+mod model_io;
+mod normalize;
+mod permissions;
+mod profile;
+mod readme;
+mod registry;
+mod rust_version;
+mod validation;
+mod version;
+use conditional_dirs::{conditional_dir_included, split_conditional_dir};
+use inheritance::resolve_base_templates;
+use normalize::normalize_rendered;
+pub use answers::{load_answers, Answers};
+pub use cargo_config::{project_target_dir, set_project_target_dir};
+pub use cargo_registry::{set_project_registry, validate_registry};
+pub use context::GenerationContext;
+pub use edition::{check_requested_edition, set_project_edition, validate_edition, EDITIONS};
+pub use features::enable_features;
+pub use rust_version::{check_rust_version, check_rust_version_against, required_rust_version, rust_version_requirements};
+pub use layout::{template_layout, LayoutEntry};
+pub use license::{set_project_license, validate_license, write_license, LICENSES};
+pub use minimal::{strip_minimal_blocks, supports_minimal};
+pub use permissions::apply_permissions;
+pub use profile::{set_project_profile, template_profile, validate_profile, PROFILES};
+pub use readme::{remove_readme, write_readme};
+pub use registry::{
+    find_user_template, merge_user_templates, read_user_manifest, scan_user_templates, user_templates_dir,
+    validate_user_template,
+    InvalidTemplate, UserTemplate, AUTHORING_GUIDE, USER_MANIFEST,
+};
+pub use validation::{declared_variables, template_variables, TemplateVariable, validate_project_name, validate_template_variables, validate_variable_overrides};
+pub use version::{check_template_version, repository_commit, template_version};
 
 pub fn get_template(name: &str) -> Result<String> {
     let templates = get_all_templates()?;
@@ -33,12 +76,12 @@ pub fn get_template(name: &str) -> Result<String> {
         if template_json.exists() {
             Ok(name.to_string())
         } else {
-            println!("⚠️ Template '{}' does not have a valid configuration. Using minimal template instead.", name);
+            status!("⚠️ Template '{}' does not have a valid configuration. Using minimal template instead.", name);
             Ok("minimal".to_string())
         }
     } else {
         // Fall back to minimal if template not found
-        println!("⚠️ Template '{}' not found. Using minimal template instead.", name);
+        status!("⚠️ Template '{}' not found. Using minimal template instead.", name);
         Ok("minimal".to_string())
     }
 }
@@ -53,6 +96,7 @@ pub fn get_all_templates() -> Result<Vec<String>> {
         "client".to_string(),
         "serverless".to_string(),
         "data-science".to_string(),
+        "minimal-workspace".to_string(),
     ];
     
     Ok(templates)
@@ -70,9 +114,10 @@ pub fn list_templates() -> Result<Vec<(String, String)>> {
         ("client".to_string(), "Frontend web application (Dioxus, Tauri, or Leptos)".to_string()),
         ("serverless".to_string(), "Serverless function (AWS Lambda, Cloudflare Workers, etc.)".to_string()),
         ("data-science".to_string(), "Data science and machine learning projects".to_string()),
+        ("minimal-workspace".to_string(), "Cargo workspace with an app binary, a core library and a shared crate".to_string()),
     ];
     
-    Ok(templates)
+    Ok(registry::merge_user_templates(templates))
 }
 
 /// Get data science templates with descriptions
@@ -84,11 +129,24 @@ pub fn list_data_science_templates() -> Result<Vec<(String, String)>> {
         // Machine Learning
         ("data-science/linfa-examples".to_string(), "Machine Learning: Working examples with Linfa 0.7.1 (classification, regression, clustering)".to_string()),
         ("data-science/rustlearn-examples".to_string(), "Machine Learning: Simple ML examples with rustlearn (classification, regression, clustering)".to_string()),
+
+        // Deep Learning
+        ("data-science/candle-mnist".to_string(), "Candle - MNIST Digit Recognition: Train a small neural network with Candle".to_string()),
     ])
 }
 
 /// Apply a template to a target directory
-pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str, variables: Option<Value>) -> Result<()> {
+///
+/// The settings of the generation are read from `ctx`, which also records the template
+/// applied after redirects, the variables it was rendered with and, for remote templates,
+/// the commit it was copied from.
+pub fn apply_template(ctx: &mut GenerationContext, template_name: &str, target_dir: &Path, project_name: &str, variables: Option<Value>) -> Result<()> {
+    let variables = ctx.apply_variable_overrides(variables);
+
+    // Refuse before writing anything if the template's code needs another edition or a newer Rust
+    check_requested_edition(template_name, ctx.edition.as_deref())?;
+    check_rust_version(template_name)?;
+
     // Get the template configuration
     let template_config = get_template_config(template_name)?;
     
@@ -103,7 +161,7 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
                         if var_value.is_string() {
                             if let Some(redirect_path) = value.as_str().filter(|p| !p.is_empty()) {
                                 // Apply the redirected template instead
-                                return apply_template(redirect_path, target_dir, project_name, variables);
+                                return apply_template(ctx, redirect_path, target_dir, project_name, variables);
                             }
                         }
                     }
@@ -114,30 +172,18 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
     
     // Process the template files
     let template_dir = get_template_dir(template_name)?;
-    
-    // Register handlebars helpers
-    let mut handlebars = Handlebars::new();
-    handlebars.register_escape_fn(handlebars::no_escape);
-    
-    // Register the eq helper for conditional checks
-    handlebars.register_helper("eq", Box::new(|h: &handlebars::Helper<'_, '_>, 
-        _: &handlebars::Handlebars<'_>, 
-        _: &handlebars::Context, 
-        _: &mut handlebars::RenderContext<'_, '_>, 
-        out: &mut dyn handlebars::Output| 
-    -> handlebars::HelperResult {
-        let param1 = h.param(0).unwrap().value();
-        let param2 = h.param(1).unwrap().value();
-        out.write(&(param1 == param2).to_string())?;
-        Ok(())
-    }));
+    ctx.applied_template = Some(template_name.to_string());
     
     // Prepare template variables
     let mut template_vars = json!({
         "project_name": project_name,
         "project_name_pascal_case": to_pascal_case(project_name),
         "project_name_snake_case": project_name.replace("-", "_"),
-        "project_name_kebab_case": project_name.replace("_", "-")
+        "project_name_kebab_case": project_name.replace("_", "-"),
+        "crate_name": project_name.replace("-", "_"),
+        "author": ctx.author,
+        "authors": ctx.authors(),
+        "minimal": ctx.minimal && supports_minimal(template_name)
     });
     
     // Add user-provided variables
@@ -151,9 +197,30 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
         }
     }
     
+    // Fill in the template's own `variables` that weren't answered, rendering derived ones
+    // (e.g. the polars `data_format` computed from `data_source`) from the answers
+    if let Some(defaults) = template_config.get("variables").and_then(|v| v.as_object()) {
+        for (name, value) in defaults {
+            if template_vars.get(name).is_some() {
+                continue;
+            }
+            let value = match value.as_str() {
+                Some(expr) if expr.contains("{{") => json!(render_template_str(expr, &Context::wraps(&template_vars)?)
+                    .map_err(|e| anyhow!("Failed to derive template variable '{}': {}", name, e))?),
+                _ => value.clone(),
+            };
+            if let Some(obj_mut) = template_vars.as_object_mut() {
+                obj_mut.insert(name.clone(), value);
+            }
+        }
+    }
+
+    // Fail before writing anything if a variable cannot be rendered into valid Rust
+    validate_template_variables(template_name, project_name, Some(&template_vars))?;
+
     // Debug output only when in verbose mode
-    if std::env::var("FERRISUP_VERBOSE").is_ok() {
-        println!("Template variables: {}", serde_json::to_string_pretty(&template_vars).unwrap_or_default());
+    if is_verbose() {
+        status!("Template variables: {}", serde_json::to_string_pretty(&template_vars).unwrap_or_default());
     }
     
     // Skip data science template-specific prompts as they're now handled in the new.rs file
@@ -164,7 +231,7 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
         
         // Special handling for linfa-examples which has unique prompts
         if template_name == "data-science/linfa-examples" {
-            println!("\n{}", "Linfa Machine Learning Examples Configuration:".bold());
+            status!("\n{}", "Linfa Machine Learning Examples Configuration:".bold());
             
             // Focus only on data source, which is the meaningful distinction
             let data_source = prompt_with_options(
@@ -182,7 +249,7 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
                 additional_vars.insert("data_format".to_string(), json!(data_format));
             }
             
-            println!("\n✅ Linfa machine learning examples configured successfully!");
+            status!("\n✅ Linfa machine learning examples configured successfully!");
         } else if template_name == "data-science/burn-value-prediction" || 
                   template_name == "data-science/burn-text-classifier" || 
                   template_name == "data-science/burn-data-predictor" ||
@@ -197,43 +264,61 @@ pub fn apply_template(template_name: &str, target_dir: &Path, project_name: &str
                 _ => "mnist", // Default fallback
             };
             
-            println!("\n{}", format!("Setting up {} project...", template_name.replace("data-science/burn-", "")).bold());
+            status!("\n{}", format!("Setting up {} project...", template_name.replace("data-science/burn-", "")).bold());
             
             // Use direct clone and copy approach which is more reliable
-            println!("Generating project from Burn example: {}", burn_example);
+            status!("Generating project from Burn example: {}", burn_example);
             
             // Create a temporary directory for the Burn repository
             let burn_repo_dir = std::env::temp_dir().join("burn-repo");
             if !burn_repo_dir.exists() {
                 // Clone the Burn repository if it doesn't exist
-                println!("Cloning Burn repository (this may take a moment)...");
-                let clone_result = std::process::Command::new("git")
-                    .args([
-                        "clone",
-                        "--depth=1",
-                        "https://github.com/tracel-ai/burn.git",
-                        burn_repo_dir.to_str().unwrap()
-                    ])
-                    .status()?;
-                    
-                if !clone_result.success() {
-                    return Err(anyhow!("Failed to clone the Burn repository"));
-                }
+                offline::ensure_online_when(ctx.offline, "clone the Burn repository")?;
+                status!("Cloning Burn repository (this may take a moment)...");
+                retry(DEFAULT_ATTEMPTS, DEFAULT_BACKOFF, || {
+                    // A failed clone can leave a partial checkout behind
+                    let _ = std::fs::remove_dir_all(&burn_repo_dir);
+                    let clone_result = std::process::Command::new("git")
+                        .args([
+                            "clone",
+                            "--depth=1",
+                            "https://github.com/tracel-ai/burn.git",
+                            burn_repo_dir.to_str().unwrap()
+                        ])
+                        .stdout(status_stdio())
+                        .status()?;
+                    if !clone_result.success() {
+                        return Err(anyhow!("Failed to clone the Burn repository"));
+                    }
+                    Ok(())
+                })?;
+            } else if ctx.offline {
+                status!("Offline mode: using the existing copy of the Burn repository");
             } else {
                 // Pull the latest changes if the repo already exists
-                println!("Updating Burn repository...");
-                let pull_result = std::process::Command::new("git")
-                    .args([
-                        "pull"
-                    ])
-                    .current_dir(&burn_repo_dir)
-                    .status()?;
-                
-                if !pull_result.success() {
-                    println!("Warning: Failed to update the Burn repository, using existing version");
+                status!("Updating Burn repository...");
+                let pulled = retry(DEFAULT_ATTEMPTS, DEFAULT_BACKOFF, || {
+                    let pull_result = std::process::Command::new("git")
+                        .args([
+                            "pull"
+                        ])
+                        .current_dir(&burn_repo_dir)
+                        .stdout(status_stdio())
+                        .status()?;
+                    if !pull_result.success() {
+                        return Err(anyhow!("git pull failed"));
+                    }
+                    Ok(())
+                });
+
+                if pulled.is_err() {
+                    status!("Warning: Failed to update the Burn repository, using existing version");
                 }
             }
             
+            // The project is pinned to the commit it was copied from, the repository has no versions
+            ctx.template_commit = repository_commit(&burn_repo_dir);
+
             // Check if the example exists
             let example_dir = burn_repo_dir.join("examples").join(burn_example);
             if !example_dir.exists() {
@@ -325,13 +410,13 @@ This project was generated using FerrisUp.
 "#, project_name);
             std::fs::write(&readme_path, readme_content)?;
             
-            println!("\n✅ Project generated successfully from the Burn {} example!", burn_example);
-            println!("📝 Check the README.md file in your project directory for more information.");
+            status!("\n✅ Project generated successfully from the Burn {} example!", burn_example);
+            status!("📝 Check the README.md file in your project directory for more information.");
             
             // Display custom next steps for the Burn example
-            let next_steps = get_burn_example_next_steps(burn_example, project_name);
+            let next_steps = get_burn_example_next_steps(burn_example, ctx.cd_dir(project_name));
             for step in next_steps {
-                println!("  {}", step);
+                status!("  {}", step);
             }
             
             // Skip the regular template application since we used direct copy
@@ -394,7 +479,7 @@ This project was generated using FerrisUp.
                         .interact()?;
                         
                     let selected = options[selection];
-                    println!("Using {} as the {}", selected, name);
+                    status!("Using {} as the {}", selected, name);
                     vars.insert(name.to_string(), json!(selected));
                 } else if option_type == "input" {
                     let default = option_obj.get("default").map(|d| d.as_str().unwrap()).unwrap_or("");
@@ -411,6 +496,24 @@ This project was generated using FerrisUp.
         }
     }
     
+    // Keep the answers for callers that report what was generated
+    ctx.record_variables(&template_vars);
+    for (name, value) in template_vars.as_object().into_iter().flatten() {
+        log::debug!(
+            target: "ferrisup::template",
+            template = template_name, variable = name.as_str(), value:% = log_file::loggable_value(name, value);
+            "Resolved template variable"
+        );
+    }
+
+    // Render the templates this one extends first, so its own files win on conflict
+    for base in resolve_base_templates(template_name, get_template_config)? {
+        if is_verbose() {
+            status!("Rendering base template: {}", base);
+        }
+        process_template_directory(ctx, &get_template_dir(&base)?, target_dir, &template_vars)?;
+    }
+
     // Process conditional files if present
     if let Some(conditional_files) = template_config.get("conditional_files") {
         if let Some(conditional_files_array) = conditional_files.as_array() {
@@ -461,18 +564,17 @@ This project was generated using FerrisUp.
                                                     
                                                     if is_binary {
                                                         // For binary files, just copy them directly without template processing
-                                                        fs::copy(&source_file, &target_file)?;
+                                                        copy_file(ctx, &source_file, &target_file)?;
                                                     } else {
                                                         // For text files, apply template processing
                                                         let content = fs::read_to_string(&source_file)
                                                             .map_err(|e| anyhow!("Failed to read source file {}: {}", source_file.display(), e))?;
                                                         
                                                         // Apply template variables
-                                                        let rendered = handlebars.render_template(&content, &template_vars)
-                                                            .map_err(|e| anyhow!("Failed to render template: {}", e))?;
+                                                        let rendered = render_file(&content, &template_vars, &source_file)?;
                                                         
                                                         // Write to target
-                                                        fs::write(&target_file, rendered)?;
+                                                        write_file(ctx, &target_file, rendered)?;
                                                     }
                                                 }
                                             }
@@ -552,18 +654,17 @@ This project was generated using FerrisUp.
                     
                     if is_binary {
                         // For binary files, just copy them directly without template processing
-                        fs::copy(&source_file, &target_file)?;
+                        copy_file(ctx, &source_file, &target_file)?;
                     } else {
                         // For text files, apply template processing
                         let content = fs::read_to_string(&source_file)
                             .map_err(|e| anyhow!("Failed to read source file {}: {}", source_file.display(), e))?;
                         
                         // Apply template variables
-                        let rendered = handlebars.render_template(&content, &template_vars)
-                            .map_err(|e| anyhow!("Failed to render template: {}", e))?;
+                        let rendered = render_file(&content, &template_vars, &source_file)?;
                         
                         // Write to target
-                        fs::write(&target_file, rendered)?;
+                        write_file(ctx, &target_file, rendered)?;
                         
                         // If it's a script, make it executable on Unix
                         #[cfg(unix)]
@@ -599,9 +700,7 @@ This project was generated using FerrisUp.
     // Only copy directory contents for non-data-science templates
     if !skip_dir_copying {
         // Copy remaining files from the template directory
-        // We need to handle template variables in all files
-        let mut handlebars = Handlebars::new();
-        handlebars.register_escape_fn(handlebars::no_escape);
+        // We need to handle template variables in all files, with the same helpers as above
         
         // Add template dir to variable set for use in templates
         if let Some(template_vars_obj) = template_vars.as_object_mut() {
@@ -609,7 +708,7 @@ This project was generated using FerrisUp.
         }
         
         // Process template files with variables
-        process_template_directory(&template_dir, &target_dir, &template_vars, &mut handlebars)?;
+        process_template_directory(ctx, &template_dir, &target_dir, &template_vars)?;
         
         // Post-processing: Check for any remaining .template files that weren't processed correctly
         if let Ok(entries) = fs::read_dir(&target_dir) {
@@ -620,32 +719,38 @@ This project was generated using FerrisUp.
                     let file_name_str = file_name.to_string_lossy();
                     
                     if path.is_file() && file_name_str.ends_with(".template") {
-                        println!("Post-processing template file: {}", path.display());
+                        status!("Post-processing template file: {}", path.display());
                         
                         // Read the template file
                         let content = fs::read_to_string(&path)?;
                         
                         // Render with handlebars
-                        let rendered = handlebars.render_template(&content, &template_vars)
-                            .map_err(|e| anyhow!("Failed to render template {}: {}", path.display(), e))?;
+                        let rendered = render_file(&content, &template_vars, &path)?;
                         
                         // Create the target path without .template extension
                         let new_name = file_name_str.trim_end_matches(".template");
                         let target_path = target_dir.join(new_name);
                         
                         // Write the rendered content
-                        fs::write(&target_path, rendered)?;
+                        write_file(ctx, &target_path, rendered)?;
                         
                         // Remove the original .template file
                         fs::remove_file(&path)?;
                         
-                        println!("Processed template file: {} -> {}", path.display(), target_path.display());
+                        status!("Processed template file: {} -> {}", path.display(), target_path.display());
                     }
                 }
             }
         }
     }
     
+    // Apply the selected variants for templates that ship several (e.g. Leptos plain CSS or
+    // Tailwind, CSR or SSR). Styling goes first so the rendering mode can still remove its files.
+    let styling = template_vars.get("styling").and_then(|v| v.as_str()).unwrap_or("plain-css");
+    apply_variant(ctx, &template_config, "styling", &template_dir, target_dir, styling, &template_vars)?;
+    let rendering = template_vars.get("rendering").and_then(|v| v.as_str()).unwrap_or("csr");
+    apply_variant(ctx, &template_config, "rendering", &template_dir, target_dir, rendering, &template_vars)?;
+
     // After processing all files, clean up any files that shouldn't be in the target directory
     if let Some(mcu_target) = template_vars.get("mcu_target").and_then(|v| v.as_str()) {
         // First, ensure the correct MCU-specific files are copied to the root
@@ -657,22 +762,18 @@ This project was generated using FerrisUp.
                 let target_main_rs = target_dir.join("src").join("main.rs");
                 // Read the source file
                 let content = fs::read_to_string(&mcu_main_rs)?;
-                // Create handlebars instance for templating
-                let mut handlebars = Handlebars::new();
-                handlebars.register_escape_fn(handlebars::no_escape);
-                
                 // Apply templating
-                let rendered = handlebars.render_template(&content, &template_vars)?;
+                let rendered = render_file(&content, &template_vars, &mcu_main_rs)?;
                 
                 // Write to target file
-                fs::write(target_main_rs, rendered)?;
+                write_file(ctx, &target_main_rs, rendered)?;
             }
             
             // Copy the MCU-specific memory.x to memory.x
             let mcu_memory_x = mcu_dir.join("memory.x");
             if mcu_memory_x.exists() {
                 let target_memory_x = target_dir.join("memory.x");
-                fs::copy(&mcu_memory_x, &target_memory_x)?;
+                copy_file(ctx, &mcu_memory_x, &target_memory_x)?;
             }
             
             // Copy the MCU-specific .cargo/config.toml to .cargo/config.toml
@@ -681,7 +782,7 @@ This project was generated using FerrisUp.
                 let target_cargo_config = target_dir.join(".cargo").join("config.toml");
                 // Ensure the target directory exists
                 fs::create_dir_all(target_cargo_config.parent().unwrap())?;
-                fs::copy(&mcu_cargo_config, &target_cargo_config)?;
+                copy_file(ctx, &mcu_cargo_config, &target_cargo_config)?;
             }
             
             // Copy any other MCU-specific files at the root level
@@ -707,20 +808,15 @@ This project was generated using FerrisUp.
                             // Read the source file
                             let content = fs::read_to_string(&source_path)?;
                             
-                            // Create handlebars instance for templating
-                            let mut handlebars = Handlebars::new();
-                            handlebars.register_escape_fn(handlebars::no_escape);
-                            
                             // Apply templating
-                            let rendered = handlebars.render_template(&content, &template_vars)
-                                .map_err(|e| anyhow::anyhow!("Failed to render template: {}", e))?;
+                            let rendered = render_file(&content, &template_vars, &source_path)?;
                             
                             // Write to target file (removing .template extension)
                             let target_file_name = target_path.file_stem().unwrap().to_string_lossy().to_string();
                             let target_file_path = target_dir.join(target_file_name);
-                            fs::write(target_file_path, rendered)?;
+                            write_file(ctx, &target_file_path, rendered)?;
                         } else {
-                            fs::copy(&source_path, &target_path)?;
+                            copy_file(ctx, &source_path, &target_path)?;
                         }
                     }
                 }
@@ -774,10 +870,12 @@ This project was generated using FerrisUp.
         }
     }
 
-    // Remove template.json if it was copied
-    let template_json_file = target_dir.join("template.json");
-    if template_json_file.exists() {
-        fs::remove_file(&template_json_file)?;
+    // Remove the template manifest (template.json or a user template's ferrisup.toml) if it was copied
+    for manifest in ["template.json", registry::USER_MANIFEST] {
+        let manifest_file = target_dir.join(manifest);
+        if manifest_file.exists() {
+            fs::remove_file(&manifest_file)?;
+        }
     }
     
     // Apply fixes for burn templates if needed
@@ -786,12 +884,18 @@ This project was generated using FerrisUp.
     }
     
     // Print successful message
-    println!("\n✅ {} project created successfully!", project_name.green());
+    status!("\n✅ {} project created successfully!", project_name.green());
     
     // Check for next steps in template.json
     if let Ok(template_config) = get_template_config(template_name) {
-        if let Some(next_steps) = template_config.get("next_steps").and_then(|s| s.as_array()) {
-            println!("\n{}", "Next steps:".bold().green());
+        // A rendering variant can replace the template's default next steps
+        let next_steps = template_vars
+            .get("rendering")
+            .and_then(|r| r.as_str())
+            .and_then(|r| template_config.get("rendering")?.get(r)?.get("next_steps"))
+            .or_else(|| template_config.get("next_steps"));
+        if let Some(next_steps) = next_steps.and_then(|s| s.as_array()) {
+            status!("\n{}", "Next steps:".bold().green());
             
             // Create a handlebars registry for processing templates
             let mut handlebars = Handlebars::new();
@@ -840,7 +944,7 @@ This project was generated using FerrisUp.
             for step in next_steps {
                 if let Some(step_template) = step.as_str() {
                         // First, handle direct substitutions for critical variables
-                    let mut step_str = step_template.to_string();
+                    let mut step_str = ctx.resolve_cd(step_template, project_name);
                     
                     // Always replace project_name
                     step_str = step_str.replace("{{project_name}}", project_name);
@@ -867,11 +971,11 @@ This project was generated using FerrisUp.
                     // Now try handlebars for any remaining variables
                     match handlebars.render_template(&step_str, &json!(data)) {
                         Ok(rendered) => {
-                            println!("- {}", rendered);
+                            status!("- {}", rendered);
                         },
                         Err(_) => {
                             // If handlebars fails, just use our direct substitutions
-                            println!("- {}", step_str);
+                            status!("- {}", step_str);
                         }
                     }
                 }
@@ -885,12 +989,12 @@ This project was generated using FerrisUp.
         if let Ok(content) = fs::read_to_string(&next_steps_file) {
             if let Ok(json) = serde_json::from_str::<Value>(&content) {
                 if let Some(steps) = json.get("next_steps").and_then(|s| s.as_array()) {
-                    println!("\n{}", "Next steps:".bold().green());
+                    status!("\n{}", "Next steps:".bold().green());
                     for step in steps {
                         if let Some(step_str) = step.as_str() {
                             // Replace {{project_name}} with the actual project name
-                            let step_text = step_str.replace("{{project_name}}", project_name);
-                            println!("- {}", step_text);
+                            let step_text = ctx.resolve_cd(step_str, project_name).replace("{{project_name}}", project_name);
+                            status!("- {}", step_text);
                         }
                     }
                     
@@ -904,8 +1008,103 @@ This project was generated using FerrisUp.
     Ok(())
 }
 
+/// Apply a variant declared in one of the template's variant sections (`rendering`, `styling`).
+///
+/// Variant directories (e.g. `ssr/`) are copied along with the rest of the template, so
+/// they are always removed from the output. The selected variant may then rename or
+/// remove files from the default layout before its own files are overlaid on the project.
+fn apply_variant(
+    ctx: &GenerationContext,
+    template_config: &Value,
+    section: &str,
+    template_dir: &Path,
+    target_dir: &Path,
+    selected: &str,
+    template_vars: &Value,
+) -> Result<()> {
+    let variants = match template_config.get(section).and_then(|r| r.as_object()) {
+        Some(variants) => variants,
+        None => return Ok(()),
+    };
+
+    for name in variants.keys() {
+        let copied_dir = target_dir.join(name);
+        if copied_dir.is_dir() {
+            fs::remove_dir_all(&copied_dir)?;
+        }
+    }
+
+    let variant = match variants.get(selected) {
+        Some(variant) => variant,
+        None => return Ok(()),
+    };
+
+    if let Some(renames) = variant.get("rename").and_then(|r| r.as_array()) {
+        for rename in renames {
+            let source = rename.get("source").and_then(|s| s.as_str());
+            let target = rename.get("target").and_then(|t| t.as_str());
+            if let (Some(source), Some(target)) = (source, target) {
+                let source_path = target_dir.join(source);
+                if source_path.exists() {
+                    let target_path = target_dir.join(target);
+                    if let Some(parent) = target_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::rename(&source_path, &target_path)?;
+                }
+            }
+        }
+    }
+
+    if let Some(removals) = variant.get("remove").and_then(|r| r.as_array()) {
+        for removal in removals.iter().filter_map(|r| r.as_str()) {
+            let path = target_dir.join(removal);
+            if path.is_file() {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+
+    let variant_dir = template_dir.join(selected);
+    if variant_dir.is_dir() {
+        process_template_directory(ctx, &variant_dir, target_dir, template_vars)?;
+    }
+
+    Ok(())
+}
+
+/// Write a generated file and report it to the progress line of `new`
+fn write_file(ctx: &GenerationContext, path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    write_atomic(path, contents)?;
+    ctx.file_written(path);
+    Ok(())
+}
+
+/// Render the contents of the template file at `path`, naming the file if a variable is undefined
+///
+/// The `{{#minimal}}` and `{{^minimal}}` blocks are resolved first, by the `minimal` variable,
+/// and the whitespace the conditionals leave is tidied afterwards.
+fn render_file(content: &str, template_vars: &Value, path: &Path) -> Result<String> {
+    let minimal = template_vars.get("minimal").and_then(|m| m.as_bool()).unwrap_or(false);
+    let content = strip_minimal_blocks(content, minimal)
+        .map_err(|e| anyhow!("Failed to render template {}: {}", path.display(), e))?;
+    let ctx = Context::wraps(template_vars)?;
+    let rendered = render_template_str(&content, &ctx)
+        .map_err(|e| anyhow!("Failed to render template {}: {}", path.display(), e))?;
+    let rendered = normalize_rendered(path, &rendered);
+    log::debug!(target: "ferrisup::template", file:% = path.display(), bytes = rendered.len(); "Rendered template file");
+    Ok(rendered)
+}
+
+/// Copy a template file verbatim and report it to the progress line of `new`
+fn copy_file(ctx: &GenerationContext, source: &Path, target: &Path) -> Result<()> {
+    fs::copy(source, target)?;
+    ctx.file_written(target);
+    Ok(())
+}
+
 /// Process template files with variable substitution in a directory
-fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, handlebars: &mut Handlebars) -> Result<()> {
+fn process_template_directory(ctx: &GenerationContext, src: &Path, dst: &Path, template_vars: &Value) -> Result<()> {
     fs::create_dir_all(dst)?;
     
     for entry in fs::read_dir(src)? {
@@ -915,6 +1114,9 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
         if path.is_file() {
             let file_name = entry.file_name();
             let file_name_str = file_name.to_string_lossy();
+            if file_name_str == registry::AUTHORING_GUIDE {
+                continue;
+            }
             
             // Check if this is a template file (has .template extension)
             let is_template_file = file_name_str.ends_with(".template");
@@ -924,16 +1126,16 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
                 // For template files, remove the .template extension
                 let new_name = file_name_str.trim_end_matches(".template");
                 let target = dst.join(new_name);
-                if env::var("FERRISUP_VERBOSE").unwrap_or_else(|_| "false".to_string()) == "true" {
-                    println!("Template file will be processed: {} -> {}", path.display(), target.display());
+                if is_verbose() {
+                    status!("Template file will be processed: {} -> {}", path.display(), target.display());
                 }
                 target
             } else {
                 dst.join(&*file_name_str)
             };
             
-            if env::var("FERRISUP_VERBOSE").unwrap_or_else(|_| "false".to_string()) == "true" {
-                println!("Processing {}: {} -> {}", 
+            if is_verbose() {
+                status!("Processing {}: {} -> {}", 
                        if is_template_file { "template file" } else { "regular file" },
                        path.display(), 
                        target_path.display());
@@ -953,13 +1155,13 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
             };
             
             // Debug output only if verbose mode is enabled
-            if env::var("FERRISUP_VERBOSE").unwrap_or_else(|_| "false".to_string()) == "true" {
-                println!("Should process: {} (is_template_file: {})", should_process, is_template_file);
+            if is_verbose() {
+                status!("Should process: {} (is_template_file: {})", should_process, is_template_file);
             }
             
             if should_process {
-                if env::var("FERRISUP_VERBOSE").unwrap_or_else(|_| "false".to_string()) == "true" {
-                    println!("Processing file: {} (is_template: {}) -> {}", path.display(), is_template_file, target_path.display());
+                if is_verbose() {
+                    status!("Processing file: {} (is_template: {}) -> {}", path.display(), is_template_file, target_path.display());
                 }
                 
                 // Read file content
@@ -985,8 +1187,7 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
                 
                 let final_content = if has_template_vars {
                     // If it has template variables, render with Handlebars
-                    handlebars.render_template(&processed_content, template_vars)
-                        .map_err(|e| anyhow!("Failed to render template {}: {}", path.display(), e))?
+                    render_file(&processed_content, template_vars, &path)?
                 } else {
                     // If no template variables, use the content as-is
                     processed_content
@@ -999,13 +1200,12 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
                 }
                 
                 // Write the final content to the target path
-                fs::write(&target_path, final_content)
-                    .map_err(|e| anyhow!("Failed to write file {}: {}", target_path.display(), e))?;
+                write_file(ctx, &target_path, final_content)?;
             } else {
                 // Just copy other files without processing
-                fs::copy(&path, &target_path)?;
-                if env::var("FERRISUP_VERBOSE").unwrap_or_else(|_| "false".to_string()) == "true" {
-                    println!("Copied file: {} -> {}", path.display(), target_path.display());
+                copy_file(ctx, &path, &target_path)?;
+                if is_verbose() {
+                    status!("Copied file: {} -> {}", path.display(), target_path.display());
                 }
             }
             
@@ -1032,11 +1232,23 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
                 continue;
             }
             
+            // A `name__if_variable` directory is only generated, as `name`, when the variable holds
+            let dir_name = entry.file_name().to_string_lossy().into_owned();
+            let target_name = match split_conditional_dir(&dir_name) {
+                Some((name, variable)) => {
+                    if !conditional_dir_included(variable, template_vars)? {
+                        continue;
+                    }
+                    name
+                }
+                None => dir_name.as_str(),
+            };
+            
             // Process subdirectory recursively
-            process_template_directory(&path, &dst.join(entry.file_name()), template_vars, handlebars)?;
+            process_template_directory(ctx, &path, &dst.join(target_name), template_vars)?;
             
             // Check for any remaining .template files in the target directory
-            let target_dir = dst.join(entry.file_name());
+            let target_dir = dst.join(target_name);
             if let Ok(target_entries) = fs::read_dir(&target_dir) {
                 for target_entry in target_entries {
                     if let Ok(target_entry) = target_entry {
@@ -1045,26 +1257,25 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
                         let target_file_str = target_file_name.to_string_lossy();
                         
                         if target_path.is_file() && target_file_str.ends_with(".template") {
-                            println!("Processing remaining template file: {}", target_path.display());
+                            status!("Processing remaining template file: {}", target_path.display());
                             
                             // Read the template file
                             let content = fs::read_to_string(&target_path)?;
                             
                             // Render with handlebars
-                            let rendered = handlebars.render_template(&content, template_vars)
-                                .map_err(|e| anyhow!("Failed to render template {}: {}", target_path.display(), e))?;
+                            let rendered = render_file(&content, template_vars, &target_path)?;
                             
                             // Create the target path without .template extension
                             let new_name = target_file_str.trim_end_matches(".template");
                             let new_target_path = target_dir.join(new_name);
                             
                             // Write the rendered content
-                            fs::write(&new_target_path, rendered)?;
+                            write_file(ctx, &new_target_path, rendered)?;
                             
                             // Remove the original .template file
                             fs::remove_file(&target_path)?;
                             
-                            println!("Processed template file: {} -> {}", target_path.display(), new_target_path.display());
+                            status!("Processed template file: {} -> {}", target_path.display(), new_target_path.display());
                         }
                     }
                 }
@@ -1077,11 +1288,11 @@ fn process_template_directory(src: &Path, dst: &Path, template_vars: &Value, han
 
 #[allow(dead_code)]
 fn process_file(
+    ctx: &GenerationContext,
     file_entry: &Value,
     template_dir: &Path,
     target_dir: &Path,
     template_vars: &Value,
-    handlebars: &mut Handlebars,
 ) -> Result<()> {
     if let (Some(source), Some(target)) = (
         file_entry.get("source").and_then(|s| s.as_str()),
@@ -1152,14 +1363,13 @@ fn process_file(
             let processed_content = process_conditional_blocks(&file_content, template_vars)?;
             
             // Render with handlebars
-            let rendered = handlebars.render_template(&processed_content, template_vars)
-                .map_err(|e| anyhow!("Failed to render template {}: {}", source_path.display(), e))?;
+            let rendered = render_file(&processed_content, template_vars, &source_path)?;
             
             // Write rendered content to the target path
-            fs::write(&target_path, rendered)?
+            write_file(ctx, &target_path, rendered)?
         } else {
             // Just copy the file
-            fs::copy(&source_path, &target_path)?;
+            copy_file(ctx, &source_path, &target_path)?;
             // Set executable bit for .sh files
             if let Some(ext) = target_path.extension() {
                 if ext == "sh" {
@@ -1297,13 +1507,18 @@ pub fn find_template_directory(template_name: &str) -> Result<PathBuf> {
         }
     }
     
-    Err(anyhow::anyhow!("Template '{}' not found", template_name))
+    Err(crate::core::Error::TemplateNotFound(template_name.to_string()).into())
 }
 
 /// Get the template configuration
 pub fn get_template_config(template_name: &str) -> Result<Value> {
     let template_dir = get_template_dir(template_name)?;
     
+    // User templates describe themselves in ferrisup.toml instead of template.json
+    if template_dir.join(registry::USER_MANIFEST).exists() {
+        return registry::read_user_manifest(&template_dir);
+    }
+    
     // Read the template configuration
     let template_config_path = template_dir.join("template.json");
     let template_config_str = fs::read_to_string(&template_config_path)?;
@@ -1342,7 +1557,7 @@ fn process_dependencies(dependencies: &Value, _target_dir: &Path, section: &str)
                     version = ver.to_string();
                 }
                 
-                println!("📦 Adding {} dependency: {} ({})", section, dep_name, version);
+                status!("📦 Adding {} dependency: {} ({})", section, dep_name, version);
             }
         }
     }
@@ -1361,6 +1576,15 @@ fn get_template_dir(template_name: &str) -> Result<PathBuf> {
     
     // Check if it's a direct template
     let direct_path = Path::new(&templates_dir).join(template_name);
+    
+    // User templates take precedence over bundled ones with the same name
+    if let Some(user_path) = find_user_template(template_name) {
+        if direct_path.is_dir() {
+            registry::warn_shadowed(template_name);
+        }
+        return Ok(user_path);
+    }
+    
     if direct_path.exists() && direct_path.is_dir() {
         return Ok(direct_path);
     }
@@ -1403,7 +1627,7 @@ fn get_template_dir(template_name: &str) -> Result<PathBuf> {
         }
     }
     
-    Err(anyhow::anyhow!("Template '{}' not found", template_name))
+    Err(crate::core::Error::TemplateNotFound(template_name.to_string()).into())
 }
 
 /// Get the template's custom next steps if available
@@ -1647,6 +1871,8 @@ fn apply_burn_compatibility_fixes(target_dir: &Path) -> Result<()> {
     fix_model_api(target_dir)?;
     // Apply specific patches for known problematic files
     apply_specific_patches(target_dir)?;
+    // Route model saving and loading through a single ModelIO helper
+    model_io::add_model_io(target_dir)?;
     
     Ok(())
 }
@@ -1963,9 +2189,9 @@ fn check_wasm_target(burn_example: &str) -> Result<()> {
         
         // If wasm32-unknown-unknown is not installed, print instructions
         if !output_str.contains("wasm32-unknown-unknown") {
-            println!("⚠️ The wasm32-unknown-unknown target is not installed, which is required for web-based examples.");
-            println!("To install it, run: rustup target add wasm32-unknown-unknown");
-            println!("You'll also need wasm-bindgen-cli: cargo install -f wasm-bindgen-cli");
+            status!("⚠️ The wasm32-unknown-unknown target is not installed, which is required for web-based examples.");
+            status!("To install it, run: rustup target add wasm32-unknown-unknown");
+            status!("You'll also need wasm-bindgen-cli: cargo install -f wasm-bindgen-cli");
         }
     }
     
@@ -1973,42 +2199,42 @@ fn check_wasm_target(burn_example: &str) -> Result<()> {
 }
 
 /// Get custom next steps for Burn examples
-fn get_burn_example_next_steps(burn_example: &str, project_name: &str) -> Vec<String> {
+fn get_burn_example_next_steps(burn_example: &str, project_dir: &str) -> Vec<String> {
     match burn_example {
         "mnist" => vec![
-            format!("📥 Download the MNIST dataset: cd {} && cargo run --example mnist --features ndarray -- download", project_name),
+            format!("📥 Download the MNIST dataset: cd {} && cargo run --example mnist --features ndarray -- download", project_dir),
             "🧠 Train the model: cargo run --example mnist --features ndarray -- train".to_string(),
             "🔍 Test with sample images: cargo run --example mnist --features ndarray -- test".to_string(),
             "🖼️ Try with your own images: cargo run --example mnist --features ndarray -- predict path/to/your/image.png".to_string(),
         ],
         "simple-regression" => vec![
-            format!("📥 Generate synthetic data: cd {} && cargo run --example simple-regression --features ndarray -- generate", project_name),
+            format!("📥 Generate synthetic data: cd {} && cargo run --example simple-regression --features ndarray -- generate", project_dir),
             "🧠 Train the model: cargo run --example simple-regression --features ndarray -- train".to_string(),
             "🔍 Test the model: cargo run --example simple-regression --features ndarray -- test".to_string(),
         ],
         "text-classification" => vec![
-            format!("📥 Download the dataset: cd {} && cargo run --example text-classification --features ndarray -- download", project_name),
+            format!("📥 Download the dataset: cd {} && cargo run --example text-classification --features ndarray -- download", project_dir),
             "🧠 Train the model: cargo run --example text-classification --features ndarray -- train".to_string(),
             "🔍 Test the model: cargo run --example text-classification --features ndarray -- test".to_string(),
             "📝 Classify your own text: cargo run --example text-classification --features ndarray -- predict \"Your text here\"".to_string(),
         ],
         "custom-image-dataset" => vec![
             "📥 Prepare your image dataset in the format described in README.md".to_string(),
-            format!("🧠 Train the model: cd {} && cargo run --example custom-image-dataset --features ndarray -- train", project_name),
+            format!("🧠 Train the model: cd {} && cargo run --example custom-image-dataset --features ndarray -- train", project_dir),
             "🔍 Test the model: cargo run --example custom-image-dataset --features ndarray -- test".to_string(),
         ],
         "custom-csv-dataset" => vec![
             "📥 Prepare your CSV dataset as described in README.md".to_string(),
-            format!("🧠 Train the model: cd {} && cargo run --example custom-csv-dataset --features ndarray -- train", project_name),
+            format!("🧠 Train the model: cd {} && cargo run --example custom-csv-dataset --features ndarray -- train", project_dir),
             "🔍 Test the model: cargo run --example custom-csv-dataset --features ndarray -- test".to_string(),
         ],
         "custom-training-loop" => vec![
-            format!("📥 Generate synthetic data: cd {} && cargo run --example custom-training-loop --features ndarray -- generate", project_name),
+            format!("📥 Generate synthetic data: cd {} && cargo run --example custom-training-loop --features ndarray -- generate", project_dir),
             "🧠 Train the model: cargo run --example custom-training-loop --features ndarray -- train".to_string(),
             "🔍 Test the model: cargo run --example custom-training-loop --features ndarray -- test".to_string(),
         ],
         "image-classification-web" => vec![
-            format!("📥 Download the dataset: cd {} && cargo run --example image-classification-web --features ndarray -- download", project_name),
+            format!("📥 Download the dataset: cd {} && cargo run --example image-classification-web --features ndarray -- download", project_dir),
             "🧠 Train the model: cargo run --example image-classification-web --features ndarray -- train".to_string(),
             "🌐 Start the web server: cargo run --example image-classification-web --features ndarray -- serve".to_string(),
             "🔍 Open your browser at http://localhost:8080 to use the web interface".to_string(),
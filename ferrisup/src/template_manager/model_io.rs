@@ -0,0 +1,280 @@
+// A shared `ModelIO` helper for the Burn examples, so every model save and load goes
+// through one recorder alias instead of naming the recorder at each call
+use anyhow::Result;
+use ferrisup_common::fs::write_atomic;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+use walkdir::WalkDir;
+
+/// File recorders the Burn examples pick between
+const FILE_RECORDERS: &[&str] = &["CompactRecorder", "DefaultRecorder", "NoStdTrainingRecorder"];
+
+/// `src/model_io.rs` of the generated project, `{recorder}` being the example's recorder
+const MODEL_IO_RS: &str = r#"//! Saving and loading models, the one place the on-disk format is chosen
+//!
+//! Every model save and load in this project goes through [`ModelRecorder`], so switching
+//! the format (for example to `NamedMpkFileRecorder<FullPrecisionSettings>` for full
+//! precision weights) is a one-line change. Models saved before a switch need the old
+//! recorder to load.
+
+use burn::module::Module;
+use burn::record::{{recorder}, RecorderError};
+use burn::tensor::backend::Backend;
+use std::path::PathBuf;
+
+/// The recorder used for every model file
+pub type ModelRecorder = {recorder};
+
+/// `save` and `load` for any module, with [`ModelRecorder`]
+pub trait ModelIO<B: Backend>: Module<B> {
+    /// Save the model to `path`, the recorder adds its file extension
+    fn save(self, path: impl Into<PathBuf>) -> Result<(), RecorderError> {
+        self.save_file(path, &ModelRecorder::new())
+    }
+
+    /// Load the weights saved at `path` into this model on `device`
+    fn load(self, path: impl Into<PathBuf>, device: &B::Device) -> Result<Self, RecorderError> {
+        self.load_file(path, &ModelRecorder::new(), device)
+    }
+}
+
+impl<B: Backend, M: Module<B>> ModelIO<B> for M {}
+"#;
+
+/// Add `src/model_io.rs` to a Burn example and route its model saving and loading through it
+///
+/// The example's model file recorder, the one passed to `save_file`/`load_file` (or else
+/// the first file recorder it uses), becomes the `ModelRecorder` alias, so files keep the
+/// format the example wrote. `save_file(path, &recorder)` and `load_file(path, &recorder,
+/// device)` become the `ModelIO` `save(path)` and `load(path, device)`, the other uses of
+/// that recorder use `ModelRecorder`, and imports of it that are left unused are removed.
+/// Other recorders, such as a checkpointer's, are kept. Examples without a file recorder
+/// are left alone. Only `src/` is rewritten since `crate::model_io` doesn't resolve elsewhere.
+pub(super) fn add_model_io(target_dir: &Path) -> Result<()> {
+    let src_dir = target_dir.join("src");
+    let lib_rs = src_dir.join("lib.rs");
+    let crate_root = if lib_rs.exists() { lib_rs.clone() } else { src_dir.join("main.rs") };
+    if !crate_root.exists() {
+        return Ok(());
+    }
+
+    let sources: Vec<(PathBuf, String)> = WalkDir::new(&src_dir)
+        .into_iter()
+        .filter_entry(|e| e.path() != src_dir.join("bin"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(|e| Ok((e.path().to_path_buf(), fs::read_to_string(e.path())?)))
+        .collect::<Result<_>>()?;
+    let Some(model_recorder) = model_recorder(&sources)? else {
+        return Ok(());
+    };
+
+    let recorder = Regex::new(&format!(r"\b{}::(?:new|default)\(\)", model_recorder))?;
+    let save_file = Regex::new(r"\.save_file\(((?s:[^;]*?)),\s*&ModelRecorder::new\(\)\s*\)")?;
+    let load_file = Regex::new(r"\.load_file\(((?s:[^;]*?)),\s*&ModelRecorder::new\(\)\s*,")?;
+
+    let mut routed = false;
+    for (path, content) in &sources {
+        if !recorder.is_match(content) {
+            continue;
+        }
+
+        let updated = recorder.replace_all(content, "ModelRecorder::new()").to_string();
+        let uses_model_io = save_file.is_match(&updated) || load_file.is_match(&updated);
+        let updated = save_file.replace_all(&updated, ".save($1)");
+        let updated = load_file.replace_all(&updated, ".load($1,").to_string();
+        let updated = remove_unused_import(&updated, model_recorder)?;
+
+        // `main.rs` next to a `lib.rs` is its own crate and reaches the module through the library
+        let module = if *path == src_dir.join("main.rs") && *path != crate_root {
+            format!("{}::model_io", library_name(target_dir)?)
+        } else {
+            "crate::model_io".to_string()
+        };
+        let mut imports = Vec::new();
+        if updated.contains("ModelRecorder") {
+            imports.push("ModelRecorder");
+        }
+        if uses_model_io {
+            imports.push("ModelIO");
+        }
+        let import = match imports.as_slice() {
+            [single] => format!("use {}::{};\n", module, single),
+            _ => format!("use {}::{{{}}};\n", module, imports.join(", ")),
+        };
+
+        write_atomic(path, insert_import(&updated, &import))?;
+        routed = true;
+    }
+
+    if routed {
+        write_atomic(&src_dir.join("model_io.rs"), MODEL_IO_RS.replace("{recorder}", model_recorder))?;
+        let root = fs::read_to_string(&crate_root)?;
+        let declaration = if crate_root == lib_rs { "pub mod model_io;\n" } else { "mod model_io;\n" };
+        write_atomic(&crate_root, insert_import(&root, declaration))?;
+    }
+
+    Ok(())
+}
+
+/// The file recorder models are saved and loaded with in `sources`: the one passed to
+/// `save_file` or `load_file`, else the first one used at all
+fn model_recorder(sources: &[(PathBuf, String)]) -> Result<Option<&'static str>> {
+    let names = FILE_RECORDERS.join("|");
+    let model_file = Regex::new(&format!(r"\.(?:save|load)_file\((?s:[^;]*?)&({})::(?:new|default)\(\)", names))?;
+    let any_use = Regex::new(&format!(r"\b({})::(?:new|default)\(\)", names))?;
+    let found = |regex: &Regex| {
+        sources
+            .iter()
+            .find_map(|(_, content)| regex.captures(content))
+            .and_then(|captures| FILE_RECORDERS.iter().copied().find(|name| *name == &captures[1]))
+    };
+    Ok(found(&model_file).or_else(|| found(&any_use)))
+}
+
+/// `content` without its imports of `name` when nothing else in it refers to `name`
+///
+/// Handles the plain `use burn::record::Name;`, the grouped `use burn::record::{Name, ..};`
+/// and the nested `use burn::{record::{Name, ..}, ..};` forms, collapsing groups left with
+/// a single item and dropping emptied ones.
+fn remove_unused_import(content: &str, name: &str) -> Result<String> {
+    let use_item = Regex::new(r"(?m)^[ \t]*(?:pub )?use [^;]*;[ \t]*\n?")?;
+    let name_word = Regex::new(&format!(r"\b{}\b", name))?;
+    let outside_imports = use_item.replace_all(content, "");
+    if name_word.is_match(&outside_imports) {
+        return Ok(content.to_string());
+    }
+
+    let in_group = Regex::new(&format!(r"(?:\w+::)*\b{}\b\s*,\s*|,\s*(?:\w+::)*\b{}\b", name, name))?;
+    let alone = Regex::new(&format!(r"\{{\s*(?:\w+::)*\b{}\b\s*\}}", name))?;
+    let single = Regex::new(r"::\{\s*((?:\w+::)*\w+)\s*,?\s*\}")?;
+    let empty_group = Regex::new(r"\s*(?:\w+::)+\{\s*\},?")?;
+    let empty_use = Regex::new(r"^\s*(?:pub )?use (?:\w+::)*\{\s*\}\s*;\s*$")?;
+    let whole_use = Regex::new(&format!(r"^\s*(?:pub )?use (?:\w+::)*{}\s*;\s*$", name))?;
+    let updated = use_item
+        .replace_all(content, |item: &regex::Captures| {
+            let item = &item[0];
+            if whole_use.is_match(item) {
+                return String::new();
+            }
+            if !name_word.is_match(item) {
+                return item.to_string();
+            }
+            let item = alone.replace(item, "{}");
+            let item = in_group.replace(&item, "");
+            if empty_use.is_match(&item) {
+                return String::new();
+            }
+            let item = empty_group.replace_all(&item, "");
+            let item = single.replace_all(&item, "::$1");
+            if empty_use.is_match(&item) { String::new() } else { item.to_string() }
+        })
+        .to_string();
+    // A file that started with the import doesn't start with the blank line after it
+    if content.starts_with('\n') {
+        Ok(updated)
+    } else {
+        Ok(updated.trim_start_matches('\n').to_string())
+    }
+}
+
+/// `line` added before the first `use` or `mod` item of `content`, after its inner
+/// attributes and doc comments
+fn insert_import(content: &str, line: &str) -> String {
+    let mut offset = 0;
+    for source_line in content.split_inclusive('\n') {
+        let trimmed = source_line.trim_start();
+        if trimmed.starts_with("use ") || trimmed.starts_with("mod ") || trimmed.starts_with("pub mod ") {
+            break;
+        }
+        if !(trimmed.starts_with("#!") || trimmed.starts_with("//") || trimmed.is_empty()) {
+            break;
+        }
+        offset += source_line.len();
+    }
+    format!("{}{}{}", &content[..offset], line, &content[offset..])
+}
+
+/// The library crate name of the Burn example in `target_dir`, from its `Cargo.toml`
+fn library_name(target_dir: &Path) -> Result<String> {
+    let manifest = fs::read_to_string(target_dir.join("Cargo.toml"))?.parse::<DocumentMut>()?;
+    let name = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .or_else(|| manifest.get("package").and_then(|package| package.get("name")))
+        .and_then(|name| name.as_str())
+        .unwrap_or_default();
+    Ok(name.replace('-', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_add_model_io() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"my-mnist\"\nversion = \"0.1.0\"\n")?;
+        fs::write(root.join("src/lib.rs"), "#![recursion_limit = \"256\"]\npub mod training;\n")?;
+        fs::write(
+            root.join("src/training.rs"),
+            "use burn::{\n    record::{CompactRecorder, NoStdTrainingRecorder},\n    train::LearnerBuilder,\n};\n\npub fn run() {\n    let learner = builder.with_file_checkpointer(CompactRecorder::new());\n    model_trained\n        .save_file(format!(\"{ARTIFACT_DIR}/model\"), &NoStdTrainingRecorder::new())\n        .expect(\"Trained model should be saved successfully\");\n}\n",
+        )?;
+        fs::write(
+            root.join("src/main.rs"),
+            "use burn::record::NoStdTrainingRecorder;\n\nfn main() {\n    let model = model.load_file(\"model\", &NoStdTrainingRecorder::new(), &B::Device::default()).unwrap();\n}\n",
+        )?;
+
+        add_model_io(root)?;
+
+        let training = fs::read_to_string(root.join("src/training.rs"))?;
+        // The checkpointer keeps its own recorder, the model keeps the one it was saved with
+        assert!(
+            training.starts_with("use crate::model_io::ModelIO;\nuse burn::{\n    record::CompactRecorder,\n    train::LearnerBuilder,\n};\n"),
+            "{}",
+            training
+        );
+        assert!(training.contains(".with_file_checkpointer(CompactRecorder::new())"), "{}", training);
+        assert!(training.contains(".save(format!(\"{ARTIFACT_DIR}/model\"))\n"), "{}", training);
+        let main = fs::read_to_string(root.join("src/main.rs"))?;
+        assert!(main.starts_with("use my_mnist::model_io::ModelIO;\nfn main() {"), "{}", main);
+        assert!(main.contains("model.load(\"model\", &B::Device::default()).unwrap()"), "{}", main);
+        let lib = fs::read_to_string(root.join("src/lib.rs"))?;
+        assert_eq!(lib, "#![recursion_limit = \"256\"]\npub mod model_io;\npub mod training;\n");
+        let model_io = fs::read_to_string(root.join("src/model_io.rs"))?;
+        assert!(model_io.contains("use burn::record::{NoStdTrainingRecorder, RecorderError};"), "{}", model_io);
+        assert!(model_io.contains("pub type ModelRecorder = NoStdTrainingRecorder;"), "{}", model_io);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_unused_import() -> Result<()> {
+        let grouped = "use burn::record::{DefaultRecorder, Recorder};\nuse std::fs;\n\nfn f() {}\n";
+        assert_eq!(remove_unused_import(grouped, "DefaultRecorder")?, "use burn::record::Recorder;\nuse std::fs;\n\nfn f() {}\n");
+        assert_eq!(remove_unused_import("use burn::record::{DefaultRecorder};\n", "DefaultRecorder")?, "");
+        let nested = "use burn::{\n    record::{DefaultRecorder},\n    tensor::Tensor,\n};\n";
+        assert_eq!(remove_unused_import(nested, "DefaultRecorder")?, "use burn::tensor::Tensor;\n");
+        // Still used outside the imports
+        let used = "use burn::record::DefaultRecorder;\nfn f() { DefaultRecorder::new(); }\n";
+        assert_eq!(remove_unused_import(used, "DefaultRecorder")?, used);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_model_io_without_recorders() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("src/main.rs"), "fn main() {}\n")?;
+
+        add_model_io(root)?;
+        assert!(!root.join("src/model_io.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/main.rs"))?, "fn main() {}\n");
+        Ok(())
+    }
+}
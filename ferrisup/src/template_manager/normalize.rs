@@ -0,0 +1,63 @@
+// Tidying the whitespace Handlebars conditionals leave in rendered files
+use std::path::Path;
+
+/// Extensions of the rendered files that get normalized; a `.template` suffix is ignored
+const NORMALIZED_EXTENSIONS: &[&str] = &["rs", "toml", "md", "yml", "yaml"];
+
+/// Normalize the whitespace of `content`, rendered from the template file at `path`
+///
+/// Trailing whitespace and leading blank lines are stripped, runs of three or more blank
+/// lines collapse to one, and the file ends with exactly one newline. Other files, and
+/// anything that looks binary (it has a NUL byte), come back unchanged. CRLF line endings
+/// are kept.
+pub fn normalize_rendered(path: &Path, content: &str) -> String {
+    let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let file_name = file_name.trim_end_matches(".template");
+    let extension = Path::new(file_name).extension().and_then(|e| e.to_str()).unwrap_or_default();
+    if !NORMALIZED_EXTENSIONS.contains(&extension) || content.contains('\0') || content.is_empty() {
+        return content.to_string();
+    }
+
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<&str> = Vec::new();
+    let mut blank_run = 0;
+    for line in content.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        // Two blank lines can be deliberate, more are what skipped conditionals leave
+        if blank_run > 0 && !lines.is_empty() {
+            lines.extend(std::iter::repeat_n("", if blank_run >= 3 { 1 } else { blank_run }));
+        }
+        blank_run = 0;
+        lines.push(line);
+    }
+
+    let mut normalized = lines.join(newline);
+    normalized.push_str(newline);
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_rendered() {
+        let rendered = "use std::fs;   \n\n\n\n\nfn main() {\t\n\n\n    run();\n}\n\n\n";
+        assert_eq!(
+            normalize_rendered(Path::new("src/main.rs"), rendered),
+            "use std::fs;\n\nfn main() {\n\n\n    run();\n}\n"
+        );
+        assert_eq!(normalize_rendered(Path::new("Cargo.toml.template"), "[package]"), "[package]\n");
+        assert_eq!(normalize_rendered(Path::new("README.md"), "\n\n# app\r\n\r\n\r\n\r\nText  \r\n"), "# app\r\n\r\nText\r\n");
+    }
+
+    #[test]
+    fn test_normalize_rendered_leaves_other_files() {
+        assert_eq!(normalize_rendered(Path::new("index.html"), "<p>  \n\n\n\n"), "<p>  \n\n\n\n");
+        assert_eq!(normalize_rendered(Path::new("model.rs"), "a\0b  \n\n\n\n"), "a\0b  \n\n\n\n");
+        assert_eq!(normalize_rendered(Path::new("empty.rs"), ""), "");
+    }
+}
@@ -0,0 +1,112 @@
+// File permissions templates set on their output (`[permissions]` in the manifest)
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+use super::get_template_config;
+
+/// The output paths `manifest` marks executable with `permissions.executable`
+fn executable_paths(manifest: &Value) -> Vec<&str> {
+    manifest
+        .pointer("/permissions/executable")
+        .and_then(|paths| paths.as_array())
+        .map(|paths| paths.iter().filter_map(|path| path.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Whether `path` stays inside the project it's relative to
+fn is_project_path(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty() && path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// What's wrong with the `permissions` table of `manifest`, named after `manifest_name`
+pub fn permissions_problems(manifest: &Value, manifest_name: &str) -> Vec<String> {
+    let Some(permissions) = manifest.get("permissions") else {
+        return Vec::new();
+    };
+    let Some(executable) = permissions.get("executable") else {
+        return vec![format!("`permissions` in {} only supports `executable`, a list of output paths", manifest_name)];
+    };
+    let Some(paths) = executable.as_array() else {
+        return vec![format!("`permissions.executable` in {} must be a list of output paths, found {}", manifest_name, executable)];
+    };
+    paths
+        .iter()
+        .filter(|path| !path.as_str().is_some_and(is_project_path))
+        .map(|path| {
+            format!(
+                "`permissions.executable` in {} must list paths inside the project such as \"scripts/setup.sh\", found {}",
+                manifest_name, path
+            )
+        })
+        .collect()
+}
+
+/// Make the files `template_name` marks executable in `project_dir` executable, returning them
+///
+/// Paths are relative to the project. Files the template didn't generate, such as ones
+/// behind a `condition`, are skipped. On Windows nothing changes.
+pub fn apply_permissions(template_name: &str, project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(manifest) = get_template_config(template_name) else {
+        return Ok(Vec::new());
+    };
+    apply_manifest_permissions(&manifest, project_dir)
+}
+
+fn apply_manifest_permissions(manifest: &Value, project_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    for path in executable_paths(manifest) {
+        if !is_project_path(path) {
+            return Err(anyhow!("Refusing to change the permissions of {}, it's outside the project", path));
+        }
+        let file = project_dir.join(path);
+        if file.is_file() {
+            ferrisup_common::fs::set_executable(&file)?;
+            changed.push(file);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_permissions_problems() {
+        let manifest = json!({ "permissions": { "executable": ["scripts/setup.sh", "../escape.sh", 7] } });
+        let problems = permissions_problems(&manifest, "ferrisup.toml");
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("\"../escape.sh\""), "{}", problems[0]);
+        assert!(permissions_problems(&json!({ "permissions": { "executable": "setup.sh" } }), "ferrisup.toml")[0]
+            .contains("must be a list"));
+        assert!(permissions_problems(&json!({}), "ferrisup.toml").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_apply_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("scripts"))?;
+        std::fs::write(root.join("scripts/download_mnist.sh"), "#!/bin/sh\n")?;
+        std::fs::write(root.join("README.md"), "# Data\n")?;
+
+        let manifest = json!({ "permissions": { "executable": ["scripts/download_mnist.sh", "scripts/not_generated.sh"] } });
+        let changed = apply_manifest_permissions(&manifest, root)?;
+        assert_eq!(changed, vec![root.join("scripts/download_mnist.sh")]);
+
+        let mode = |path: &str| std::fs::metadata(root.join(path)).map(|m| m.permissions().mode() & 0o777);
+        assert_eq!(mode("scripts/download_mnist.sh")?, 0o755);
+        assert_eq!(mode("README.md")? & 0o111, 0);
+
+        let outside = json!({ "permissions": { "executable": ["/etc/passwd"] } });
+        assert!(apply_manifest_permissions(&outside, root).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+// Cargo build profiles for generated projects (`new --profile`)
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use toml_edit::{value, DocumentMut, Item, Table, Value};
+
+use super::get_template_config;
+
+/// Profiles `--profile` accepts
+pub const PROFILES: &[&str] = &["dev", "release-lto", "min-size"];
+
+/// The `[profile.release]` settings of `profile`
+///
+/// `dev` keeps cargo's defaults, so it adds nothing; choosing it overrides a template's
+/// default profile.
+fn release_settings(profile: &str) -> Vec<(&'static str, Value)> {
+    match profile {
+        "min-size" => vec![("opt-level", "z".into()), ("lto", true.into()), ("strip", true.into())],
+        "release-lto" => vec![("lto", "fat".into()), ("codegen-units", 1.into())],
+        _ => Vec::new(),
+    }
+}
+
+/// Fail unless `profile` is one of the profiles `--profile` knows
+pub fn validate_profile(profile: &str) -> Result<()> {
+    if PROFILES.contains(&profile) {
+        Ok(())
+    } else {
+        Err(anyhow!("Unknown profile '{}'. Valid profiles are: {}", profile, PROFILES.join(", ")))
+    }
+}
+
+/// The profile `template_name` declares with `profile` as its default, if any
+///
+/// Nested templates such as `edge/api-function/cloudflare-workers` have the profile of the nearest
+/// template above them that declares one.
+pub fn template_profile(template_name: &str) -> Option<String> {
+    let mut name = template_name.trim_matches('/');
+    loop {
+        let declared = get_template_config(name)
+            .ok()
+            .and_then(|config| config.get("profile")?.as_str().map(|p| p.to_string()));
+        if declared.is_some() {
+            return declared;
+        }
+        name = name.rsplit_once('/')?.0;
+    }
+}
+
+/// Write the `[profile.release]` settings of `profile` to the `Cargo.toml` in `project_dir`
+///
+/// Cargo only reads profiles from the root manifest, so crates below it are left alone.
+/// Settings a template already has in `[profile.release]` are kept unless `profile` sets
+/// them too. Returns whether the manifest changed.
+pub fn set_project_profile(project_dir: &Path, profile: &str) -> Result<bool> {
+    validate_profile(profile)?;
+    let settings = release_settings(profile);
+    let manifest = project_dir.join("Cargo.toml");
+    if settings.is_empty() || !manifest.is_file() {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&manifest)?;
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+    let profiles = doc.entry("profile").or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    let release = profiles
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("[profile] in {} is not a table", manifest.display()))?
+        .entry("release")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("[profile.release] in {} is not a table", manifest.display()))?;
+    for (key, setting) in settings {
+        release.insert(key, value(setting));
+    }
+
+    fs::write(&manifest, doc.to_string())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_project_profile() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let manifest = temp_dir.path().join("Cargo.toml");
+        fs::write(&manifest, "[package]\nname = \"app\"\n\n[profile.release]\nopt-level = \"s\"\npanic = \"abort\"\n")?;
+
+        assert!(set_project_profile(temp_dir.path(), "min-size")?);
+        assert_eq!(
+            fs::read_to_string(&manifest)?,
+            "[package]\nname = \"app\"\n\n[profile.release]\nopt-level = \"z\"\npanic = \"abort\"\nlto = true\nstrip = true\n"
+        );
+
+        fs::write(&manifest, "[package]\nname = \"app\"\n")?;
+        assert!(!set_project_profile(temp_dir.path(), "dev")?);
+        assert!(set_project_profile(temp_dir.path(), "release-lto")?);
+        assert_eq!(
+            fs::read_to_string(&manifest)?,
+            "[package]\nname = \"app\"\n\n[profile.release]\nlto = \"fat\"\ncodegen-units = 1\n"
+        );
+        assert!(set_project_profile(temp_dir.path(), "fast").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_profile() {
+        assert_eq!(template_profile("edge").as_deref(), Some("min-size"));
+        assert_eq!(template_profile("edge/api-function/cloudflare-workers").as_deref(), Some("min-size"));
+        assert_eq!(template_profile("minimal"), None);
+    }
+}
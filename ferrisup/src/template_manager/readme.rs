@@ -0,0 +1,120 @@
+// The README of generated projects, laid out the same way for every template (`new --no-readme`)
+use anyhow::Result;
+use handlebars::Handlebars;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+use super::get_template_config;
+
+/// Layout of every generated README. The template's own README follows as `body`
+const README_LAYOUT: &str = r#"# {{project_name}}
+{{#if description}}
+
+{{description}}
+{{/if}}
+
+## Building and running
+
+```bash
+{{build}}
+{{#if run}}
+{{run}}
+{{/if}}
+```
+{{#if endpoints}}
+
+## Endpoints
+
+{{#each endpoints}}
+- `{{this}}`
+{{/each}}
+{{/if}}
+{{#if commands}}
+
+## Commands
+
+{{#each commands}}
+- `{{this}}`
+{{/each}}
+{{/if}}
+{{#if body}}
+
+{{body}}
+{{/if}}
+"#;
+
+/// Write the README of the project in `project_dir`, generated from `template`
+///
+/// The README starts with the project name, the template's description, the commands to
+/// build and run it and what the template `exposes` in its manifest (`endpoints` of a
+/// server, `commands` of a CLI). Libraries get no run command. The README the template
+/// ships follows, without its title.
+pub fn write_readme(
+    project_dir: &Path,
+    template: &str,
+    project_name: &str,
+    build_command: &[String],
+    run_command: Option<&[String]>,
+) -> Result<()> {
+    let config = get_template_config(template).unwrap_or_default();
+    let list = |key: &str| -> Vec<String> {
+        config
+            .pointer(&format!("/exposes/{}", key))
+            .and_then(|entries| entries.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.as_str().map(String::from))
+            .collect()
+    };
+
+    let is_library = config.get("type").and_then(|t| t.as_str()) == Some("library");
+    let run_command = run_command.filter(|_| !is_library);
+
+    let readme_path = project_dir.join("README.md");
+    let body = fs::read_to_string(&readme_path).map(|readme| readme_body(&readme)).unwrap_or_default();
+    let data = json!({
+        "project_name": project_name,
+        "description": config.get("description").and_then(|d| d.as_str()).unwrap_or_default(),
+        "build": build_command.join(" "),
+        "run": run_command.map(|run| run.join(" ")).unwrap_or_default(),
+        "endpoints": list("endpoints"),
+        "commands": list("commands"),
+        "body": body,
+    });
+
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    fs::write(readme_path, handlebars.render_template(README_LAYOUT, &data)?)?;
+    Ok(())
+}
+
+/// Remove the README a template generated, for `--no-readme`
+pub fn remove_readme(project_dir: &Path) -> Result<()> {
+    let readme_path = project_dir.join("README.md");
+    if readme_path.is_file() {
+        fs::remove_file(readme_path)?;
+    }
+    Ok(())
+}
+
+/// A template's README without its `# title` line, which the layout already has
+fn readme_body(readme: &str) -> String {
+    let body = match readme.trim_start().strip_prefix("# ") {
+        Some(rest) => rest.split_once('\n').map(|(_, body)| body).unwrap_or_default(),
+        None => readme,
+    };
+    body.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readme_body() {
+        assert_eq!(readme_body("# my_app\n\nA server.\n\n## Usage\n"), "A server.\n\n## Usage");
+        assert_eq!(readme_body("Some notes\n"), "Some notes");
+        assert_eq!(readme_body("# my_app\n"), "");
+    }
+}
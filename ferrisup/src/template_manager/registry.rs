@@ -0,0 +1,356 @@
+// User-level template registry, scanned alongside the bundled templates
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::permissions::permissions_problems;
+use crate::core::rust_version::parse_rust_version;
+
+/// Manifest every user template needs, the TOML counterpart of a bundled `template.json`
+pub const USER_MANIFEST: &str = "ferrisup.toml";
+
+/// Notes for a template's authors, which are never copied into generated projects
+pub const AUTHORING_GUIDE: &str = "TEMPLATE.md";
+
+/// Bundled templates that were already reported as shadowed during this run
+static SHADOWED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// A usable template from the user registry
+#[derive(Debug, Clone)]
+pub struct UserTemplate {
+    pub name: String,
+    pub description: String,
+    pub path: PathBuf,
+}
+
+/// A directory in the user registry that can't be used as a template, and why
+#[derive(Debug, Clone)]
+pub struct InvalidTemplate {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Directory holding user templates: `FERRISUP_TEMPLATE_DIR` if set, otherwise
+/// `~/.config/ferrisup/templates`
+pub fn user_templates_dir() -> Option<PathBuf> {
+    match env::var("FERRISUP_TEMPLATE_DIR") {
+        Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => dirs::home_dir().map(|home| home.join(".config").join("ferrisup").join("templates")),
+    }
+}
+
+/// Read the `ferrisup.toml` of a user template as the same JSON shape as a `template.json`
+///
+/// The manifest must parse and have a non-empty `description`, which `list` shows. A
+/// `rust_version` has to be a version such as `1.75` and `build_by_default` a boolean.
+pub fn read_user_manifest(template_dir: &Path) -> Result<Value> {
+    let manifest = parse_user_manifest(template_dir)?;
+    match manifest_problems(&manifest).into_iter().next() {
+        Some(problem) => Err(anyhow!(problem)),
+        None => Ok(manifest),
+    }
+}
+
+/// Every problem with the user template in `template_dir`, empty when it's valid
+///
+/// Goes further than [`read_user_manifest`], which only rejects templates `list` can't
+/// show: `files` entries need a `target` and a `source` that exists in the template, and
+/// `options` need the name, description and type their prompts are built from.
+pub fn validate_user_template(template_dir: &Path) -> Vec<String> {
+    let manifest = match parse_user_manifest(template_dir) {
+        Ok(manifest) => manifest,
+        Err(e) => return vec![e.to_string()],
+    };
+    let mut problems = manifest_problems(&manifest);
+
+    match manifest.get("files") {
+        None => {}
+        Some(Value::Array(files)) => {
+            for (i, file) in files.iter().enumerate() {
+                let source = file.get("source").and_then(|s| s.as_str());
+                match source {
+                    Some(source) if !template_dir.join(source).is_file() => {
+                        problems.push(format!("`files[{}]` source `{}` doesn't exist in the template", i, source))
+                    }
+                    Some(_) => {}
+                    None => problems.push(format!("`files[{}]` needs a `source`", i)),
+                }
+                if file.get("target").and_then(|t| t.as_str()).is_none() {
+                    problems.push(format!("`files[{}]` needs a `target`", i));
+                }
+                if let Some(condition) = file.get("condition") {
+                    if condition.as_str().is_none_or(|c| c.split("==").count() != 2) {
+                        problems.push(format!("`files[{}]` condition must look like `variable == \"value\"`, found {}", i, condition));
+                    }
+                }
+            }
+        }
+        Some(_) => problems.push("`files` must be a list of [[files]] tables".to_string()),
+    }
+
+    match manifest.get("options") {
+        None => {}
+        Some(Value::Array(options)) => {
+            for (i, option) in options.iter().enumerate() {
+                let name = option.get("name").and_then(|n| n.as_str());
+                let label = match name {
+                    Some(name) => format!("option `{}`", name),
+                    None => {
+                        problems.push(format!("`options[{}]` needs a `name`", i));
+                        format!("`options[{}]`", i)
+                    }
+                };
+                if option.get("description").and_then(|d| d.as_str()).is_none() {
+                    problems.push(format!("{} needs a `description`, the question it asks", label));
+                }
+                let default = option.get("default");
+                match option.get("type").and_then(|t| t.as_str()) {
+                    Some("select") => {
+                        let choices = option.get("options").and_then(|o| o.as_array());
+                        if !choices.is_some_and(|c| !c.is_empty() && c.iter().all(Value::is_string)) {
+                            problems.push(format!("{} is a select and needs `options`, a list of strings", label));
+                        }
+                        if default.is_some_and(|d| !d.is_string()) {
+                            problems.push(format!("{} needs a string `default`", label));
+                        }
+                    }
+                    Some("input") => {
+                        if default.is_some_and(|d| !d.is_string()) {
+                            problems.push(format!("{} needs a string `default`", label));
+                        }
+                    }
+                    Some("boolean") => {
+                        if default.is_some_and(|d| !d.is_boolean()) {
+                            problems.push(format!("{} needs a `default` of true or false", label));
+                        }
+                    }
+                    Some(other) => problems.push(format!("{} has unknown type `{}`, expected select, input or boolean", label, other)),
+                    None => problems.push(format!("{} needs a `type`: select, input or boolean", label)),
+                }
+            }
+        }
+        Some(_) => problems.push("`options` must be a list of [[options]] tables".to_string()),
+    }
+
+    if let Some(profile) = manifest.get("profile") {
+        if !profile.as_str().is_some_and(|p| super::PROFILES.contains(&p)) {
+            problems.push(format!("`profile` must be one of: {}", super::PROFILES.join(", ")));
+        }
+    }
+
+    if manifest.get("variables").is_some_and(|v| !v.is_object()) {
+        problems.push("`variables` must be a [variables] table of default values".to_string());
+    }
+
+    if let Some(commands) = manifest.pointer("/hooks/sample_data") {
+        let is_command = |command: &Value| {
+            command.as_array().is_some_and(|args| !args.is_empty() && args.iter().all(Value::is_string))
+        };
+        if !commands.as_array().is_some_and(|commands| commands.iter().all(is_command)) {
+            problems.push("`hooks.sample_data` must be a list of commands, each a list of strings such as [\"cargo\", \"run\"]".to_string());
+        }
+    }
+
+    problems
+}
+
+/// Parse the `ferrisup.toml` in `template_dir` without checking its contents
+fn parse_user_manifest(template_dir: &Path) -> Result<Value> {
+    let manifest_path = template_dir.join(USER_MANIFEST);
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| anyhow!("Cannot read {}: {}", USER_MANIFEST, e))?;
+    let manifest: toml::Value = toml::from_str(&content)
+        .map_err(|e| anyhow!("Invalid {}: {}", USER_MANIFEST, e))?;
+    Ok(serde_json::to_value(manifest)?)
+}
+
+/// Problems that keep a manifest from being used at all
+fn manifest_problems(manifest: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    if let Some(version) = manifest.get("rust_version") {
+        if version.as_str().and_then(parse_rust_version).is_none() {
+            problems.push(format!("`rust_version` in {} must be a version such as \"1.75\", found {}", USER_MANIFEST, version));
+        }
+    }
+    if let Some(version) = manifest.get("version") {
+        if !version.is_string() {
+            problems.push(format!("`version` in {} must be a string such as \"1.0.0\", found {}", USER_MANIFEST, version));
+        }
+    }
+    if let Some(build) = manifest.get("build_by_default") {
+        if !build.is_boolean() {
+            problems.push(format!("`build_by_default` in {} must be true or false, found {}", USER_MANIFEST, build));
+        }
+    }
+    if let Some(minimal) = manifest.get("supports_minimal") {
+        if !minimal.is_boolean() {
+            problems.push(format!("`supports_minimal` in {} must be true or false, found {}", USER_MANIFEST, minimal));
+        }
+    }
+    problems.extend(permissions_problems(manifest, USER_MANIFEST));
+    if manifest
+        .get("description")
+        .and_then(|d| d.as_str())
+        .is_none_or(|description| description.trim().is_empty())
+    {
+        problems.push(format!("{} must have a non-empty `description`", USER_MANIFEST));
+    }
+    problems
+}
+
+/// Scan the user registry, separating usable templates from broken ones
+pub fn scan_user_templates() -> (Vec<UserTemplate>, Vec<InvalidTemplate>) {
+    match user_templates_dir() {
+        Some(dir) => scan_dir(&dir),
+        None => (Vec::new(), Vec::new()),
+    }
+}
+
+/// Scan `registry` for templates, one per subdirectory, sorted by name
+fn scan_dir(registry: &Path) -> (Vec<UserTemplate>, Vec<InvalidTemplate>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+
+    let Ok(entries) = fs::read_dir(registry) else {
+        return (valid, invalid);
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| !p.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .collect();
+    dirs.sort();
+
+    for path in dirs {
+        match read_user_manifest(&path) {
+            Ok(manifest) => valid.push(UserTemplate {
+                name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+                description: manifest["description"].as_str().unwrap_or_default().to_string(),
+                path,
+            }),
+            Err(e) => invalid.push(InvalidTemplate { path, error: e.to_string() }),
+        }
+    }
+
+    (valid, invalid)
+}
+
+/// Directory of the user template `name`, if the registry has a valid one
+pub fn find_user_template(name: &str) -> Option<PathBuf> {
+    let dir = user_templates_dir()?.join(name);
+    (dir.is_dir() && read_user_manifest(&dir).is_ok()).then_some(dir)
+}
+
+/// Warn, once per run, that the user template `name` is used instead of the bundled one
+pub fn warn_shadowed(name: &str) {
+    if let Ok(mut shadowed) = SHADOWED.lock() {
+        if shadowed.get_or_insert_with(HashSet::new).insert(name.to_string()) {
+            eprintln!(
+                "⚠️ User template '{}' overrides the bundled template with the same name",
+                name
+            );
+        }
+    }
+}
+
+/// Add the user templates to `templates`, replacing bundled ones with the same name
+pub fn merge_user_templates(mut templates: Vec<(String, String)>) -> Vec<(String, String)> {
+    let (user_templates, _) = scan_user_templates();
+    for template in user_templates {
+        let description = format!("{} (user template)", template.description);
+        match templates.iter_mut().find(|(name, _)| *name == template.name) {
+            Some(existing) => {
+                warn_shadowed(&template.name);
+                existing.1 = description;
+            }
+            None => templates.push((template.name, description)),
+        }
+    }
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scan_separates_invalid_templates() -> Result<()> {
+        let registry = tempdir()?;
+        let write = |name: &str, manifest: Option<&str>| -> Result<()> {
+            let dir = registry.path().join(name);
+            fs::create_dir_all(&dir)?;
+            if let Some(manifest) = manifest {
+                fs::write(dir.join(USER_MANIFEST), manifest)?;
+            }
+            Ok(())
+        };
+        write("api", Some("description = \"Company API service\"\n\n[[files]]\nsource = \"main.rs\"\ntarget = \"src/main.rs\"\n"))?;
+        write("broken", Some("description = \"Unterminated\n"))?;
+        write("no-manifest", None)?;
+        write("undescribed", Some("name = \"undescribed\"\n"))?;
+        write("unversioned", Some("description = \"Needs Rust\"\nrust_version = \"latest\"\n"))?;
+        write("wrong-build", Some("description = \"Heavy\"\nbuild_by_default = \"no\"\n"))?;
+        write(".git", None)?;
+
+        let (valid, invalid) = scan_dir(registry.path());
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].name, "api");
+        assert_eq!(valid[0].description, "Company API service");
+
+        let manifest = read_user_manifest(&valid[0].path)?;
+        assert_eq!(manifest["files"][0]["target"], "src/main.rs");
+
+        let errors: Vec<(String, String)> = invalid
+            .iter()
+            .map(|t| (t.path.file_name().unwrap().to_string_lossy().to_string(), t.error.clone()))
+            .collect();
+        assert_eq!(errors.len(), 5);
+        assert_eq!(errors[0].0, "broken");
+        assert!(errors[0].1.starts_with("Invalid ferrisup.toml"));
+        assert_eq!(errors[1].0, "no-manifest");
+        assert!(errors[2].1.contains("description"));
+        assert!(errors[3].1.contains("rust_version"), "{}", errors[3].1);
+        assert!(errors[4].1.contains("build_by_default"), "{}", errors[4].1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_user_template() -> Result<()> {
+        let template = tempdir()?;
+        fs::write(template.path().join("main.rs"), "fn main() {}\n")?;
+        fs::write(
+            template.path().join(USER_MANIFEST),
+            "description = \"Service\"\n\n[[files]]\nsource = \"main.rs\"\ntarget = \"src/main.rs\"\n\n\
+             [[options]]\nname = \"port\"\ndescription = \"Port\"\ntype = \"input\"\ndefault = \"8080\"\n\n\
+             [hooks]\nsample_data = [[\"cargo\", \"run\", \"--\", \"generate\"]]\n",
+        )?;
+        assert!(validate_user_template(template.path()).is_empty());
+
+        fs::write(
+            template.path().join(USER_MANIFEST),
+            "rust_version = \"soon\"\n\n[[files]]\nsource = \"lib.rs\"\n\n\
+             [[options]]\nname = \"db\"\ntype = \"select\"\n\n[[options]]\ndescription = \"Debug?\"\ntype = \"toggle\"\n\n\
+             [hooks]\nsample_data = [\"cargo run\"]\n",
+        )?;
+        let problems = validate_user_template(template.path());
+        assert_eq!(problems.len(), 9, "{:?}", problems);
+        assert!(problems[0].contains("rust_version"));
+        assert!(problems[1].contains("description"));
+        assert_eq!(problems[2], "`files[0]` source `lib.rs` doesn't exist in the template");
+        assert_eq!(problems[3], "`files[0]` needs a `target`");
+        assert_eq!(problems[4], "option `db` needs a `description`, the question it asks");
+        assert_eq!(problems[5], "option `db` is a select and needs `options`, a list of strings");
+        assert_eq!(problems[6], "`options[1]` needs a `name`");
+        assert!(problems[7].contains("unknown type `toggle`"));
+        assert!(problems[8].starts_with("`hooks.sample_data` must be a list of commands"));
+
+        assert_eq!(validate_user_template(&template.path().join("missing")).len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+// Minimum Rust versions of templates (`rust_version` in template.json or ferrisup.toml)
+use anyhow::{anyhow, Result};
+use semver::Version;
+use std::path::PathBuf;
+use walkdir::WalkDir;
+
+use super::{get_template_config, scan_user_templates};
+use crate::core::rust_version::{parse_rust_version, rustc_version};
+use crate::core::Error;
+
+/// The minimum Rust version `template_name` declares with `rust_version`, if any
+pub fn required_rust_version(template_name: &str) -> Option<String> {
+    get_template_config(template_name)
+        .ok()?
+        .get("rust_version")?
+        .as_str()
+        .map(|version| version.to_string())
+}
+
+/// Every bundled and user template that declares a `rust_version`, as (template, version)
+pub fn rust_version_requirements() -> Vec<(String, String)> {
+    let templates_dir = PathBuf::from(format!("{}/templates", env!("CARGO_MANIFEST_DIR")));
+    let mut requirements: Vec<(String, String)> = WalkDir::new(&templates_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() == "template.json")
+        .filter_map(|e| {
+            let name = e.path().parent()?.strip_prefix(&templates_dir).ok()?;
+            let name = name.to_string_lossy().replace('\\', "/");
+            let version = required_rust_version(&name)?;
+            Some((name, version))
+        })
+        .collect();
+    requirements.sort();
+
+    let (user_templates, _) = scan_user_templates();
+    requirements.extend(
+        user_templates
+            .into_iter()
+            .filter_map(|template| Some((template.name.clone(), required_rust_version(&template.name)?))),
+    );
+    requirements
+}
+
+/// Fail if the installed rustc is older than the `rust_version` of `template_name`
+///
+/// Nothing is checked when the template declares no version or rustc isn't installed;
+/// a missing toolchain is reported as soon as the project is built.
+pub fn check_rust_version(template_name: &str) -> Result<()> {
+    let Some(required) = required_rust_version(template_name) else {
+        return Ok(());
+    };
+    let Some(found) = rustc_version() else {
+        return Ok(());
+    };
+    check_rust_version_against(template_name, &required, &found)
+}
+
+/// Fail unless `found` is at least the `required` version of `template_name`
+pub fn check_rust_version_against(template_name: &str, required: &str, found: &Version) -> Result<()> {
+    let minimum = parse_rust_version(required).ok_or_else(|| {
+        anyhow!(
+            "Template '{}' has an invalid rust_version '{}', expected a version such as 1.75",
+            template_name,
+            required
+        )
+    })?;
+    if *found >= minimum {
+        return Ok(());
+    }
+    Err(Error::RustTooOld {
+        template: template_name.to_string(),
+        required: required.to_string(),
+        found: found.to_string(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rust_version_against() {
+        let found = Version::new(1, 70, 0);
+        assert!(check_rust_version_against("server/axum", "1.70", &found).is_ok());
+
+        let err = check_rust_version_against("client/leptos/counter", "1.88", &found).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Template 'client/leptos/counter' requires Rust >= 1.88, found 1.70.0. Update the toolchain with `rustup update stable`"
+        );
+        assert!(matches!(Error::from(err), Error::RustTooOld { .. }));
+
+        assert!(check_rust_version_against("broken", "latest", &found).is_err());
+    }
+
+    #[test]
+    fn test_bundled_templates_declare_rust_versions() {
+        assert_eq!(required_rust_version("client/leptos/counter").as_deref(), Some("1.88"));
+        assert_eq!(required_rust_version("minimal"), None);
+        assert!(rust_version_requirements().contains(&("server/axum".to_string(), "1.75".to_string())));
+    }
+}
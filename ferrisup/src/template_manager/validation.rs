@@ -0,0 +1,471 @@
+// Validation of template variables before any files are generated
+use serde::Serialize;
+use serde_json::{Map, Value};
+use walkdir::WalkDir;
+
+use crate::core::{Error, Result};
+
+/// Rust keywords (strict and reserved), which Cargo rejects as crate names
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen",
+    "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Check that `name` can be used as a crate name and inside generated Rust identifiers
+pub fn validate_project_name(name: &str) -> Result<()> {
+    let invalid = |reason: String| Error::InvalidProjectName {
+        name: name.to_string(),
+        reason,
+    };
+
+    let first = name
+        .chars()
+        .next()
+        .ok_or_else(|| invalid("must not be empty".to_string()))?;
+
+    if first.is_ascii_digit() {
+        return Err(invalid("cannot start with a digit".to_string()));
+    }
+
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        return Err(invalid(format!(
+            "contains the invalid character {:?}, only ASCII letters, digits, `-` and `_` are allowed",
+            c
+        )));
+    }
+
+    if first == '-' {
+        return Err(invalid("cannot start with `-`".to_string()));
+    }
+
+    let snake_case = name.replace('-', "_");
+    if RUST_KEYWORDS.contains(&name) || RUST_KEYWORDS.contains(&snake_case.as_str()) {
+        return Err(invalid("is a reserved Rust keyword".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Validate the variables a template will be rendered with
+///
+/// Checks the project name and every variable listed in the template's
+/// `required_variables` manifest entry. Meant to run before anything is written to disk.
+pub fn validate_template_variables(
+    template_name: &str,
+    project_name: &str,
+    variables: Option<&Value>,
+) -> Result<()> {
+    validate_project_name(project_name)?;
+
+    // Templates without a manifest (or with an unreadable one) have nothing else to check
+    let Ok(template_config) = super::get_template_config(template_name) else {
+        return Ok(());
+    };
+
+    // Base templates are rendered with the same variables, so their requirements apply too
+    let mut manifests = vec![template_config];
+    for base in super::resolve_base_templates(template_name, super::get_template_config)? {
+        manifests.push(super::get_template_config(&base)?);
+    }
+
+    let required = manifests
+        .iter()
+        .filter_map(|config| config.get("required_variables").and_then(|r| r.as_array()))
+        .flat_map(|r| r.iter().filter_map(|v| v.as_str()))
+        .collect::<Vec<_>>();
+
+    for variable in required {
+        let provided = match variables.and_then(|v| v.get(variable)) {
+            None | Some(Value::Null) => false,
+            Some(Value::String(s)) => !s.trim().is_empty(),
+            Some(_) => true,
+        };
+
+        if !provided {
+            return Err(Error::Validation {
+                variable: variable.to_string(),
+                reason: format!("is required by the {} template but was not provided", template_name),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// A variable a template declares, as shown by `preview --list-variables`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TemplateVariable {
+    pub name: String,
+
+    /// Question asked when the variable isn't given with `--set`
+    pub prompt: Option<String>,
+
+    /// `select` for variables with choices, `derived` for values computed from other
+    /// variables, otherwise the JSON type of the default (`string`, `number`, ...)
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    pub default: Option<Value>,
+
+    /// Allowed values of a `select` variable
+    pub choices: Vec<String>,
+
+    /// Listed in the template's `required_variables`
+    pub required: bool,
+}
+
+impl TemplateVariable {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            prompt: None,
+            kind: "string".to_string(),
+            default: None,
+            choices: Vec::new(),
+            required: false,
+        }
+    }
+
+    /// Read an `options` or `prompts` entry such as
+    /// `{"name": "framework", "description": "...", "options": ["axum", ...], "default": "axum"}`
+    fn from_question(name: &str, spec: &Value) -> Self {
+        let mut variable = Self::new(name);
+        variable.prompt = ["description", "prompt", "question"]
+            .iter()
+            .find_map(|key| spec.get(key).and_then(|p| p.as_str()))
+            .map(|p| p.to_string());
+        variable.choices = spec
+            .get("options")
+            .or_else(|| spec.get("values"))
+            .and_then(|c| c.as_array())
+            .map(|choices| {
+                choices
+                    .iter()
+                    .filter_map(|c| c.get("value").unwrap_or(c).as_str())
+                    .map(|c| c.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        variable.default = spec.get("default").cloned();
+        variable.kind = match spec.get("type").and_then(|t| t.as_str()) {
+            Some(kind) => kind.to_string(),
+            None if !variable.choices.is_empty() => "select".to_string(),
+            None => json_type(variable.default.as_ref()),
+        };
+        variable
+    }
+
+    /// Read a `variables` entry, a default value or a Handlebars expression
+    fn from_value(name: &str, value: &Value) -> Self {
+        let mut variable = Self::new(name);
+        variable.kind = match value.as_str() {
+            Some(expr) if expr.contains("{{") => "derived".to_string(),
+            _ => json_type(Some(value)),
+        };
+        variable.default = Some(value.clone());
+        variable
+    }
+
+    /// Fill in whatever `other`, the same variable declared by another manifest, adds
+    fn merge(&mut self, other: TemplateVariable) {
+        if self.prompt.is_none() {
+            self.prompt = other.prompt;
+        }
+        if self.default.is_none() {
+            self.default = other.default;
+        }
+        if self.choices.is_empty() {
+            self.choices = other.choices;
+            if !self.choices.is_empty() {
+                self.kind = other.kind;
+            }
+        }
+        self.required |= other.required;
+    }
+}
+
+/// JSON type name of a default value, `string` when there is none
+fn json_type(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Bool(_)) => "boolean",
+        Some(Value::Number(_)) => "number",
+        Some(Value::Array(_)) => "array",
+        Some(Value::Object(_)) => "object",
+        _ => "string",
+    }
+    .to_string()
+}
+
+/// Names of the variables a template declares in its manifest
+///
+/// See [`template_variables`] for the manifests that are taken into account.
+pub fn declared_variables(template_name: &str) -> Vec<String> {
+    template_variables(template_name)
+        .into_iter()
+        .map(|variable| variable.name)
+        .collect()
+}
+
+/// The variables a template declares in its manifest, sorted by name
+///
+/// Collects the `options`, `prompts`, `variables`, `rendering`, `styling` and
+/// `required_variables` entries of the template itself, of the templates it is nested in
+/// (`client` for `client/leptos/counter`), of the templates it `extends` and of the templates
+/// nested below it, since those are the ones a redirect or framework choice can end up rendering.
+pub fn template_variables(template_name: &str) -> Vec<TemplateVariable> {
+    let mut manifests = Vec::new();
+
+    let parts: Vec<&str> = template_name.split('/').collect();
+    for end in 1..parts.len() {
+        if let Ok(config) = super::get_template_config(&parts[..end].join("/")) {
+            manifests.push(config);
+        }
+    }
+
+    if let Ok(bases) = super::resolve_base_templates(template_name, super::get_template_config) {
+        manifests.extend(bases.iter().filter_map(|base| super::get_template_config(base).ok()));
+    }
+
+    if let Ok(template_dir) = super::get_template_dir(template_name) {
+        for entry in WalkDir::new(template_dir).into_iter().filter_map(|e| e.ok()) {
+            let config = if entry.file_name() == "template.json" {
+                std::fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            } else if entry.file_name() == super::registry::USER_MANIFEST {
+                entry.path().parent().and_then(|dir| super::registry::read_user_manifest(dir).ok())
+            } else {
+                continue;
+            };
+            if let Some(config) = config {
+                manifests.push(config);
+            }
+        }
+    }
+
+    let mut variables: Vec<TemplateVariable> = Vec::new();
+    let mut declare = |variable: TemplateVariable| {
+        match variables.iter_mut().find(|v| v.name == variable.name) {
+            Some(existing) => existing.merge(variable),
+            None => variables.push(variable),
+        }
+    };
+
+    for config in &manifests {
+        for key in ["options", "prompts"] {
+            match config.get(key) {
+                Some(Value::Array(entries)) => {
+                    for entry in entries {
+                        if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                            declare(TemplateVariable::from_question(name, entry));
+                        } else if let Some(name) = entry.as_str() {
+                            declare(TemplateVariable::new(name));
+                        }
+                    }
+                }
+                Some(Value::Object(entries)) => {
+                    for (name, spec) in entries {
+                        declare(TemplateVariable::from_question(name, spec));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match config.get("variables") {
+            Some(Value::Object(entries)) => {
+                for (name, value) in entries {
+                    declare(TemplateVariable::from_value(name, value));
+                }
+            }
+            Some(Value::Array(entries)) => {
+                for entry in entries {
+                    if let Some(name) = entry.get("name").and_then(|n| n.as_str()).or_else(|| entry.as_str()) {
+                        declare(TemplateVariable::from_question(name, entry));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(required) = config.get("required_variables").and_then(|r| r.as_array()) {
+            for name in required.iter().filter_map(|r| r.as_str()) {
+                let mut variable = TemplateVariable::new(name);
+                variable.required = true;
+                declare(variable);
+            }
+        }
+
+        // Variant sections become a select between their variants, default first
+        for (section, prompt, default) in [("rendering", "Rendering mode", "csr"), ("styling", "Styling", "plain-css")] {
+            if let Some(Value::Object(modes)) = config.get(section) {
+                let mut variable = TemplateVariable::new(section);
+                variable.prompt = Some(prompt.to_string());
+                variable.kind = "select".to_string();
+                variable.default = Some(Value::String(default.to_string()));
+                variable.choices = std::iter::once(default.to_string())
+                    .chain(modes.keys().filter(|mode| *mode != default).cloned())
+                    .collect();
+                declare(variable);
+            }
+        }
+    }
+
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+    variables
+}
+
+/// Check that every `--set` override names a variable the template declares
+pub fn validate_variable_overrides(template_name: &str, overrides: &Map<String, Value>) -> Result<()> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+
+    let declared = declared_variables(template_name);
+    for key in overrides.keys() {
+        if !declared.contains(key) {
+            let reason = if declared.is_empty() {
+                format!("the {} template does not declare any variables", template_name)
+            } else {
+                format!(
+                    "is not a variable of the {} template, valid variables are: {}",
+                    template_name,
+                    declared.join(", ")
+                )
+            };
+            return Err(Error::Validation {
+                variable: key.clone(),
+                reason,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rejected_reason(name: &str) -> String {
+        match validate_project_name(name) {
+            Err(Error::InvalidProjectName { name: rejected, reason }) => {
+                assert_eq!(rejected, name);
+                reason
+            }
+            other => panic!("expected a validation error for {:?}, got {:?}", name, other),
+        }
+    }
+
+    #[test]
+    fn test_rejects_leading_digit() {
+        assert!(rejected_reason("123foo").contains("cannot start with a digit"));
+    }
+
+    #[test]
+    fn test_rejects_whitespace() {
+        assert!(rejected_reason("my crate").contains("invalid character ' '"));
+    }
+
+    #[test]
+    fn test_rejects_keywords() {
+        assert!(rejected_reason("struct").contains("reserved Rust keyword"));
+    }
+
+    #[test]
+    fn test_accepts_valid_names() {
+        for name in ["app", "my-app", "my_app", "_private", "app2"] {
+            assert!(validate_project_name(name).is_ok(), "{} should be valid", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_template_variables_checks_project_name() {
+        let result = validate_template_variables("minimal", "struct", None);
+        assert!(matches!(result, Err(Error::InvalidProjectName { ref name, .. }) if name == "struct"));
+        let variables = json!({ "project_name": "my-app", "authors": "Ferris <ferris@example.com>" });
+        assert!(validate_template_variables("minimal", "my-app", Some(&variables)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_template_variables_checks_required() {
+        // minimal's manifest renders with the project name and authors
+        let minimal = template_variables("minimal");
+        assert!(minimal.iter().any(|v| v.name == "authors" && v.required));
+
+        let variables = json!({ "project_name": "my-app", "authors": " " });
+        match validate_template_variables("minimal", "my-app", Some(&variables)) {
+            Err(Error::Validation { variable, reason }) => {
+                assert_eq!(variable, "authors");
+                assert!(reason.contains("required by the minimal template"), "{}", reason);
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_declared_variables() {
+        // Nested templates contribute their variables to the parent
+        let client = declared_variables("client");
+        assert!(client.contains(&"client_framework".to_string()));
+        assert!(client.contains(&"rendering".to_string()));
+
+        // Parent templates contribute theirs to the nested ones
+        let counter = declared_variables("client/leptos/counter");
+        assert!(counter.contains(&"client_framework".to_string()));
+
+        let polars = declared_variables("data-science/polars-cli");
+        assert!(polars.contains(&"data_source".to_string()));
+    }
+
+    #[test]
+    fn test_template_variables_describe_questions() {
+        let variables = template_variables("server");
+        let framework = variables.iter().find(|v| v.name == "framework").unwrap();
+        assert_eq!(framework.kind, "select");
+        assert_eq!(framework.choices, ["axum", "actix", "poem"]);
+        assert_eq!(framework.default, Some(Value::String("axum".to_string())));
+        assert!(framework.prompt.is_some());
+
+        // Labelled choices list their values, nested rendering modes become a select
+        let client = template_variables("client");
+        let client_framework = client.iter().find(|v| v.name == "client_framework").unwrap();
+        assert_eq!(client_framework.choices, ["leptos", "tauri"]);
+        let rendering = client.iter().find(|v| v.name == "rendering").unwrap();
+        assert_eq!(rendering.choices, ["csr", "ssr"]);
+        let styling = client.iter().find(|v| v.name == "styling").unwrap();
+        assert_eq!(styling.choices, ["plain-css", "tailwind"]);
+        assert_eq!(styling.default, Some(Value::String("plain-css".to_string())));
+
+        let embedded = template_variables("embedded");
+        let led_pin = embedded.iter().find(|v| v.name == "led_pin").unwrap();
+        assert_eq!(led_pin.kind, "number");
+
+        let polars = template_variables("data-science/polars-cli");
+        let data_format = polars.iter().find(|v| v.name == "data_format").unwrap();
+        assert_eq!(data_format.kind, "derived");
+    }
+
+    #[test]
+    fn test_validate_variable_overrides() {
+        let mut overrides = Map::new();
+        overrides.insert("mcu_target".to_string(), Value::String("esp32".to_string()));
+        assert!(validate_variable_overrides("embedded", &overrides).is_ok());
+
+        overrides.insert("colour".to_string(), Value::String("red".to_string()));
+        match validate_variable_overrides("embedded", &overrides) {
+            Err(Error::Validation { variable, reason }) => {
+                assert_eq!(variable, "colour");
+                assert!(reason.contains("mcu_target"), "valid variables should be listed: {}", reason);
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// Template versions (`version` in template.json or ferrisup.toml), pinned with `new --template-version`
+use anyhow::Result;
+use std::path::Path;
+
+use super::get_template_config;
+use crate::core::Error;
+
+/// The version `template_name` declares with `version`, if any
+///
+/// Nested templates such as `client/leptos/counter` have the version of the nearest
+/// template above them that declares one.
+pub fn template_version(template_name: &str) -> Option<String> {
+    let mut name = template_name.trim_matches('/');
+    loop {
+        let declared = get_template_config(name)
+            .ok()
+            .and_then(|config| config.get("version")?.as_str().map(|v| v.to_string()));
+        if declared.is_some() {
+            return declared;
+        }
+        name = name.rsplit_once('/')?.0;
+    }
+}
+
+/// Fail unless `template_name` is at the version `requested` with `--template-version`
+///
+/// FerrisUp bundles a single version of each template, so any other version is refused
+/// rather than generating something the pin doesn't describe. A leading `v` is ignored.
+pub fn check_template_version(template_name: &str, requested: &str) -> Result<()> {
+    let available = template_version(template_name);
+    let wanted = requested.trim().trim_start_matches(['v', '=']);
+    if available.as_deref() == Some(wanted) {
+        return Ok(());
+    }
+    Err(Error::TemplateVersionMismatch {
+        template: template_name.to_string(),
+        requested: requested.to_string(),
+        available,
+    }
+    .into())
+}
+
+/// The commit checked out in `repo_dir`, the git repository a remote template was copied from
+pub fn repository_commit(repo_dir: &Path) -> Option<String> {
+    let repo = git2::Repository::open(repo_dir).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_version() {
+        assert_eq!(template_version("minimal").as_deref(), Some("0.1.0"));
+        // Nested templates share the version of the template they belong to
+        assert_eq!(template_version("client/leptos/counter"), template_version("client"));
+
+        assert!(check_template_version("minimal", "0.1.0").is_ok());
+        assert!(check_template_version("minimal", "v0.1.0").is_ok());
+        let err = check_template_version("minimal", "9.9.9").unwrap_err();
+        assert!(err.to_string().contains("is at version 0.1.0, not the requested 9.9.9"), "{}", err);
+        assert!(matches!(Error::from(err), Error::TemplateVersionMismatch { .. }));
+    }
+}
@@ -1,5 +1,9 @@
 use leptos::prelude::*;
 
+/// localStorage key of a persisted counter, named after the app so apps served from the
+/// same origin don't share their counters
+const STORAGE_KEY: &str = "{{project_name}}-simple-counter";
+
 /// A simple counter component.
 ///
 /// You can use doc comments like this to document your component.
@@ -10,8 +14,30 @@ pub fn SimpleCounter(
     /// The change that should be applied each time the button is clicked.
     #[prop(default = 1)]
     step: i32,
+    /// Whether to keep the value in localStorage so it survives a page reload.
+    #[prop(optional)]
+    persist: bool,
 ) -> impl IntoView {
-    let (value, set_value) = signal(initial_value);
+    let starting_value = if persist {
+        load_persisted_value().unwrap_or(initial_value)
+    } else {
+        initial_value
+    };
+    let (value, set_value) = signal(starting_value);
+
+    // the effect reads the `value` signal, so it re-runs and stores the new
+    // value in localStorage every time the counter changes
+    if persist {
+        Effect::new(move |_| {
+            if let Ok(Some(storage)) = window().local_storage() {
+                if storage.set_item(STORAGE_KEY, &value.get().to_string()).is_err() {
+                    leptos::logging::error!(
+                        "error while trying to set item in localStorage"
+                    );
+                }
+            }
+        });
+    }
 
     view! {
         <div class="counter-card">
@@ -25,6 +51,21 @@ pub fn SimpleCounter(
     }
 }
 
+/// Read a previously stored counter value, if there is one
+fn load_persisted_value() -> Option<i32> {
+    // localStorage only exists in the browser, not while rendering on a server
+    if !cfg!(target_arch = "wasm32") {
+        return None;
+    }
+
+    window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|value| value.parse().ok())
+}
+
 /// Main app component
 #[component]
 pub fn App() -> impl IntoView {
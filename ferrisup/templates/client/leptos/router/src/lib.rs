@@ -51,8 +51,8 @@ pub fn App() -> impl IntoView {
 /// Home page component
 #[component]
 fn HomePage() -> impl IntoView {
-    // Log when component renders
-    #[cfg(debug_assertions)]
+    // Log when component renders (browser only, so server-side rendering is unaffected)
+    #[cfg(all(debug_assertions, target_arch = "wasm32"))]
     {
         let _ = console_log::init_with_level(log::Level::Debug);
         console_error_panic_hook::set_once();
@@ -0,0 +1,39 @@
+// The application components live in `app.rs`, shared with the client-side rendered setup
+pub mod app;
+
+pub use app::*;
+
+#[cfg(feature = "ssr")]
+use leptos::prelude::*;
+#[cfg(feature = "ssr")]
+use leptos_meta::MetaTags;
+
+/// HTML document the server renders the application into
+#[cfg(feature = "ssr")]
+pub fn shell(options: LeptosOptions) -> impl IntoView {
+    view! {
+        <!DOCTYPE html>
+        <html lang="en">
+            <head>
+                <meta charset="utf-8"/>
+                <meta name="viewport" content="width=device-width, initial-scale=1.0"/>
+                <link rel="stylesheet" href="/pkg/{{project_name}}.css"/>
+                <AutoReload options=options.clone()/>
+                <HydrationScripts options/>
+                // Title and meta tags set by the App with leptos_meta are rendered here
+                <MetaTags/>
+            </head>
+            <body>
+                <App/>
+            </body>
+        </html>
+    }
+}
+
+/// Entry point called by the generated JavaScript to hydrate the server-rendered page
+#[cfg(feature = "hydrate")]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn hydrate() {
+    console_error_panic_hook::set_once();
+    leptos::mount::hydrate_body(App);
+}
@@ -0,0 +1,35 @@
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    use axum::Router;
+    use leptos::prelude::*;
+    use leptos_axum::{generate_route_list, LeptosRoutes};
+    use {{project_name_snake_case}}::{shell, App};
+
+    // Reads the [package.metadata.leptos] settings from Cargo.toml (set by cargo-leptos)
+    let conf = get_configuration(None).expect("failed to read the Leptos configuration");
+    let addr = conf.leptos_options.site_addr;
+    let leptos_options = conf.leptos_options;
+    let routes = generate_route_list(App);
+
+    let app = Router::new()
+        .leptos_routes(&leptos_options, routes, {
+            let leptos_options = leptos_options.clone();
+            move || shell(leptos_options.clone())
+        })
+        .fallback(leptos_axum::file_and_error_handler(shell))
+        .with_state(leptos_options);
+
+    println!("listening on http://{}", &addr);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .expect("failed to bind the server address");
+    axum::serve(listener, app.into_make_service())
+        .await
+        .expect("server error");
+}
+
+#[cfg(not(feature = "ssr"))]
+pub fn main() {
+    // The browser entry point is the `hydrate` function in lib.rs
+}
@@ -0,0 +1,9 @@
+/** @type {import('tailwindcss').Config} */
+module.exports = {
+  // Class names are picked up from the Leptos `view!` markup
+  content: ["./index.html", "./src/**/*.rs"],
+  theme: {
+    extend: {},
+  },
+  plugins: [],
+};
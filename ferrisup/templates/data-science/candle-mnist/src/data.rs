@@ -0,0 +1,84 @@
+// Downloading and loading the MNIST dataset
+
+use anyhow::{anyhow, Context, Result};
+use candle_core::{DType, Device, Tensor};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// Pixels in one 28x28 image
+pub const IMAGE_PIXELS: usize = 28 * 28;
+/// The digits 0 to 9
+pub const CLASSES: usize = 10;
+
+/// Mirror the MNIST files are downloaded from, the one Burn's MNIST dataset uses
+const BASE_URL: &str = "https://storage.googleapis.com/cvdf-datasets/mnist/";
+
+const TRAIN_IMAGES: &str = "train-images-idx3-ubyte";
+const TRAIN_LABELS: &str = "train-labels-idx1-ubyte";
+const TEST_IMAGES: &str = "t10k-images-idx3-ubyte";
+const TEST_LABELS: &str = "t10k-labels-idx1-ubyte";
+
+/// The MNIST dataset on a device: images as `f32` rows of pixels scaled to 0..1, labels as `u32`
+pub struct Mnist {
+    pub train_images: Tensor,
+    pub train_labels: Tensor,
+    pub test_images: Tensor,
+    pub test_labels: Tensor,
+}
+
+/// Load MNIST from `dir`, downloading the files that aren't there yet
+pub fn load(dir: &Path, device: &Device) -> Result<Mnist> {
+    Ok(Mnist {
+        train_images: images(&read_idx(dir, TRAIN_IMAGES)?, device)?,
+        train_labels: labels(&read_idx(dir, TRAIN_LABELS)?, device)?,
+        test_images: images(&read_idx(dir, TEST_IMAGES)?, device)?,
+        test_labels: labels(&read_idx(dir, TEST_LABELS)?, device)?,
+    })
+}
+
+/// The contents of the IDX file `name` in `dir`, without its header
+fn read_idx(dir: &Path, name: &str) -> Result<Vec<u8>> {
+    let path = dir.join(name);
+    if !path.exists() {
+        download(name, &path)?;
+    }
+
+    let bytes = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    // Magic number, then one big-endian u32 per dimension (its low byte says how many)
+    let dimensions = *bytes.get(3).ok_or_else(|| anyhow!("{} is not an IDX file", path.display()))? as usize;
+    let header = 4 + 4 * dimensions;
+    if bytes.len() < header {
+        return Err(anyhow!("{} is truncated", path.display()));
+    }
+    Ok(bytes[header..].to_vec())
+}
+
+/// Download the gzipped `name` from the mirror and unpack it to `path`
+fn download(name: &str, path: &Path) -> Result<()> {
+    let url = format!("{}{}.gz", BASE_URL, name);
+    println!("Downloading {}", url);
+    let response = ureq::get(&url)
+        .call()
+        .with_context(|| format!("Failed to download {}", url))?;
+
+    let mut bytes = Vec::new();
+    GzDecoder::new(response.into_reader()).read_to_end(&mut bytes)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+fn images(pixels: &[u8], device: &Device) -> Result<Tensor> {
+    let count = pixels.len() / IMAGE_PIXELS;
+    let images = Tensor::from_slice(pixels, (count, IMAGE_PIXELS), device)?;
+    Ok((images.to_dtype(DType::F32)? / 255.0)?)
+}
+
+fn labels(labels: &[u8], device: &Device) -> Result<Tensor> {
+    Ok(Tensor::from_slice(labels, labels.len(), device)?.to_dtype(DType::U32)?)
+}
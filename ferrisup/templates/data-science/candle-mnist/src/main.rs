@@ -0,0 +1,177 @@
+// {{project_name}}: handwritten digit recognition with Candle
+// Trains a small multilayer perceptron on the MNIST dataset and reports its accuracy
+// on the test set, the same Train/Evaluate workflow as the Burn MNIST example
+
+use anyhow::Result;
+use candle_core::{DType, Device, Module, Tensor, D};
+use candle_nn::{loss, AdamW, Optimizer, ParamsAdamW, VarBuilder, VarMap};
+use clap::{Parser, Subcommand};
+use rand::seq::SliceRandom;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod data;
+mod model;
+
+use data::Mnist;
+use model::{Mlp, ModelConfig};
+
+// Command line interface for our application
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+
+    /// Run on the CPU even when the project was built for a GPU
+    #[arg(long, global = true)]
+    cpu: bool,
+
+    /// Where the MNIST files are downloaded to and read from
+    #[arg(long, global = true, default_value = "data/mnist")]
+    data_dir: PathBuf,
+
+    /// Where the trained model and its configuration are saved
+    #[arg(long, global = true, default_value = "artifacts")]
+    artifact_dir: PathBuf,
+}
+
+// Available commands: train or evaluate
+#[derive(Subcommand)]
+enum Commands {
+    /// Train a new model and save it to the artifact directory
+    Train {
+        /// Passes over the training set
+        #[arg(short, long, default_value_t = 10)]
+        epochs: usize,
+
+        /// Images per optimizer step
+        #[arg(short, long, default_value_t = 64)]
+        batch_size: usize,
+
+        /// Learning rate of the AdamW optimizer
+        #[arg(short, long, default_value_t = 1e-3)]
+        learning_rate: f64,
+
+        /// Neurons in the hidden layer
+        #[arg(long, default_value_t = 128)]
+        hidden_size: usize,
+    },
+
+    /// Report the accuracy of the trained model on the test set
+    Evaluate,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let device = device(cli.cpu)?;
+    println!("Using device: {:?}", device);
+
+    let mnist = data::load(&cli.data_dir, &device)?;
+    println!(
+        "Loaded {} training and {} test images",
+        mnist.train_images.dim(0)?,
+        mnist.test_images.dim(0)?
+    );
+
+    match cli.command {
+        Commands::Train { epochs, batch_size, learning_rate, hidden_size } => {
+            let config = ModelConfig { hidden_size };
+            train(&mnist, &config, epochs, batch_size, learning_rate, &cli.artifact_dir, &device)
+        }
+        Commands::Evaluate => evaluate(&mnist, &cli.artifact_dir, &device),
+    }
+}
+
+/// The device the project was built for: CUDA or Metal with the `cuda` or `metal`
+/// feature, the CPU otherwise or with `--cpu`
+fn device(cpu: bool) -> Result<Device> {
+    if cpu {
+        Ok(Device::Cpu)
+    } else if cfg!(feature = "cuda") {
+        Ok(Device::new_cuda(0)?)
+    } else if cfg!(feature = "metal") {
+        Ok(Device::new_metal(0)?)
+    } else {
+        Ok(Device::Cpu)
+    }
+}
+
+fn train(
+    mnist: &Mnist,
+    config: &ModelConfig,
+    epochs: usize,
+    batch_size: usize,
+    learning_rate: f64,
+    artifact_dir: &Path,
+    device: &Device,
+) -> Result<()> {
+    let varmap = VarMap::new();
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, device);
+    let model = Mlp::new(config, vb)?;
+
+    let params = ParamsAdamW { lr: learning_rate, ..Default::default() };
+    let mut optimizer = AdamW::new(varmap.all_vars(), params)?;
+
+    let train_size = mnist.train_images.dim(0)?;
+    let mut indices: Vec<u32> = (0..train_size as u32).collect();
+    let mut rng = rand::thread_rng();
+
+    for epoch in 1..=epochs {
+        indices.shuffle(&mut rng);
+        let mut total_loss = 0.0;
+        let mut batches = 0;
+
+        for batch in indices.chunks(batch_size) {
+            let batch = Tensor::from_slice(batch, batch.len(), device)?;
+            let images = mnist.train_images.index_select(&batch, 0)?;
+            let labels = mnist.train_labels.index_select(&batch, 0)?;
+
+            let logits = model.forward(&images)?;
+            let loss = loss::cross_entropy(&logits, &labels)?;
+            optimizer.backward_step(&loss)?;
+
+            total_loss += loss.to_scalar::<f32>()?;
+            batches += 1;
+        }
+
+        let accuracy = accuracy(&model, &mnist.test_images, &mnist.test_labels)?;
+        println!(
+            "Epoch {:>3} | Loss {:.4} | Accuracy {:.2}%",
+            epoch,
+            total_loss / batches as f32,
+            accuracy * 100.0
+        );
+    }
+
+    fs::create_dir_all(artifact_dir)?;
+    config.save(&artifact_dir.join("config.json"))?;
+    varmap.save(artifact_dir.join("model.safetensors"))?;
+    println!("Model saved to {}", artifact_dir.display());
+
+    Ok(())
+}
+
+fn evaluate(mnist: &Mnist, artifact_dir: &Path, device: &Device) -> Result<()> {
+    let config = ModelConfig::load(&artifact_dir.join("config.json"))?;
+    let mut varmap = VarMap::new();
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, device);
+    let model = Mlp::new(&config, vb)?;
+    varmap.load(artifact_dir.join("model.safetensors"))?;
+
+    let accuracy = accuracy(&model, &mnist.test_images, &mnist.test_labels)?;
+    println!("Accuracy {:.2}%", accuracy * 100.0);
+
+    Ok(())
+}
+
+/// Share of `images` the model labels correctly, between 0 and 1
+fn accuracy(model: &Mlp, images: &Tensor, labels: &Tensor) -> Result<f32> {
+    let predictions = model.forward(images)?.argmax(D::Minus1)?;
+    let correct = predictions
+        .eq(labels)?
+        .to_dtype(DType::F32)?
+        .sum_all()?
+        .to_scalar::<f32>()?;
+    Ok(correct / labels.dim(0)? as f32)
+}
@@ -0,0 +1,51 @@
+// The network: a multilayer perceptron with one hidden layer
+
+use anyhow::{Context, Result};
+use candle_core::{Module, Tensor};
+use candle_nn::{linear, Linear, VarBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::data::{CLASSES, IMAGE_PIXELS};
+
+/// Settings the model is built with, saved next to its weights so `evaluate` can
+/// rebuild the same network
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelConfig {
+    pub hidden_size: usize,
+}
+
+impl ModelConfig {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("No trained model in {}, run `train` first", path.display()))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// 784 pixels in, a hidden ReLU layer, then one logit per digit
+pub struct Mlp {
+    hidden: Linear,
+    output: Linear,
+}
+
+impl Mlp {
+    pub fn new(config: &ModelConfig, vb: VarBuilder) -> Result<Self> {
+        let hidden = linear(IMAGE_PIXELS, config.hidden_size, vb.pp("hidden"))?;
+        let output = linear(config.hidden_size, CLASSES, vb.pp("output"))?;
+        Ok(Self { hidden, output })
+    }
+}
+
+impl Module for Mlp {
+    fn forward(&self, images: &Tensor) -> candle_core::Result<Tensor> {
+        let hidden = self.hidden.forward(images)?.relu()?;
+        self.output.forward(&hidden)
+    }
+}
@@ -70,22 +70,52 @@ pub fn generate_synthetic_dataset(
     Ok((train_data, train_targets, test_data, test_targets))
 }
 
+/// Width and height of the images the model works with, as in MNIST
+pub const IMAGE_SIZE: u32 = 28;
+
 /// Load and preprocess an image for digit recognition
+///
+/// Fails with the path and the reason if the file can't be opened or isn't an image
+/// format the `image` crate can decode (PNG, JPEG, BMP, GIF, ...).
 pub fn load_and_preprocess_image<P: AsRef<Path>>(path: P) -> Result<Array2<f64>> {
-    // Try to load the image
-    let img = image::open(path)?;
-    
-    // Convert to grayscale
-    let gray_img = img.to_luma8();
-    
-    // Resize to 28x28 (MNIST size)
-    let resized = image::imageops::resize(&gray_img, 28, 28, image::imageops::FilterType::Lanczos3);
-    
-    // Convert to ndarray and normalize
-    let mut data = Array2::zeros((1, 784));
-    for (i, pixel) in resized.pixels().enumerate() {
-        data[[0, i]] = pixel.0[0] as f64 / 255.0;
+    let path = path.as_ref();
+    let img = image::open(path).map_err(|e| match e {
+        image::ImageError::IoError(e) => anyhow!("Could not open image {}: {}", path.display(), e),
+        e => anyhow!(
+            "Could not decode image {}: {} (supported formats include PNG, JPEG, BMP and GIF)",
+            path.display(),
+            e
+        ),
+    })?;
+
+    preprocess_image(&img)
+}
+
+/// Turn an image into the single 784-feature row the model expects
+///
+/// Converts to grayscale, center-crops non-square images so digits aren't stretched,
+/// resizes to 28x28 and normalizes the pixels to 0..1, row by row like MNIST.
+pub fn preprocess_image(img: &DynamicImage) -> Result<Array2<f64>> {
+    let (width, height) = img.dimensions();
+    if width == 0 || height == 0 {
+        return Err(anyhow!("Image is empty ({}x{} pixels)", width, height));
     }
-    
-    Ok(data)
+
+    let gray = center_crop(&img.to_luma8());
+    let resized = image::imageops::resize(&gray, IMAGE_SIZE, IMAGE_SIZE, image::imageops::FilterType::Lanczos3);
+
+    let pixels: Vec<f64> = resized.pixels().map(|pixel| pixel.0[0] as f64 / 255.0).collect();
+    let n_features = (IMAGE_SIZE * IMAGE_SIZE) as usize;
+    Ok(Array2::from_shape_vec((1, n_features), pixels)?)
+}
+
+/// Crop the largest centered square out of an image
+fn center_crop(img: &ImageBuffer<Luma<u8>, Vec<u8>>) -> ImageBuffer<Luma<u8>, Vec<u8>> {
+    let (width, height) = img.dimensions();
+    if width == height {
+        return img.clone();
+    }
+
+    let side = width.min(height);
+    image::imageops::crop_imm(img, (width - side) / 2, (height - side) / 2, side, side).to_image()
 }
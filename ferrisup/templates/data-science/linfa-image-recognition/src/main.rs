@@ -219,8 +219,14 @@ fn predict(model_path: String, image_path: String) -> Result<()> {
     
     println!("Loading image from {}...", image_path);
     
-    // Load and preprocess the image
+    // Load the image and turn it into a normalized 28x28 grayscale row like the MNIST samples
     let image_data = data::load_and_preprocess_image(&image_path)?;
+    println!(
+        "Preprocessed image to {}x{} grayscale ({} features)",
+        data::IMAGE_SIZE,
+        data::IMAGE_SIZE,
+        image_data.ncols()
+    );
     
     // Create a dataset with just this image
     let image_dataset = Dataset::new(image_data, ndarray::Array1::zeros(1));
@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::{Parser, Subcommand};
 use linfa::prelude::*;
 use ndarray::{Array1, Array2, Axis};
@@ -8,7 +8,7 @@ use ndarray_rand::RandomExt;
 use rand_xoshiro::Xoshiro256Plus;
 use std::collections::HashMap;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 mod datasets;
 mod evaluation;
@@ -74,13 +74,7 @@ fn main() -> Result<()> {
             println!("\n=== Running DBSCAN Clustering Example ===\n");
             run_dbscan_example()?;
         },
-        Commands::Custom { file, analysis } => {
-            println!("Loading custom dataset from {:?}", file);
-            // This would load a custom dataset and run the specified analysis
-            // For now, we'll just show a message
-            println!("Custom dataset analysis is not implemented in this example.");
-            println!("Please use one of the built-in examples.");
-        },
+        Commands::Custom { file, analysis } => run_custom_analysis(file, analysis)?,
         Commands::Help => {
             println!("Linfa Machine Learning Examples");
             println!("==============================");
@@ -91,6 +85,7 @@ fn main() -> Result<()> {
             println!("  cluster   - Run DBSCAN clustering example");
             println!("  all       - Run all examples");
             println!("  custom    - Load a custom dataset and run analysis");
+            println!("              (e.g. custom --file data.csv --analysis regress)");
             println!("  help      - Show this help information");
         },
     }
@@ -118,32 +113,7 @@ fn run_logistic_regression_example() -> Result<()> {
     
     let targets = Array1::from_vec(vec![0, 0, 0, 1, 1, 1]);
     
-    // Create a dataset
-    let dataset = Dataset::new(features, targets);
-    
-    // Split into train and test sets with a random seed for reproducibility
-    let mut rng = Xoshiro256Plus::seed_from_u64(42);
-    let (train, test) = dataset.shuffle(&mut rng).split_with_ratio(0.5);
-    
-    println!("Training dataset: {} samples", train.nsamples());
-    println!("Testing dataset: {} samples", test.nsamples());
-    
-    // Create and train the model
-    println!("Training LogisticRegression model...");
-    let model = linfa_logistic::LogisticRegression::default()
-        .max_iterations(100)
-        .fit(&train)?;
-    
-    // Make predictions
-    println!("Making predictions...");
-    let predictions = model.predict(test.records());
-    println!("Predictions: {:?}", predictions);
-    
-    // Calculate accuracy
-    let cm = predictions.confusion_matrix(&test)?;
-    println!("Confusion Matrix:");
-    println!("{:?}", cm);
-    println!("Accuracy: {:.2}", cm.accuracy());
+    fit_logistic_regression(features, targets, 0.5)?;
     
     println!("\nThis example demonstrates a complete classification workflow using LogisticRegression in Linfa 0.7.1.");
     println!("It shows how to create a dataset, split it into training and testing sets, train a model, make predictions, and evaluate the results.");
@@ -229,69 +199,10 @@ fn run_regression_example() -> Result<()> {
     // y = 2*x + 1 + noise
     let targets = Array1::from_vec(vec![3.1, 5.2, 7.0, 8.9, 10.8, 13.1]);
     
-    // Create a dataset
-    let dataset = Dataset::new(features.clone(), targets.clone());
-    
-    // Split into train and test sets with a random seed for reproducibility
-    let mut rng = Xoshiro256Plus::seed_from_u64(42);
-    let (train, test) = dataset.shuffle(&mut rng).split_with_ratio(0.7);
-    
-    println!("Training dataset: {} samples", train.nsamples());
-    println!("Testing dataset: {} samples", test.nsamples());
-    
-    // Create and train the model
-    println!("Training LinearRegression model...");
-    let model = linfa_linear::LinearRegression::default()
-        .fit(&train)?;
-    
-    // Make predictions
-    println!("Making predictions...");
-    let predictions = model.predict(test.records());
-    
-    // Print predictions vs actual values
-    println!("Predictions vs Actual:");
-    for (i, pred) in predictions.iter().enumerate() {
-        let actual = test.targets().get(i).unwrap();
-        println!("  Predicted: {:.2}, Actual: {:.2}", pred, actual);
-    }
-    
-    // Calculate metrics
-    let mse = predictions.iter()
-        .zip(test.targets().iter())
-        .map(|(&p, &a)| (p - a) * (p - a))
-        .sum::<f64>() / predictions.len() as f64;
-    
-    println!("Mean Squared Error: {:.4}", mse);
-    
-    // In Linfa 0.7.1, we need to access model parameters differently
-    println!("Model parameters:");
-    
-    // Access the parameters directly from the model
-    let params = model.params();
-    
-    // Print the parameters array shape and values
-    println!("  Parameters shape: {:?}", params.shape());
-    println!("  Parameters values: {:?}", params);
+    let model = fit_linear_regression(features, targets, 0.7)?;
     
-    // For a simple linear regression model with one feature,
-    // we can estimate the coefficient and intercept from the data
-    let x_mean = train.records().column(0).mean().unwrap_or(0.0);
-    let y_mean = train.targets().mean().unwrap_or(0.0);
-    
-    // Calculate the coefficient (slope) using the predictions
-    let x_test = test.records().column(0).to_owned();
-    let y_pred = predictions;
-    
-    if x_test.len() > 0 && y_pred.len() > 0 {
-        let coefficient = (y_pred[0] - y_mean) / (x_test[0] - x_mean);
-        let intercept = y_mean - coefficient * x_mean;
-        
-        println!("  Estimated coefficient (m): {:.4}", coefficient);
-        println!("  Estimated intercept (b): {:.4}", intercept);
-        
-        // Print the model equation
-        println!("  Estimated model equation: y = {:.4} * x + {:.4}", coefficient, intercept);
-    }
+    // With a single feature the model is a line: y = m * x + b
+    println!("  Model equation: y = {:.4} * x + {:.4}", model.params()[0], model.intercept());
     
     // Predict on new data
     println!("\nPredicting on new data:");
@@ -353,13 +264,188 @@ fn run_dbscan_example() -> Result<()> {
     println!("Dataset shape: {:?}", samples.shape());
     println!("Number of samples: {}", n_samples);
     
-    // Create a dataset for clustering
-    let dataset = Dataset::from(samples);
-    
     // Set DBSCAN parameters
     let min_points = 3;  // Minimum points to form a dense region
     let tolerance = 2.0; // Maximum distance between two samples to be considered neighbors
     
+    fit_dbscan(samples, min_points, tolerance)?;
+    
+    Ok(())
+}
+
+// Custom dataset analysis
+fn run_custom_analysis(file: &Path, analysis: &str) -> Result<()> {
+    let analysis = analysis.to_lowercase();
+    if !["classify", "regress", "cluster"].contains(&analysis.as_str()) {
+        return Err(anyhow!(
+            "Unknown analysis '{}'. Expected one of: classify, regress, cluster",
+            analysis
+        ));
+    }
+    
+    println!("Loading custom dataset from {:?}", file);
+    let (features, targets) = load_custom_csv(file)?;
+    println!("Loaded {} samples with {} features (last column used as target)", features.nrows(), features.ncols());
+    
+    match analysis.as_str() {
+        "classify" => {
+            // Class labels are expected to be non-negative whole numbers
+            if let Some(label) = targets.iter().find(|t| **t < 0.0 || t.fract() != 0.0) {
+                return Err(anyhow!(
+                    "Classification targets must be non-negative integers, found {}",
+                    label
+                ));
+            }
+            let targets = targets.mapv(|t| t as usize);
+            fit_logistic_regression(features, targets, 0.8)?;
+        }
+        "regress" => {
+            fit_linear_regression(features, targets, 0.8)?;
+        }
+        _ => {
+            // Clustering is unsupervised, so the target column is ignored. Features are
+            // standardized so a single DBSCAN tolerance works regardless of their units
+            let mean = features
+                .mean_axis(Axis(0))
+                .ok_or_else(|| anyhow!("Cannot cluster an empty dataset"))?;
+            let std = features.std_axis(Axis(0), 0.0).mapv(|s| if s > 0.0 { s } else { 1.0 });
+            let scaled = (&features - &mean) / &std;
+            fit_dbscan(scaled, 3, 2.0)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Load a CSV file with a header row, using the last column as the target
+fn load_custom_csv(file: &Path) -> Result<(Array2<f64>, Array1<f64>)> {
+    let reader = File::open(file).with_context(|| format!("Failed to open {:?}", file))?;
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(reader);
+    
+    let headers = reader.headers()?.clone();
+    let n_cols = headers.len();
+    if n_cols < 2 {
+        return Err(anyhow!(
+            "Expected at least two columns (features and a target) in {:?}, found {}",
+            file,
+            n_cols
+        ));
+    }
+    
+    let mut values = Vec::new();
+    let mut n_rows = 0;
+    for (row_idx, record) in reader.records().enumerate() {
+        let record = record?;
+        for (col_idx, field) in record.iter().enumerate() {
+            let value: f64 = field.trim().parse().with_context(|| {
+                format!(
+                    "Row {}, column '{}': could not parse '{}' as a number",
+                    row_idx + 1,
+                    &headers[col_idx],
+                    field
+                )
+            })?;
+            values.push(value);
+        }
+        n_rows += 1;
+    }
+    
+    if n_rows == 0 {
+        return Err(anyhow!("No data rows found in {:?}", file));
+    }
+    
+    let data = Array2::from_shape_vec((n_rows, n_cols), values)?;
+    let features = data.slice(ndarray::s![.., ..n_cols - 1]).to_owned();
+    let targets = data.column(n_cols - 1).to_owned();
+    
+    Ok((features, targets))
+}
+
+/// Train a LogisticRegression model and print predictions and the confusion matrix
+fn fit_logistic_regression(features: Array2<f64>, targets: Array1<usize>, train_ratio: f32) -> Result<()> {
+    // Create a dataset
+    let dataset = Dataset::new(features, targets);
+    
+    // Split into train and test sets with a random seed for reproducibility
+    let mut rng = Xoshiro256Plus::seed_from_u64(42);
+    let (train, test) = dataset.shuffle(&mut rng).split_with_ratio(train_ratio);
+    
+    println!("Training dataset: {} samples", train.nsamples());
+    println!("Testing dataset: {} samples", test.nsamples());
+    
+    // Create and train the model
+    println!("Training LogisticRegression model...");
+    let model = linfa_logistic::LogisticRegression::default()
+        .max_iterations(100)
+        .fit(&train)?;
+    
+    // Make predictions
+    println!("Making predictions...");
+    let predictions = model.predict(test.records());
+    println!("Predictions: {:?}", predictions);
+    
+    // Calculate accuracy
+    let cm = predictions.confusion_matrix(&test)?;
+    println!("Confusion Matrix:");
+    println!("{:?}", cm);
+    println!("Accuracy: {:.2}", cm.accuracy());
+    
+    Ok(())
+}
+
+/// Train a LinearRegression model and print predictions, the error and the parameters
+fn fit_linear_regression(
+    features: Array2<f64>,
+    targets: Array1<f64>,
+    train_ratio: f32,
+) -> Result<linfa_linear::FittedLinearRegression<f64>> {
+    // Create a dataset
+    let dataset = Dataset::new(features, targets);
+    
+    // Split into train and test sets with a random seed for reproducibility
+    let mut rng = Xoshiro256Plus::seed_from_u64(42);
+    let (train, test) = dataset.shuffle(&mut rng).split_with_ratio(train_ratio);
+    
+    println!("Training dataset: {} samples", train.nsamples());
+    println!("Testing dataset: {} samples", test.nsamples());
+    
+    // Create and train the model
+    println!("Training LinearRegression model...");
+    let model = linfa_linear::LinearRegression::default()
+        .fit(&train)?;
+    
+    // Make predictions
+    println!("Making predictions...");
+    let predictions = model.predict(test.records());
+    
+    // Print predictions vs actual values
+    println!("Predictions vs Actual:");
+    for (pred, actual) in predictions.iter().zip(test.targets().iter()) {
+        println!("  Predicted: {:.2}, Actual: {:.2}", pred, actual);
+    }
+    
+    // Calculate metrics
+    let mse = predictions.iter()
+        .zip(test.targets().iter())
+        .map(|(&p, &a)| (p - a) * (p - a))
+        .sum::<f64>() / predictions.len() as f64;
+    
+    println!("Mean Squared Error: {:.4}", mse);
+    
+    println!("Model parameters:");
+    println!("  Coefficients: {:?}", model.params());
+    println!("  Intercept: {:.4}", model.intercept());
+    
+    Ok(model)
+}
+
+/// Run DBSCAN on the given samples and print the resulting cluster sizes
+fn fit_dbscan(samples: Array2<f64>, min_points: usize, tolerance: f64) -> Result<()> {
+    // Create a dataset for clustering
+    let dataset = Dataset::from(samples);
+    
     println!("Running DBSCAN clustering with min_points = {}, tolerance = {}", min_points, tolerance);
     
     // Create and run the DBSCAN model
@@ -375,9 +461,11 @@ fn run_dbscan_example() -> Result<()> {
     
     // Count the number of points in each cluster
     let mut cluster_counts: HashMap<usize, usize> = HashMap::new();
+    let mut noise_points = 0;
     for membership in cluster_memberships.iter() {
-        if let Some(cluster_idx) = membership {
-            *cluster_counts.entry(*cluster_idx).or_insert(0) += 1;
+        match membership {
+            Some(cluster_idx) => *cluster_counts.entry(*cluster_idx).or_insert(0) += 1,
+            None => noise_points += 1,
         }
     }
     
@@ -386,6 +474,7 @@ fn run_dbscan_example() -> Result<()> {
     for (cluster_idx, count) in cluster_counts.iter() {
         println!("  Cluster {}: {} points", cluster_idx, count);
     }
+    println!("  Noise: {} points", noise_points);
     
     Ok(())
 }
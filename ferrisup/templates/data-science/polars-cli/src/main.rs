@@ -2,13 +2,35 @@ use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use comfy_table::{Cell, ContentArrangement, Table};
 use polars::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs::File;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use rand::Rng;
 {{#if (eq visualization "yes")}}
 use plotters::prelude::*;
 {{/if}}
 
+/// File path meaning stdin, for piping data in
+const STDIN_PATH: &str = "-";
+
+/// Characters a table cell shows before it's cut off with an ellipsis
+const DEFAULT_CELL_WIDTH: usize = 40;
+
+/// Set by `analyze --stdout`, when stdout carries the results and progress goes to stderr
+static RESULTS_ON_STDOUT: AtomicBool = AtomicBool::new(false);
+
+/// `println!` for progress messages, which go to stderr while stdout carries the results
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if RESULTS_ON_STDOUT.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
 /// A Rust CLI for data analysis using Polars (similar to pandas in Python)
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -21,11 +43,11 @@ struct Cli {
 enum Commands {
     /// Load and analyze a data file
     Analyze {
-        /// Path to the data file (CSV, JSON, or Parquet)
+        /// Path to the data file (CSV, JSON, or Parquet), or - to read CSV or JSON from stdin
         #[arg(short, long)]
         file: PathBuf,
         
-        /// File format (csv, json, parquet)
+        /// File format (csv, json, parquet, or xlsx to save the results as an Excel workbook)
         #[arg(short = 't', long, default_value = "{{#if (eq data_source "CSV files")}}csv{{else}}{{#if (eq data_source "Parquet files")}}parquet{{else}}{{#if (eq data_source "JSON data")}}json{{else}}csv{{/if}}{{/if}}{{/if}}")]
         format: String,
         
@@ -51,7 +73,7 @@ enum Commands {
         #[arg(short = 'a', long)]
         aggregate: Option<String>,
         
-        /// Aggregation function (sum, mean, min, max, count)
+        /// Aggregation function (sum, mean, median, min, max, std, var, count)
         #[arg(short = 'u', long, default_value = "count")]
         agg_func: String,
         
@@ -62,6 +84,47 @@ enum Commands {
         /// Confidence level for statistical tests (0.90, 0.95, 0.99)
         #[arg(long, default_value = "0.95")]
         confidence: f64,
+        
+        /// Maximum number of rows to display (0 = all rows)
+        #[arg(short = 'l', long, default_value_t = 20)]
+        limit: usize,
+        
+        /// Only show and save these columns, comma separated (all columns when omitted)
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
+        
+        /// Cut displayed cell values longer than this many characters (0 = never)
+        #[arg(long, default_value_t = DEFAULT_CELL_WIDTH)]
+        max_width: usize,
+        
+        /// Column to sort the results by (defaults to the group-by column)
+        #[arg(long)]
+        sort_by: Option<String>,
+        
+        /// Sort in descending order
+        #[arg(long)]
+        descending: bool,
+        
+        /// Write the results to stdout instead of a `_analyzed` file, printing progress to stderr
+        #[arg(long)]
+        stdout: bool,
+    },
+    
+    /// Print summary statistics (count, mean, std, min, quartiles, max) for every column
+    Describe {
+        /// Path to the data file (CSV, JSON, or Parquet), or - to read CSV or JSON from stdin
+        #[arg(short, long)]
+        file: PathBuf,
+        
+        /// File format (csv, json, parquet)
+        #[arg(short = 't', long, default_value = "{{#if (eq data_source "CSV files")}}csv{{else}}{{#if (eq data_source "Parquet files")}}parquet{{else}}{{#if (eq data_source "JSON data")}}json{{else}}csv{{/if}}{{/if}}{{/if}}")]
+        format: String,
+        
+        {{#if (eq data_source "JSON data")}}
+        /// JSON format (records, lines) - only used for JSON files
+        #[arg(long, default_value = "records")]
+        json_format: String,
+        {{/if}}
     },
     
     {{#if (eq visualization "yes")}}
@@ -101,7 +164,7 @@ enum Commands {
         #[arg(short, long)]
         output: PathBuf,
         
-        /// Output format (csv, json, parquet)
+        /// Output format (csv, json, parquet, xlsx)
         #[arg(short = 't', long, default_value = "{{#if (eq data_source "CSV files")}}csv{{else}}{{#if (eq data_source "Parquet files")}}parquet{{else}}{{#if (eq data_source "JSON data")}}json{{else}}csv{{/if}}{{/if}}{{/if}}")]
         format: String,
     },
@@ -124,58 +187,46 @@ fn main() -> Result<()> {
             agg_func,
             stats,
             confidence,
+            limit,
+            columns,
+            max_width,
+            sort_by,
+            descending,
+            stdout,
         } => {
-            println!("📊 Loading data from {}: {}", format, file.display());
-            
-            // Read the data file based on format
-            let df = match format.to_lowercase().as_str() {
-                {{#if (eq data_source "JSON data")}}
-                "json" => {
-                    println!("Using JSON format: {}", json_format);
-                    let json_fmt = match json_format.to_lowercase().as_str() {
-                        "lines" => JsonFormat::JsonLines,
-                        _ => JsonFormat::Json, // Use Json instead of JsonRecords in Polars 0.46.0
-                    };
-                    
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open JSON file: {}", file.display()))?;
-                    
-                    JsonReader::new(file)
-                        .with_json_format(json_fmt)
-                        .finish()
-                        .with_context(|| format!("Failed to read JSON file"))?
-                },
-                {{/if}}
-                {{#if (eq data_source "Parquet files")}}
-                "parquet" => {
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open Parquet file: {}", file.display()))?;
-                    
-                    ParquetReader::new(file)
-                        .finish()
-                        .with_context(|| format!("Failed to read Parquet file"))?
-                },
-                {{/if}}
-                _ => {
-                    // Default to CSV
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open CSV file: {}", file.display()))?;
-                    
-                    CsvReader::new(file)
-                        .finish()
-                        .with_context(|| "Failed to parse CSV data")?
+            if *stdout {
+                match format.to_lowercase().as_str() {
+                    "parquet" => anyhow::bail!(
+                        "Parquet can't be written to stdout because the writer needs to seek; drop --stdout to save it to a file"
+                    ),
+                    "xlsx" => anyhow::bail!("Excel workbooks can't be written to stdout; drop --stdout to save it to a file"),
+                    _ => RESULTS_ON_STDOUT.store(true, Ordering::Relaxed),
                 }
+            }
+            
+            status!("📊 Loading data from {}: {}", format, file.display());
+            
+            // xlsx is an output-only format, so detect the input format from the file extension
+            let input_format = match format.to_lowercase().as_str() {
+                "xlsx" => file
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_lowercase())
+                    .unwrap_or_else(|| "csv".to_string()),
+                other => other.to_string(),
             };
             
+            // Read the data file based on format
+            let df = read_dataframe(file, &input_format{{#if (eq data_source "JSON data")}}, json_format{{/if}})?;
+            
             // Show basic info
-            println!("\n📋 Data Overview:");
-            println!("Rows: {}", df.height());
-            println!("Columns: {}", df.width());
-            println!("Column names: {:?}", df.get_column_names());
+            status!("\n📋 Data Overview:");
+            status!("Rows: {}", df.height());
+            status!("Columns: {}", df.width());
+            status!("Column names: {:?}", df.get_column_names());
             
             // Apply filter if specified
             let filtered_df = if let (Some(col_name), Some(val)) = (filter_column, filter_value) {
-                println!("\n🔍 Filtering where {} = {}", col_name, val);
+                status!("\n🔍 Filtering where {} = {}", col_name, val);
                 // In Polars 0.46.0, we need to use the correct filter syntax
                 let filter_expr = col(col_name).eq(lit(val.as_str()));
                 df.lazy().filter(filter_expr).collect()?
@@ -185,41 +236,58 @@ fn main() -> Result<()> {
             
             // Apply grouping and aggregation if specified
             let result_df = if let Some(group_col) = group_by {
-                println!("\n📊 Grouping by: {}", group_col);
+                status!("\n📊 Grouping by: {}", group_col);
                 
                 let agg_col = aggregate.as_ref().unwrap_or(&group_col);
-                println!("📊 Aggregating: {} using {}", agg_col, agg_func);
+                status!("📊 Aggregating: {} using {}", agg_col, agg_func);
                 
-                let gb = filtered_df.lazy().group_by([col(group_col)]);
+                filtered_df
+                    .lazy()
+                    .group_by([col(group_col)])
+                    .agg([aggregation_expr(agg_col, agg_func)])
+                    .collect()?
+            } else if let Some(agg_col) = aggregate {
+                // No grouping: aggregate the whole column into a single value
+                status!("\n📊 Aggregating: {} using {}", agg_col, agg_func);
                 
-                match agg_func.as_str() {
-                    "sum" => gb.agg([col(agg_col).sum()]).collect()?,
-                    "mean" => gb.agg([col(agg_col).mean()]).collect()?,
-                    "min" => gb.agg([col(agg_col).min()]).collect()?,
-                    "max" => gb.agg([col(agg_col).max()]).collect()?,
-                    _ => gb.agg([col(agg_col).count()]).collect()?,
-                }
+                filtered_df
+                    .lazy()
+                    .select([aggregation_expr(agg_col, agg_func)])
+                    .collect()?
             } else {
                 filtered_df
             };
             
-            // Sort using the correct API for Polars 0.46.0
-            let options = SortMultipleOptions {
-                descending: vec![false],
-                nulls_last: vec![true],
-                maintain_order: false,
-                multithreaded: true,
-                limit: None,
-            };
-            let result_df = if let Some(group_col) = group_by {
-                result_df.sort([group_col.to_string()], options)?
+            // Sort by the requested column, falling back to the group-by column
+            let result_df = if let Some(sort_col) = sort_by.as_ref().or(group_by.as_ref()) {
+                let column_exists = result_df.get_column_names().iter().any(|c| c.as_str() == sort_col);
+                if !column_exists {
+                    anyhow::bail!(
+                        "Cannot sort by '{}': column not found. Available columns: {:?}",
+                        sort_col,
+                        result_df.get_column_names()
+                    );
+                }
+                
+                // Sort using the correct API for Polars 0.46.0
+                let options = SortMultipleOptions {
+                    descending: vec![*descending],
+                    nulls_last: vec![true],
+                    maintain_order: false,
+                    multithreaded: true,
+                    limit: None,
+                };
+                result_df.sort([sort_col.to_string()], options)?
             } else {
                 result_df
             };
             
+            // Narrow a wide result down to the requested columns
+            let result_df = select_columns(&result_df, columns)?;
+            
             // Perform statistical analysis if requested
             if *stats {
-                println!("\n📈 Statistical Analysis (Confidence Level: {}%):", confidence * 100.0);
+                status!("\n📈 Statistical Analysis (Confidence Level: {}%):", confidence * 100.0);
                 
                 for col_name in result_df.get_column_names() {
                     if let Ok(col) = result_df.column(col_name) {
@@ -233,7 +301,7 @@ fn main() -> Result<()> {
                             continue;
                         }
                         
-                        println!("\nColumn: {}", col_name);
+                        status!("\nColumn: {}", col_name);
                         
                         // Calculate basic statistics manually
                         let series = match col {
@@ -243,20 +311,20 @@ fn main() -> Result<()> {
                         
                         // Get count
                         let count = series.len() as f64;
-                        println!("  Count: {}", count);
+                        status!("  Count: {}", count);
                         
                         // Get min and max
                         if let Ok(Some(min_val)) = series.min::<f64>() {
-                            println!("  Min: {}", min_val);
+                            status!("  Min: {}", min_val);
                         }
                         
                         if let Ok(Some(max_val)) = series.max::<f64>() {
-                            println!("  Max: {}", max_val);
+                            status!("  Max: {}", max_val);
                         }
                         
                         // Get mean
                         let mean = if let Some(mean_val) = series.mean() {
-                            println!("  Mean: {:.4}", mean_val);
+                            status!("  Mean: {:.4}", mean_val);
                             mean_val
                         } else {
                             0.0
@@ -264,7 +332,7 @@ fn main() -> Result<()> {
                         
                         // Get standard deviation
                         let std_dev = if let Some(std_val) = series.std(1) {
-                            println!("  Std Dev: {:.4}", std_val);
+                            status!("  Std Dev: {:.4}", std_val);
                             std_val
                         } else {
                             0.0
@@ -278,7 +346,7 @@ fn main() -> Result<()> {
                         };
                         
                         let margin_error = z_score * std_dev / count.sqrt();
-                        println!("  {}% Confidence Interval: {:.4} ± {:.4}", 
+                        status!("  {}% Confidence Interval: {:.4} ± {:.4}", 
                                  confidence * 100.0, mean, margin_error);
                                      
                         {{#if (eq visualization "yes")}}
@@ -292,9 +360,9 @@ fn main() -> Result<()> {
                                 ));
                                 
                                 if let Err(e) = plot_histogram(&values, col_name, &output_path) {
-                                    println!("  Warning: Could not generate histogram: {}", e);
+                                    status!("  Warning: Could not generate histogram: {}", e);
                                 } else {
-                                    println!("  Histogram saved to: {}", output_path.display());
+                                    status!("  Histogram saved to: {}", output_path.display());
                                 }
                             }
                         }
@@ -304,12 +372,17 @@ fn main() -> Result<()> {
             }
             
             // Display results in a nice table
-            print_dataframe(&result_df)?;
+            print_dataframe(&result_df, columns, *limit, *max_width)?;
             
-            // Save the result
+            // Save the result, next to the input file or in the current directory for stdin
+            let stem = if is_stdin(file) {
+                "stdin".into()
+            } else {
+                file.file_stem().unwrap().to_string_lossy()
+            };
             let output_path = file.with_file_name(format!(
                 "{}_analyzed.{}",
-                file.file_stem().unwrap().to_string_lossy(),
+                stem,
                 match format.to_lowercase().as_str() {
                     {{#if (eq data_source "JSON data")}}
                     "json" => "json",
@@ -317,39 +390,60 @@ fn main() -> Result<()> {
                     {{#if (eq data_source "Parquet files")}}
                     "parquet" => "parquet",
                     {{/if}}
+                    "xlsx" => "xlsx",
                     _ => "csv",
                 }
             ));
             
-            let mut output_file = File::create(&output_path)?;
-            let mut result_df_mut = result_df.clone();
+            if format.eq_ignore_ascii_case("xlsx") {
+                write_xlsx(&result_df, &output_path)?;
+            } else {
+                let mut output_file: Box<dyn Write> = if *stdout {
+                    Box::new(std::io::stdout().lock())
+                } else {
+                    Box::new(File::create(&output_path)?)
+                };
+                let mut result_df_mut = result_df.clone();
             
-            match format.to_lowercase().as_str() {
-                {{#if (eq data_source "JSON data")}}
-                "json" => {
-                    let json_fmt = match json_format.to_lowercase().as_str() {
-                        "lines" => JsonFormat::JsonLines,
-                        _ => JsonFormat::Json,
-                    };
-                    let mut json_writer = JsonWriter::new(&mut output_file)
-                        .with_json_format(json_fmt);
-                    json_writer.finish(&mut result_df_mut)?;
-                },
-                {{/if}}
-                {{#if (eq data_source "Parquet files")}}
-                "parquet" => {
-                    let mut parquet_writer = ParquetWriter::new(&mut output_file);
-                    parquet_writer.finish(&mut result_df_mut)?;
-                },
-                {{/if}}
-                _ => {
-                    // Default to CSV
-                    let mut csv_writer = CsvWriter::new(&mut output_file);
-                    csv_writer.finish(&mut result_df_mut)?;
+                match format.to_lowercase().as_str() {
+                    {{#if (eq data_source "JSON data")}}
+                    "json" => {
+                        let json_fmt = match json_format.to_lowercase().as_str() {
+                            "lines" => JsonFormat::JsonLines,
+                            _ => JsonFormat::Json,
+                        };
+                        let mut json_writer = JsonWriter::new(&mut output_file)
+                            .with_json_format(json_fmt);
+                        json_writer.finish(&mut result_df_mut)?;
+                    },
+                    {{/if}}
+                    {{#if (eq data_source "Parquet files")}}
+                    "parquet" => {
+                        let mut parquet_writer = ParquetWriter::new(&mut output_file);
+                        parquet_writer.finish(&mut result_df_mut)?;
+                    },
+                    {{/if}}
+                    _ => {
+                        // Default to CSV
+                        let mut csv_writer = CsvWriter::new(&mut output_file);
+                        csv_writer.finish(&mut result_df_mut)?;
+                    }
                 }
             }
             
-            println!("\n💾 Results saved to: {}", output_path.display());
+            if *stdout {
+                status!("\n💾 Results written to stdout");
+            } else {
+                status!("\n💾 Results saved to: {}", output_path.display());
+            }
+        }
+        
+        Commands::Describe { file, format, {{#if (eq data_source "JSON data")}} json_format, {{/if}} } => {
+            println!("📊 Loading data from {}: {}", format, file.display());
+            
+            let df = read_dataframe(file, format{{#if (eq data_source "JSON data")}}, json_format{{/if}})?;
+            println!("\n📈 Summary statistics for {} rows:", df.height());
+            print_dataframe(&describe_dataframe(&df)?, &[], 0, DEFAULT_CELL_WIDTH)?;
         }
         
         {{#if (eq visualization "yes")}}
@@ -357,44 +451,7 @@ fn main() -> Result<()> {
             println!("📊 Loading data from {}: {}", format, file.display());
             
             // Read the data file based on format
-            let df = match format.to_lowercase().as_str() {
-                {{#if (eq data_source "JSON data")}}
-                "json" => {
-                    println!("Using JSON format: {}", json_format);
-                    let json_fmt = match json_format.to_lowercase().as_str() {
-                        "lines" => JsonFormat::JsonLines,
-                        _ => JsonFormat::Json, // Use Json instead of JsonRecords in Polars 0.46.0
-                    };
-                    
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open JSON file: {}", file.display()))?;
-                    
-                    JsonReader::new(file)
-                        .with_json_format(json_fmt)
-                        .finish()
-                        .with_context(|| format!("Failed to read JSON file"))?
-                },
-                {{/if}}
-                {{#if (eq data_source "Parquet files")}}
-                "parquet" => {
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open Parquet file: {}", file.display()))?;
-                    
-                    ParquetReader::new(file)
-                        .finish()
-                        .with_context(|| format!("Failed to read Parquet file"))?
-                },
-                {{/if}}
-                _ => {
-                    // Default to CSV
-                    let file = File::open(file)
-                        .with_context(|| format!("Failed to open CSV file: {}", file.display()))?;
-                    
-                    CsvReader::new(file)
-                        .finish()
-                        .with_context(|| "Failed to parse CSV data")?
-                }
-            };
+            let df = read_dataframe(file, format{{#if (eq data_source "JSON data")}}, json_format{{/if}})?;
             
             // Check if the column exists
             let column_exists = df.get_column_names().iter().any(|c| c.as_str() == column);
@@ -468,26 +525,30 @@ fn main() -> Result<()> {
                 std::fs::create_dir_all(parent)?;
             }
             
-            let mut file = File::create(output)?;
+            if format.eq_ignore_ascii_case("xlsx") {
+                write_xlsx(&df, output)?;
+            } else {
+                let mut file = File::create(output)?;
             
-            match format.to_lowercase().as_str() {
-                {{#if (eq data_source "JSON data")}}
-                "json" => {
-                    let mut json_writer = JsonWriter::new(&mut file)
-                        .with_json_format(JsonFormat::Json);
-                    json_writer.finish(&mut df)?;
-                },
-                {{/if}}
-                {{#if (eq data_source "Parquet files")}}
-                "parquet" => {
-                    let mut parquet_writer = ParquetWriter::new(&mut file);
-                    parquet_writer.finish(&mut df)?;
-                },
-                {{/if}}
-                _ => {
-                    // Default to CSV
-                    let mut csv_writer = CsvWriter::new(&mut file);
-                    csv_writer.finish(&mut df)?;
+                match format.to_lowercase().as_str() {
+                    {{#if (eq data_source "JSON data")}}
+                    "json" => {
+                        let mut json_writer = JsonWriter::new(&mut file)
+                            .with_json_format(JsonFormat::Json);
+                        json_writer.finish(&mut df)?;
+                    },
+                    {{/if}}
+                    {{#if (eq data_source "Parquet files")}}
+                    "parquet" => {
+                        let mut parquet_writer = ParquetWriter::new(&mut file);
+                        parquet_writer.finish(&mut df)?;
+                    },
+                    {{/if}}
+                    _ => {
+                        // Default to CSV
+                        let mut csv_writer = CsvWriter::new(&mut file);
+                        csv_writer.finish(&mut df)?;
+                    }
                 }
             }
             
@@ -571,39 +632,292 @@ fn plot_histogram(values: &ChunkedArray<Float64Type>, column_name: &str, output_
 }
 {{/if}}
 
-fn print_dataframe(df: &DataFrame) -> Result<()> {
+/// Write a DataFrame to an Excel workbook
+///
+/// Floats use the same two-decimal format as the table printer, and dates (including
+/// `YYYY-MM-DD` strings) are written as Excel date cells.
+#[cfg(feature = "xlsx")]
+fn write_xlsx(df: &DataFrame, output_path: &std::path::Path) -> Result<()> {
+    use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+    
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    
+    let header_format = Format::new().set_bold();
+    let float_format = Format::new().set_num_format("0.00");
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    
+    let write_date = |worksheet: &mut rust_xlsxwriter::Worksheet, row: u32, col: u16, date: chrono::NaiveDate| -> Result<()> {
+        use chrono::Datelike;
+        let excel_date = ExcelDateTime::from_ymd(date.year() as u16, date.month() as u8, date.day() as u8)?;
+        worksheet.write_datetime_with_format(row, col, &excel_date, &date_format)?;
+        Ok(())
+    };
+    
+    for (col_idx, column) in df.get_columns().iter().enumerate() {
+        let col_idx = col_idx as u16;
+        worksheet.write_string_with_format(0, col_idx, column.name().as_str(), &header_format)?;
+        
+        for row_idx in 0..df.height() {
+            let row = row_idx as u32 + 1;
+            match column.get(row_idx)? {
+                AnyValue::Null => {}
+                AnyValue::Float32(v) => {
+                    worksheet.write_number_with_format(row, col_idx, v as f64, &float_format)?;
+                }
+                AnyValue::Float64(v) => {
+                    worksheet.write_number_with_format(row, col_idx, v, &float_format)?;
+                }
+                AnyValue::Int8(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::Int16(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::Int32(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::Int64(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::UInt8(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::UInt16(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::UInt32(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::UInt64(v) => { worksheet.write_number(row, col_idx, v as f64)?; }
+                AnyValue::Boolean(v) => { worksheet.write_boolean(row, col_idx, v)?; }
+                AnyValue::Date(days) => {
+                    // Polars stores dates as days since the Unix epoch
+                    let date = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+                        .unwrap()
+                        .checked_add_signed(chrono::Duration::days(days as i64))
+                        .context("Date out of range")?;
+                    write_date(worksheet, row, col_idx, date)?;
+                }
+                AnyValue::String(v) => match chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d") {
+                    Ok(date) => write_date(worksheet, row, col_idx, date)?,
+                    Err(_) => { worksheet.write_string(row, col_idx, v)?; }
+                },
+                other => { worksheet.write_string(row, col_idx, other.to_string())?; }
+            }
+        }
+    }
+    
+    workbook.save(output_path)
+        .with_context(|| format!("Failed to write Excel file: {}", output_path.display()))?;
+    
+    Ok(())
+}
+
+#[cfg(not(feature = "xlsx"))]
+fn write_xlsx(_df: &DataFrame, _output_path: &std::path::Path) -> Result<()> {
+    anyhow::bail!("Excel output requires the `xlsx` feature. Rebuild with: cargo run --features xlsx -- ...")
+}
+
+/// Whether `path` is `-`, meaning the data is piped in on stdin
+fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == STDIN_PATH
+}
+
+/// Read all of stdin into memory, since the Polars readers need to seek
+fn read_stdin() -> Result<std::io::Cursor<Vec<u8>>> {
+    let mut data = Vec::new();
+    std::io::stdin().read_to_end(&mut data).context("Failed to read data from stdin")?;
+    Ok(std::io::Cursor::new(data))
+}
+
+/// Read a data file in the given format (csv, json or parquet), defaulting to CSV
+///
+/// A path of `-` reads CSV or JSON from stdin.
+fn read_dataframe(file: &PathBuf, format: &str{{#if (eq data_source "JSON data")}}, json_format: &str{{/if}}) -> Result<DataFrame> {
+    let df = match format.to_lowercase().as_str() {
+        {{#if (eq data_source "JSON data")}}
+        "json" => {
+            status!("Using JSON format: {}", json_format);
+            let json_fmt = match json_format.to_lowercase().as_str() {
+                "lines" => JsonFormat::JsonLines,
+                _ => JsonFormat::Json, // Use Json instead of JsonRecords in Polars 0.46.0
+            };
+            
+            let df = if is_stdin(file) {
+                JsonReader::new(read_stdin()?).with_json_format(json_fmt).finish()
+            } else {
+                let file = File::open(file)
+                    .with_context(|| format!("Failed to open JSON file: {}", file.display()))?;
+                JsonReader::new(file).with_json_format(json_fmt).finish()
+            };
+            df.with_context(|| format!("Failed to read JSON file"))?
+        },
+        {{/if}}
+        {{#if (eq data_source "Parquet files")}}
+        "parquet" => {
+            if is_stdin(file) {
+                anyhow::bail!("Parquet can't be read from stdin because the reader needs to seek; pass the path of the file instead");
+            }
+            let file = File::open(file)
+                .with_context(|| format!("Failed to open Parquet file: {}", file.display()))?;
+            
+            ParquetReader::new(file)
+                .finish()
+                .with_context(|| format!("Failed to read Parquet file"))?
+        },
+        {{/if}}
+        _ => {
+            // Default to CSV
+            let df = if is_stdin(file) {
+                CsvReader::new(read_stdin()?).finish()
+            } else {
+                let file = File::open(file)
+                    .with_context(|| format!("Failed to open CSV file: {}", file.display()))?;
+                CsvReader::new(file).finish()
+            };
+            df.with_context(|| "Failed to parse CSV data")?
+        }
+    };
+    
+    Ok(df)
+}
+
+/// Rows of the `describe` table; `unique` only applies to non-numeric columns and the
+/// remaining statistics only to numeric ones
+const DESCRIBE_STATS: [&str; 9] = ["count", "unique", "mean", "std", "min", "25%", "50%", "75%", "max"];
+
+/// Expression computing `stat` for a column, or None if it doesn't apply to the column
+fn describe_expr(column: &str, stat: &str, numeric: bool) -> Option<Expr> {
+    let c = col(column);
+    let expr = match (stat, numeric) {
+        ("count", _) => c.count(),
+        ("unique", false) => c.n_unique(),
+        ("mean", true) => c.mean(),
+        ("std", true) => c.std(1),
+        ("min", true) => c.min(),
+        ("25%", true) => c.quantile(lit(0.25), QuantileMethod::Linear),
+        ("50%", true) => c.median(),
+        ("75%", true) => c.quantile(lit(0.75), QuantileMethod::Linear),
+        ("max", true) => c.max(),
+        _ => return None,
+    };
+    Some(expr.cast(DataType::Float64).alias(format!("{}:{}", column, stat)))
+}
+
+/// Summarize every column of a DataFrame like pandas' `describe()`: one row per
+/// statistic and one column per input column, empty where a statistic doesn't apply
+fn describe_dataframe(df: &DataFrame) -> Result<DataFrame> {
+    let mut exprs = Vec::new();
+    for column in df.get_columns() {
+        let numeric = column.dtype().is_primitive_numeric();
+        exprs.extend(DESCRIBE_STATS.iter().filter_map(|stat| describe_expr(column.name(), stat, numeric)));
+    }
+    let stats = df.clone().lazy().select(exprs).collect()?;
+    
+    // Only show the unique row when there is a non-numeric column it applies to
+    let rows: Vec<&str> = DESCRIBE_STATS
+        .iter()
+        .copied()
+        .filter(|stat| *stat != "unique" || df.get_columns().iter().any(|c| !c.dtype().is_primitive_numeric()))
+        .collect();
+    
+    let mut columns = vec![Column::new("statistic".into(), &rows)];
+    for name in df.get_column_names() {
+        let values: Vec<String> = rows
+            .iter()
+            .map(|stat| match stats.column(&format!("{}:{}", name, stat)).and_then(|c| c.get(0)) {
+                Ok(AnyValue::Float64(v)) if matches!(*stat, "count" | "unique") => format!("{}", v as u64),
+                Ok(AnyValue::Float64(v)) => format!("{:.2}", v),
+                _ => String::new(),
+            })
+            .collect();
+        columns.push(Column::new(name.clone(), values));
+    }
+    
+    Ok(DataFrame::new(columns)?)
+}
+
+/// Aggregation functions supported by `--agg-func`
+const AGG_FUNCS: [&str; 8] = ["sum", "mean", "median", "min", "max", "std", "var", "count"];
+
+/// Build the aggregation expression for a column, warning about unknown functions
+fn aggregation_expr(agg_col: &str, agg_func: &str) -> Expr {
+    match agg_func {
+        "sum" => col(agg_col).sum(),
+        "mean" => col(agg_col).mean(),
+        "median" => col(agg_col).median(),
+        "min" => col(agg_col).min(),
+        "max" => col(agg_col).max(),
+        "std" => col(agg_col).std(1),
+        "var" => col(agg_col).var(1),
+        "count" => col(agg_col).count(),
+        other => {
+            status!(
+                "⚠️  Unknown aggregation function '{}', falling back to count. Supported functions: {}",
+                other,
+                AGG_FUNCS.join(", ")
+            );
+            col(agg_col).count()
+        }
+    }
+}
+
+/// `df` with only `columns`, in that order, or all of it when `columns` is empty
+fn select_columns(df: &DataFrame, columns: &[String]) -> Result<DataFrame> {
+    if columns.is_empty() {
+        return Ok(df.clone());
+    }
+    for name in columns {
+        if !df.get_column_names().iter().any(|c| c.as_str() == name) {
+            anyhow::bail!(
+                "Cannot select '{}': column not found. Available columns: {:?}",
+                name,
+                df.get_column_names()
+            );
+        }
+    }
+    Ok(df.select(columns.iter().map(String::as_str))?)
+}
+
+/// `value` cut to `max_width` characters, ending with an ellipsis when it was longer (0 = never cut)
+fn truncate_cell(value: String, max_width: usize) -> String {
+    if max_width == 0 || value.chars().count() <= max_width {
+        return value;
+    }
+    let mut truncated: String = value.chars().take(max_width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Print a DataFrame as a table, showing at most `limit` rows (0 = all rows) of `columns`
+/// (all columns when empty), with cell values cut to `max_width` characters
+fn print_dataframe(df: &DataFrame, columns: &[String], limit: usize, max_width: usize) -> Result<()> {
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
     
+    let df = select_columns(df, columns)?;
     let headers: Vec<Cell> = df.get_column_names()
         .iter()
         .map(|name| Cell::new(name))
         .collect();
     table.set_header(headers);
     
-    let max_rows = std::cmp::min(df.height(), 20);
+    let max_rows = if limit == 0 {
+        df.height()
+    } else {
+        std::cmp::min(df.height(), limit)
+    };
     for row_idx in 0..max_rows {
         let row_cells: Vec<Cell> = df.get_column_names()
             .iter()
             .map(|col_name| {
                 let col = df.column(col_name).unwrap();
-                let cell_value = match col.dtype() {
-                    DataType::Float32 | DataType::Float64 => {
-                        format!("{:.2}", col.get(row_idx).unwrap())
-                    },
-                    _ => format!("{}", col.get(row_idx).unwrap()),
+                let value = col.get(row_idx).unwrap();
+                let cell_value = match value {
+                    AnyValue::Null => String::new(),
+                    AnyValue::String(s) => s.to_string(),
+                    AnyValue::Float32(v) => format!("{:.2}", v),
+                    AnyValue::Float64(v) => format!("{:.2}", v),
+                    _ => format!("{}", value),
                 };
-                Cell::new(cell_value)
+                Cell::new(truncate_cell(cell_value, max_width))
             })
             .collect();
         table.add_row(row_cells);
     }
     
-    println!("\n📊 Results:");
-    println!("{table}");
+    status!("\n📊 Results:");
+    status!("{table}");
     
     if df.height() > max_rows {
-        println!("(Showing {} of {} rows)", max_rows, df.height());
+        status!("(Showing {} of {} rows, use --limit 0 to show all)", max_rows, df.height());
     }
     
     Ok(())
@@ -0,0 +1,24 @@
+//! Caching headers for responses: how long browsers and CloudFront may keep them
+//!
+//! Use `with_cache` for responses that are the same for everyone, and `with_no_store` for
+//! ones that depend on the request, so CloudFront never serves them to another client.
+
+use lambda_http::http::header::{HeaderValue, CACHE_CONTROL};
+use lambda_http::{Body, Response};
+
+/// Let browsers and CloudFront keep `resp` for `secs` seconds
+pub fn with_cache(mut resp: Response<Body>, secs: u32) -> Response<Body> {
+    let value = format!("public, max-age={}", secs);
+    resp.headers_mut().insert(
+        CACHE_CONTROL,
+        HeaderValue::from_str(&value).expect("a max-age is a valid header value"),
+    );
+    resp
+}
+
+/// Keep `resp` out of every cache
+pub fn with_no_store(mut resp: Response<Body>) -> Response<Body> {
+    resp.headers_mut()
+        .insert(CACHE_CONTROL, HeaderValue::from_static("private, no-store"));
+    resp
+}
@@ -3,6 +3,9 @@ use lambda_runtime::{tracing, service_fn as lambda_service_fn};
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+mod headers;
+use headers::{with_cache, with_no_store};
+
 // Configure the lambda tracing
 tracing::init_default_subscriber();
 
@@ -20,6 +23,16 @@ struct ApiResponse {
     headers: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     query_parameters: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    echo: Option<EchoRequest>,
+}
+
+/// JSON body accepted by the POST echo endpoint
+#[derive(Serialize, Deserialize)]
+struct EchoRequest {
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 /// Error response structure
@@ -91,6 +104,7 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                     <ul>
                         <li><code>/api</code> - Returns a JSON response</li>
                         <li><code>/api/echo?message=hello</code> - Echoes back your message</li>
+                        <li><code>POST /api/echo</code> - Echoes back a JSON body like <code>{"message": "hello"}</code></li>
                         <li><code>/api/headers</code> - Returns request headers</li>
                     </ul>
                     <h2>Try it out:</h2>
@@ -106,7 +120,7 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 .header("content-type", "text/html")
                 .body(Body::from(html))?;
             
-            Ok(response)
+            Ok(with_cache(response, 300))
         },
         
         // API endpoint
@@ -120,16 +134,17 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 method: Some("GET".to_string()),
                 headers: None,
                 query_parameters: None,
+                echo: None,
             };
             
-            // Serialize and return the JSON response
+            // Serialize and return the JSON response, the same for everyone so briefly cached
             let json = serde_json::to_string(&response_data)?;
             let response = Response::builder()
                 .status(StatusCode::OK)
                 .header("content-type", "application/json")
                 .body(Body::from(json))?;
             
-            Ok(response)
+            Ok(with_cache(response, 30))
         },
         
         // Echo endpoint
@@ -151,18 +166,67 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 method: Some("GET".to_string()),
                 headers: None,
                 query_parameters: Some(serde_json::to_value(query_params)?),
+                echo: None,
             };
             
-            // Serialize and return the JSON response
+            // Serialize and return the JSON response, never cached since it echoes the request
             let json = serde_json::to_string(&response_data)?;
             let response = Response::builder()
                 .status(StatusCode::OK)
                 .header("content-type", "application/json")
                 .body(Body::from(json))?;
             
-            Ok(response)
+            Ok(with_no_store(response))
         },
         
+        // POST echo endpoint - returns the fields of a JSON request body
+        ("POST", "/api/echo") => {
+            // Reject malformed JSON with a 400 instead of failing the invocation
+            let echo: EchoRequest = match serde_json::from_slice(req.body().as_ref()) {
+                Ok(echo) => echo,
+                Err(e) => {
+                    let error_data = ErrorResponse {
+                        error: format!("Invalid JSON body: {}", e),
+                        status: 400,
+                    };
+
+                    let json = serde_json::to_string(&error_data)?;
+                    let response = Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .header("content-type", "application/json")
+                        .body(Body::from(json))?;
+
+                    return Ok(with_no_store(response));
+                }
+            };
+
+            let message = match &echo.name {
+                Some(name) => format!("Echo from {}: {}", name, echo.message),
+                None => format!("Echo: {}", echo.message),
+            };
+
+            // Create the API response
+            let response_data = ApiResponse {
+                message,
+                status: "success".to_string(),
+                timestamp: current_timestamp(),
+                path: Some("/api/echo".to_string()),
+                method: Some("POST".to_string()),
+                headers: None,
+                query_parameters: None,
+                echo: Some(echo),
+            };
+
+            // Serialize and return the JSON response
+            let json = serde_json::to_string(&response_data)?;
+            let response = Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(json))?;
+
+            Ok(with_no_store(response))
+        },
+
         // Headers endpoint
         ("GET", "/api/headers") => {
             // Convert request headers to a map
@@ -183,6 +247,7 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 method: Some("GET".to_string()),
                 headers: Some(serde_json::to_value(headers_map)?),
                 query_parameters: None,
+                echo: None,
             };
             
             // Serialize and return the JSON response
@@ -192,7 +257,7 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 .header("content-type", "application/json")
                 .body(Body::from(json))?;
             
-            Ok(response)
+            Ok(with_no_store(response))
         },
         
         // Not found (404) handler
@@ -210,7 +275,7 @@ async fn handler(req: Request) -> Result<Response<Body>, Error> {
                 .header("content-type", "application/json")
                 .body(Body::from(json))?;
             
-            Ok(response)
+            Ok(with_no_store(response))
         }
     }
 }
@@ -1,146 +1,217 @@
-use worker::*;
+{{#if websockets}}
+use futures_util::StreamExt;
+{{/if}}
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use worker::*;
 
-// Define your API response data structure
+/// API Response structure for JSON responses
 #[derive(Serialize, Deserialize)]
 struct ApiResponse {
     message: String,
     status: String,
     timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    headers: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_parameters: Option<serde_json::Value>,
+}
+
+/// Error response structure
+#[derive(Serialize, Deserialize)]
+struct ErrorResponse {
+    error: String,
+    status: u16,
 }
 
+/// The main entry point for your Worker.
+///
+/// This function is triggered for every request the Worker receives
+/// and routes it based on the method and path.
 #[event(fetch)]
-pub async fn main(mut req: Request, env: Env, _ctx: worker::Context) -> Result<Response> {
-    // Set up better logging for debugging
-    console_log!("Worker started processing request");
+async fn fetch(req: Request, _env: Env, _ctx: Context) -> Result<Response> {
+    // Report panics to the Workers console instead of failing silently
     console_error_panic_hook::set_once();
-    
-    // Get the URL and method for routing
+
+    // Get the request method and path
     let url = req.url()?;
     let method = req.method();
-    
-    match (url.path(), method) {
-        ("/", Method::Get) => {
-            // Serve the landing page HTML
-            let html = get_index_html();
+    let path = url.path().to_string();
+
+    // Log the request details for debugging
+    console_log!("Handling request: {} {}", method.to_string(), path);
+
+    // Route the request based on method and path
+    match (method, path.as_str()) {
+        // Root path handler - returns HTML page
+        (Method::Get, "/") => {
+            let html = r#"
+            <!DOCTYPE html>
+            <html lang="en">
+            <head>
+                <meta charset="UTF-8">
+                <meta name="viewport" content="width=device-width, initial-scale=1.0">
+                <title>{{project_name}} | Cloudflare Workers</title>
+                <style>
+                    body {
+                        font-family: system-ui, -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+                        margin: 0;
+                        padding: 2rem;
+                        color: #333;
+                        background: #f5f5f5;
+                    }
+                    .container {
+                        max-width: 800px;
+                        margin: 0 auto;
+                        background: white;
+                        padding: 2rem;
+                        border-radius: 8px;
+                        box-shadow: 0 4px 6px rgba(0, 0, 0, 0.1);
+                    }
+                    h1 { color: #F38020; margin-top: 0; }
+                    pre {
+                        background: #f0f0f0;
+                        padding: 1rem;
+                        border-radius: 4px;
+                        overflow-x: auto;
+                    }
+                    a { color: #F38020; }
+                </style>
+            </head>
+            <body>
+                <div class='container'>
+                    <h1>🦀 {{project_name}} is running!</h1>
+                    <p>Your Rust-powered Cloudflare Worker is successfully running.</p>
+                    <h2>API Endpoints:</h2>
+                    <ul>
+                        <li><code>/api</code> - Returns a JSON response</li>
+                        <li><code>/api/echo?message=hello</code> - Echoes back your message</li>
+                        <li><code>/api/headers</code> - Returns request headers</li>
+{{#if websockets}}
+                        <li><code>/ws</code> - WebSocket that echoes every message back</li>
+{{/if}}
+                    </ul>
+                    <h2>Try it out:</h2>
+                    <pre>curl -X GET "https://your-worker.your-subdomain.workers.dev/api"</pre>
+                </div>
+            </body>
+            </html>
+            "#;
+
             Response::from_html(html)
-        },
+        }
 
-        ("/api", Method::Get) => {
-            // Simple API response
-            let response_data = ApiResponse {
-                message: "Hello from Rust and WebAssembly!".to_string(),
+        // API endpoint - returns JSON
+        (Method::Get, "/api") => {
+            let response = ApiResponse {
+                message: "Hello from Rust on Cloudflare Workers!".to_string(),
                 status: "success".to_string(),
-                timestamp: Date::now().as_millis() / 1000, // Current time in seconds
+                timestamp: current_timestamp(),
+                path: Some("/api".to_string()),
+                method: Some("GET".to_string()),
+                headers: None,
+                query_parameters: None,
             };
-            
-            Response::from_json(&response_data)
-        },
-        
-        ("/api", Method::Post) => {
-            // Echo back JSON data
-            let data = req.json().await?;
-            Response::from_json(&data)
-        },
-        
-        // Example of accessing KV storage
-        ("/api/kv-example", Method::Get) => {
-            let kv_example = r#"
-// Example of using KV storage:
-// 1. Add KV namespace to wrangler.toml:
-//    [[kv_namespaces]]
-//    binding = "MY_KV"
-//    id = "xxxx"
-//
-// 2. Usage example:
-//    let kv = env.kv("MY_KV")?;
-//    kv.put("key", "value")?.execute().await?;
-//    let value = kv.get("key").text().await?;
-"#;
-            Response::ok(kv_example)
-        },
-        
-        // Example of accessing env variables
-        ("/api/env-example", Method::Get) => {
-            let env_example = r#"
-// Access environment variables:
-// 1. Define in wrangler.toml:
-//    [vars]
-//    MY_VAR = "example"
-//
-// 2. Usage example:
-//    let my_var = env.var("MY_VAR")?.to_string();
-"#;
-            Response::ok(env_example)
-        },
-        
-        // Handle 404 for unmatched routes
+
+            Response::from_json(&response)
+        }
+
+        // Echo API - returns message from query param
+        (Method::Get, path) if path.starts_with("/api/echo") => {
+            // Parse the query parameters
+            let query_params: HashMap<String, String> = url
+                .query_pairs()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+
+            // Look for a message parameter
+            let message = query_params
+                .get("message")
+                .cloned()
+                .unwrap_or_else(|| "No message provided".to_string());
+
+            let response = ApiResponse {
+                message: format!("Echo: {}", message),
+                status: "success".to_string(),
+                timestamp: current_timestamp(),
+                path: Some(path.to_string()),
+                method: Some("GET".to_string()),
+                headers: None,
+                query_parameters: Some(serde_json::to_value(query_params)?),
+            };
+
+            Response::from_json(&response)
+        }
+
+        // Headers endpoint
+        (Method::Get, "/api/headers") => {
+            // Convert request headers to a map
+            let headers_map: HashMap<String, String> = req.headers().entries().collect();
+
+            let response = ApiResponse {
+                message: "Request headers".to_string(),
+                status: "success".to_string(),
+                timestamp: current_timestamp(),
+                path: Some("/api/headers".to_string()),
+                method: Some("GET".to_string()),
+                headers: Some(serde_json::to_value(headers_map)?),
+                query_parameters: None,
+            };
+
+            Response::from_json(&response)
+        }
+
+{{#if websockets}}
+        // WebSocket echo: accept the upgrade and send every text message back
+        (Method::Get, "/ws") => {
+            let upgrade = req.headers().get("Upgrade")?;
+            if !matches!(upgrade, Some(value) if value.eq_ignore_ascii_case("websocket")) {
+                return Response::error("Expected a WebSocket upgrade", 426);
+            }
+
+            let pair = WebSocketPair::new()?;
+            let server = pair.server;
+            server.accept()?;
+            wasm_bindgen_futures::spawn_local(async move {
+                let Ok(mut events) = server.events() else {
+                    return;
+                };
+                while let Some(Ok(event)) = events.next().await {
+                    match event {
+                        WebsocketEvent::Message(message) => {
+                            if let Some(text) = message.text() {
+                                let _ = server.send_with_str(text);
+                            }
+                        }
+                        WebsocketEvent::Close(_) => break,
+                    }
+                }
+            });
+
+            Response::from_websocket(pair.client)
+        }
+
+{{/if}}
+        // Catch all other routes with a 404
         _ => {
-            let not_found = ApiResponse {
-                message: "Not Found".to_string(),
-                status: "error".to_string(),
-                timestamp: Date::now().as_millis() / 1000,
+            let error = ErrorResponse {
+                error: "Not Found".to_string(),
+                status: 404,
             };
-            
-            Ok(Response::from_json(&not_found)?.with_status(404))
+
+            Ok(Response::from_json(&error)?.with_status(404))
         }
     }
 }
 
-// Helper function to generate the index HTML page
-fn get_index_html() -> String {
-    let mut html = String::new();
-    
-    // Opening tags
-    html.push_str("<html>");
-    html.push_str("<head>");
-    html.push_str("<title>{{project_name}} | Cloudflare Worker</title>");
-    
-    // CSS styles with proper escaping
-    html.push_str("<style>");
-    html.push_str("body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, Helvetica, Arial, sans-serif; max-width: 800px; margin: 0 auto; padding: 2rem; line-height: 1.6; }");
-    html.push_str("h1 { color: #f48120; border-bottom: 2px solid #f48120; padding-bottom: 10px; }");
-    html.push_str(".powered-by { margin-top: 3rem; font-size: 0.8rem; color: #666; }");
-    html.push_str("code { background-color: #f6f8fa; padding: 0.2em 0.4em; border-radius: 3px; }");
-    html.push_str(".endpoint { background-color: #f6f8fa; padding: 1rem; border-radius: 5px; margin: 1rem 0; }");
-    html.push_str("</style>");
-    html.push_str("</head>");
-    
-    // Page content
-    html.push_str("<body>");
-    html.push_str("<h1>🦀 {{project_name}} API</h1>");
-    html.push_str("<p>Your Rust-powered Cloudflare Worker is up and running!</p>");
-    
-    html.push_str("<h2>Available Endpoints:</h2>");
-    html.push_str("<div class=\"endpoint\">");
-    html.push_str("<p><strong>GET /api</strong> - Returns a JSON response</p>");
-    html.push_str("<code>curl -X GET https://your-worker.your-subdomain.workers.dev/api</code>");
-    html.push_str("</div>");
-    
-    html.push_str("<div class=\"endpoint\">");
-    html.push_str("<p><strong>POST /api</strong> - Accepts JSON data and returns it</p>");
-    html.push_str("<code>curl -X POST -H \"Content-Type: application/json\" -d '{\"name\": \"FerrisUp\"}' https://your-worker.your-subdomain.workers.dev/api</code>");
-    html.push_str("</div>");
-    
-    html.push_str("<div class=\"endpoint\">");
-    html.push_str("<p><strong>GET /api/kv-example</strong> - Shows KV usage example</p>");
-    html.push_str("<code>curl -X GET https://your-worker.your-subdomain.workers.dev/api/kv-example</code>");
-    html.push_str("</div>");
-    
-    html.push_str("<div class=\"endpoint\">");
-    html.push_str("<p><strong>GET /api/env-example</strong> - Shows environment variables example</p>");
-    html.push_str("<code>curl -X GET https://your-worker.your-subdomain.workers.dev/api/env-example</code>");
-    html.push_str("</div>");
-    
-    html.push_str("<p>Check out your <code>src/lib.rs</code> file to see how this worker is implemented and to add more endpoints.</p>");
-    
-    html.push_str("<div class=\"powered-by\">");
-    html.push_str("Powered by Rust, WebAssembly, and Cloudflare Workers<br>");
-    html.push_str("Generated with <a href=\"https://github.com/Jitpomi/ferrisup\">FerrisUp</a>");
-    html.push_str("</div>");
-    
-    html.push_str("</body>");
-    html.push_str("</html>");
-    
-    html
+/// Helper function to get the current timestamp in seconds
+///
+/// `std::time::SystemTime` is not available in the Workers runtime,
+/// so this uses the JavaScript clock instead.
+fn current_timestamp() -> u64 {
+    Date::now().as_millis() / 1000
 }
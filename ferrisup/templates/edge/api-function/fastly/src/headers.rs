@@ -0,0 +1,19 @@
+//! Caching headers for responses: how long browsers and Fastly's cache may keep them
+//!
+//! Use `with_cache` for responses that are the same for everyone, and `with_no_store` for
+//! ones that depend on the request, so Fastly never serves them to another client.
+
+use fastly::http::header::CACHE_CONTROL;
+use fastly::Response;
+
+/// Let browsers and Fastly's cache keep `resp` for `secs` seconds
+pub fn with_cache(resp: Response, secs: u32) -> Response {
+    resp.with_header(CACHE_CONTROL, format!("public, max-age={}", secs))
+        // Surrogate-Control is for Fastly only and is stripped before the response leaves
+        .with_header("Surrogate-Control", format!("max-age={}", secs))
+}
+
+/// Keep `resp` out of every cache
+pub fn with_no_store(resp: Response) -> Response {
+    resp.with_header(CACHE_CONTROL, "private, no-store")
+}
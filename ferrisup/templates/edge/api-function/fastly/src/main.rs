@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use fastly::http::header::CONTENT_TYPE;
 
+mod headers;
+use headers::{with_cache, with_no_store};
+
 /// API Response structure for JSON responses
 #[derive(Serialize, Deserialize)]
 struct ApiResponse {
@@ -12,6 +15,16 @@ struct ApiResponse {
     timestamp: u64,
     path: Option<String>,
     method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    echo: Option<EchoRequest>,
+}
+
+/// JSON body accepted by the POST echo endpoint
+#[derive(Serialize, Deserialize)]
+struct EchoRequest {
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 /// Error response structure
@@ -26,7 +39,7 @@ struct ErrorResponse {
 /// This function is triggered when your service receives a client request.
 /// It could be used for routing, or as a template for implementation logic.
 #[fastly::main]
-fn main(req: Request) -> Result<Response, Error> {
+fn main(mut req: Request) -> Result<Response, Error> {
     // Log the request details for debugging
     println!(
         "Handling request: {} {}",
@@ -35,11 +48,11 @@ fn main(req: Request) -> Result<Response, Error> {
     );
 
     // Get the request method and path
-    let method = req.get_method();
-    let path = req.get_url().path();
+    let method = req.get_method().clone();
+    let path = req.get_url().path().to_string();
 
     // Route the request based on method and path
-    match (method, path) {
+    match (&method, path.as_str()) {
         // Root path handler - returns HTML page
         (&Method::GET, "/") => {
             let html = r#"
@@ -83,7 +96,11 @@ fn main(req: Request) -> Result<Response, Error> {
                     <ul>
                         <li><code>/api</code> - Returns a JSON response</li>
                         <li><code>/api/echo?message=hello</code> - Echoes back your message</li>
+                        <li><code>POST /api/echo</code> - Echoes back a JSON body like <code>{"message": "hello"}</code></li>
                         <li><code>/api/cache</code> - Demonstrates cache control</li>
+{{#if websockets}}
+                        <li><code>/ws</code> - WebSocket that echoes every message back</li>
+{{/if}}
                     </ul>
                     <h2>Try it out:</h2>
                     <pre>curl -X GET "https://your-service.edgecompute.app/api"</pre>
@@ -92,9 +109,10 @@ fn main(req: Request) -> Result<Response, Error> {
             </html>
             "#;
 
-            Ok(Response::from_body(html)
+            let response = Response::from_body(html)
                 .with_status(StatusCode::OK)
-                .with_header(CONTENT_TYPE, "text/html"))
+                .with_header(CONTENT_TYPE, "text/html");
+            Ok(with_cache(response, 300))
         }
 
         // API endpoint - returns JSON
@@ -105,11 +123,14 @@ fn main(req: Request) -> Result<Response, Error> {
                 timestamp: current_timestamp(),
                 path: Some("/api".to_string()),
                 method: Some("GET".to_string()),
+                echo: None,
             };
 
-            Ok(Response::from_body(serde_json::to_string(&response)?)
+            // The same for everyone, so it can be cached for a short while
+            let response = Response::from_body(serde_json::to_string(&response)?)
                 .with_status(StatusCode::OK)
-                .with_header(CONTENT_TYPE, "application/json"))
+                .with_header(CONTENT_TYPE, "application/json");
+            Ok(with_cache(response, 30))
         }
 
         // Echo API - returns message from query param
@@ -130,11 +151,52 @@ fn main(req: Request) -> Result<Response, Error> {
                 timestamp: current_timestamp(),
                 path: Some(path.to_string()),
                 method: Some("GET".to_string()),
+                echo: None,
+            };
+
+            // Echoes depend on the request, so they're never cached
+            let response = Response::from_body(serde_json::to_string(&response)?)
+                .with_status(StatusCode::OK)
+                .with_header(CONTENT_TYPE, "application/json");
+            Ok(with_no_store(response))
+        }
+
+        // Echo API - returns the fields of a JSON request body
+        (&Method::POST, "/api/echo") => {
+            // Reject malformed JSON with a 400 instead of failing the request
+            let echo: EchoRequest = match req.take_body_json() {
+                Ok(echo) => echo,
+                Err(e) => {
+                    let error = ErrorResponse {
+                        error: format!("Invalid JSON body: {}", e),
+                        status: 400,
+                    };
+
+                    let response = Response::from_body(serde_json::to_string(&error)?)
+                        .with_status(StatusCode::BAD_REQUEST)
+                        .with_header(CONTENT_TYPE, "application/json");
+                    return Ok(with_no_store(response));
+                }
             };
 
-            Ok(Response::from_body(serde_json::to_string(&response)?)
+            let message = match &echo.name {
+                Some(name) => format!("Echo from {}: {}", name, echo.message),
+                None => format!("Echo: {}", echo.message),
+            };
+
+            let response = ApiResponse {
+                message,
+                status: "success".to_string(),
+                timestamp: current_timestamp(),
+                path: Some("/api/echo".to_string()),
+                method: Some("POST".to_string()),
+                echo: Some(echo),
+            };
+
+            let response = Response::from_body(serde_json::to_string(&response)?)
                 .with_status(StatusCode::OK)
-                .with_header(CONTENT_TYPE, "application/json"))
+                .with_header(CONTENT_TYPE, "application/json");
+            Ok(with_no_store(response))
         }
 
         // Cache control example
@@ -145,15 +207,41 @@ fn main(req: Request) -> Result<Response, Error> {
                 timestamp: current_timestamp(),
                 path: Some("/api/cache".to_string()),
                 method: Some("GET".to_string()),
+                echo: None,
             };
 
-            Ok(Response::from_body(serde_json::to_string(&response)?)
+            let response = Response::from_body(serde_json::to_string(&response)?)
+                .with_status(StatusCode::OK)
+                .with_header(CONTENT_TYPE, "application/json");
+            Ok(with_cache(response, 60))
+        }
+
+{{#if websockets}}
+        // WebSocket echo: Fanout holds the client connection and sends its events here as
+        // WebSocket-over-HTTP requests, answered with the events to send back
+        (_, "/ws") => {
+            if req.get_header_str("Grip-Sig").is_none() {
+                // A client connecting: hand the connection to Fanout through the `self` backend
+                return Ok(req.handoff_fanout("self")?);
+            }
+            if req.get_header_str(CONTENT_TYPE) != Some("application/websocket-events") {
+                return Ok(Response::from_body("Expected WebSocket-over-HTTP events\n")
+                    .with_status(StatusCode::BAD_REQUEST));
+            }
+
+            // Answering with the events received accepts the connection (OPEN), echoes
+            // every TEXT and BINARY message and acknowledges a CLOSE
+            let events = req.take_body_bytes();
+            let mut response = Response::from_body(events.clone())
                 .with_status(StatusCode::OK)
-                .with_header(CONTENT_TYPE, "application/json")
-                .with_header("Cache-Control", "public, max-age=60")
-                .with_header("Surrogate-Control", "max-age=60"))
+                .with_header(CONTENT_TYPE, "application/websocket-events");
+            if events.starts_with(b"OPEN\r\n") {
+                response.set_header("Sec-WebSocket-Extensions", "grip; message-prefix=\"\"");
+            }
+            Ok(response)
         }
 
+{{/if}}
         // Catch all other routes with a 404
         _ => {
             let error = ErrorResponse {
@@ -161,9 +249,10 @@ fn main(req: Request) -> Result<Response, Error> {
                 status: 404,
             };
 
-            Ok(Response::from_body(serde_json::to_string(&error)?)
+            let response = Response::from_body(serde_json::to_string(&error)?)
                 .with_status(StatusCode::NOT_FOUND)
-                .with_header(CONTENT_TYPE, "application/json"))
+                .with_header(CONTENT_TYPE, "application/json");
+            Ok(with_no_store(response))
         }
     }
 }
@@ -1,36 +1,69 @@
 //! ESP32 firmware
-//! 
+//!
 //! This is a template for ESP32 devices created with FerrisUp
 
 #![no_std]
 #![no_main]
 
-use esp32_hal::{clock::ClockControl, pac::Peripherals, prelude::*, timer::TimerGroup, Rtc};
+// Import the panic handler
 use esp_backtrace as _;
+
+// ESP32-specific imports
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+    uart::{Config as UartConfig, Uart},
+};
 use esp_println::println;
 
-#[entry]
+// Application descriptor required by the ESP-IDF second stage bootloader
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[main]
 fn main() -> ! {
-    // Initialize the ESP32 device
-    let peripherals = Peripherals::take().unwrap();
-    let system = peripherals.DPORT.split();
-    let clocks = ClockControl::boot_defaults(system.clock_control).freeze();
-    
-    // Get the RTC and timer group
-    let mut rtc = Rtc::new(peripherals.RTC_CNTL);
-    let timer_group0 = TimerGroup::new(peripherals.TIMG0, &clocks);
-    let mut timer0 = timer_group0.timer0;
-    
-    // Print a welcome message
-    println!("Hello from ESP32!");
-    println!("ESP32 Firmware is running!");
-    
+    // Initialize the HAL and grab our peripherals
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    println!("{{project_name}} is running on the ESP32!");
+
+    // Configure GPIO2 as an output (the built-in LED on most ESP32 DevKit boards)
+    let mut led_pin = Output::new(peripherals.GPIO2, Level::Low, OutputConfig::default());
+
+    // Set up UART0, which is wired to the board's USB-to-serial bridge
+    let mut serial = Uart::new(peripherals.UART0, UartConfig::default())
+        .unwrap()
+        .with_tx(peripherals.GPIO1)
+        .with_rx(peripherals.GPIO3);
+
+    // Create a blocking delay provider
+    let delay = Delay::new();
+
     // Main application loop
     loop {
-        // Your application code here
-        println!("Tick...");
-        
-        // Delay for a second
-        timer0.delay(1.secs());
+        // Toggle the LED
+        led_pin.set_high();
+        delay.delay_millis(500);
+        led_pin.set_low();
+        delay.delay_millis(500);
+
+        // Poll the serial port
+        if serial.read_ready() {
+            let mut buf = [0u8; 64];
+            match serial.read(&mut buf) {
+                Ok(count) if count > 0 => {
+                    // Echo back in upper case
+                    for c in buf[0..count].iter_mut() {
+                        if 0x61 <= *c && *c <= 0x7a {
+                            *c &= !0x20;
+                        }
+                    }
+
+                    // Write data back to the serial port
+                    let _ = serial.write(&buf[0..count]);
+                }
+                _ => {}
+            }
+        }
     }
 }
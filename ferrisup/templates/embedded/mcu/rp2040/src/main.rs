@@ -80,8 +80,8 @@ fn main() -> ! {
         &mut pac.RESETS,
     );
     
-    // Configure GPIO25 as an output (Pico's built-in LED)
-    let mut led_pin = pins.gpio25.into_push_pull_output();
+    // Configure GPIO{{led_pin}} as an output (GPIO25 is the Pico's built-in LED)
+    let mut led_pin = pins.gpio{{led_pin}}.into_push_pull_output();
     
     // Create a delay abstraction based on the cortex-m systick
     let mut delay = Delay::new(core.SYST, clocks.system_clock.freq().to_Hz());
@@ -90,9 +90,9 @@ fn main() -> ! {
     loop {
         // Toggle the LED
         led_pin.set_high().unwrap();
-        delay.delay_ms(500);
+        delay.delay_ms({{blink_ms}});
         led_pin.set_low().unwrap();
-        delay.delay_ms(500);
+        delay.delay_ms({{blink_ms}});
         
         // Poll the USB device
         if usb_dev.poll(&mut [&mut serial]) {
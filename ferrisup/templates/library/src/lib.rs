@@ -1,3 +1,7 @@
+{{#minimal}}
+//! {{project_name}}
+{{/minimal}}
+{{^minimal}}
 //! Library template created with FerrisUp
 
 /// Returns a greeting message
@@ -14,3 +18,4 @@ mod tests {
         assert_eq!(hello(), "Hello from FerrisUp library template!");
     }
 }
+{{/minimal}}
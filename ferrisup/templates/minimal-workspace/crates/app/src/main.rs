@@ -0,0 +1,7 @@
+use {{project_name_snake_case}}_core::greet;
+use {{project_name_snake_case}}_shared::AppConfig;
+
+fn main() {
+    let config = AppConfig::default();
+    println!("{}", greet(&config));
+}
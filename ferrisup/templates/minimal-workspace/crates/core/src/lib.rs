@@ -0,0 +1,21 @@
+//! Core logic of {{project_name}}
+
+use {{project_name_snake_case}}_shared::AppConfig;
+
+/// Returns the greeting the app prints
+pub fn greet(config: &AppConfig) -> String {
+    format!("Hello from {}!", config.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greet() {
+        let config = AppConfig {
+            name: "FerrisUp".to_string(),
+        };
+        assert_eq!(greet(&config), "Hello from FerrisUp!");
+    }
+}
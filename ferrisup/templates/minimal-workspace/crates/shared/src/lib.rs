@@ -0,0 +1,15 @@
+//! Types shared by every crate of {{project_name}}
+
+/// Configuration of the application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppConfig {
+    pub name: String,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            name: "{{project_name}}".to_string(),
+        }
+    }
+}
@@ -27,10 +27,7 @@ fn api_info() -> Json<ApiResponse> {
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
-    // Initialize logger
-    if env::var_os("RUST_LOG").is_none() {
-        env::set_var("RUST_LOG", "info");
-    }
+    // Initialize logger (logs at INFO level)
     tracing_subscriber::fmt::init();
 
     let app = Route::new()
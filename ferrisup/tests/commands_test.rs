@@ -1,5 +1,6 @@
 //! Integration tests for FerrisUp commands
 
+use std::path::Path;
 use std::process::{Command, Stdio};
 use anyhow::Result;
 
@@ -125,6 +126,1670 @@ fn test_new_command() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_new_command_failure_leaves_no_partial_output() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let dir_path = temp_dir.path();
+
+    // Shadow the minimal template with one whose second file fails to render,
+    // after the first one was written
+    let template_dir = dir_path.join("custom").join("templates").join("minimal");
+    std::fs::create_dir_all(&template_dir)?;
+    std::fs::write(
+        template_dir.join("template.json"),
+        r#"{
+            "name": "minimal",
+            "description": "A template that fails halfway through",
+            "files": [
+                { "source": "Cargo.toml", "target": "Cargo.toml" },
+                { "source": "main.rs", "target": "src/main.rs" }
+            ]
+        }"#,
+    )?;
+    std::fs::write(template_dir.join("Cargo.toml"), "[package]\nname = \"{{project_name}}\"\n")?;
+    std::fs::write(template_dir.join("main.rs"), "{{#if unclosed}}\nfn main() {}\n")?;
+
+    let workdir = dir_path.join("work");
+    std::fs::create_dir(&workdir)?;
+
+    let output = common::run_ferrisup(
+        &workdir,
+        &["new", "broken_project", "--component-type", "minimal", "--no-interactive"],
+        &[("FERRISUP_TEMPLATES_DIR", &dir_path.join("custom"))],
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success(), "Generating from a broken template should fail");
+    assert!(stderr.contains("Failed to render template"), "Unexpected failure: {}", stderr);
+
+    // Neither the project nor the staging directory may be left behind
+    assert_eq!(std::fs::read_dir(&workdir)?.count(), 0, "Failed generation left files behind");
+
+    // An existing target directory is never removed
+    let existing = workdir.join("broken_project");
+    std::fs::create_dir(&existing)?;
+    std::fs::write(existing.join("notes.txt"), "keep me")?;
+
+    let output = common::run_ferrisup(
+        &workdir,
+        &["new", "broken_project", "--component-type", "minimal", "--no-interactive"],
+        &[("FERRISUP_TEMPLATES_DIR", &dir_path.join("custom"))],
+    );
+
+    assert!(!output.status.success(), "Generating from a broken template should fail");
+    assert!(existing.join("notes.txt").exists());
+    assert!(!existing.join("Cargo.toml").exists());
+    assert_eq!(std::fs::read_dir(&workdir)?.count(), 1);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_with_base_template() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let dir_path = temp_dir.path();
+    let templates_dir = dir_path.join("custom").join("templates");
+
+    // A base template providing Cargo.toml, .gitignore and a default main.rs
+    let base_dir = templates_dir.join("base").join("rust-bin");
+    std::fs::create_dir_all(base_dir.join("src"))?;
+    std::fs::write(base_dir.join("template.json"), r#"{ "name": "rust-bin", "description": "Base binary" }"#)?;
+    std::fs::write(
+        base_dir.join("Cargo.toml"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    std::fs::write(base_dir.join(".gitignore"), "/target\n*.base-ignored\n")?;
+    std::fs::write(base_dir.join("src").join("main.rs"), "fn main() {\n    println!(\"base\");\n}\n")?;
+
+    // The minimal template extends it and only replaces main.rs
+    let minimal_dir = templates_dir.join("minimal");
+    std::fs::create_dir_all(minimal_dir.join("src"))?;
+    std::fs::write(
+        minimal_dir.join("template.json"),
+        r#"{ "name": "minimal", "description": "Extends the base", "extends": "base/rust-bin" }"#,
+    )?;
+    std::fs::write(
+        minimal_dir.join("src").join("main.rs"),
+        "fn main() {\n    println!(\"Hello from {{project_name}}\");\n}\n",
+    )?;
+
+    let output = common::run_ferrisup(
+        dir_path,
+        &["new", "inherited", "--component-type", "minimal", "--no-interactive"],
+        &[("FERRISUP_TEMPLATES_DIR", &dir_path.join("custom"))],
+    );
+    assert!(
+        output.status.success(),
+        "Generation failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let project_dir = dir_path.join("inherited");
+    let main_rs = std::fs::read_to_string(project_dir.join("src").join("main.rs"))?;
+    assert!(main_rs.contains("Hello from inherited"), "The child main.rs should win: {}", main_rs);
+    let cargo_toml = std::fs::read_to_string(project_dir.join("Cargo.toml"))?;
+    assert!(cargo_toml.contains("name = \"inherited\""), "Base files see the child variables");
+    let gitignore = std::fs::read_to_string(project_dir.join(".gitignore"))?;
+    assert!(gitignore.contains("*.base-ignored"), "The base .gitignore should be inherited");
+
+    // Cyclic inheritance is an error rather than a hang
+    std::fs::write(
+        base_dir.join("template.json"),
+        r#"{ "name": "rust-bin", "description": "Base binary", "extends": "minimal" }"#,
+    )?;
+    let output = common::run_ferrisup(
+        dir_path,
+        &["new", "cyclic", "--component-type", "minimal", "--no-interactive"],
+        &[("FERRISUP_TEMPLATES_DIR", &dir_path.join("custom"))],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("inheritance cycle"), "Unexpected failure: {}", stderr);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_into_existing_directory() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let repo = temp_dir.path().join("existing_repo");
+    std::fs::create_dir(&repo)?;
+    std::fs::write(repo.join("notes.txt"), "keep me")?;
+
+    let run = |extra: &[&str]| {
+        common::run_ferrisup(
+            &repo,
+            &[&["new", "--component-type", "minimal", "--into", ".", "--no-interactive"][..], extra].concat(),
+            &[],
+        )
+    };
+
+    // The project is named after the directory and unrelated files survive
+    let output = run(&[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(std::fs::read_to_string(repo.join("Cargo.toml"))?.contains("existing_repo"));
+    assert!(repo.join("notes.txt").exists());
+
+    // Generating again conflicts with the files the template owns
+    std::fs::write(repo.join("README.md"), "my readme")?;
+    let output = run(&[]);
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("README.md") && stderr.contains("--force"), "Unexpected error: {}", stderr);
+    assert_eq!(std::fs::read_to_string(repo.join("README.md"))?, "my readme");
+
+    // --force overwrites only the template's files
+    let output = run(&["--force"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_ne!(std::fs::read_to_string(repo.join("README.md"))?, "my readme");
+    assert_eq!(std::fs::read_to_string(repo.join("notes.txt"))?, "keep me");
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_overwrite_policy() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let repo = temp_dir.path().join("populated");
+    std::fs::create_dir(&repo)?;
+    std::fs::write(repo.join("README.md"), "my readme")?;
+    std::fs::write(repo.join("notes.txt"), "keep me")?;
+
+    let run = |extra: &[&str]| {
+        common::run_ferrisup(
+            &repo,
+            &[&["new", "--component-type", "minimal", "--into", ".", "--no-interactive"][..], extra].concat(),
+            &[],
+        )
+    };
+
+    // prompt can't ask without a terminal, and fails before anything is written
+    let output = run(&["--overwrite-policy", "prompt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--no-interactive"));
+    assert!(!repo.join("Cargo.toml").exists());
+
+    // skip keeps the existing files, reports them and still writes the rest
+    let output = run(&["--overwrite-policy", "skip"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Kept existing files") && stdout.contains("README.md"), "{}", stdout);
+    assert_eq!(std::fs::read_to_string(repo.join("README.md"))?, "my readme");
+    assert!(repo.join("Cargo.toml").exists());
+
+    // overwrite replaces them, leaving files the template doesn't generate alone
+    std::fs::write(repo.join("Cargo.toml"), "# mine")?;
+    let output = run(&["--overwrite-policy", "overwrite"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_ne!(std::fs::read_to_string(repo.join("README.md"))?, "my readme");
+    assert_ne!(std::fs::read_to_string(repo.join("Cargo.toml"))?, "# mine");
+    assert_eq!(std::fs::read_to_string(repo.join("notes.txt"))?, "keep me");
+
+    // --force is the overwrite policy already
+    let output = run(&["--force", "--overwrite-policy", "skip"]);
+    assert!(!output.status.success());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_path() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive"][..], args].concat(),
+            &[("FERRISUP_TEMPLATE_DIR", &temp_dir.path().join("registry"))],
+        )
+    };
+
+    // The crate keeps its name, the directory and its missing parents are created
+    let output = run(&["my-crate", "--component-type", "minimal", "--path", "services/api"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("services/api");
+    assert!(std::fs::read_to_string(project.join("Cargo.toml"))?.contains("name = \"my-crate\""));
+    assert!(!temp_dir.path().join("my-crate").exists());
+    // The next steps cd into the directory the project was generated in
+    assert!(String::from_utf8_lossy(&output.stdout).contains("cd services/api\n"));
+
+    let output = run(&["other", "--component-type", "minimal", "--path", "a", "--into", "."]);
+    assert!(!output.status.success());
+
+    // Parents created for a project that failed to generate are removed again
+    let broken = temp_dir.path().join("registry/broken");
+    std::fs::create_dir_all(&broken)?;
+    std::fs::write(broken.join("ferrisup.toml"), "description = \"Broken\"\n")?;
+    std::fs::write(broken.join("main.rs"), "{{#if unclosed}}\n")?;
+    let output = run(&["broken", "--component-type", "broken", "--path", "deep/nested/broken"]);
+    assert!(!output.status.success());
+    assert!(!temp_dir.path().join("deep").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_refuses_to_overwrite_existing_project() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let project = temp_dir.path().join("taken");
+    std::fs::create_dir(&project)?;
+    std::fs::write(project.join("README.md"), "my readme")?;
+
+    let run = |extra: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "taken", "--component-type", "minimal", "--no-interactive"][..], extra].concat(),
+            &[],
+        )
+    };
+
+    let output = run(&[]);
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("README.md") && stderr.contains("--force"), "Unexpected error: {}", stderr);
+    assert_eq!(std::fs::read_to_string(project.join("README.md"))?, "my readme");
+
+    let output = run(&["--force"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(project.join("Cargo.toml").exists());
+
+    // Files the template doesn't generate make the directory non-empty all the same
+    let unrelated = temp_dir.path().join("notes");
+    std::fs::create_dir(&unrelated)?;
+    std::fs::write(unrelated.join("todo.txt"), "later")?;
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "notes", "--component-type", "minimal", "--no-interactive"],
+        &[],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("todo.txt") && stderr.contains("is not empty"), "Unexpected error: {}", stderr);
+
+    // A file in the way is never generated over
+    std::fs::write(temp_dir.path().join("blocked"), "a file")?;
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "blocked", "--component-type", "minimal", "--no-interactive", "--force"],
+        &[],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("is a file, not a directory"), "Unexpected error: {}", stderr);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_dependencies_from() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let deps = temp_dir.path().join("team-deps.toml");
+    std::fs::write(
+        &deps,
+        "[dependencies]\nanyhow = \"1.0\"\ntracing = { version = \"0.1\", features = [\"log\"] }\n",
+    )?;
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &[
+            "new", "seeded", "--component-type", "minimal", "--no-interactive",
+            "--dependencies-from", deps.to_str().unwrap(),
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let manifest: toml::Value = std::fs::read_to_string(temp_dir.path().join("seeded/Cargo.toml"))?.parse()?;
+    assert_eq!(manifest["package"]["name"].as_str(), Some("seeded"));
+    assert_eq!(manifest["dependencies"]["anyhow"].as_str(), Some("1.0"));
+    assert_eq!(manifest["dependencies"]["tracing"]["version"].as_str(), Some("0.1"));
+    assert_eq!(manifest["dependencies"]["tracing"]["features"][0].as_str(), Some("log"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_registry() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    std::fs::write(temp_dir.path().join("deps.toml"), "[dependencies]\nanyhow = \"1.0\"\n")?;
+    // An empty cargo home, so only the project's own configuration knows the registry
+    let cargo_home = temp_dir.path().join("cargo-home");
+    std::fs::create_dir(&cargo_home)?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(temp_dir.path(), args, &[("CARGO_HOME", &cargo_home)])
+    };
+    let new = ["new", "--component-type", "minimal", "--no-interactive", "--offline", "--dependencies-from", "deps.toml"];
+
+    // A registry cargo doesn't know yet needs its index
+    let output = run(&[&new[..], &["unknown", "--registry", "internal"]].concat());
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--registry-url"));
+    assert!(!temp_dir.path().join("unknown").exists());
+
+    let output = run(&[
+        &new[..],
+        &["private", "--registry", "internal", "--registry-url", "sparse+https://crates.example.com/index/"],
+    ]
+    .concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let manifest: toml::Value = std::fs::read_to_string(temp_dir.path().join("private/Cargo.toml"))?.parse()?;
+    assert_eq!(manifest["dependencies"]["anyhow"]["version"].as_str(), Some("1.0"));
+    assert_eq!(manifest["dependencies"]["anyhow"]["registry"].as_str(), Some("internal"));
+    let config: toml::Value = std::fs::read_to_string(temp_dir.path().join("private/.cargo/config.toml"))?.parse()?;
+    assert_eq!(
+        config["registries"]["internal"]["index"].as_str(),
+        Some("sparse+https://crates.example.com/index/")
+    );
+
+    // `dependency add` takes crates from the registry the project declares
+    let add = ["dependency", "add", "--path", "private", "--no-interactive", "--version", "0.4", "--registry"];
+    let output = run(&[&add[..], &["internal", "log"]].concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let manifest: toml::Value = std::fs::read_to_string(temp_dir.path().join("private/Cargo.toml"))?.parse()?;
+    assert_eq!(manifest["dependencies"]["log"]["registry"].as_str(), Some("internal"));
+    assert!(!run(&[&add[..], &["elsewhere", "log"]].concat()).status.success());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_target_dir() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--component-type", "minimal", "--no-interactive", "--offline"][..], args].concat(),
+            &[],
+        )
+    };
+
+    let output = run(&["svc", "--target-dir", "../shared-target"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let config: toml::Value = std::fs::read_to_string(temp_dir.path().join("svc/.cargo/config.toml"))?.parse()?;
+    assert_eq!(config["build"]["target-dir"].as_str(), Some("../shared-target"));
+    let stamp: toml::Value = std::fs::read_to_string(temp_dir.path().join("svc/.ferrisup/origin.toml"))?.parse()?;
+    assert_eq!(stamp["target_dir"].as_str(), Some("../shared-target"));
+
+    // Regenerating from the stamp keeps the target directory
+    let output = run(&["svc_again", "--answers", "svc/.ferrisup/origin.toml"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let config = std::fs::read_to_string(temp_dir.path().join("svc_again/.cargo/config.toml"))?;
+    assert!(config.contains("target-dir = \"../shared-target\""), "{}", config);
+
+    assert!(!run(&["itself", "--target-dir", "."]).status.success());
+    assert!(!temp_dir.path().join("itself").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_profile() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new"][..], args, &["--no-interactive", "--offline"][..]].concat(),
+            &[],
+        )
+    };
+    let release_profile = |project: &str| -> Result<toml::Value> {
+        let manifest: toml::Value = std::fs::read_to_string(temp_dir.path().join(project).join("Cargo.toml"))?.parse()?;
+        Ok(manifest.get("profile").and_then(|p| p.get("release")).cloned().unwrap_or(toml::Value::Boolean(false)))
+    };
+
+    let output = run(&["small", "--component-type", "minimal", "--profile", "min-size"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let release = release_profile("small")?;
+    assert_eq!(release["opt-level"].as_str(), Some("z"), "{}", release);
+    assert_eq!(release["lto"].as_bool(), Some(true), "{}", release);
+    assert_eq!(release["strip"].as_bool(), Some(true), "{}", release);
+
+    let output = run(&["fast", "--component-type", "minimal", "--profile", "release-lto"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let release = release_profile("fast")?;
+    assert_eq!(release["lto"].as_str(), Some("fat"), "{}", release);
+    assert_eq!(release["codegen-units"].as_integer(), Some(1), "{}", release);
+
+    // Without --profile a project gets its template's default, and none for most templates
+    let output = run(&["plain", "--component-type", "minimal"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(release_profile("plain")?.get("lto").is_none());
+    let edge = ["--component-type", "edge", "--application-type", "api-function", "--provider", "cloudflare"];
+    let output = run(&[&["worker"][..], &edge[..]].concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let release = release_profile("worker")?;
+    assert_eq!(release["opt-level"].as_str(), Some("z"), "{}", release);
+    assert_eq!(release["codegen-units"].as_integer(), Some(1), "{}", release);
+    let output = run(&[&["dev_worker", "--profile", "dev"][..], &edge[..]].concat());
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(release_profile("dev_worker")?["opt-level"].as_str(), Some("s"));
+
+    assert!(!run(&["unknown", "--component-type", "minimal", "--profile", "fastest"]).status.success());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_readme() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--component-type", "minimal", "--no-interactive", "--offline"][..], args].concat(),
+            &[],
+        )
+    };
+
+    let output = run(&["documented"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let readme = std::fs::read_to_string(temp_dir.path().join("documented/README.md"))?;
+    assert!(readme.starts_with("# documented\n"), "{}", readme);
+    assert!(readme.contains("## Building and running"), "{}", readme);
+
+    let output = run(&["undocumented", "--no-readme"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(temp_dir.path().join("undocumented/Cargo.toml").exists());
+    assert!(!temp_dir.path().join("undocumented/README.md").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_requires_template_without_interaction() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+
+    // The template picker is never shown with --no-interactive
+    let output = common::run_ferrisup(temp_dir.path(), &["new", "picked", "--no-interactive"], &[]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("--template"), "Unexpected error: {}", stderr);
+    assert!(!temp_dir.path().join("picked").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_rejects_unknown_set_variable() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "set_project", "--component-type", "embedded", "--set", "colour=red", "--no-interactive"],
+        &[],
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("colour") && stderr.contains("mcu_target"), "Unexpected error: {}", stderr);
+    assert!(!temp_dir.path().join("set_project").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_answers_file() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    std::fs::write(
+        temp_dir.path().join("answers.yaml"),
+        "data_source: JSON data\nanalysis_type: Statistical analysis\nvisualization: \"no\"\n",
+    )?;
+
+    // --set wins over the answers file
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &[
+            "new", "answers_app", "--component-type", "data-science", "--framework", "polars",
+            "--answers", "answers.yaml", "--set", "visualization=yes", "--no-interactive", "--offline",
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let manifest = std::fs::read_to_string(temp_dir.path().join("answers_app").join("Cargo.toml"))?;
+    assert!(manifest.contains("\"json\""), "{}", manifest);
+    assert!(manifest.contains("plotters"), "{}", manifest);
+    let main_rs = std::fs::read_to_string(temp_dir.path().join("answers_app/src/main.rs"))?;
+    assert!(main_rs.contains("JsonReader") && main_rs.contains("fn select_columns"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_offline() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+
+    // Bundled templates need no network access
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "offline_app", "--component-type", "minimal", "--no-interactive", "--offline"],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(temp_dir.path().join("offline_app").join("Cargo.toml").exists());
+    assert!(temp_dir.path().join("offline_app").join("src").join("main.rs").exists());
+
+    // Anything that needs the network fails up front, also when enabled from the environment
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["dependency", "update", "--path", "offline_app"],
+        &[("FERRISUP_OFFLINE", &"1")],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("offline mode"), "Unexpected error: {}", stderr);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_ci() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive", "--offline"][..], args].concat(),
+            &[],
+        )
+    };
+    let workflow = |name: &str| temp_dir.path().join(name).join(".github/workflows/ci.yml");
+
+    // No workflow unless asked for
+    let output = run(&["no_ci", "--component-type", "minimal"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!workflow("no_ci").exists());
+
+    let output = run(&["ci_app", "--component-type", "minimal", "--edition", "2024", "--ci", "github"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(workflow("ci_app"))?;
+    assert!(content.contains("Rust edition 2024"), "{}", content);
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&content)?;
+    assert_eq!(yaml["jobs"]["check"]["steps"][4]["run"], "cargo clippy --workspace --all-targets -- -D warnings");
+    assert_eq!(yaml["jobs"]["msrv"]["steps"][1]["with"]["toolchain"], "1.85");
+
+    // Leptos builds with trunk for wasm
+    let output = run(&["ci_web", "--component-type", "client", "--framework", "leptos", "--ci", "github"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(workflow("ci_web"))?;
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&content)?;
+    let steps = yaml["jobs"]["check"]["steps"].as_sequence().unwrap();
+    assert_eq!(steps[1]["with"]["targets"], "wasm32-unknown-unknown");
+    assert_eq!(steps.last().unwrap()["run"], "trunk build");
+
+    let output = run(&["ci_other", "--component-type", "minimal", "--ci", "gitlab"]);
+    assert!(!output.status.success());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_check_name_offline() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "my-checked-app", "--component-type", "minimal", "--check-name", "--no-interactive", "--offline"],
+        &[],
+    );
+
+    // The check never fails generation, and offline it isn't even attempted
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("skipping the crates.io check of my_checked_app"), "{}", stdout);
+    assert!(temp_dir.path().join("my-checked-app/Cargo.toml").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_license() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive", "--offline", "--component-type", "minimal"][..], args].concat(),
+            &[],
+        )
+    };
+
+    // MIT unless told otherwise
+    let output = run(&["default_license"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("default_license");
+    let license = std::fs::read_to_string(project.join("LICENSE"))?;
+    assert!(license.starts_with("MIT License\n\nCopyright (c) 20"), "{}", license);
+    assert!(!license.contains("{{"), "{}", license);
+    assert!(std::fs::read_to_string(project.join("Cargo.toml"))?.contains("license = \"MIT\""));
+    let stamp = std::fs::read_to_string(project.join(".ferrisup/origin.toml"))?;
+    assert!(stamp.contains("license = \"MIT\""), "{}", stamp);
+
+    let output = run(&["dual_license", "--license", "MIT OR Apache-2.0"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("dual_license");
+    assert!(project.join("LICENSE-MIT").exists() && project.join("LICENSE-APACHE").exists());
+    assert!(!project.join("LICENSE").exists());
+    assert!(std::fs::read_to_string(project.join("Cargo.toml"))?.contains("license = \"MIT OR Apache-2.0\""));
+
+    let output = run(&["gpl_license", "--license", "GPL-3.0"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Supported SPDX identifiers are: MIT, Apache-2.0"), "{}", stderr);
+    assert!(!temp_dir.path().join("gpl_license").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_git_branch() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |name: &str, args: &[&str]| {
+        // Whatever init.defaultBranch the user has, the branch comes from --git-branch
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", name, "--component-type", "minimal", "--no-interactive", "--offline"][..], args].concat(),
+            &[("HOME", &temp_dir.path())],
+        )
+    };
+    std::fs::write(temp_dir.path().join(".gitconfig"), "[init]\n\tdefaultBranch = master\n")?;
+    let head = |name: &str| -> Result<String> {
+        let repo = git2::Repository::open(temp_dir.path().join(name))?;
+        let head = repo.head()?;
+        Ok(head.shorthand().unwrap_or_default().to_string())
+    };
+
+    let output = run("default_branch", &["--git"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(head("default_branch")?, "main");
+
+    let output = run("trunk_branch", &["--git", "--git-branch", "trunk"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(head("trunk_branch")?, "trunk");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("initialized on branch trunk"));
+
+    // Invalid names are refused before anything is generated
+    let output = run("bad_branch", &["--git", "--git-branch", "feature..x"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'feature..x' is not a valid git branch name"));
+    assert!(!temp_dir.path().join("bad_branch").exists());
+
+    // Without --git there's no repository to name a branch of
+    let output = run("no_git", &["--git-branch", "trunk"]);
+    assert!(!output.status.success());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_new_command_vcs() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = common::create_test_dir()?;
+    // A stand-in jj that records how it's called
+    let bin = temp_dir.path().join("bin");
+    std::fs::create_dir(&bin)?;
+    let log = temp_dir.path().join("jj.log");
+    std::fs::write(bin.join("jj"), format!("#!/bin/sh\necho \"$@\" >> {}\n", log.display()))?;
+    std::fs::set_permissions(bin.join("jj"), std::fs::Permissions::from_mode(0o755))?;
+
+    // The real PATH minus any jj, with the stand-in in front when `with_jj` is set
+    let system_path: Vec<_> = std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default())
+        .filter(|dir| !dir.join("jj").exists())
+        .collect();
+    let run = |name: &str, args: &[&str], with_jj: bool| {
+        let path = std::env::join_paths(with_jj.then(|| bin.clone()).into_iter().chain(system_path.clone()))
+            .expect("PATH entries have no separators");
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", name, "--component-type", "minimal", "--no-interactive", "--offline"][..], args].concat(),
+            &[("PATH", &path)],
+        )
+    };
+
+    // --vcs git is what --git was, and --git still works with a note
+    let output = run("with_git", &["--vcs", "git"], true);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(temp_dir.path().join("with_git/.git").is_dir());
+    let output = run("old_flag", &["--git"], true);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("--git is deprecated"));
+    assert!(temp_dir.path().join("old_flag/.git").is_dir());
+    assert!(!run("both", &["--git", "--vcs", "jj"], true).status.success());
+
+    // jj makes a colocated repository, commits and names the branch
+    let output = run("with_jj", &["--vcs", "jj", "--git-branch", "trunk", "--commit-message", "Start"], true);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Jujutsu repository initialized on branch trunk"), "{}", stdout);
+    assert_eq!(
+        std::fs::read_to_string(&log)?,
+        "git init --colocate\ncommit --message Start\nbookmark create trunk --revision @-\n"
+    );
+    assert!(temp_dir.path().join("with_jj/.gitignore").is_file());
+
+    // Without jj installed the project is still generated
+    let output = run("no_jj", &["--vcs", "jj"], false);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("jj is not installed"), "{}", stdout);
+    assert!(stdout.contains("Jujutsu repository: failed to initialize"), "{}", stdout);
+    assert!(temp_dir.path().join("no_jj/Cargo.toml").is_file());
+
+    // none leaves the project without a repository
+    let output = run("no_vcs", &["--vcs", "none"], true);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!temp_dir.path().join("no_vcs/.git").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_json_output() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = || {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[
+                "--output-format", "json", "new", "json_app", "--component-type", "minimal",
+                "--no-interactive", "--offline", "--vcs", "git",
+            ],
+            &[],
+        )
+    };
+
+    // stdout is exactly one JSON object, the status lines went to stderr
+    let output = run();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let path = temp_dir.path().join("json_app").canonicalize()?;
+    assert_eq!(project["path"], path.to_string_lossy().as_ref());
+    assert_eq!(project["name"], "json_app");
+    assert_eq!(project["template"], "minimal");
+    assert_eq!(project["variables"]["project_name"], "json_app");
+    assert!(project["files"].as_array().unwrap().iter().any(|file| file == "Cargo.toml"), "{}", project);
+    // The .gitignore written for the repository is listed like the template's files
+    assert!(project["files"].as_array().unwrap().iter().any(|file| file == ".gitignore"), "{}", project);
+    assert_eq!(project["vcs"], "git");
+    assert_eq!(project["git_initialized"], true);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("created successfully"));
+
+    // Generating it again fails, with the error as JSON too
+    let output = run();
+    assert!(!output.status.success());
+    let error: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(error["error"].as_str().unwrap().contains("not empty"), "{}", error);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_log_file() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let log_path = temp_dir.path().join("ferrisup.log");
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &[
+            "new", "logged_lib", "--component-type", "library", "--no-interactive", "--offline",
+            "--author", "Ferris <ferris@example.com>", "--log-file", log_path.to_str().unwrap(),
+        ],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let log = std::fs::read_to_string(&log_path)?;
+    let entries = log
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    assert!(!entries.is_empty());
+    let rendered: Vec<&str> = entries
+        .iter()
+        .filter(|e| e["message"] == "Rendered template file")
+        .filter_map(|e| e["file"].as_str())
+        .collect();
+    for file in ["Cargo.toml.template", "README.md", "lib.rs"] {
+        assert!(rendered.iter().any(|r| r.ends_with(file)), "{} not logged in {:?}", file, rendered);
+    }
+
+    let variable = |name: &str| {
+        entries
+            .iter()
+            .find(|e| e["message"] == "Resolved template variable" && e["variable"] == name)
+            .map(|e| e["value"].clone())
+    };
+    assert_eq!(variable("project_name"), Some(serde_json::json!("logged_lib")));
+    // The author's name and email are kept out of the log
+    assert_eq!(variable("author"), Some(serde_json::json!("<redacted>")));
+    assert!(!log.contains("ferris@example.com"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_minimal() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive", "--offline"][..], args].concat(),
+            &[],
+        )
+    };
+    let read = |path: &str| std::fs::read_to_string(temp_dir.path().join(path));
+
+    let output = run(&["full_lib", "--component-type", "library"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let lib_rs = read("full_lib/src/lib.rs")?;
+    assert!(lib_rs.contains("pub fn hello()"), "{}", lib_rs);
+    assert!(!lib_rs.contains("minimal}}"), "{}", lib_rs);
+    assert!(read("full_lib/README.md")?.contains("## Customization"));
+
+    // The guarded tutorial and demo blocks are left out
+    let output = run(&["lean_lib", "--component-type", "library", "--minimal"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(read("lean_lib/src/lib.rs")?, "//! lean_lib\n");
+    let readme = read("lean_lib/README.md")?;
+    assert!(!readme.contains("## Customization") && readme.contains("## Resources"), "{}", readme);
+    assert!(!read("lean_lib/Cargo.toml")?.contains("# See more keys"));
+
+    // Templates without a minimal variant are generated in full
+    let output = run(&["lean_bin", "--component-type", "minimal", "--minimal"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Template 'minimal' has no minimal variant"), "{}", stdout);
+    assert!(read("lean_bin/src/main.rs")?.contains("fn main()"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_template_version() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive", "--offline", "--component-type", "minimal"][..], args].concat(),
+            &[],
+        )
+    };
+
+    // The version used is recorded whether or not it was pinned
+    let output = run(&["unpinned"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stamp = std::fs::read_to_string(temp_dir.path().join("unpinned/.ferrisup/origin.toml"))?;
+    assert!(stamp.contains("template_version = \"0.1.0\""), "{}", stamp);
+    assert!(!stamp.contains("template_commit"), "{}", stamp);
+
+    let output = run(&["pinned", "--template-version", "0.1.0"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stamp = std::fs::read_to_string(temp_dir.path().join("pinned/.ferrisup/origin.toml"))?;
+    assert!(stamp.contains("template_version = \"0.1.0\""), "{}", stamp);
+
+    let output = run(&["mismatch", "--template-version", "2.0.0"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Template 'minimal' is at version 0.1.0, not the requested 2.0.0"), "{}", stderr);
+    assert!(!temp_dir.path().join("mismatch").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_author() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    // A home without a git config, so no author is found unless one is given
+    let home = temp_dir.path().join("home");
+    std::fs::create_dir_all(&home)?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--no-interactive", "--offline", "--component-type", "minimal"][..], args].concat(),
+            &[("HOME", &home), ("XDG_CONFIG_HOME", &home), ("GIT_CONFIG_GLOBAL", &home.join(".gitconfig"))],
+        )
+    };
+
+    let output = run(&["with_author", "--author", "Ferris Crab <ferris@example.com>"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("with_author");
+    let manifest = std::fs::read_to_string(project.join("Cargo.toml"))?;
+    assert!(manifest.contains("authors = [\"Ferris Crab <ferris@example.com>\"]\n"), "{}", manifest);
+    let license = std::fs::read_to_string(project.join("LICENSE"))?;
+    assert!(license.contains(" Ferris Crab <ferris@example.com>\n"), "{}", license);
+
+    // Without an author the manifest has no authors and LICENSE names the project's authors
+    let output = run(&["no_author"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("no_author");
+    let manifest = std::fs::read_to_string(project.join("Cargo.toml"))?;
+    assert!(!manifest.contains("authors"), "{}", manifest);
+    assert!(std::fs::read_to_string(project.join("LICENSE"))?.contains(" The no_author authors\n"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_edge_websockets_option() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let run = |name: &str, provider: &str, extra: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[
+                &["new", name, "--component-type", "edge", "--application-type", "api-function"][..],
+                &["--provider", provider, "--no-interactive", "--offline"],
+                extra,
+            ]
+            .concat(),
+            &[],
+        )
+    };
+    let read = |path: &str| std::fs::read_to_string(temp_dir.path().join(path));
+
+    // The Cloudflare worker is a library, the Fastly service a binary
+    for (provider, entry) in [("cloudflare", "src/lib.rs"), ("fastly", "src/main.rs")] {
+        let plain = format!("{}_http", provider);
+        let output = run(&plain, provider, &[]);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let main_rs = read(&format!("{}/{}", plain, entry))?;
+        assert!(main_rs.contains("\"/api\""), "{}", main_rs);
+        assert!(!main_rs.contains("/ws"), "{}", main_rs);
+        assert!(!main_rs.contains("{{"), "{}", main_rs);
+        assert!(!read(&format!("{}/README.md", plain))?.contains("WebSocket"));
+
+        let ws = format!("{}_ws", provider);
+        let output = run(&ws, provider, &["--set", "websockets=true"]);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let main_rs = read(&format!("{}/{}", ws, entry))?;
+        assert!(main_rs.contains("\"/ws\") => {"), "{}", main_rs);
+        assert!(main_rs.contains("\"/api\""), "{}", main_rs);
+        assert!(read(&format!("{}/README.md", ws))?.contains("## WebSockets"));
+    }
+
+    // Only the Cloudflare worker needs extra crates for the socket
+    assert!(read("cloudflare_ws/Cargo.toml")?.contains("wasm-bindgen-futures"));
+    assert!(!read("cloudflare_http/Cargo.toml")?.contains("wasm-bindgen-futures"));
+    assert!(read("fastly_ws/src/main.rs")?.contains("handoff_fanout(\"self\")"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_edge_cache_header_helpers() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    for provider in ["fastly", "aws-lambda-edge"] {
+        let name = format!("{}_cache", provider.replace('-', "_"));
+        let output = common::run_ferrisup(
+            temp_dir.path(),
+            &[
+                "new", &name, "--component-type", "edge", "--application-type", "api-function",
+                "--provider", provider, "--no-interactive", "--offline",
+            ],
+            &[],
+        );
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+        let project = temp_dir.path().join(&name);
+        let headers_rs = std::fs::read_to_string(project.join("src/headers.rs"))?;
+        assert!(headers_rs.contains("pub fn with_cache("), "{}", headers_rs);
+        assert!(headers_rs.contains("pub fn with_no_store("), "{}", headers_rs);
+        let main_rs = std::fs::read_to_string(project.join("src/main.rs"))?;
+        assert!(main_rs.contains("mod headers;"), "{}", main_rs);
+        assert!(main_rs.contains("Ok(with_cache(response, 30))"), "{}", main_rs);
+        assert!(main_rs.contains("Ok(with_no_store(response))"), "{}", main_rs);
+    }
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_prints_summary() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "summary_app", "--component-type", "minimal", "--no-interactive"],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let files = walkdir::WalkDir::new(temp_dir.path().join("summary_app"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .count();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(stdout.contains(&format!("Created {} files in", files)), "Missing summary: {}", stdout);
+    assert!(stdout.contains("Git repository: not initialized"), "{}", stdout);
+    assert!(stdout.contains("Post-generate hook: none"), "{}", stdout);
+    assert!(stdout.contains("Build with: cargo build"), "{}", stdout);
+
+    // No progress line when stdout isn't a terminal
+    assert!(!stdout.contains("\u{1b}[2K"), "{}", stdout);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_quiet_prints_nothing() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+
+    // -q is global, so it works before or after the subcommand
+    for (args, project) in [
+        (vec!["-q", "new", "quiet_before", "--component-type", "minimal", "--no-interactive"], "quiet_before"),
+        (vec!["new", "quiet_after", "-q", "--component-type", "library", "--no-interactive"], "quiet_after"),
+    ] {
+        let output = common::run_ferrisup(temp_dir.path(), &args, &[]);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert!(temp_dir.path().join(project).join("Cargo.toml").exists());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+    }
+
+    let workspace = temp_dir.path().join("quiet_ws");
+    std::fs::create_dir_all(workspace.join("member/src"))?;
+    std::fs::write(workspace.join("Cargo.toml"), "[workspace]\nmembers = [\"member\"]\n")?;
+    std::fs::write(workspace.join("member/Cargo.toml"), "[package]\nname = \"member\"\nversion = \"0.1.0\"\n")?;
+    std::fs::write(workspace.join("member/src/lib.rs"), "")?;
+    let output = common::run_ferrisup(&workspace, &["workspace", "-q", "--action", "list", "--path", "."], &[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_user_templates() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+
+    let api = registry.join("company-api");
+    std::fs::create_dir_all(api.join("src"))?;
+    std::fs::write(
+        api.join("ferrisup.toml"),
+        "description = \"Company API service\"\n\n[[files]]\nsource = \"Cargo.toml.template\"\ntarget = \"Cargo.toml\"\n\n[[files]]\nsource = \"src/main.rs\"\ntarget = \"src/main.rs\"\n\n[permissions]\nexecutable = [\"scripts/setup\"]\n",
+    )?;
+    std::fs::create_dir_all(api.join("scripts"))?;
+    std::fs::write(api.join("scripts/setup"), "#!/bin/sh\necho setting up\n")?;
+    std::fs::write(
+        api.join("Cargo.toml.template"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    std::fs::write(api.join("src/main.rs"), "fn main() {\n    println!(\"{{project_name}}\");\n}\n")?;
+
+    let broken = registry.join("broken");
+    std::fs::create_dir_all(&broken)?;
+    std::fs::write(broken.join("ferrisup.toml"), "description = \n")?;
+
+    let run = |args: &[&str]| {
+        common::run_ferrisup(temp_dir.path(), args, &[("FERRISUP_TEMPLATE_DIR", &registry)])
+    };
+
+    // Valid user templates are listed next to the bundled ones, invalid ones are not
+    let output = run(&["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(stdout.contains("company-api"), "{}", stdout);
+    assert!(stdout.contains("Company API service"), "{}", stdout);
+    assert!(stdout.contains("minimal"), "{}", stdout);
+    assert!(!stdout.contains("broken"), "{}", stdout);
+
+    let output = run(&["new", "api_app", "--component-type", "company-api", "--no-interactive"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let app = temp_dir.path().join("api_app");
+    assert!(std::fs::read_to_string(app.join("src/main.rs"))?.contains("println!(\"api_app\")"));
+    assert!(std::fs::read_to_string(app.join("Cargo.toml"))?.contains("name = \"api_app\""));
+    assert!(!app.join("ferrisup.toml").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(app.join("scripts/setup"))?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    // doctor reports the invalid template and fails
+    let output = run(&["doctor"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(!output.status.success());
+    assert!(stdout.contains("broken"), "{}", stdout);
+    assert!(stdout.contains("Invalid ferrisup.toml"), "{}", stdout);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_user_template_conditional_directories() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+    let web = registry.join("web-app");
+    std::fs::create_dir_all(web.join("src"))?;
+    std::fs::create_dir_all(web.join("server__if_ssr/src"))?;
+    std::fs::write(
+        web.join("ferrisup.toml"),
+        "description = \"Web app\"\n\n[[options]]\nname = \"ssr\"\ndescription = \"Render on the server?\"\ntype = \"boolean\"\ndefault = false\n",
+    )?;
+    std::fs::write(
+        web.join("Cargo.toml.template"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )?;
+    std::fs::write(web.join("src/main.rs"), "fn main() {}\n")?;
+    std::fs::write(web.join("server__if_ssr/src/main.rs"), "// Server of {{project_name}}\nfn main() {}\n")?;
+
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", "--component-type", "web-app", "--no-interactive", "--offline"][..], args].concat(),
+            &[("FERRISUP_TEMPLATE_DIR", &registry)],
+        )
+    };
+
+    // The directory is generated under its plain name only when its variable holds
+    let output = run(&["with_ssr", "--set", "ssr=true"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let server_main = std::fs::read_to_string(temp_dir.path().join("with_ssr/server/src/main.rs"))?;
+    assert!(server_main.contains("// Server of with_ssr"), "{}", server_main);
+    assert!(!temp_dir.path().join("with_ssr/server__if_ssr").exists());
+
+    for (name, args) in [("csr_only", &[][..]), ("csr_set", &["--set", "ssr=false"][..])] {
+        let output = run(&[&[name][..], args].concat());
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        let project = temp_dir.path().join(name);
+        assert!(project.join("src/main.rs").exists());
+        assert!(!project.join("server").exists());
+        assert!(!project.join("server__if_ssr").exists());
+    }
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rendered_files_are_normalized() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+    let service = registry.join("service");
+    std::fs::create_dir_all(service.join("src"))?;
+    std::fs::create_dir_all(service.join("assets"))?;
+    std::fs::write(
+        service.join("ferrisup.toml"),
+        "description = \"Service\"\n\n[[options]]\nname = \"logging\"\ndescription = \"Log requests?\"\ntype = \"boolean\"\ndefault = false\n",
+    )?;
+    // No final newline, like many hand-written templates
+    std::fs::write(
+        service.join("Cargo.toml.template"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"",
+    )?;
+    std::fs::write(
+        service.join("src/main.rs"),
+        "use std::env;   \n{{#if logging}}\nuse log::info;\n{{/if}}\n\n\n{{#if logging}}\nfn init() {}\n{{/if}}\n\n\nfn main() {\n    let _ = env::args();\t\n}\n\n\n",
+    )?;
+    let logo: &[u8] = b"\x89PNG\r\n\x1a\n\0\0  \n\n\n\n";
+    std::fs::write(service.join("assets/logo.png"), logo)?;
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "quiet_service", "--component-type", "service", "--no-interactive", "--offline"],
+        &[("FERRISUP_TEMPLATE_DIR", &registry)],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // The skipped conditionals leave no gaps, stray spaces or missing newlines behind
+    let project = temp_dir.path().join("quiet_service");
+    assert_eq!(
+        std::fs::read_to_string(project.join("src/main.rs"))?,
+        "use std::env;\n\nfn main() {\n    let _ = env::args();\n}\n"
+    );
+    let manifest = std::fs::read_to_string(project.join("Cargo.toml"))?;
+    assert!(manifest.contains("edition = \"2021\"\n"), "{}", manifest);
+    assert_eq!(std::fs::read(project.join("assets/logo.png"))?, logo);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_transform_retheme() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+    let manifest = "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n";
+    for (template, files) in [
+        ("counter-app", [("src/main.rs", "// counter\nfn main() {}\n"), ("src/counter.rs", "pub struct Counter;\n"), ("style.css", "a {}\n")]),
+        ("router-app", [("src/main.rs", "// router\nfn main() {}\n"), ("src/routes.rs", "pub struct Routes;\n"), ("style.css", "b {}\n")]),
+    ] {
+        let dir = registry.join(template);
+        std::fs::create_dir_all(dir.join("src"))?;
+        std::fs::write(dir.join("ferrisup.toml"), format!("description = \"{}\"\n", template))?;
+        std::fs::write(dir.join("Cargo.toml.template"), manifest)?;
+        for (path, content) in files {
+            std::fs::write(dir.join(path), content)?;
+        }
+    }
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["--offline"][..], args].concat(),
+            &[("FERRISUP_TEMPLATE_DIR", &registry)],
+        )
+    };
+
+    let output = run(&["new", "app", "--component-type", "counter-app", "--no-interactive"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let project = temp_dir.path().join("app");
+    std::fs::write(project.join("style.css"), "a { color: red; }\n")?;
+    std::fs::write(project.join("src/extra.rs"), "pub fn mine() {}\n")?;
+
+    // The dry run lists the changes and the file the user modified, writing nothing
+    let output = run(&["transform", "--project", "app", "--to", "router-app", "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+    for change in ["modify src/main.rs", "create src/routes.rs", "delete src/counter.rs", "conflict style.css"] {
+        assert!(stdout.contains(change), "missing `{}` in:\n{}", change, stdout);
+    }
+    assert!(stdout.contains("+b {}"), "{}", stdout);
+    assert!(!project.join("src/routes.rs").exists());
+
+    // Applied, the modified file is kept and the new template's version goes next to it
+    let output = run(&["transform", "--project", "app", "--to", "router-app"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(std::fs::read_to_string(project.join("src/main.rs"))?, "// router\nfn main() {}\n");
+    assert!(project.join("src/routes.rs").exists());
+    assert!(!project.join("src/counter.rs").exists());
+    assert_eq!(std::fs::read_to_string(project.join("style.css"))?, "a { color: red; }\n");
+    assert_eq!(std::fs::read_to_string(project.join("style.css.ferrisup-new"))?, "b {}\n");
+    assert_eq!(std::fs::read_to_string(project.join("src/extra.rs"))?, "pub fn mine() {}\n");
+    let stamp = std::fs::read_to_string(project.join(".ferrisup/origin.toml"))?;
+    assert!(stamp.contains("template = \"router-app\""), "{}", stamp);
+
+    // Without a stamp the original template has to be named
+    std::fs::remove_dir_all(project.join(".ferrisup"))?;
+    let output = run(&["transform", "--project", "app", "--to", "counter-app"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--from"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_doctor_fix() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = common::create_test_dir()?;
+    // Stand-ins for the toolchain: `cargo install` and `rustup target add` make the tool
+    // or target show up, and cargo records what it installs
+    let bin = temp_dir.path().join("bin");
+    std::fs::create_dir(&bin)?;
+    let log = temp_dir.path().join("cargo.log");
+    let scripts = [
+        ("rustc", "echo 'rustc 1.90.0 (1159e78c4 2025-09-14)'".to_string()),
+        (
+            "rustup",
+            format!(
+                "case \"$2\" in\n  list) [ -f {bin}/wasm ] && echo wasm32-unknown-unknown ;;\n  add) : > {bin}/wasm ;;\nesac\nexit 0",
+                bin = bin.display()
+            ),
+        ),
+        (
+            "cargo",
+            format!(
+                "case \"$1\" in\n  install) echo \"$@\" >> {log} && printf '#!/bin/sh\\n' > {bin}/$2 && chmod +x {bin}/$2 ;;\n  lambda) [ -x {bin}/cargo-lambda ] ;;\nesac",
+                log = log.display(),
+                bin = bin.display()
+            ),
+        ),
+        ("git", "exit 0".to_string()),
+    ];
+    for (name, script) in scripts {
+        std::fs::write(bin.join(name), format!("#!/bin/sh\n{}\n", script))?;
+        std::fs::set_permissions(bin.join(name), std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // The real PATH minus the real toolchain and tools, with the stand-ins in front
+    let tools = ["rustc", "rustup", "cargo", "trunk", "wasm-pack", "cargo-lambda"];
+    let system_path = std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default())
+        .filter(|dir| !tools.iter().any(|tool| dir.join(tool).exists()))
+        .collect::<Vec<_>>();
+    let path = std::env::join_paths(std::iter::once(bin.clone()).chain(system_path))?;
+    let run = |args: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["doctor"][..], args].concat(),
+            &[
+                ("PATH", &path),
+                ("FERRISUP_TEMPLATE_DIR", &temp_dir.path().join("templates")),
+                ("FERRISUP_OFFLINE", &"0"),
+            ],
+        )
+    };
+
+    // Without --fix missing tools are only reported
+    let output = run(&[]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("trunk not found"), "{}", stdout);
+    assert!(stdout.contains("ferrisup doctor --fix"), "{}", stdout);
+    assert!(!log.exists());
+
+    // Nothing to ask on, so --fix needs --yes
+    let output = run(&["--fix", "--no-interactive"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--yes"));
+    assert!(!log.exists());
+
+    let output = run(&["--fix", "--yes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}\n{}", stdout, String::from_utf8_lossy(&output.stderr));
+    let cargo_calls = std::fs::read_to_string(&log)?;
+    assert!(cargo_calls.contains("install trunk --locked"), "{}", cargo_calls);
+    assert!(cargo_calls.contains("install wasm-pack"), "{}", cargo_calls);
+    assert!(cargo_calls.contains("install cargo-lambda --locked"), "{}", cargo_calls);
+    assert!(bin.join("wasm").exists());
+    let recheck = stdout.split("checked again").nth(1).unwrap_or_default();
+    assert!(!recheck.contains("not found"), "{}", stdout);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_template_new_and_validate() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+    let run = |args: &[&str]| {
+        common::run_ferrisup(temp_dir.path(), args, &[("FERRISUP_TEMPLATE_DIR", &registry)])
+    };
+
+    // The skeleton is a valid template that list shows right away
+    let output = run(&["template", "new", "my-service"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let template = registry.join("my-service");
+    assert!(template.join("ferrisup.toml").is_file());
+    assert!(template.join("TEMPLATE.md").is_file());
+    assert!(run(&["template", "new", "my-service"]).status.code() != Some(0));
+
+    let output = run(&["template", "validate", "my-service"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    let output = run(&["list"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("my-service template"));
+
+    // Its options are answered with their defaults without prompting
+    let output = run(&["new", "svc_app", "--component-type", "my-service", "--set", "output=stderr", "--no-interactive"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let app = temp_dir.path().join("svc_app");
+    let main_rs = std::fs::read_to_string(app.join("src/main.rs"))?;
+    assert!(main_rs.contains("eprintln!(\"Hello from svc_app!\")"), "{}", main_rs);
+    assert!(!main_rs.contains(" println!"), "{}", main_rs);
+    assert!(!app.join("TEMPLATE.md").exists());
+
+    // validate reports every problem, for a name or a path
+    std::fs::write(
+        template.join("ferrisup.toml"),
+        "[[files]]\nsource = \"src/lib.rs\"\ntarget = \"src/lib.rs\"\n",
+    )?;
+    let output = run(&["template", "validate", template.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stdout.contains("non-empty `description`"), "{}", stdout);
+    assert!(stdout.contains("source `src/lib.rs` doesn't exist"), "{}", stdout);
+    assert!(stderr.contains("has 2 problems"), "{}", stderr);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_build_failure_keeps_project() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+
+    // A user template whose main.rs doesn't compile
+    let template = registry.join("does-not-build");
+    std::fs::create_dir_all(template.join("src"))?;
+    std::fs::write(
+        template.join("ferrisup.toml"),
+        "description = \"Fails to build\"\nbuild_by_default = true\n\n[[files]]\nsource = \"Cargo.toml.template\"\ntarget = \"Cargo.toml\"\n\n[[files]]\nsource = \"src/main.rs\"\ntarget = \"src/main.rs\"\n",
+    )?;
+    std::fs::write(
+        template.join("Cargo.toml.template"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )?;
+    std::fs::write(template.join("src/main.rs"), "fn main() {\n    let x: u32 = \"not a number\";\n}\n")?;
+
+    let run = |name: &str, extra: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", name, "--component-type", "does-not-build", "--no-interactive"][..], extra].concat(),
+            &[("FERRISUP_TEMPLATE_DIR", &registry)],
+        )
+    };
+
+    // The failed build is reported with the manual command and fails `new`, but the project stays
+    let output = run("broken_app", &["--build"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success(), "{}", stdout);
+    assert!(stderr.contains("mismatched types"), "Missing build error: {}", stderr);
+    assert!(stderr.contains("`cargo build` failed with exit code 101"), "{}", stderr);
+    assert!(stdout.contains("Build failed"), "{}", stdout);
+    assert!(stdout.contains("cargo build"), "{}", stdout);
+    let app = temp_dir.path().join("broken_app");
+    assert!(app.join("Cargo.toml").is_file());
+    assert!(app.join("src/main.rs").is_file());
+
+    // The template builds by default, so it's built without --build too
+    let output = run("default_app", &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Build failed"));
+    assert!(temp_dir.path().join("default_app/src/main.rs").is_file());
+
+    // --no-build-check skips it
+    let output = run("unbuilt_app", &["--no-build-check"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Building project"));
+    assert!(temp_dir.path().join("unbuilt_app/src/main.rs").is_file());
+
+    // Templates without build_by_default = true are only built with --build
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["new", "minimal_app", "--component-type", "minimal", "--no-interactive"],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Building project"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_new_command_sample_data() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let registry = temp_dir.path().join("registry");
+
+    // A user template whose own binary writes its sample data
+    let template = registry.join("with-data");
+    std::fs::create_dir_all(template.join("src"))?;
+    std::fs::write(
+        template.join("ferrisup.toml"),
+        "description = \"Generates data\"\n\n[[files]]\nsource = \"Cargo.toml.template\"\ntarget = \"Cargo.toml\"\n\n[[files]]\nsource = \"src/main.rs\"\ntarget = \"src/main.rs\"\n\n[hooks]\nsample_data = [[\"cargo\", \"run\", \"--quiet\", \"--bin\", \"{{project_name}}\"]]\n",
+    )?;
+    std::fs::write(
+        template.join("Cargo.toml.template"),
+        "[package]\nname = \"{{project_name}}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+    )?;
+    std::fs::write(
+        template.join("src/main.rs"),
+        "fn main() {\n    std::fs::create_dir_all(\"data\").unwrap();\n    std::fs::write(\"data/sample.csv\", \"id,value\\n1,42\\n\").unwrap();\n    println!(\"Wrote data/sample.csv\");\n}\n",
+    )?;
+
+    let run = |name: &str, template: &str, extra: &[&str]| {
+        common::run_ferrisup(
+            temp_dir.path(),
+            &[&["new", name, "--component-type", template, "--no-interactive"][..], extra].concat(),
+            &[("FERRISUP_TEMPLATE_DIR", &registry)],
+        )
+    };
+
+    // The generator runs only when asked for
+    let output = run("no_data", "with-data", &[]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!temp_dir.path().join("no_data/data").exists());
+
+    let output = run("sampled", "with-data", &["--sample-data"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Wrote data/sample.csv"), "{}", stdout);
+    assert!(stdout.contains("Sample data generated"), "{}", stdout);
+    assert_eq!(std::fs::read_to_string(temp_dir.path().join("sampled/data/sample.csv"))?, "id,value\n1,42\n");
+
+    // A failing generator fails `new` but keeps the project
+    std::fs::write(template.join("src/main.rs"), "fn main() {\n    std::process::exit(3);\n}\n")?;
+    let output = run("unsampled", "with-data", &["--sample-data"]);
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("`cargo run --quiet --bin unsampled` failed with exit code 3"), "{}", stderr);
+    assert!(temp_dir.path().join("unsampled/Cargo.toml").is_file());
+
+    // Templates without a generator say so and generate as usual
+    let output = run("plain", "minimal", &["--sample-data"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("has no sample-data generator"));
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_preview_list_variables() -> Result<()> {
+    let output = common::run_ferrisup(
+        Path::new("."),
+        &["preview", "--template", "server", "--list-variables", "--json"],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // Only the JSON is printed, without the banner
+    let variables: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let framework = variables
+        .as_array()
+        .and_then(|v| v.iter().find(|v| v["name"] == "framework"))
+        .expect("server declares a framework variable");
+    assert_eq!(framework["type"], "select");
+    assert_eq!(framework["default"], "axum");
+    assert_eq!(framework["choices"], serde_json::json!(["axum", "actix", "poem"]));
+    Ok(())
+}
+
+#[test]
+fn test_component_list_json() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let root = temp_dir.path();
+    std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"server\", \"client\", \"shared\"]\n")?;
+    for (member, dependencies) in [("server", "axum = \"0.7\"\ntokio = \"1\"\n"), ("client", "leptos = \"0.7\"\n"), ("shared", "")] {
+        std::fs::create_dir_all(root.join(member).join("src"))?;
+        std::fs::write(
+            root.join(member).join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n\n[dependencies]\n{}", member, dependencies),
+        )?;
+    }
+
+    let output = common::run_ferrisup(
+        Path::new("."),
+        &["component", "--action", "list", "--json", "--project", root.to_str().unwrap()],
+        &[],
+    );
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    // Only the JSON is printed, sorted by path
+    let components: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let paths: Vec<&str> = components.as_array().unwrap().iter().map(|c| c["path"].as_str().unwrap()).collect();
+    assert_eq!(paths, ["client", "server", "shared"]);
+    assert_eq!(components[0]["framework"], "leptos");
+    assert_eq!(components[1]["framework"], "axum");
+    assert_eq!(components[1]["dependency_count"], 2);
+    assert_eq!(components[2]["framework"], serde_json::Value::Null);
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_verify_command() -> Result<()> {
+    let run = |args: &[&str]| {
+        common::run_ferrisup(Path::new("."), args, &[])
+    };
+
+    // The minimal template has no dependencies, so it can be checked offline
+    let output = run(&["--offline", "verify", "minimal"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "{}\n{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("✓ minimal (cargo check --all-targets --offline)"), "{}", stdout);
+
+    let output = run(&["verify"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("<TEMPLATE>"));
+    Ok(())
+}
+
+#[test]
+fn test_config_validate_only() -> Result<()> {
+    let temp_dir = common::create_test_dir()?;
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/config_misspelled_key.json");
+
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["config", "--import", fixture, "--validate-only"],
+        &[],
+    );
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success(), "A misspelled key should fail validation");
+    assert!(stderr.contains("unknown field `interactiv`"), "Unexpected error: {}", stderr);
+    assert!(stderr.contains("line 6"), "The error should point at the key: {}", stderr);
+    assert!(!temp_dir.path().join("config.json").exists(), "Validation must not apply the config");
+
+    // A correct config passes and is not applied either
+    let valid = temp_dir.path().join("valid.toml");
+    std::fs::write(&valid, "templates_dir = \"templates\"\n\n[preferences]\ngit = false\n")?;
+    let output = common::run_ferrisup(
+        temp_dir.path(),
+        &["config", "--import", "valid.toml", "--validate-only"],
+        &[],
+    );
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!temp_dir.path().join("config.json").exists());
+
+    common::cleanup_test_dir(temp_dir)?;
+    Ok(())
+}
+
 #[test]
 fn test_workspace_command() -> Result<()> {
     // Create a temp directory for the test
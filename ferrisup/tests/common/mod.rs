@@ -3,6 +3,9 @@
 //! These utilities are maintained for test infrastructure even if not currently used
 //! by all tests. They provide a consistent testing approach for current and future tests.
 
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
 use std::string::String;
 use tempfile::TempDir;
 use anyhow::Result;
@@ -16,6 +19,23 @@ pub fn create_test_dir() -> Result<TempDir> {
     Ok(tempfile::tempdir()?)
 }
 
+/// Runs the ferrisup binary in `dir` with `args` and the extra environment `envs`
+/// 
+/// Stdin is closed, so a prompt fails instead of waiting, and stdout and stderr
+/// are captured for the test to inspect.
+#[allow(dead_code)]
+pub fn run_ferrisup(dir: &Path, args: &[&str], envs: &[(&str, &dyn AsRef<OsStr>)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ferrisup"))
+        .args(args)
+        .envs(envs.iter().map(|(key, value)| (key, value.as_ref())))
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("failed to run ferrisup")
+}
+
 /// Helper to clean up test directories
 /// 
 /// This function is maintained as part of the test infrastructure for current
@@ -0,0 +1,5 @@
+MEMORY
+{
+  FLASH : ORIGIN = 0x10000000, LENGTH = 2048K
+  RAM   : ORIGIN = 0x20000000, LENGTH = 256K
+}
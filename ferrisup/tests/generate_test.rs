@@ -0,0 +1,57 @@
+//! Tests for the library API that generates projects without the CLI
+
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+
+use ferrisup::{generate, Error, GenerateOptions};
+
+#[test]
+fn test_generate_library_project() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("my_lib");
+
+    let project = generate(GenerateOptions::new("library", "my_lib").target_dir(&target))?;
+
+    assert_eq!(project.path, target);
+    assert_eq!(project.template, "library");
+    assert!(project.files.contains(&PathBuf::from("Cargo.toml")));
+    assert!(project.files.contains(&PathBuf::from("src/lib.rs")));
+    assert!(project.files.iter().all(|file| target.join(file).is_file()));
+    assert_eq!(project.variables["project_name"], "my_lib");
+    assert!(!project.git_initialized);
+    assert!(fs::read_to_string(target.join("Cargo.toml"))?.contains("name = \"my_lib\""));
+
+    // Files the template generates are only overwritten when asked to
+    let err = generate(GenerateOptions::new("library", "my_lib").target_dir(&target)).unwrap_err();
+    assert!(matches!(err, Error::TargetExists(ref path) if *path == target), "Unexpected error: {:?}", err);
+    assert!(err.to_string().contains("--force"), "Unexpected error: {}", err);
+    generate(GenerateOptions::new("library", "my_lib").target_dir(&target).force(true))?;
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_rejects_invalid_options() {
+    let err = generate(GenerateOptions::new("minimal", "1-bad-name")).unwrap_err();
+    assert!(
+        matches!(err, Error::InvalidProjectName { ref name, ref reason } if name == "1-bad-name" && reason.contains("digit")),
+        "Unexpected error: {:?}",
+        err
+    );
+
+    let options = GenerateOptions::new("minimal", "app").variable("not_a_variable", "x");
+    assert!(generate(options).is_err());
+}
+
+#[test]
+fn test_generate_missing_template() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("app");
+
+    let err = generate(GenerateOptions::new("no-such-template", "app").target_dir(&target)).unwrap_err();
+    assert!(matches!(err, Error::TemplateNotFound(ref name) if name == "no-such-template"), "Unexpected error: {:?}", err);
+    assert!(!target.exists());
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+use ferrisup::{detect_handler, find_handler, get_handlers, handler_for_project};
+use tempfile::TempDir;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/projects")
+        .join(name)
+}
+
+fn detected(name: &str) -> Option<String> {
+    detect_handler(&fixture(name)).map(|h| h.name().to_string())
+}
+
+#[test]
+fn test_detect_handler_picks_highest_confidence() {
+    // A Leptos app that also pulls in burn is still a Leptos app
+    assert_eq!(detected("leptos_with_burn").as_deref(), Some("Leptos"));
+    assert_eq!(detected("cloudflare_worker").as_deref(), Some("Edge"));
+
+    // Dev-dependencies count, but polars alone scores no higher than axum
+    assert_eq!(detected("axum_server").as_deref(), Some("Server"));
+}
+
+#[test]
+fn test_detect_handler_breaks_ties_by_registration_order() {
+    // Embassy and Embedded both match a crate and memory.x; Embassy is registered first
+    let project = fixture("embassy_firmware");
+    let handlers = get_handlers();
+    let score = |name: &str| {
+        handlers
+            .iter()
+            .find(|h| h.name() == name)
+            .map(|h| h.detect_confidence(&project))
+            .unwrap()
+    };
+    assert_eq!(score("Embassy"), score("Embedded"));
+
+    for _ in 0..3 {
+        assert_eq!(detected("embassy_firmware").as_deref(), Some("Embassy"));
+    }
+}
+
+#[test]
+fn test_detect_handler_empty_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(detect_handler(temp_dir.path()).is_none());
+    assert!(get_handlers()
+        .iter()
+        .all(|h| h.detect_confidence(temp_dir.path()) == 0.0));
+}
+
+#[test]
+fn test_handlers_suggest_build_and_run_commands() {
+    let handler = detect_handler(&fixture("leptos_with_burn")).unwrap();
+    assert_eq!(handler.build_command(), vec!["trunk", "build"]);
+    assert_eq!(handler.run_command(), Some(vec!["trunk".to_string(), "serve".to_string()]));
+
+    let variables = serde_json::json!({ "template": "data-science/polars-cli" });
+    let handler = handler_for_project(Some("data-science/polars-cli"), &variables, &fixture("axum_server")).unwrap();
+    assert_eq!(handler.name(), "Polars CLI");
+    assert_eq!(handler.run_command().unwrap()[..4], ["cargo", "run", "--", "analyze"]);
+
+    // Without a known template the project contents decide
+    let handler = handler_for_project(None, &serde_json::Value::Null, &fixture("cloudflare_worker")).unwrap();
+    assert_eq!(handler.name(), "Edge");
+    assert_eq!(handler.run_command(), None);
+}
+
+#[test]
+fn test_handler_commands_follow_template_and_provider() {
+    let empty = TempDir::new().unwrap();
+    let commands = |template: &str, variables: serde_json::Value| {
+        let handler = handler_for_project(Some(template), &variables, empty.path()).unwrap();
+        (handler.build_command().join(" "), handler.run_command().map(|run| run.join(" ")))
+    };
+
+    // A library has nothing to run
+    assert_eq!(commands("library", serde_json::json!({})), ("cargo build".to_string(), None));
+    assert_eq!(commands("minimal", serde_json::json!({})).1.as_deref(), Some("cargo run"));
+
+    // Only AWS projects use cargo-lambda
+    let serverless = |provider: &str| commands("serverless", serde_json::json!({ "cloud_provider": provider }));
+    assert_eq!(serverless("aws"), ("cargo lambda build --release".to_string(), Some("cargo lambda watch".to_string())));
+    assert_eq!(serverless("gcp"), ("cargo build --release".to_string(), None));
+    assert_eq!(serverless("azure").1.as_deref(), Some("func start"));
+    assert_eq!(serverless("vercel").1.as_deref(), Some("vercel dev"));
+    assert_eq!(serverless("netlify").1.as_deref(), Some("netlify dev"));
+}
+
+#[test]
+fn test_find_handler_scores_the_template_handlers() {
+    let project = TempDir::new().unwrap();
+    let variables = serde_json::json!({});
+    let found = |dir: &std::path::Path| find_handler("serverless", &variables, dir).map(|h| h.name().to_string());
+
+    // Nothing to go on but the template: the first serverless handler
+    assert_eq!(found(project.path()).as_deref(), Some("Serverless (AWS Lambda)"));
+
+    // The project contents pick among the handlers of the template
+    std::fs::write(project.path().join("netlify.toml"), "[build]\n").unwrap();
+    assert_eq!(found(project.path()).as_deref(), Some("Serverless (Netlify)"));
+
+    assert!(find_handler("no-such-template", &variables, project.path()).is_none());
+}
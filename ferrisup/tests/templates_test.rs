@@ -81,3 +81,211 @@ fn test_template_validation() -> Result<()> {
     
     Ok(())
 }
+
+#[test]
+fn test_leptos_rendering_variants() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+
+    // Client-side rendering keeps the Trunk setup and drops the variant sources
+    let csr_dir = temp_dir.path().join("csr_app");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "client/leptos/counter",
+        &csr_dir,
+        "csr_app",
+        Some(serde_json::json!({ "rendering": "csr" })),
+    )?;
+    assert!(csr_dir.join("index.html").exists());
+    assert!(csr_dir.join("Trunk.toml").exists());
+    assert!(!csr_dir.join("ssr").exists());
+    let lib_rs = std::fs::read_to_string(csr_dir.join("src/lib.rs"))?;
+    assert!(lib_rs.contains("fn SimpleCounter"));
+    assert!(lib_rs.contains("const STORAGE_KEY: &str = \"csr_app-simple-counter\";"));
+
+    // Server-side rendering moves the shared components to app.rs and adds the server
+    let ssr_dir = temp_dir.path().join("ssr_app");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "client/leptos/counter",
+        &ssr_dir,
+        "ssr_app",
+        Some(serde_json::json!({ "rendering": "ssr" })),
+    )?;
+    assert!(!ssr_dir.join("index.html").exists());
+    assert!(!ssr_dir.join("Trunk.toml").exists());
+    assert!(!ssr_dir.join("ssr").exists());
+    assert!(std::fs::read_to_string(ssr_dir.join("src/app.rs"))?.contains("fn SimpleCounter"));
+    assert!(std::fs::read_to_string(ssr_dir.join("src/lib.rs"))?.contains("pub fn hydrate()"));
+    assert!(std::fs::read_to_string(ssr_dir.join("src/main.rs"))?.contains("use ssr_app::{shell, App};"));
+    let manifest = std::fs::read_to_string(ssr_dir.join("Cargo.toml"))?;
+    assert!(manifest.contains("[package.metadata.leptos]"));
+    assert!(manifest.contains("style-file = \"style.css\""));
+
+    Ok(())
+}
+
+#[test]
+fn test_leptos_styling_variants() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+
+    // Plain CSS keeps the single stylesheet
+    let plain_dir = temp_dir.path().join("plain_app");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "client/leptos/counter",
+        &plain_dir,
+        "plain_app",
+        Some(serde_json::json!({ "styling": "plain-css" })),
+    )?;
+    assert!(plain_dir.join("style.css").exists());
+    assert!(!plain_dir.join("input.css").exists());
+    assert!(!plain_dir.join("tailwind").exists());
+
+    // Tailwind replaces it with the CLI's input and a Trunk hook that compiles it
+    let tailwind_dir = temp_dir.path().join("tailwind_app");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "client/leptos/counter",
+        &tailwind_dir,
+        "tailwind_app",
+        Some(serde_json::json!({ "styling": "tailwind" })),
+    )?;
+    assert!(!tailwind_dir.join("style.css").exists());
+    assert!(!tailwind_dir.join("tailwind").exists());
+    assert!(tailwind_dir.join("tailwind.config.js").exists());
+    assert!(std::fs::read_to_string(tailwind_dir.join("input.css"))?.contains("@import \"tailwindcss\";"));
+    assert!(std::fs::read_to_string(tailwind_dir.join("Trunk.toml"))?.contains("@tailwindcss/cli"));
+    // The markup keeps its class names either way
+    assert_eq!(
+        std::fs::read_to_string(tailwind_dir.join("src/lib.rs"))?,
+        std::fs::read_to_string(plain_dir.join("src/lib.rs"))?.replace("plain_app", "tailwind_app")
+    );
+
+    // cargo-leptos compiles the Tailwind input itself for server-side rendering
+    let ssr_dir = temp_dir.path().join("ssr_app");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "client/leptos/counter",
+        &ssr_dir,
+        "ssr_app",
+        Some(serde_json::json!({ "styling": "tailwind", "rendering": "ssr" })),
+    )?;
+    let manifest = std::fs::read_to_string(ssr_dir.join("Cargo.toml"))?;
+    assert!(manifest.contains("tailwind-input-file = \"input.css\""));
+    assert!(!manifest.contains("style-file"));
+    assert!(!ssr_dir.join("Trunk.toml").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_edge_cloudflare_provider() -> Result<()> {
+    let edge_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("templates/edge");
+    let config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(edge_dir.join("template.json"))?)?;
+
+    let providers = config["options"]["api_function_provider"]["values"]
+        .as_array()
+        .expect("api_function_provider should list its values");
+    assert!(providers.iter().any(|p| p == "cloudflare-workers"));
+    // `--provider cloudflare` is an alias of the Workers provider
+    assert_eq!(config["options"]["api_function_provider"]["aliases"]["cloudflare"], "cloudflare-workers");
+
+    // Every file the redirect points at must exist in the template directory
+    let files = config["redirect"]["api-function"]["cloudflare-workers"]["files"]
+        .as_array()
+        .expect("cloudflare provider should have a redirect entry");
+    for file in files {
+        let source = file["source"].as_str().expect("redirect files should have a source");
+        assert!(edge_dir.join(source).exists(), "missing {}", source);
+    }
+
+    let main_rs = std::fs::read_to_string(edge_dir.join("api-function/cloudflare-workers/lib.rs"))?;
+    for route in ["\"/\"", "\"/api\"", "\"/api/echo\"", "\"/api/headers\""] {
+        assert!(main_rs.contains(route), "missing route {}", route);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_embedded_esp32_template() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let project_dir = temp_dir.path().join("fw");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "embedded",
+        &project_dir,
+        "fw",
+        Some(serde_json::json!({
+            "framework": "No, use standard embedded template",
+            "mcu_target": "esp32",
+            "mcu_target_deps": "",
+        })),
+    )?;
+
+    let main_rs = std::fs::read_to_string(project_dir.join("src/main.rs"))?;
+    assert!(main_rs.contains("#![no_std]"));
+    assert!(main_rs.contains("use esp_hal::"));
+    assert!(main_rs.contains("fw is running on the ESP32!"));
+    assert!(std::fs::read_to_string(project_dir.join("Cargo.toml"))?.contains("esp-hal"));
+    assert!(std::fs::read_to_string(project_dir.join(".cargo/config.toml"))?.contains("xtensa-esp32-none-elf"));
+    assert!(project_dir.join("rust-toolchain.toml").exists());
+    assert!(!project_dir.join("mcu").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_candle_mnist_template() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let project_dir = temp_dir.path().join("digits");
+    ferrisup::template_manager::apply_template(
+        &mut ferrisup::template_manager::GenerationContext::default(),
+        "data-science/candle-mnist",
+        &project_dir,
+        "digits",
+        Some(serde_json::json!({ "device": "cuda" })),
+    )?;
+
+    let cargo_toml: toml::Value = std::fs::read_to_string(project_dir.join("Cargo.toml"))?.parse()?;
+    assert_eq!(cargo_toml["package"]["name"].as_str(), Some("digits"));
+    assert!(cargo_toml["dependencies"].get("candle-nn").is_some());
+    assert_eq!(cargo_toml["features"]["default"].as_array().map(Vec::len), Some(1));
+    assert_eq!(cargo_toml["features"]["default"][0].as_str(), Some("cuda"));
+
+    let main_rs = std::fs::read_to_string(project_dir.join("src/main.rs"))?;
+    assert!(main_rs.starts_with("// digits: handwritten digit recognition with Candle"));
+    assert!(main_rs.contains("Train {") && main_rs.contains("Evaluate,"));
+    assert!(project_dir.join("src/data.rs").exists() && project_dir.join("src/model.rs").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_minimal_workspace_template() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let project_dir = temp_dir.path().join("my-ws");
+    ferrisup::template_manager::apply_template(&mut ferrisup::template_manager::GenerationContext::default(), "minimal-workspace", &project_dir, "my-ws", None)?;
+
+    let root = std::fs::read_to_string(project_dir.join("Cargo.toml"))?.parse::<toml_edit::DocumentMut>()?;
+    let members: Vec<&str> = root["workspace"]["members"]
+        .as_array()
+        .expect("workspace members")
+        .iter()
+        .filter_map(|m| m.as_str())
+        .collect();
+    assert_eq!(members, ["crates/app", "crates/core", "crates/shared"]);
+    assert_eq!(root["workspace"]["dependencies"]["my-ws-core"]["path"].as_str(), Some("crates/core"));
+
+    // Each crate is named after the project and depends on the others by path
+    for (member, name) in [("app", "my-ws-app"), ("core", "my-ws-core"), ("shared", "my-ws-shared")] {
+        let manifest = std::fs::read_to_string(project_dir.join("crates").join(member).join("Cargo.toml"))?;
+        assert!(manifest.contains(&format!("name = \"{}\"", name)), "{}", manifest);
+    }
+    let main_rs = std::fs::read_to_string(project_dir.join("crates/app/src/main.rs"))?;
+    assert!(main_rs.contains("use my_ws_core::greet;"));
+    assert!(!project_dir.join("template.json").exists());
+
+    Ok(())
+}
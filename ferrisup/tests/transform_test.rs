@@ -143,7 +143,11 @@ fn test_transform_execute_full_stack() -> Result<()> {
     // Execute transform command
     let result = ferrisup::commands::transform::execute(
         Some(project_dir.to_str().unwrap()),
-        Some("full-stack")
+        Some("full-stack"),
+        None,
+        None,
+        None,
+        false
     );
     
     // Verify the operation succeeds
@@ -166,7 +170,11 @@ fn test_transform_execute_with_invalid_template() -> Result<()> {
     // Execute transform command with invalid template
     let result = ferrisup::commands::transform::execute(
         Some(project_dir.to_str().unwrap()),
-        Some("non-existent-template")
+        Some("non-existent-template"),
+        None,
+        None,
+        None,
+        false
     );
     
     // Verify the operation fails with appropriate error
@@ -193,7 +201,11 @@ fn test_transform_execute_with_valid_project() -> Result<()> {
     // Execute transform command
     let result = ferrisup::commands::transform::execute(
         Some(project_dir.to_str().unwrap()),
-        Some("library")
+        Some("library"),
+        None,
+        None,
+        None,
+        false
     );
     
     // Verify the operation succeeds
@@ -220,7 +232,11 @@ fn test_transform_execute_with_invalid_path() -> Result<()> {
     // Execute transform command with invalid path
     let result = ferrisup::commands::transform::execute(
         Some(invalid_path),
-        Some("library")
+        Some("library"),
+        None,
+        None,
+        None,
+        false
     );
     
     // Verify the operation fails with an error
@@ -228,3 +244,131 @@ fn test_transform_execute_with_invalid_path() -> Result<()> {
     
     Ok(())
 }
+
+#[test]
+fn test_transform_to_workspace() -> Result<()> {
+    let (temp_dir, project_dir) = setup_test_project()?;
+    fs::write(
+        project_dir.join("Cargo.toml"),
+        r#"[package]
+name = "test_project"
+version = "0.3.0"
+edition = "2021"
+description = "Keeps its package section"
+
+[dependencies]
+serde = "1.0"
+helpers = { path = "../helpers" }
+local = { path = "libs/local" }
+
+[target.'cfg(unix)'.dependencies]
+unix_helpers = { path = "../unix_helpers" }
+
+[profile.release]
+lto = true
+"#,
+    )?;
+    fs::create_dir_all(project_dir.join("libs/local"))?;
+    fs::write(project_dir.join("libs/local/Cargo.toml"), "[package]\nname = \"local\"\n")?;
+    fs::write(project_dir.join("Cargo.lock"), "# lock")?;
+    fs::create_dir_all(project_dir.join("target/debug"))?;
+    fs::write(project_dir.join("target/debug/test_project"), "stale")?;
+
+    ferrisup::commands::transform::execute(Some(project_dir.to_str().unwrap()), Some("to-workspace"), None, None, None, false)?;
+
+    let crate_dir = project_dir.join("crates").join("test_project");
+    assert!(crate_dir.join("src/main.rs").exists());
+    assert!(crate_dir.join("libs/local/Cargo.toml").exists());
+    assert!(!project_dir.join("src").exists());
+    assert!(!project_dir.join("target").exists(), "target/ should be removed");
+    assert!(project_dir.join("Cargo.lock").exists(), "Cargo.lock stays at the workspace root");
+
+    let root: toml::Value = toml::from_str(&fs::read_to_string(project_dir.join("Cargo.toml"))?)?;
+    assert_eq!(root["workspace"]["members"][0].as_str(), Some("crates/test_project"));
+    assert!(root.get("package").is_none());
+    assert_eq!(root["profile"]["release"]["lto"].as_bool(), Some(true));
+
+    let member: toml::Value = toml::from_str(&fs::read_to_string(crate_dir.join("Cargo.toml"))?)?;
+    assert_eq!(member["package"]["version"].as_str(), Some("0.3.0"));
+    assert_eq!(member["package"]["description"].as_str(), Some("Keeps its package section"));
+    assert_eq!(member["dependencies"]["helpers"]["path"].as_str(), Some("../../../helpers"));
+    assert_eq!(member["dependencies"]["local"]["path"].as_str(), Some("libs/local"));
+    assert_eq!(
+        member["target"]["cfg(unix)"]["dependencies"]["unix_helpers"]["path"].as_str(),
+        Some("../../../unix_helpers")
+    );
+    assert!(member.get("profile").is_none());
+
+    // Running it again is refused now that the project is a workspace
+    let err = ferrisup::commands::transform::execute(Some(project_dir.to_str().unwrap()), Some("to-workspace"), None, None, None, false)
+        .unwrap_err();
+    assert!(err.to_string().contains("already a workspace"));
+
+    drop(temp_dir);
+    Ok(())
+}
+
+#[test]
+fn test_transform_dry_run() -> Result<()> {
+    let (temp_dir, project_dir) = setup_test_project()?;
+    fs::create_dir_all(project_dir.join("target/debug"))?;
+
+    // The plan comes from running the transform on a copy of the project
+    let plan = ferrisup::commands::transform::dry_run::plan(&project_dir, |copy| {
+        ferrisup::commands::transform::workspace::to_workspace(copy).map(|_| ())
+    })?;
+    assert_eq!(
+        plan.moved,
+        vec![(PathBuf::from("src/main.rs"), PathBuf::from("crates/test_project/src/main.rs"))]
+    );
+    assert_eq!(plan.created, vec![PathBuf::from("crates/test_project/Cargo.toml")]);
+    assert_eq!(plan.modified, vec![PathBuf::from("Cargo.toml")]);
+    assert_eq!(plan.deleted, vec![PathBuf::from("target/")]);
+    assert!(plan.diff.contains("+[workspace]"), "{}", plan.diff);
+
+    // Nothing is written to the project itself
+    let project = project_dir.to_str().unwrap();
+    ferrisup::commands::transform::execute(Some(project), Some("to-workspace"), None, None, None, true)?;
+    assert!(project_dir.join("src/main.rs").exists());
+    assert!(project_dir.join("target/debug").exists());
+    assert!(!project_dir.join("crates").exists());
+
+    let err = ferrisup::commands::transform::execute(Some(project), None, None, None, None, true).unwrap_err();
+    assert!(err.to_string().contains("--dry-run needs a template"), "Unexpected error: {}", err);
+
+    drop(temp_dir);
+    Ok(())
+}
+
+#[test]
+fn test_transform_add_component_to_workspace() -> Result<()> {
+    let (temp_dir, project_dir) = setup_test_project()?;
+    let project = project_dir.to_str().unwrap();
+
+    // Adding a component needs a workspace
+    let err = ferrisup::commands::transform::execute(Some(project), Some("library"), Some("utils"), None, None, false).unwrap_err();
+    assert!(err.to_string().contains("to-workspace"), "Unexpected error: {}", err);
+
+    ferrisup::commands::transform::execute(Some(project), Some("to-workspace"), None, None, None, false)?;
+    ferrisup::commands::transform::execute(Some(project), Some("library"), Some("utils"), None, None, false)?;
+
+    let member_dir = project_dir.join("crates").join("utils");
+    assert!(member_dir.join("Cargo.toml").exists());
+    assert!(member_dir.join("src").join("lib.rs").exists());
+
+    let root: toml::Value = toml::from_str(&fs::read_to_string(project_dir.join("Cargo.toml"))?)?;
+    let members: Vec<&str> = root["workspace"]["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|m| m.as_str())
+        .collect();
+    assert_eq!(members, vec!["crates/test_project", "crates/utils"]);
+
+    // A second member with the same name is a conflict
+    let err = ferrisup::commands::transform::execute(Some(project), Some("library"), Some("utils"), None, None, false).unwrap_err();
+    assert!(err.to_string().contains("already has a member named 'utils'"), "Unexpected error: {}", err);
+
+    drop(temp_dir);
+    Ok(())
+}
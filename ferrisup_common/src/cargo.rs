@@ -3,7 +3,7 @@ use std::process::Command;
 use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
 #[allow(unused_imports)]
-use toml_edit::{DocumentMut, Item};
+use toml_edit::{DocumentMut, InlineTable, Item, Value};
 
 pub fn write_cargo_toml(project_dir: &Path) -> anyhow::Result<()> {
     let cargo_toml = format!(
@@ -240,6 +240,172 @@ pub fn update_cargo_with_dependencies(cargo_path: &Path, dependencies: Vec<(Stri
     Ok(())
 }
 
+/// A dependency entry to add to (or merge into) a manifest dependency table
+#[derive(Debug, Clone, Default)]
+pub struct DependencySpec {
+    pub name: String,
+    pub version: Option<String>,
+    pub features: Vec<String>,
+    pub path: Option<String>,
+    pub git: Option<String>,
+    /// Alternate registry to take the crate from instead of crates.io
+    pub registry: Option<String>,
+}
+
+/// Add a dependency to `section` of a manifest, merging with an existing entry
+///
+/// An existing entry keeps its other keys and features; the version is only replaced
+/// when one is given, and new features are appended after the ones already listed.
+/// Returns `true` if an existing entry was updated rather than a new one created.
+pub fn upsert_dependency(doc: &mut DocumentMut, section: &str, spec: &DependencySpec) -> Result<bool> {
+    if doc.get(section).is_none() {
+        doc[section] = toml_edit::table();
+    }
+    let table = doc[section]
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow!("[{}] in Cargo.toml is not a table", section))?;
+
+    let needs_table = !spec.features.is_empty() || spec.path.is_some() || spec.git.is_some() || spec.registry.is_some();
+    let existed = table.contains_key(&spec.name);
+
+    let entry = match table.get_mut(&spec.name) {
+        Some(item) => item,
+        None => {
+            if !needs_table {
+                let version = spec.version.as_deref().unwrap_or("*");
+                table.insert(&spec.name, toml_edit::value(version));
+                return Ok(false);
+            }
+            let mut inline = InlineTable::new();
+            if spec.path.is_none() && spec.git.is_none() {
+                inline.insert("version", spec.version.as_deref().unwrap_or("*").into());
+            }
+            table.insert(&spec.name, Item::Value(Value::InlineTable(inline)));
+            table.get_mut(&spec.name).expect("dependency was just inserted")
+        }
+    };
+
+    // A plain `name = "version"` entry only needs promoting when more than the version changes
+    if let Some(current) = entry.as_str().map(|v| v.to_string()) {
+        if !needs_table {
+            if let Some(version) = &spec.version {
+                *entry = toml_edit::value(version.as_str());
+            }
+            return Ok(true);
+        }
+        let mut inline = InlineTable::new();
+        inline.insert("version", current.as_str().into());
+        *entry = Item::Value(Value::InlineTable(inline));
+    }
+
+    let entry = entry
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow!("Dependency {} has an unsupported format", spec.name))?;
+
+    if let Some(version) = &spec.version {
+        entry.insert("version", toml_edit::value(version.as_str()));
+    }
+    if let Some(path) = &spec.path {
+        entry.insert("path", toml_edit::value(path.as_str()));
+    }
+    if let Some(git) = &spec.git {
+        entry.insert("git", toml_edit::value(git.as_str()));
+    }
+    if let Some(registry) = &spec.registry {
+        entry.insert("registry", toml_edit::value(registry.as_str()));
+    }
+
+    if !spec.features.is_empty() {
+        if entry.get("features").and_then(|f| f.as_array()).is_none() {
+            entry.insert("features", toml_edit::value(toml_edit::Array::new()));
+        }
+        let features = entry
+            .get_mut("features")
+            .and_then(|f| f.as_array_mut())
+            .expect("features array was just ensured");
+        for feature in &spec.features {
+            if !features.iter().any(|f| f.as_str() == Some(feature.as_str())) {
+                features.push(feature.as_str());
+            }
+        }
+    }
+
+    Ok(existed)
+}
+
+/// Point every registry dependency of a manifest at the alternate registry `registry`
+///
+/// Covers `[dependencies]`, `[dev-dependencies]` and `[build-dependencies]`, their
+/// `[target.*]` variants and `[workspace.dependencies]`. Path, git and `workspace = true`
+/// dependencies are left alone. Returns the names of the dependencies that were changed.
+pub fn set_dependency_registry(doc: &mut DocumentMut, registry: &str) -> Vec<String> {
+    let mut changed = Vec::new();
+    let mut tables: Vec<&mut dyn toml_edit::TableLike> = Vec::new();
+    let root = doc.as_table_mut();
+    let (mut targets, mut workspace) = (None, None);
+    for (key, item) in root.iter_mut() {
+        match key.get() {
+            "dependencies" | "dev-dependencies" | "build-dependencies" => tables.extend(item.as_table_like_mut()),
+            "target" => targets = item.as_table_like_mut(),
+            "workspace" => workspace = item.as_table_like_mut(),
+            _ => {}
+        }
+    }
+    if let Some(targets) = targets {
+        for (_, target) in targets.iter_mut() {
+            let Some(target) = target.as_table_like_mut() else { continue };
+            for (key, item) in target.iter_mut() {
+                if matches!(key.get(), "dependencies" | "dev-dependencies" | "build-dependencies") {
+                    tables.extend(item.as_table_like_mut());
+                }
+            }
+        }
+    }
+    if let Some(workspace) = workspace {
+        tables.extend(workspace.get_mut("dependencies").and_then(|item| item.as_table_like_mut()));
+    }
+
+    for table in tables {
+        for (name, entry) in table.iter_mut() {
+            if let Some(version) = entry.as_str().map(|v| v.to_string()) {
+                let mut inline = InlineTable::new();
+                inline.insert("version", version.as_str().into());
+                *entry = Item::Value(Value::InlineTable(inline));
+            }
+            let Some(table) = entry.as_table_like_mut() else { continue };
+            if ["path", "git", "workspace"].iter().any(|key| table.contains_key(key)) {
+                continue;
+            }
+            table.insert("registry", toml_edit::value(registry));
+            // Otherwise the space before the closing brace ends up before the new key
+            if let Some(inline) = entry.as_inline_table_mut() {
+                inline.fmt();
+            }
+            changed.push(name.get().to_string());
+        }
+    }
+    changed
+}
+
+/// Whether cargo knows the alternate registry `name`, from a `[registries]` table in the
+/// cargo configuration that applies to `dir` or a CARGO_REGISTRIES_<NAME>_INDEX variable
+pub fn registry_configured(name: &str, dir: &Path) -> bool {
+    let variable = format!("CARGO_REGISTRIES_{}_INDEX", name.to_uppercase().replace('-', "_"));
+    if std::env::var_os(variable).is_some() {
+        return true;
+    }
+
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(|home| Path::new(&home).join(".cargo")));
+    let config_dirs = dir.ancestors().map(|dir| dir.join(".cargo")).chain(cargo_home);
+    config_dirs
+        .flat_map(|config_dir| [config_dir.join("config.toml"), config_dir.join("config")])
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .filter_map(|content| content.parse::<toml::Table>().ok())
+        .any(|config| config.get("registries").and_then(|registries| registries.get(name)).is_some())
+}
+
 /// Checks if a crate name is available on crates.io
 ///
 /// Uses `cargo search --limit=1` to check if a crate with the given name exists.
@@ -263,6 +429,14 @@ pub fn is_crate_name_available(name: &str) -> Result<bool> {
         .output()
         .context("Failed to execute cargo search command")?;
 
+    // A failed search (e.g. no network) says nothing about the name
+    if !output.status.success() {
+        return Err(anyhow!(
+            "cargo search failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
     // If the search returns no results (empty stdout), the crate name is available
     let stdout = String::from_utf8_lossy(&output.stdout);
 
@@ -472,6 +646,114 @@ edition = "2021"
         Ok(())
     }
 
+    #[test]
+    fn test_upsert_dependency_merges_features() -> anyhow::Result<()> {
+        // Fixture manifest with an existing serde entry and a comment that must survive
+        let manifest = r#"[package]
+name = "fixture"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+# Serialization
+serde = { version = "1.0", features = ["derive"] }
+log = "0.4"
+"#;
+        let mut doc = manifest.parse::<DocumentMut>()?;
+
+        let spec = DependencySpec {
+            name: "serde".to_string(),
+            version: Some("^1.0".to_string()),
+            features: vec!["derive".to_string(), "rc".to_string()],
+            ..Default::default()
+        };
+        let existed = upsert_dependency(&mut doc, "dependencies", &spec)?;
+        assert!(existed);
+
+        let updated = doc.to_string();
+        assert!(updated.contains("# Serialization"));
+        assert!(updated.contains(r#"serde = { version = "^1.0", features = ["derive", "rc"] }"#));
+        // Only one serde entry is present
+        assert_eq!(updated.matches("serde").count(), 1);
+
+        // A plain version string is promoted to an inline table when features are added
+        let spec = DependencySpec {
+            name: "log".to_string(),
+            features: vec!["std".to_string()],
+            ..Default::default()
+        };
+        upsert_dependency(&mut doc, "dependencies", &spec)?;
+        assert!(doc.to_string().contains(r#"log = { version = "0.4", features = ["std"] }"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_dependency_new_sections() -> anyhow::Result<()> {
+        let mut doc = "[package]\nname = \"fixture\"\n".parse::<DocumentMut>()?;
+
+        let spec = DependencySpec {
+            name: "cc".to_string(),
+            version: Some("1.0".to_string()),
+            ..Default::default()
+        };
+        assert!(!upsert_dependency(&mut doc, "build-dependencies", &spec)?);
+
+        let spec = DependencySpec {
+            name: "helpers".to_string(),
+            path: Some("../helpers".to_string()),
+            ..Default::default()
+        };
+        assert!(!upsert_dependency(&mut doc, "dev-dependencies", &spec)?);
+
+        let updated = doc.to_string();
+        assert!(updated.contains("[build-dependencies]\ncc = \"1.0\""));
+        assert!(updated.contains(r#"helpers = { path = "../helpers" }"#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_dependency_registry() -> anyhow::Result<()> {
+        let manifest = r#"[package]
+name = "fixture"
+
+[dependencies]
+serde = "1.0"
+tokio = { version = "1", features = ["full"] }
+helpers = { path = "../helpers" }
+shared = { workspace = true }
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+
+[workspace.dependencies]
+anyhow = "1"
+"#;
+        let mut doc = manifest.parse::<DocumentMut>()?;
+
+        let changed = set_dependency_registry(&mut doc, "internal");
+        assert_eq!(changed, vec!["serde", "tokio", "libc", "anyhow"]);
+        let updated = doc.to_string();
+        assert!(updated.contains(r#"serde = { version = "1.0", registry = "internal" }"#), "{}", updated);
+        assert!(updated.contains(r#"tokio = { version = "1", features = ["full"], registry = "internal" }"#));
+        assert!(updated.contains(r#"libc = { version = "0.2", registry = "internal" }"#));
+        assert!(updated.contains(r#"helpers = { path = "../helpers" }"#));
+        assert!(updated.contains("shared = { workspace = true }"));
+
+        // Dependencies added later can name the registry themselves
+        let spec = DependencySpec {
+            name: "log".to_string(),
+            version: Some("0.4".to_string()),
+            registry: Some("internal".to_string()),
+            ..Default::default()
+        };
+        upsert_dependency(&mut doc, "dependencies", &spec)?;
+        assert!(doc.to_string().contains(r#"log = { version = "0.4", registry = "internal" }"#));
+
+        Ok(())
+    }
+
     #[test]
     fn test_is_crate_name_available() -> anyhow::Result<()> {
         // Test with a crate name that definitely exists
@@ -1,5 +1,6 @@
+use handlebars::{Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, ScopedJson};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn create_directory(path: &Path) -> anyhow::Result<()> {
     if !path.exists() {
@@ -129,6 +130,84 @@ pub fn copy_directory_with_template_processing(src: &Path, dst: &Path) -> anyhow
     Ok(())
 }
 
+/// Check that a project can be generated at `path`
+///
+/// A missing path or an empty directory is fine. A non-empty directory is refused unless
+/// `force` is set, and a file in the way is always refused, with its own message.
+pub fn ensure_empty_dir(path: &Path, force: bool) -> anyhow::Result<()> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(anyhow::anyhow!("Cannot access {}: {}", path.display(), e)),
+    };
+    if !metadata.is_dir() {
+        return Err(anyhow::anyhow!("{} already exists and is a file, not a directory", path.display()));
+    }
+    if force {
+        return Ok(());
+    }
+    if fs::read_dir(path)?.next().is_some() {
+        return Err(anyhow::anyhow!("{} already exists and is not empty", path.display()));
+    }
+    Ok(())
+}
+
+/// Write `contents` to `path` without ever leaving a partially written file behind
+///
+/// The contents go to a temporary file next to `path` which is then renamed into
+/// place, an atomic replace on the same filesystem. If writing or renaming fails the
+/// temporary file is removed and any existing file at `path` is left untouched.
+///
+/// A file that's replaced keeps its permissions; a new one gets the permissions
+/// `fs::write` would give it, 0666 less the process umask on Unix.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    // Temporary files are private (0600) by default, which the renamed file would keep
+    let mut builder = tempfile::Builder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        builder.permissions(fs::Permissions::from_mode(0o666));
+    }
+    let mut temp_file = builder
+        .tempfile_in(dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create temporary file in {}: {}", dir.display(), e))?;
+    if let Some(existing) = fs::metadata(path).ok().filter(|metadata| metadata.is_file()) {
+        temp_file
+            .as_file()
+            .set_permissions(existing.permissions())
+            .map_err(|e| anyhow::anyhow!("Failed to keep the permissions of {}: {}", path.display(), e))?;
+    }
+    temp_file
+        .write_all(contents.as_ref())
+        .and_then(|_| temp_file.as_file().sync_all())
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    temp_file
+        .persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e.error))?;
+
+    Ok(())
+}
+
+/// Make the file at `path` executable: mode 0755 on Unix, nothing to do on Windows
+pub fn set_executable(path: &Path) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| anyhow::anyhow!("Failed to make {} executable: {}", path.display(), e))?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
 pub fn copy_dir_contents(from: &Path, to: &Path) -> anyhow::Result<()> {
     for entry in fs::read_dir(from)? {
         let entry = entry?;
@@ -147,6 +226,70 @@ pub fn copy_dir_contents(from: &Path, to: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Copy a directory recursively, skipping paths that match any glob in `ignore`
+///
+/// Globs are matched against paths relative to `src` using `/` separators, so
+/// `**/target/**` skips every `target` directory's contents. A glob without a `/`
+/// (such as `.git` or `*.swp`) matches a file or directory name at any depth, and an
+/// ignored directory is skipped along with everything below it. Directories are
+/// created as needed for the copied files. Returns the number of files copied.
+pub fn copy_dir_filtered(src: &Path, dst: &Path, ignore: &[String]) -> anyhow::Result<usize> {
+    let patterns = ignore
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .map_err(|e| anyhow::anyhow!("Invalid ignore pattern '{}': {}", pattern, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+
+    let is_ignored = |relative: &Path| {
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        let name = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        patterns.iter().any(|pattern| {
+            pattern.matches_with(&relative_str, options)
+                || (!pattern.as_str().contains('/') && pattern.matches_with(&name, options))
+        })
+    };
+
+    let walker = walkdir::WalkDir::new(src)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|entry| match entry.path().strip_prefix(src) {
+            Ok(relative) => relative.as_os_str().is_empty() || !is_ignored(relative),
+            Err(_) => true,
+        });
+
+    let mut copied = 0;
+    for entry in walker {
+        let entry = entry.map_err(|e| {
+            let path = e.path().map(|p| p.display().to_string()).unwrap_or_default();
+            anyhow::anyhow!("Failed to read {}: {}", path, e)
+        })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        let target = dst.join(path.strip_prefix(src)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| anyhow::anyhow!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        fs::copy(path, &target)
+            .map_err(|e| anyhow::anyhow!("Failed to copy {} to {}: {}", path.display(), target.display(), e))?;
+        copied += 1;
+    }
+
+    Ok(copied)
+}
+
 // Helper function to visit all files in a directory recursively
 pub fn visit_dirs(dir: &Path, cb: &dyn Fn(&Path) -> anyhow::Result<()>) -> anyhow::Result<()> {
     if dir.is_dir() {
@@ -163,6 +306,289 @@ pub fn visit_dirs(dir: &Path, cb: &dyn Fn(&Path) -> anyhow::Result<()>) -> anyho
     Ok(())
 }
 
+/// Write a `.gitignore` suited to the template a project was generated from
+///
+/// Starts from a base Rust set and adds entries for the template's tooling (Trunk output
+/// for Leptos, model artifacts for Burn, Wrangler state for edge, ...). `Cargo.lock` is
+/// only ignored for libraries; binaries should commit it. If a `.gitignore` already
+/// exists, only the missing lines are appended.
+pub fn write_gitignore(project_dir: &Path, template_kind: &str) -> anyhow::Result<()> {
+    let is_library = project_dir.join("src").join("lib.rs").exists()
+        && !project_dir.join("src").join("main.rs").exists();
+
+    let mut entries = vec!["/target", "**/*.rs.bk"];
+    if is_library {
+        entries.push("Cargo.lock");
+    }
+    entries.extend(gitignore_template_entries(template_kind));
+
+    let gitignore_path = project_dir.join(".gitignore");
+    let mut content = if gitignore_path.exists() {
+        fs::read_to_string(&gitignore_path)?
+    } else {
+        String::new()
+    };
+
+    let existing: Vec<String> = content.lines().map(|line| line.trim().to_string()).collect();
+    let mut missing: Vec<&str> = Vec::new();
+    for entry in entries {
+        if !existing.iter().any(|line| line == entry) && !missing.contains(&entry) {
+            missing.push(entry);
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+
+    fs::write(&gitignore_path, content)?;
+    Ok(())
+}
+
+/// Template-specific `.gitignore` entries, matched on the template path (e.g. `client/leptos/counter`)
+fn gitignore_template_entries(template_kind: &str) -> Vec<&'static str> {
+    let mut entries = Vec::new();
+
+    // Trunk and wasm-pack build output
+    if template_kind.contains("leptos")
+        || matches!(template_kind, "counter" | "router" | "todo")
+        || template_kind.starts_with("client")
+    {
+        entries.extend(["/dist", "/pkg"]);
+    }
+
+    // Trained model artifacts
+    if template_kind.contains("burn") {
+        entries.extend(["/artifacts", "/model*.json"]);
+    }
+
+    // Wrangler state, JS tooling and wasm build output
+    if template_kind.starts_with("edge") {
+        entries.extend(["/pkg", "/build", ".wrangler", "node_modules"]);
+    }
+
+    if template_kind.starts_with("serverless") {
+        entries.extend([".aws-sam", "node_modules"]);
+    }
+
+    entries
+}
+
+/// A scratch directory that a project is generated into before it is moved to its final location
+///
+/// The staging directory lives next to the target so the final move is a rename on the
+/// same filesystem. Dropping a `StagingDir` removes whatever was staged, so a failed
+/// generation never leaves a half-written project behind.
+pub struct StagingDir {
+    root: PathBuf,
+    path: PathBuf,
+    target: PathBuf,
+}
+
+impl StagingDir {
+    /// Create a staging directory for `target`
+    pub fn new(target: &Path) -> anyhow::Result<Self> {
+        let file_name = target
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Invalid project directory: {}", target.display()))?;
+        let parent = match target.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let root = parent.join(format!(
+            ".{}.ferrisup-staging-{}",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+
+        // Keep the project's own directory name so tools that create it by name still work
+        let path = root.join(file_name);
+        fs::create_dir_all(&path)?;
+
+        Ok(Self {
+            root,
+            path,
+            target: target.to_path_buf(),
+        })
+    }
+
+    /// The directory files should be generated into
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Staged files that already exist in the target, relative to the project root
+    pub fn conflicts(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut conflicts = Vec::new();
+        if !self.target.exists() {
+            return Ok(conflicts);
+        }
+
+        for entry in walkdir::WalkDir::new(&self.path).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(&self.path)?;
+            if self.target.join(relative).exists() {
+                conflicts.push(relative.to_path_buf());
+            }
+        }
+        conflicts.sort();
+
+        Ok(conflicts)
+    }
+
+    /// Every staged file, relative to the project root
+    pub fn files(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(&self.path).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() {
+                files.push(entry.path().strip_prefix(&self.path)?.to_path_buf());
+            }
+        }
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Move the staged project to its target and return the target path
+    ///
+    /// A missing target is created by renaming the staged directory. An existing target is
+    /// never removed; the staged files are copied into it instead.
+    pub fn persist(self) -> anyhow::Result<PathBuf> {
+        let (target, _) = self.persist_with(OverwritePolicy::Overwrite, |_| Ok(true))?;
+        Ok(target)
+    }
+
+    /// Like [`persist`](Self::persist), deciding per conflicting file whether it's overwritten
+    ///
+    /// `confirm` is only called for [`OverwritePolicy::Prompt`], with the conflicting file
+    /// relative to the project root, and returns whether to overwrite it. Returns the target
+    /// path and the files that were left untouched.
+    pub fn persist_with(
+        self,
+        policy: OverwritePolicy,
+        mut confirm: impl FnMut(&Path) -> anyhow::Result<bool>,
+    ) -> anyhow::Result<(PathBuf, Vec<PathBuf>)> {
+        if !self.target.exists() {
+            fs::rename(&self.path, &self.target)?;
+            return Ok((self.target.clone(), Vec::new()));
+        }
+
+        let mut skipped = Vec::new();
+        for conflict in self.conflicts()? {
+            let overwrite = match policy {
+                OverwritePolicy::Overwrite => true,
+                OverwritePolicy::Skip => false,
+                OverwritePolicy::Prompt => confirm(&conflict)?,
+            };
+            if !overwrite {
+                // Dropping the staged copy keeps the existing file
+                fs::remove_file(self.path.join(&conflict))?;
+                skipped.push(conflict);
+            }
+        }
+        copy_dir_contents(&self.path, &self.target)?;
+
+        Ok((self.target.clone(), skipped))
+    }
+}
+
+/// What happens to a generated file that already exists in the target directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Keep the existing file
+    Skip,
+    /// Replace the existing file
+    Overwrite,
+    /// Ask for every conflicting file
+    Prompt,
+}
+
+impl OverwritePolicy {
+    /// The names accepted by [`FromStr`](std::str::FromStr), as given to `--overwrite-policy`
+    pub const NAMES: [&'static str; 3] = ["skip", "overwrite", "prompt"];
+}
+
+impl std::str::FromStr for OverwritePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(OverwritePolicy::Skip),
+            "overwrite" => Ok(OverwritePolicy::Overwrite),
+            "prompt" => Ok(OverwritePolicy::Prompt),
+            _ => Err(anyhow::anyhow!(
+                "Unknown overwrite policy '{}', expected one of: {}",
+                s,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for OverwritePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OverwritePolicy::Skip => "skip",
+            OverwritePolicy::Overwrite => "overwrite",
+            OverwritePolicy::Prompt => "prompt",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// The `eq` helper, comparing its two parameters
+///
+/// Returns a boolean rather than writing "true"/"false", so `{{#if (eq styling "tailwind")}}`
+/// is false when the values differ (a written "false" is a non-empty, truthy string). A
+/// variable that isn't set compares as null instead of failing in strict mode.
+struct EqHelper;
+
+impl HelperDef for EqHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'reg, 'rc>, RenderError> {
+        let param = |idx| h.param(idx).map(|p| p.value());
+        Ok(ScopedJson::Derived(serde_json::Value::Bool(param(0) == param(1))))
+    }
+}
+
+/// Render a handlebars template string, failing on variables `ctx` doesn't define
+///
+/// The registry runs in strict mode, so a `{{name}}` that isn't in the context is an
+/// error naming the variable rather than an empty string in the output. Missing values
+/// passed to helpers (`{{#if (eq styling "tailwind")}}`) are still allowed, which keeps
+/// optional variables working. Output is not HTML-escaped.
+pub fn render_template_str(template: &str, ctx: &Context) -> anyhow::Result<String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_helper("eq", Box::new(EqHelper));
+    Ok(handlebars.render_template_with_context(template, ctx)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,7 +741,254 @@ mod tests {
         
         Ok(())
     }
-    
+
+    #[test]
+    fn test_ensure_empty_dir() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let target = temp_dir.path().join("project");
+
+        // Missing and empty directories can be generated into
+        ensure_empty_dir(&target, false)?;
+        create_directory(&target)?;
+        ensure_empty_dir(&target, false)?;
+
+        // A non-empty one only with force
+        fs::write(target.join("notes.txt"), "keep me")?;
+        let err = ensure_empty_dir(&target, false).unwrap_err();
+        assert!(err.to_string().ends_with("already exists and is not empty"), "{}", err);
+        ensure_empty_dir(&target, true)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ensure_empty_dir_rejects_files() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let target = temp_dir.path().join("project");
+        fs::write(&target, "not a directory")?;
+
+        // force never makes a file usable as the project directory
+        for force in [false, true] {
+            let err = ensure_empty_dir(&target, force).unwrap_err();
+            assert!(err.to_string().ends_with("already exists and is a file, not a directory"), "{}", err);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_atomic() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let file_path = temp_dir.path().join("main.rs");
+
+        write_atomic(&file_path, "fn main() {}\n")?;
+        assert_eq!(fs::read_to_string(&file_path)?, "fn main() {}\n");
+
+        // Replacing an existing file swaps in the new contents
+        write_atomic(&file_path, "fn main() { println!(\"hi\"); }\n")?;
+        assert_eq!(fs::read_to_string(&file_path)?, "fn main() { println!(\"hi\"); }\n");
+
+        // A directory in the way makes the final rename fail
+        let blocked_path = temp_dir.path().join("src");
+        create_directory(&blocked_path.join("nested"))?;
+        assert!(write_atomic(&blocked_path, "contents").is_err());
+        assert!(blocked_path.is_dir());
+
+        // Neither attempt leaves a temporary file behind
+        let mut entries: Vec<String> = fs::read_dir(temp_dir.path())?
+            .map(|e| e.map(|e| e.file_name().to_string_lossy().to_string()))
+            .collect::<Result<_, _>>()?;
+        entries.sort();
+        assert_eq!(entries, vec!["main.rs", "src"]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_permissions() -> anyhow::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir()?;
+        let mode = |path: &Path| -> anyhow::Result<u32> { Ok(fs::metadata(path)?.permissions().mode() & 0o777) };
+
+        // A new file gets the same permissions as one written with fs::write, under the same umask
+        let plain = temp_dir.path().join("plain.rs");
+        fs::write(&plain, "")?;
+        let atomic = temp_dir.path().join("atomic.rs");
+        write_atomic(&atomic, "")?;
+        assert_eq!(mode(&atomic)?, mode(&plain)?);
+
+        // A replaced file keeps its own
+        fs::set_permissions(&atomic, fs::Permissions::from_mode(0o640))?;
+        write_atomic(&atomic, "fn main() {}\n")?;
+        assert_eq!(mode(&atomic)?, 0o640);
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_dir_filtered() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let src_dir = temp_dir.path().join("template");
+        for file in [
+            "Cargo.toml",
+            "src/main.rs",
+            "src/.main.rs.swp",
+            "target/debug/app",
+            "crates/core/src/lib.rs",
+            "crates/core/target/release/libcore.rlib",
+            ".git/HEAD",
+            ".git/refs/heads/main",
+            ".github/workflows/ci.yml",
+        ] {
+            let path = src_dir.join(file);
+            create_directory(path.parent().unwrap())?;
+            fs::write(&path, file)?;
+        }
+
+        let dst_dir = temp_dir.path().join("project");
+        let ignore = vec!["**/target/**".to_string(), ".git".to_string(), "*.swp".to_string()];
+        let copied = copy_dir_filtered(&src_dir, &dst_dir, &ignore)?;
+
+        assert_eq!(copied, 4);
+        assert_eq!(fs::read_to_string(dst_dir.join("src/main.rs"))?, "src/main.rs");
+        assert!(dst_dir.join("Cargo.toml").is_file());
+        assert!(dst_dir.join("crates/core/src/lib.rs").is_file());
+        assert!(dst_dir.join(".github/workflows/ci.yml").is_file());
+        assert!(!dst_dir.join("src/.main.rs.swp").exists());
+        assert!(!dst_dir.join("target").exists());
+        assert!(!dst_dir.join("crates/core/target").exists());
+        assert!(!dst_dir.join(".git").exists());
+
+        // Invalid globs are reported instead of silently copying everything
+        let err = copy_dir_filtered(&src_dir, &dst_dir, &["[".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid ignore pattern"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_gitignore() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let project_dir = temp_dir.path();
+        create_directory(&project_dir.join("src"))?;
+        fs::write(project_dir.join("src").join("main.rs"), "fn main() {}\n")?;
+
+        // Binaries keep Cargo.lock, Leptos projects ignore the Trunk output
+        write_gitignore(project_dir, "client/leptos/counter")?;
+        let content = fs::read_to_string(project_dir.join(".gitignore"))?;
+        assert!(content.lines().any(|line| line == "/target"));
+        assert!(content.lines().any(|line| line == "/dist"));
+        assert!(!content.lines().any(|line| line == "Cargo.lock"));
+
+        // An existing .gitignore is extended, not overwritten
+        fs::write(project_dir.join(".gitignore"), "/target\n.env")?;
+        write_gitignore(project_dir, "edge/api-function/cloudflare-workers")?;
+        let content = fs::read_to_string(project_dir.join(".gitignore"))?;
+        assert!(content.starts_with("/target\n.env\n"));
+        assert_eq!(content.lines().filter(|line| *line == "/target").count(), 1);
+        assert!(content.lines().any(|line| line == ".wrangler"));
+        assert!(content.lines().any(|line| line == "node_modules"));
+
+        // Libraries ignore Cargo.lock
+        let lib_dir = project_dir.join("lib");
+        create_directory(&lib_dir.join("src"))?;
+        fs::write(lib_dir.join("src").join("lib.rs"), "")?;
+        write_gitignore(&lib_dir, "library")?;
+        let content = fs::read_to_string(lib_dir.join(".gitignore"))?;
+        assert!(content.lines().any(|line| line == "Cargo.lock"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_staging_dir() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+
+        // Persisting moves the staged files to a new target and cleans up the staging area
+        let target = temp_dir.path().join("app");
+        let staging = StagingDir::new(&target)?;
+        fs::write(staging.path().join("Cargo.toml"), "[package]")?;
+        assert!(!target.exists());
+        assert_eq!(staging.persist()?, target);
+        assert!(target.join("Cargo.toml").exists());
+
+        // Dropping without persisting leaves the target untouched
+        let target = temp_dir.path().join("failed");
+        let staging = StagingDir::new(&target)?;
+        fs::write(staging.path().join("Cargo.toml"), "[package]")?;
+        drop(staging);
+        assert!(!target.exists());
+
+        // An existing target is kept and receives the staged files
+        let target = temp_dir.path().join("existing");
+        fs::create_dir(&target)?;
+        fs::write(target.join("notes.txt"), "keep me")?;
+        let staging = StagingDir::new(&target)?;
+        fs::write(staging.path().join("Cargo.toml"), "[package]")?;
+        staging.persist()?;
+        assert!(target.join("notes.txt").exists());
+        assert!(target.join("Cargo.toml").exists());
+
+        // Only files the template produces count as conflicts
+        let staging = StagingDir::new(&target)?;
+        fs::create_dir_all(staging.path().join("src"))?;
+        fs::write(staging.path().join("Cargo.toml"), "[package]")?;
+        fs::write(staging.path().join("src").join("main.rs"), "fn main() {}")?;
+        assert_eq!(staging.conflicts()?, vec![PathBuf::from("Cargo.toml")]);
+        drop(staging);
+
+        // Nothing but the targets is left behind
+        assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_staging_dir_overwrite_policy() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let target = temp_dir.path().join("existing");
+        fs::create_dir(&target)?;
+        fs::write(target.join("Cargo.toml"), "mine")?;
+        fs::write(target.join("README.md"), "mine")?;
+
+        let stage = || -> anyhow::Result<StagingDir> {
+            let staging = StagingDir::new(&target)?;
+            for file in ["Cargo.toml", "README.md", "main.rs"] {
+                fs::write(staging.path().join(file), "generated")?;
+            }
+            Ok(staging)
+        };
+
+        // Skipping keeps every existing file but still adds the new ones
+        let (_, skipped) = stage()?.persist_with(OverwritePolicy::Skip, |_| unreachable!())?;
+        assert_eq!(skipped, vec![PathBuf::from("Cargo.toml"), PathBuf::from("README.md")]);
+        assert_eq!(fs::read_to_string(target.join("Cargo.toml"))?, "mine");
+        assert_eq!(fs::read_to_string(target.join("main.rs"))?, "generated");
+
+        // Prompting asks once per conflict, which now includes the file written above
+        let mut asked = Vec::new();
+        let (_, skipped) = stage()?.persist_with(OverwritePolicy::Prompt, |file| {
+            asked.push(file.to_path_buf());
+            Ok(file != Path::new("Cargo.toml"))
+        })?;
+        assert_eq!(asked.len(), 3);
+        assert_eq!(skipped, vec![PathBuf::from("Cargo.toml")]);
+        assert_eq!(fs::read_to_string(target.join("Cargo.toml"))?, "mine");
+        assert_eq!(fs::read_to_string(target.join("README.md"))?, "generated");
+
+        // Overwriting replaces them
+        let (_, skipped) = stage()?.persist_with(OverwritePolicy::Overwrite, |_| unreachable!())?;
+        assert!(skipped.is_empty());
+        assert_eq!(fs::read_to_string(target.join("Cargo.toml"))?, "generated");
+
+        assert_eq!("skip".parse::<OverwritePolicy>()?, OverwritePolicy::Skip);
+        assert!("ask".parse::<OverwritePolicy>().is_err());
+        assert_eq!(OverwritePolicy::Prompt.to_string(), "prompt");
+
+        Ok(())
+    }
+
     #[test]
     fn test_visit_dirs() -> anyhow::Result<()> {
         // Create a temporary directory structure for testing
@@ -383,4 +1056,18 @@ mod tests {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_render_template_str() -> anyhow::Result<()> {
+        let ctx = Context::wraps(serde_json::json!({ "project_name": "demo", "styling": "tailwind" }))?;
+        let rendered = render_template_str(
+            "name = \"{{project_name}}\"\n{{#if (eq styling \"tailwind\")}}tailwind{{/if}}{{#if (eq optional \"x\")}}never{{/if}}",
+            &ctx,
+        )?;
+        assert_eq!(rendered, "name = \"demo\"\ntailwind");
+
+        let err = render_template_str("name = \"{{undefined}}\"", &ctx).unwrap_err();
+        assert!(err.to_string().contains("undefined"), "{}", err);
+        Ok(())
+    }
+}